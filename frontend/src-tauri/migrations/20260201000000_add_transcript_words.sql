@@ -0,0 +1,22 @@
+-- Migration: Add transcript_words table for word-level timings and confidences
+-- Each row is one recognized word belonging to a transcript segment, enabling
+-- karaoke-style playback highlighting, precise clip extraction and
+-- low-confidence word flagging.
+
+CREATE TABLE IF NOT EXISTS transcript_words (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    transcript_id TEXT NOT NULL,
+    meeting_id TEXT NOT NULL,
+    word_index INTEGER NOT NULL,
+    word TEXT NOT NULL,
+    punctuated_word TEXT,
+    start_time REAL NOT NULL,
+    end_time REAL NOT NULL,
+    confidence REAL,
+    speaker INTEGER,
+    FOREIGN KEY (transcript_id) REFERENCES transcripts(id) ON DELETE CASCADE,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcript_words_transcript_id ON transcript_words(transcript_id, word_index);
+CREATE INDEX IF NOT EXISTS idx_transcript_words_meeting_time ON transcript_words(meeting_id, start_time);
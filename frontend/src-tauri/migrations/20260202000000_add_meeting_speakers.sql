@@ -0,0 +1,15 @@
+-- Migration: Add meeting_speakers table for per-meeting speaker naming
+-- Maps a diarized speaker label as it appears in transcripts (e.g. "Speaker 1")
+-- to a person's display name, used when writing Obsidian transcripts.
+
+CREATE TABLE IF NOT EXISTS meeting_speakers (
+    meeting_id TEXT NOT NULL,
+    speaker_label TEXT NOT NULL,
+    display_name TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (meeting_id, speaker_label),
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_meeting_speakers_meeting_id ON meeting_speakers(meeting_id);
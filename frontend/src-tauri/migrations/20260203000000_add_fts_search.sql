@@ -0,0 +1,116 @@
+-- Migration: Add FTS5 full-text search over transcripts, summaries and meeting notes
+-- Each index is a standalone FTS5 table keyed by the source row's rowid and kept in
+-- sync by triggers. Summaries index the markdown inside summary_processes.result.
+-- Run the reindex command after bulk changes (or a VACUUM, which may renumber rowids).
+
+-- ============================================================================
+-- Transcripts
+-- ============================================================================
+
+CREATE VIRTUAL TABLE IF NOT EXISTS transcripts_fts USING fts5(
+    transcript,
+    transcript_id UNINDEXED,
+    meeting_id UNINDEXED,
+    tokenize = 'porter unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_ai AFTER INSERT ON transcripts BEGIN
+    INSERT INTO transcripts_fts (rowid, transcript, transcript_id, meeting_id)
+    VALUES (new.rowid, new.transcript, new.id, new.meeting_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_ad AFTER DELETE ON transcripts BEGIN
+    DELETE FROM transcripts_fts WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_au AFTER UPDATE OF transcript, meeting_id ON transcripts BEGIN
+    DELETE FROM transcripts_fts WHERE rowid = old.rowid;
+    INSERT INTO transcripts_fts (rowid, transcript, transcript_id, meeting_id)
+    VALUES (new.rowid, new.transcript, new.id, new.meeting_id);
+END;
+
+-- ============================================================================
+-- Summaries (markdown extracted from the result JSON when present)
+-- ============================================================================
+
+CREATE VIRTUAL TABLE IF NOT EXISTS summaries_fts USING fts5(
+    content,
+    meeting_id UNINDEXED,
+    tokenize = 'porter unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS summaries_fts_ai AFTER INSERT ON summary_processes
+WHEN new.result IS NOT NULL BEGIN
+    INSERT INTO summaries_fts (rowid, content, meeting_id)
+    VALUES (
+        new.rowid,
+        CASE WHEN json_valid(new.result)
+             THEN COALESCE(json_extract(new.result, '$.markdown'), new.result)
+             ELSE new.result END,
+        new.meeting_id
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_fts_ad AFTER DELETE ON summary_processes BEGIN
+    DELETE FROM summaries_fts WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_fts_au AFTER UPDATE OF result ON summary_processes BEGIN
+    DELETE FROM summaries_fts WHERE rowid = old.rowid;
+    INSERT INTO summaries_fts (rowid, content, meeting_id)
+    SELECT
+        new.rowid,
+        CASE WHEN json_valid(new.result)
+             THEN COALESCE(json_extract(new.result, '$.markdown'), new.result)
+             ELSE new.result END,
+        new.meeting_id
+    WHERE new.result IS NOT NULL;
+END;
+
+-- ============================================================================
+-- Meeting notes
+-- ============================================================================
+
+CREATE VIRTUAL TABLE IF NOT EXISTS meeting_notes_fts USING fts5(
+    notes_markdown,
+    meeting_id UNINDEXED,
+    tokenize = 'porter unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS meeting_notes_fts_ai AFTER INSERT ON meeting_notes
+WHEN new.notes_markdown IS NOT NULL BEGIN
+    INSERT INTO meeting_notes_fts (rowid, notes_markdown, meeting_id)
+    VALUES (new.rowid, new.notes_markdown, new.meeting_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS meeting_notes_fts_ad AFTER DELETE ON meeting_notes BEGIN
+    DELETE FROM meeting_notes_fts WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS meeting_notes_fts_au AFTER UPDATE OF notes_markdown ON meeting_notes BEGIN
+    DELETE FROM meeting_notes_fts WHERE rowid = old.rowid;
+    INSERT INTO meeting_notes_fts (rowid, notes_markdown, meeting_id)
+    SELECT new.rowid, new.notes_markdown, new.meeting_id
+    WHERE new.notes_markdown IS NOT NULL;
+END;
+
+-- ============================================================================
+-- Backfill existing rows
+-- ============================================================================
+
+INSERT INTO transcripts_fts (rowid, transcript, transcript_id, meeting_id)
+SELECT rowid, transcript, id, meeting_id FROM transcripts;
+
+INSERT INTO summaries_fts (rowid, content, meeting_id)
+SELECT
+    rowid,
+    CASE WHEN json_valid(result)
+         THEN COALESCE(json_extract(result, '$.markdown'), result)
+         ELSE result END,
+    meeting_id
+FROM summary_processes
+WHERE result IS NOT NULL;
+
+INSERT INTO meeting_notes_fts (rowid, notes_markdown, meeting_id)
+SELECT rowid, notes_markdown, meeting_id FROM meeting_notes
+WHERE notes_markdown IS NOT NULL;
@@ -0,0 +1,24 @@
+-- Migration: Add embeddings table for semantic search over meetings
+-- Stores one vector per chunk of transcript or summary text. Vectors are
+-- little-endian f32 BLOBs; rows are scoped by embedding model so switching
+-- models never mixes incompatible vector spaces.
+
+CREATE TABLE IF NOT EXISTS embeddings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    meeting_id TEXT NOT NULL,
+    source_type TEXT NOT NULL,          -- 'transcript' or 'summary'
+    source_id TEXT NOT NULL,            -- first transcript id of the chunk, or the meeting id
+    chunk_index INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    timestamp TEXT,                     -- display timestamp of the first segment ("[MM:SS]")
+    start_time REAL,                    -- seconds from recording start
+    end_time REAL,
+    model TEXT NOT NULL,                -- "<provider>:<model>"
+    dimensions INTEGER NOT NULL,
+    vector BLOB NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_embeddings_meeting_model ON embeddings(meeting_id, model);
+CREATE INDEX IF NOT EXISTS idx_embeddings_model ON embeddings(model);
@@ -0,0 +1,3 @@
+-- Add remote whisper.cpp server configuration to transcript_settings
+-- This column stores: {endpoint, apiKey, authHeader, timeoutSecs, maxConcurrentRequests}
+ALTER TABLE transcript_settings ADD COLUMN remoteWhisperConfig TEXT;
@@ -0,0 +1,19 @@
+-- Migration: Add pending_transcriptions table for the offline capture queue
+-- Meetings recorded while Deepgram was unreachable are queued here and
+-- transcribed with the prerecorded API once the app is back online.
+
+CREATE TABLE IF NOT EXISTS pending_transcriptions (
+    id TEXT PRIMARY KEY,
+    folder_path TEXT NOT NULL,
+    meeting_name TEXT,
+    model TEXT,
+    language TEXT,
+    status TEXT NOT NULL DEFAULT 'pending', -- pending | completed | failed
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    meeting_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_pending_transcriptions_status ON pending_transcriptions(status, created_at);
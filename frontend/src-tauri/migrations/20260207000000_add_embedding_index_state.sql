@@ -0,0 +1,19 @@
+-- Migration: Track which meetings were indexed for semantic search, per model
+-- A meeting with no text to embed has no rows in `embeddings`, so indexing
+-- state can't be derived from them; without a marker such meetings would be
+-- picked up again on every indexing run.
+
+CREATE TABLE IF NOT EXISTS embedding_index_state (
+    meeting_id TEXT NOT NULL,
+    model TEXT NOT NULL,
+    chunk_count INTEGER NOT NULL,
+    indexed_at TEXT NOT NULL,
+    PRIMARY KEY (meeting_id, model),
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+-- Meetings indexed before this migration
+INSERT OR IGNORE INTO embedding_index_state (meeting_id, model, chunk_count, indexed_at)
+SELECT meeting_id, model, COUNT(*), MAX(created_at)
+FROM embeddings
+GROUP BY meeting_id, model;
@@ -0,0 +1,6 @@
+-- Migration: Remember the prep note an offline recording was made for
+-- The deferred transcript is written into that note and merged against
+-- `note_base`, the note as it was when recording started, like a live one.
+
+ALTER TABLE pending_transcriptions ADD COLUMN note_path TEXT;
+ALTER TABLE pending_transcriptions ADD COLUMN note_base TEXT;
@@ -4,12 +4,13 @@ use tauri::{AppHandle, Runtime};
 
 use crate::{
     database::{
-        models::MeetingModel,
+        models::{MeetingModel, TranscriptWord},
         repositories::{
-            meeting::MeetingsRepository, setting::SettingsRepository,
-            transcript::TranscriptsRepository,
+            meeting::MeetingsRepository, search::SearchRepository, setting::SettingsRepository,
+            transcript::TranscriptsRepository, transcript_word::TranscriptWordsRepository,
         },
     },
+    audio::transcription::RemoteWhisperConfig,
     state::AppState,
     summary::CustomOpenAIConfig,
 };
@@ -45,6 +46,58 @@ pub struct TranscriptSearchResult {
     pub timestamp: String,
 }
 
+/// Full-text search request with optional filters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQuery {
+    /// FTS5 query: words, "exact phrases", AND/OR/NOT, prefix*
+    pub query: String,
+    /// Restrict to "transcript", "summary" and/or "notes" (all when empty)
+    pub sources: Option<Vec<String>>,
+    /// Inclusive meeting date range, YYYY-MM-DD
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    /// Audio source, diarized label ("Speaker 1") or named speaker; transcripts only
+    pub speaker: Option<String>,
+    /// Substring of the meeting title
+    pub title: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Highlighted range in a search snippet (UTF-16 offsets)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighlightRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    /// "transcript", "summary" or "notes"
+    pub source: String,
+    /// Transcript segment id, or the meeting id for summaries and notes
+    pub source_id: String,
+    pub meeting_id: String,
+    pub meeting_title: String,
+    pub meeting_created_at: String,
+    pub snippet: String,
+    pub highlights: Vec<HighlightRange>,
+    /// Relevance, higher is better
+    pub score: f64,
+    pub timestamp: Option<String>,
+    pub audio_start_time: Option<f64>,
+    pub speaker: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchIndexStats {
+    pub transcripts: u64,
+    pub summaries: u64,
+    pub notes: u64,
+}
+
 /// DEPRECATED: Profile operations are no longer supported - backend not required
 #[allow(dead_code)]
 #[derive(Debug, Serialize, Deserialize)]
@@ -197,6 +250,23 @@ pub struct TranscriptSegment {
     pub audio_end_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    // Word-level timings (Deepgram), persisted to transcript_words
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<WordTiming>>,
+}
+
+/// Word-level timing for a transcript segment (recording-relative seconds)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordTiming {
+    pub word: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub punctuated_word: Option<String>,
+    pub start: f64,
+    pub end: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -783,6 +853,87 @@ pub async fn api_get_meeting_transcripts<R: Runtime>(
     }
 }
 
+/// Get word-level timings for a single transcript segment
+#[tauri::command]
+pub async fn api_get_transcript_words<R: Runtime>(
+    _app: AppHandle<R>,
+    transcript_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<TranscriptWord>, String> {
+    log_info!("api_get_transcript_words called for transcript_id: {}", transcript_id);
+
+    let pool = state.db_manager.pool();
+
+    TranscriptWordsRepository::get_words_for_transcript(pool, &transcript_id)
+        .await
+        .map_err(|e| {
+            log_error!("Error retrieving words for transcript {}: {}", transcript_id, e);
+            format!("Failed to retrieve transcript words: {}", e)
+        })
+}
+
+/// Get word-level timings for a meeting, optionally within a time window
+/// and/or at or below a confidence threshold
+#[tauri::command]
+pub async fn api_get_meeting_words<R: Runtime>(
+    _app: AppHandle<R>,
+    meeting_id: String,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    max_confidence: Option<f64>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<TranscriptWord>, String> {
+    log_info!(
+        "api_get_meeting_words called for meeting_id: {}, window: {:?}-{:?}, max_confidence: {:?}",
+        meeting_id,
+        start_time,
+        end_time,
+        max_confidence
+    );
+
+    let pool = state.db_manager.pool();
+
+    TranscriptWordsRepository::get_words_for_meeting(pool, &meeting_id, start_time, end_time, max_confidence)
+        .await
+        .map_err(|e| {
+            log_error!("Error retrieving words for meeting {}: {}", meeting_id, e);
+            format!("Failed to retrieve meeting words: {}", e)
+        })
+}
+
+/// Ranked full-text search across transcripts, summaries and meeting notes
+#[tauri::command]
+pub async fn api_search_meetings<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    query: SearchQuery,
+) -> Result<Vec<SearchHit>, String> {
+    log_info!("api_search_meetings called with query: {:?}", query);
+
+    let pool = state.db_manager.pool();
+
+    SearchRepository::search(pool, &query).await.map_err(|e| {
+        log_error!("Error searching meetings for query '{}': {}", query.query, e);
+        format!("Failed to search meetings: {}", e)
+    })
+}
+
+/// Rebuild the full-text search index from the existing database
+#[tauri::command]
+pub async fn api_reindex_search<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<SearchIndexStats, String> {
+    log_info!("api_reindex_search called");
+
+    let pool = state.db_manager.pool();
+
+    SearchRepository::rebuild_index(pool).await.map_err(|e| {
+        log_error!("Error rebuilding search index: {}", e);
+        format!("Failed to rebuild search index: {}", e)
+    })
+}
+
 #[tauri::command]
 pub async fn api_save_meeting_title<R: Runtime>(
     _app: AppHandle<R>,
@@ -873,6 +1024,7 @@ pub async fn api_save_transcript<R: Runtime>(
                 "Successfully saved transcript and created meeting with id: {}",
                 meeting_id
             );
+            crate::semantic::commands::spawn_auto_index(&_app, pool.clone(), meeting_id.clone());
             Ok(serde_json::json!({
                 "status": "success",
                 "message": "Transcript saved successfully",
@@ -1215,3 +1367,107 @@ pub async fn api_test_custom_openai_connection<R: Runtime>(
         }
     }
 }
+
+// ===== REMOTE WHISPER SERVER COMMANDS =====
+
+/// Build a remote whisper.cpp server config from command arguments
+fn remote_whisper_config(
+    endpoint: String,
+    api_key: Option<String>,
+    auth_header: Option<String>,
+    timeout_secs: Option<u64>,
+    max_concurrent_requests: Option<usize>,
+) -> Result<RemoteWhisperConfig, String> {
+    let config = RemoteWhisperConfig {
+        endpoint: endpoint.trim().to_string(),
+        api_key: api_key.filter(|k| !k.trim().is_empty()),
+        auth_header: auth_header
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty()),
+        timeout_secs,
+        max_concurrent_requests,
+    };
+    config.validate()?;
+    Ok(config)
+}
+
+/// Saves the remote whisper.cpp server configuration
+/// Stored as JSON next to the transcript settings and used by the "remoteWhisper" provider
+#[tauri::command]
+pub async fn api_save_remote_whisper_config<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    endpoint: String,
+    api_key: Option<String>,
+    auth_header: Option<String>,
+    timeout_secs: Option<u64>,
+    max_concurrent_requests: Option<usize>,
+) -> Result<serde_json::Value, String> {
+    log_info!("api_save_remote_whisper_config called: endpoint='{}'", &endpoint);
+
+    let config = remote_whisper_config(
+        endpoint,
+        api_key,
+        auth_header,
+        timeout_secs,
+        max_concurrent_requests,
+    )?;
+
+    match SettingsRepository::save_remote_whisper_config(state.db_manager.pool(), &config).await {
+        Ok(()) => {
+            log_info!("✅ Saved remote Whisper server config for endpoint: {}", config.endpoint);
+            Ok(serde_json::json!({
+                "status": "success",
+                "message": "Remote Whisper server configuration saved successfully"
+            }))
+        }
+        Err(e) => {
+            log_error!("❌ Failed to save remote Whisper server config: {}", e);
+            Err(format!("Failed to save remote Whisper server configuration: {}", e))
+        }
+    }
+}
+
+/// Gets the remote whisper.cpp server configuration
+#[tauri::command]
+pub async fn api_get_remote_whisper_config<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<RemoteWhisperConfig>, String> {
+    log_info!("api_get_remote_whisper_config called");
+
+    SettingsRepository::get_remote_whisper_config(state.db_manager.pool())
+        .await
+        .map_err(|e| {
+            log_error!("❌ Failed to get remote Whisper server config: {}", e);
+            format!("Failed to get remote Whisper server configuration: {}", e)
+        })
+}
+
+/// Tests a remote whisper.cpp server by transcribing one second of silence
+#[tauri::command]
+pub async fn api_test_remote_whisper_connection<R: Runtime>(
+    _app: AppHandle<R>,
+    endpoint: String,
+    api_key: Option<String>,
+    auth_header: Option<String>,
+    timeout_secs: Option<u64>,
+) -> Result<serde_json::Value, String> {
+    log_info!("api_test_remote_whisper_connection called: endpoint='{}'", &endpoint);
+
+    let config = remote_whisper_config(endpoint, api_key, auth_header, timeout_secs, None)?;
+    match crate::audio::transcription::remote_whisper_provider::test_connection(&config).await {
+        Ok(elapsed) => {
+            log_info!("✅ Remote Whisper server answered in {:?}", elapsed);
+            Ok(serde_json::json!({
+                "status": "success",
+                "message": format!("Connection successful ({} ms)", elapsed.as_millis()),
+                "latency_ms": elapsed.as_millis() as u64
+            }))
+        }
+        Err(e) => {
+            log_warn!("❌ Remote Whisper server test failed: {}", e);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,767 @@
+// audio/diarization.rs
+//
+// On-device speaker diarization for the local transcription engines (Whisper, Parakeet)
+// and the upload providers (OpenAI, Groq, ElevenLabs, remote whisper.cpp servers).
+// Each VAD speech segment is turned into a speaker embedding with an ONNX model run
+// through the same `ort` runtime Parakeet uses, and the embeddings are clustered into
+// speakers. Segments are labelled "Speaker N" (0-based, like Deepgram) so offline
+// meetings get the same `[Speaker N]: text` transcripts as Deepgram ones.
+//
+// Supported models: WeSpeaker / 3D-Speaker style exports taking Kaldi fbank features
+// `[1, frames, bins]`, and raw-waveform exports taking `[1, samples]` or `[1, 1, samples]`.
+// The model is optional and downloaded on demand (see diarization_commands.rs) to
+// `<app_data>/models/diarization/speaker-embedding.onnx`; any compatible export can be
+// dropped there instead.
+
+use crate::audio::vad::SpeechSegment;
+use log::{debug, info, warn};
+use ndarray::{Array2, Array3};
+use once_cell::sync::Lazy;
+use ort::execution_providers::CPUExecutionProvider;
+use ort::inputs;
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::TensorRef;
+use realfft::RealFftPlanner;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Sub-directory of the app models directory holding the embedding model
+pub const DIARIZATION_MODEL_DIR: &str = "diarization";
+/// File name of the speaker embedding model
+pub const DIARIZATION_MODEL_FILENAME: &str = "speaker-embedding.onnx";
+
+const SAMPLE_RATE: usize = 16000;
+/// Segments shorter than this (0.5s) give unreliable embeddings and inherit a neighbour's label
+const MIN_EMBEDDING_SAMPLES: usize = SAMPLE_RATE / 2;
+/// Long segments are embedded from their first 10s to bound inference time
+const MAX_EMBEDDING_SAMPLES: usize = SAMPLE_RATE * 10;
+/// Cosine similarity above which two embeddings are considered the same speaker
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.55;
+/// Upper bound on the number of speakers in one meeting
+pub const DEFAULT_MAX_SPEAKERS: usize = 8;
+/// Agglomerative clustering is O(n³); above this many embeddings an evenly spaced
+/// sample is clustered and the remaining segments join the nearest cluster
+const MAX_AGGLOMERATIVE_EMBEDDINGS: usize = 300;
+/// Live chunks are embedded from a centered 3s window so diarization keeps up with
+/// transcription (one embedding per chunk is unavoidable for labelling)
+const LIVE_EMBEDDING_SECONDS: usize = 3;
+
+// Kaldi-compatible fbank parameters (25ms window, 10ms shift at 16kHz)
+const FRAME_LENGTH: usize = 400;
+const FRAME_SHIFT: usize = 160;
+const FFT_SIZE: usize = 512;
+const PREEMPHASIS: f32 = 0.97;
+const MEL_LOW_FREQ: f32 = 20.0;
+
+#[derive(thiserror::Error, Debug)]
+pub enum DiarizationError {
+    #[error("ORT error: {0}")]
+    Ort(#[from] ort::Error),
+    #[error("ndarray shape error: {0}")]
+    Shape(#[from] ndarray::ShapeError),
+    #[error("FFT error: {0}")]
+    Fft(String),
+    #[error("Speaker embedding model not found: {0}")]
+    ModelNotFound(PathBuf),
+    #[error("Unsupported speaker embedding model: {0}")]
+    UnsupportedModel(String),
+    #[error("Audio too short for a speaker embedding ({0} samples)")]
+    AudioTooShort(usize),
+}
+
+// ============================================================================
+// FEATURE EXTRACTION
+// ============================================================================
+
+/// Compute Kaldi-style log mel filterbank features with per-utterance mean normalization
+///
+/// Returns a `[frames, num_bins]` matrix; empty when the input is shorter than one frame.
+pub fn compute_fbank(samples_16k: &[f32], num_bins: usize) -> Result<Array2<f32>, DiarizationError> {
+    if samples_16k.len() < FRAME_LENGTH || num_bins == 0 {
+        return Ok(Array2::zeros((0, num_bins)));
+    }
+
+    let num_frames = 1 + (samples_16k.len() - FRAME_LENGTH) / FRAME_SHIFT;
+    let window = povey_window(FRAME_LENGTH);
+    let mel_banks = mel_filterbank(num_bins, FFT_SIZE, SAMPLE_RATE as f32);
+
+    let mut planner = RealFftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(FFT_SIZE);
+    let mut input = fft.make_input_vec();
+    let mut spectrum = fft.make_output_vec();
+    let mut power = vec![0.0f32; FFT_SIZE / 2 + 1];
+
+    let mut features = Array2::<f32>::zeros((num_frames, num_bins));
+
+    for frame_index in 0..num_frames {
+        let offset = frame_index * FRAME_SHIFT;
+        // Kaldi operates on int16-scaled samples
+        let mut frame: Vec<f32> = samples_16k[offset..offset + FRAME_LENGTH]
+            .iter()
+            .map(|s| s * 32768.0)
+            .collect();
+
+        // Remove DC offset
+        let mean = frame.iter().sum::<f32>() / FRAME_LENGTH as f32;
+        frame.iter_mut().for_each(|s| *s -= mean);
+
+        // Pre-emphasis (in reverse so each sample uses the original previous value)
+        for i in (1..FRAME_LENGTH).rev() {
+            frame[i] -= PREEMPHASIS * frame[i - 1];
+        }
+        frame[0] -= PREEMPHASIS * frame[0];
+
+        input.iter_mut().for_each(|s| *s = 0.0);
+        for (i, sample) in frame.iter().enumerate() {
+            input[i] = sample * window[i];
+        }
+
+        fft.process(&mut input, &mut spectrum)
+            .map_err(|e| DiarizationError::Fft(e.to_string()))?;
+        for (p, c) in power.iter_mut().zip(spectrum.iter()) {
+            *p = c.re * c.re + c.im * c.im;
+        }
+
+        for (bin, weights) in mel_banks.iter().enumerate() {
+            let energy: f32 = weights.iter().map(|(k, w)| power[*k] * w).sum();
+            features[[frame_index, bin]] = energy.max(f32::EPSILON).ln();
+        }
+    }
+
+    // Cepstral mean normalization over the utterance
+    if let Some(mean) = features.mean_axis(ndarray::Axis(0)) {
+        features -= &mean;
+    }
+
+    Ok(features)
+}
+
+fn povey_window(length: usize) -> Vec<f32> {
+    let denom = (length - 1) as f32;
+    (0..length)
+        .map(|i| {
+            let hann = 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / denom).cos();
+            hann.powf(0.85)
+        })
+        .collect()
+}
+
+fn hz_to_mel(hz: f32) -> f32 {
+    1127.0 * (1.0 + hz / 700.0).ln()
+}
+
+/// Triangular mel filters as sparse `(fft_bin, weight)` lists
+fn mel_filterbank(num_bins: usize, fft_size: usize, sample_rate: f32) -> Vec<Vec<(usize, f32)>> {
+    let nyquist = sample_rate / 2.0;
+    let mel_low = hz_to_mel(MEL_LOW_FREQ);
+    let mel_high = hz_to_mel(nyquist);
+    let mel_delta = (mel_high - mel_low) / (num_bins + 1) as f32;
+    let fft_bin_width = sample_rate / fft_size as f32;
+
+    (0..num_bins)
+        .map(|bin| {
+            let left = mel_low + bin as f32 * mel_delta;
+            let center = left + mel_delta;
+            let right = center + mel_delta;
+
+            (0..=fft_size / 2)
+                .filter_map(|k| {
+                    let mel = hz_to_mel(k as f32 * fft_bin_width);
+                    let weight = if mel > left && mel <= center {
+                        (mel - left) / (center - left)
+                    } else if mel > center && mel < right {
+                        (right - mel) / (right - center)
+                    } else {
+                        0.0
+                    };
+                    (weight > 0.0).then_some((k, weight))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// ============================================================================
+// EMBEDDING MODEL
+// ============================================================================
+
+/// What the embedding model expects as its first input
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EmbeddingInput {
+    /// `[1, frames, bins]` Kaldi fbank features
+    Fbank { num_bins: usize },
+    /// `[1, samples]` raw 16kHz waveform
+    Waveform2d,
+    /// `[1, 1, samples]` raw 16kHz waveform
+    Waveform3d,
+}
+
+/// Speaker embedding model running on the ONNX runtime
+pub struct SpeakerEmbeddingModel {
+    session: Session,
+    input_name: String,
+    output_name: String,
+    input_kind: EmbeddingInput,
+}
+
+impl SpeakerEmbeddingModel {
+    pub fn new<P: AsRef<Path>>(model_path: P) -> Result<Self, DiarizationError> {
+        let model_path = model_path.as_ref();
+        if !model_path.exists() {
+            return Err(DiarizationError::ModelNotFound(model_path.to_path_buf()));
+        }
+
+        info!("Loading speaker embedding model from {}...", model_path.display());
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .with_execution_providers(vec![CPUExecutionProvider::default().build()])?
+            .commit_from_file(model_path)?;
+
+        let input = session
+            .inputs
+            .first()
+            .ok_or_else(|| DiarizationError::UnsupportedModel("model has no inputs".to_string()))?;
+        let output_name = session
+            .outputs
+            .first()
+            .map(|output| output.name.clone())
+            .ok_or_else(|| DiarizationError::UnsupportedModel("model has no outputs".to_string()))?;
+
+        let shape = input
+            .input_type
+            .tensor_shape()
+            .ok_or_else(|| DiarizationError::UnsupportedModel(format!("input '{}' is not a tensor", input.name)))?;
+        let input_kind = match shape.len() {
+            2 => EmbeddingInput::Waveform2d,
+            3 if shape[1] == 1 => EmbeddingInput::Waveform3d,
+            3 if shape[2] > 0 => EmbeddingInput::Fbank { num_bins: shape[2] as usize },
+            _ => {
+                return Err(DiarizationError::UnsupportedModel(format!(
+                    "unexpected input shape {:?} for '{}'",
+                    &shape[..],
+                    input.name
+                )))
+            }
+        };
+        let input_name = input.name.clone();
+
+        info!(
+            "Speaker embedding model loaded: input='{}' ({:?}), output='{}'",
+            input_name, input_kind, output_name
+        );
+
+        Ok(Self {
+            session,
+            input_name,
+            output_name,
+            input_kind,
+        })
+    }
+
+    /// Compute an L2-normalized speaker embedding for 16kHz mono audio
+    pub fn embed(&mut self, samples_16k: &[f32]) -> Result<Vec<f32>, DiarizationError> {
+        if samples_16k.len() < MIN_EMBEDDING_SAMPLES {
+            return Err(DiarizationError::AudioTooShort(samples_16k.len()));
+        }
+        let samples = &samples_16k[..samples_16k.len().min(MAX_EMBEDDING_SAMPLES)];
+
+        let embedding: Vec<f32> = match self.input_kind {
+            EmbeddingInput::Fbank { num_bins } => {
+                let features = compute_fbank(samples, num_bins)?;
+                let frames = features.nrows();
+                let features = features.into_shape_with_order((1, frames, num_bins))?;
+                self.run(TensorRef::from_array_view(features.view())?)?
+            }
+            EmbeddingInput::Waveform2d => {
+                let waveform = Array2::from_shape_vec((1, samples.len()), samples.to_vec())?;
+                self.run(TensorRef::from_array_view(waveform.view())?)?
+            }
+            EmbeddingInput::Waveform3d => {
+                let waveform = Array3::from_shape_vec((1, 1, samples.len()), samples.to_vec())?;
+                self.run(TensorRef::from_array_view(waveform.view())?)?
+            }
+        };
+
+        Ok(l2_normalize(embedding))
+    }
+
+    fn run(&mut self, input: TensorRef<'_, f32>) -> Result<Vec<f32>, DiarizationError> {
+        let outputs = self.session.run(inputs![self.input_name.as_str() => input])?;
+        let embedding = outputs
+            .get(self.output_name.as_str())
+            .ok_or_else(|| DiarizationError::UnsupportedModel(format!("output '{}' missing", self.output_name)))?
+            .try_extract_array::<f32>()?;
+        Ok(embedding.iter().copied().collect())
+    }
+}
+
+// Global embedding model, loaded lazily on first use
+static DIARIZATION_MODEL: Mutex<Option<Arc<Mutex<SpeakerEmbeddingModel>>>> = Mutex::new(None);
+
+/// Path of the speaker embedding model inside the app data directory
+pub fn diarization_model_path<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    app.path().app_data_dir().ok().map(|dir| {
+        dir.join("models")
+            .join(DIARIZATION_MODEL_DIR)
+            .join(DIARIZATION_MODEL_FILENAME)
+    })
+}
+
+/// Get the loaded speaker embedding model, loading it on first use.
+/// Returns None when no model is installed (diarization is optional).
+pub fn get_or_load_diarization_model<R: Runtime>(
+    app: &AppHandle<R>,
+) -> Option<Arc<Mutex<SpeakerEmbeddingModel>>> {
+    let mut guard = DIARIZATION_MODEL.lock().unwrap();
+    if let Some(model) = guard.as_ref() {
+        return Some(model.clone());
+    }
+
+    let path = diarization_model_path(app)?;
+    if !path.exists() {
+        debug!("No speaker embedding model at {}, diarization disabled", path.display());
+        return None;
+    }
+
+    match SpeakerEmbeddingModel::new(&path) {
+        Ok(model) => {
+            let model = Arc::new(Mutex::new(model));
+            *guard = Some(model.clone());
+            Some(model)
+        }
+        Err(e) => {
+            warn!("⚠️ Failed to load speaker embedding model: {}", e);
+            None
+        }
+    }
+}
+
+// ============================================================================
+// CLUSTERING
+// ============================================================================
+
+fn l2_normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > f32::EPSILON {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+/// Cosine similarity of two embeddings
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|v| v * v).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm_a <= f32::EPSILON || norm_b <= f32::EPSILON {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+/// Agglomerative (average-linkage) clustering of embeddings
+///
+/// Merges the most similar clusters until no pair is above `threshold`, while always
+/// merging down to at most `max_speakers`. Speaker ids are assigned in order of first
+/// appearance, so the first segment is always speaker 0.
+///
+/// Long meetings are bounded to `MAX_AGGLOMERATIVE_EMBEDDINGS`: an evenly spaced
+/// sample is clustered and every other embedding joins the most similar centroid.
+pub fn cluster_embeddings(embeddings: &[Vec<f32>], threshold: f32, max_speakers: usize) -> Vec<usize> {
+    if embeddings.len() <= MAX_AGGLOMERATIVE_EMBEDDINGS {
+        return agglomerative_clusters(embeddings, threshold, max_speakers);
+    }
+
+    let step = embeddings.len() as f64 / MAX_AGGLOMERATIVE_EMBEDDINGS as f64;
+    let sample: Vec<Vec<f32>> = (0..MAX_AGGLOMERATIVE_EMBEDDINGS)
+        .map(|i| embeddings[(i as f64 * step) as usize].clone())
+        .collect();
+    let sample_clusters = agglomerative_clusters(&sample, threshold, max_speakers);
+
+    let cluster_count = sample_clusters.iter().max().map_or(0, |max| max + 1);
+    let mut centroids = vec![vec![0.0f32; embeddings[0].len()]; cluster_count];
+    for (embedding, &cluster) in sample.iter().zip(&sample_clusters) {
+        for (c, e) in centroids[cluster].iter_mut().zip(embedding) {
+            *c += e;
+        }
+    }
+
+    let assigned: Vec<usize> = embeddings
+        .iter()
+        .map(|embedding| {
+            centroids
+                .iter()
+                .enumerate()
+                .map(|(index, centroid)| (index, cosine_similarity(centroid, embedding)))
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map_or(0, |(index, _)| index)
+        })
+        .collect();
+    renumber_by_first_appearance(&assigned)
+}
+
+fn agglomerative_clusters(embeddings: &[Vec<f32>], threshold: f32, max_speakers: usize) -> Vec<usize> {
+    let n = embeddings.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // Pairwise similarity between active clusters (average linkage via Lance-Williams)
+    let mut similarity = vec![vec![0.0f32; n]; n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let s = cosine_similarity(&embeddings[i], &embeddings[j]);
+            similarity[i][j] = s;
+            similarity[j][i] = s;
+        }
+    }
+
+    let mut cluster_of: Vec<usize> = (0..n).collect();
+    let mut sizes = vec![1usize; n];
+    let mut active = vec![true; n];
+    let mut active_count = n;
+    let max_speakers = max_speakers.max(1);
+
+    while active_count > 1 {
+        let mut best: Option<(usize, usize, f32)> = None;
+        for i in 0..n {
+            if !active[i] {
+                continue;
+            }
+            for j in (i + 1)..n {
+                if active[j] && best.map_or(true, |(_, _, s)| similarity[i][j] > s) {
+                    best = Some((i, j, similarity[i][j]));
+                }
+            }
+        }
+
+        let Some((keep, merge, score)) = best else { break };
+        if score < threshold && active_count <= max_speakers {
+            break;
+        }
+
+        // Merge `merge` into `keep`
+        let (size_keep, size_merge) = (sizes[keep] as f32, sizes[merge] as f32);
+        for k in 0..n {
+            if active[k] && k != keep && k != merge {
+                let s = (similarity[keep][k] * size_keep + similarity[merge][k] * size_merge)
+                    / (size_keep + size_merge);
+                similarity[keep][k] = s;
+                similarity[k][keep] = s;
+            }
+        }
+        sizes[keep] += sizes[merge];
+        active[merge] = false;
+        active_count -= 1;
+        cluster_of.iter_mut().filter(|c| **c == merge).for_each(|c| *c = keep);
+    }
+
+    renumber_by_first_appearance(&cluster_of)
+}
+
+/// Renumber cluster ids so they count up from 0 in order of first appearance
+fn renumber_by_first_appearance(cluster_of: &[usize]) -> Vec<usize> {
+    let mut ids: Vec<usize> = Vec::new();
+    cluster_of
+        .iter()
+        .map(|cluster| match ids.iter().position(|id| id == cluster) {
+            Some(position) => position,
+            None => {
+                ids.push(*cluster);
+                ids.len() - 1
+            }
+        })
+        .collect()
+}
+
+/// Fill unlabelled segments (too short to embed) from the nearest labelled neighbour,
+/// preferring the previous segment since short interjections usually continue a turn
+pub fn fill_missing_labels(labels: &[Option<usize>]) -> Vec<Option<usize>> {
+    let mut filled = labels.to_vec();
+    let mut previous: Option<usize> = None;
+    for label in filled.iter_mut() {
+        match label {
+            Some(speaker) => previous = Some(*speaker),
+            None => *label = previous,
+        }
+    }
+
+    // Leading segments without a previous label take the first label that follows
+    let first = filled.iter().flatten().next().copied();
+    for label in filled.iter_mut() {
+        if label.is_some() {
+            break;
+        }
+        *label = first;
+    }
+    filled
+}
+
+/// Incremental clusterer for live transcription: each segment is assigned on arrival
+#[derive(Debug, Clone)]
+pub struct OnlineSpeakerClusterer {
+    centroids: Vec<Vec<f32>>,
+    counts: Vec<usize>,
+    threshold: f32,
+    max_speakers: usize,
+}
+
+impl Default for OnlineSpeakerClusterer {
+    fn default() -> Self {
+        Self::new(DEFAULT_SIMILARITY_THRESHOLD, DEFAULT_MAX_SPEAKERS)
+    }
+}
+
+impl OnlineSpeakerClusterer {
+    pub fn new(threshold: f32, max_speakers: usize) -> Self {
+        Self {
+            centroids: Vec::new(),
+            counts: Vec::new(),
+            threshold,
+            max_speakers: max_speakers.max(1),
+        }
+    }
+
+    /// Assign an embedding to a speaker, creating a new speaker when nothing is similar enough
+    pub fn assign(&mut self, embedding: &[f32]) -> usize {
+        let best = self
+            .centroids
+            .iter()
+            .enumerate()
+            .map(|(index, centroid)| (index, cosine_similarity(centroid, embedding)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        let speaker = match best {
+            Some((index, score)) if score >= self.threshold || self.centroids.len() >= self.max_speakers => index,
+            _ => {
+                self.centroids.push(vec![0.0; embedding.len()]);
+                self.counts.push(0);
+                self.centroids.len() - 1
+            }
+        };
+
+        // Running mean of the speaker's embeddings
+        let count = self.counts[speaker] as f32;
+        for (c, e) in self.centroids[speaker].iter_mut().zip(embedding) {
+            *c = (*c * count + e) / (count + 1.0);
+        }
+        self.counts[speaker] += 1;
+        speaker
+    }
+
+    pub fn speaker_count(&self) -> usize {
+        self.centroids.len()
+    }
+
+    pub fn reset(&mut self) {
+        self.centroids.clear();
+        self.counts.clear();
+    }
+}
+
+// Live clusterer shared by the transcription workers, reset per recording session
+static LIVE_CLUSTERER: Lazy<Mutex<OnlineSpeakerClusterer>> =
+    Lazy::new(|| Mutex::new(OnlineSpeakerClusterer::default()));
+
+/// Reset live speaker clusters for a new recording session
+pub fn reset_live_diarization() {
+    LIVE_CLUSTERER.lock().unwrap().reset();
+}
+
+/// Centered window of a live chunk used for its speaker embedding
+pub fn live_embedding_window(samples: &[f32], sample_rate: u32) -> &[f32] {
+    let max_len = sample_rate as usize * LIVE_EMBEDDING_SECONDS;
+    if samples.len() <= max_len {
+        return samples;
+    }
+    let start = (samples.len() - max_len) / 2;
+    &samples[start..start + max_len]
+}
+
+/// Label one live speech chunk. Returns None when diarization is unavailable
+/// or the chunk is too short to embed.
+pub fn assign_live_speaker(
+    model: &Arc<Mutex<SpeakerEmbeddingModel>>,
+    samples_16k: &[f32],
+) -> Option<usize> {
+    if samples_16k.len() < MIN_EMBEDDING_SAMPLES {
+        return None;
+    }
+
+    let embedding = match model.lock().unwrap().embed(samples_16k) {
+        Ok(embedding) => embedding,
+        Err(e) => {
+            warn!("⚠️ Speaker embedding failed: {}", e);
+            return None;
+        }
+    };
+
+    Some(LIVE_CLUSTERER.lock().unwrap().assign(&embedding))
+}
+
+/// Diarize a whole recording's VAD segments (offline, batch clustering).
+/// Returns one speaker id per segment.
+pub fn diarize_segments(
+    model: &Arc<Mutex<SpeakerEmbeddingModel>>,
+    segments: &[SpeechSegment],
+) -> Vec<Option<usize>> {
+    let mut embeddings = Vec::new();
+    let mut embedded_indices = Vec::new();
+
+    {
+        let mut model = model.lock().unwrap();
+        for (index, segment) in segments.iter().enumerate() {
+            if segment.samples.len() < MIN_EMBEDDING_SAMPLES {
+                continue;
+            }
+            match model.embed(&segment.samples) {
+                Ok(embedding) => {
+                    embeddings.push(embedding);
+                    embedded_indices.push(index);
+                }
+                Err(e) => warn!("⚠️ Speaker embedding failed for segment {}: {}", index, e),
+            }
+        }
+    }
+
+    let clusters = cluster_embeddings(&embeddings, DEFAULT_SIMILARITY_THRESHOLD, DEFAULT_MAX_SPEAKERS);
+    let mut labels = vec![None; segments.len()];
+    for (index, speaker) in embedded_indices.into_iter().zip(clusters) {
+        labels[index] = Some(speaker);
+    }
+
+    let labels = fill_missing_labels(&labels);
+    let speaker_count = labels.iter().flatten().max().map_or(0, |max| max + 1);
+    info!(
+        "🗣️ Diarization: {} segments, {} embedded, {} speakers",
+        segments.len(),
+        embeddings.len(),
+        speaker_count
+    );
+    labels
+}
+
+/// Prefix transcript text with a speaker label, matching Deepgram's `[Speaker N]: text`
+pub fn format_text_with_speaker(speaker: Option<usize>, text: &str) -> String {
+    match speaker {
+        Some(id) => format!("[Speaker {}]: {}", id, text.trim()),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(values: &[f32]) -> Vec<f32> {
+        l2_normalize(values.to_vec())
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_cluster_embeddings_two_speakers() {
+        let embeddings = vec![
+            unit(&[1.0, 0.1, 0.0]),
+            unit(&[0.0, 1.0, 0.1]),
+            unit(&[0.9, 0.0, 0.1]),
+            unit(&[0.1, 0.9, 0.0]),
+        ];
+
+        let labels = cluster_embeddings(&embeddings, 0.7, 8);
+
+        assert_eq!(labels, vec![0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_cluster_embeddings_respects_max_speakers() {
+        let embeddings = vec![
+            unit(&[1.0, 0.0, 0.0]),
+            unit(&[0.0, 1.0, 0.0]),
+            unit(&[0.0, 0.0, 1.0]),
+        ];
+
+        let labels = cluster_embeddings(&embeddings, 0.9, 2);
+
+        let distinct: std::collections::HashSet<_> = labels.iter().collect();
+        assert_eq!(distinct.len(), 2);
+        assert_eq!(labels[0], 0);
+    }
+
+    #[test]
+    fn test_cluster_embeddings_bounds_long_meetings() {
+        let a = unit(&[1.0, 0.1, 0.0]);
+        let b = unit(&[0.0, 1.0, 0.1]);
+        let embeddings: Vec<Vec<f32>> = (0..MAX_AGGLOMERATIVE_EMBEDDINGS * 3 + 7)
+            .map(|i| if (i / 5) % 2 == 0 { a.clone() } else { b.clone() })
+            .collect();
+
+        let labels = cluster_embeddings(&embeddings, 0.7, 8);
+
+        assert_eq!(labels.len(), embeddings.len());
+        for (i, label) in labels.iter().enumerate() {
+            assert_eq!(*label, (i / 5) % 2, "embedding {}", i);
+        }
+    }
+
+    #[test]
+    fn test_live_embedding_window() {
+        let samples: Vec<f32> = (0..16000 * 5).map(|i| i as f32).collect();
+        let window = live_embedding_window(&samples, 16000);
+        assert_eq!(window.len(), 16000 * 3);
+        assert_eq!(window[0], 16000.0);
+        assert_eq!(live_embedding_window(&samples[..100], 16000).len(), 100);
+    }
+
+    #[test]
+    fn test_fill_missing_labels() {
+        let labels = vec![None, Some(1), None, Some(0), None];
+        assert_eq!(
+            fill_missing_labels(&labels),
+            vec![Some(1), Some(1), Some(1), Some(0), Some(0)]
+        );
+        assert_eq!(fill_missing_labels(&[None, None]), vec![None, None]);
+    }
+
+    #[test]
+    fn test_online_clusterer() {
+        let mut clusterer = OnlineSpeakerClusterer::new(0.7, 2);
+
+        assert_eq!(clusterer.assign(&unit(&[1.0, 0.0, 0.0])), 0);
+        assert_eq!(clusterer.assign(&unit(&[0.0, 1.0, 0.0])), 1);
+        assert_eq!(clusterer.assign(&unit(&[0.95, 0.05, 0.0])), 0);
+        // Max speakers reached: a third voice joins the closest cluster
+        assert_eq!(clusterer.assign(&unit(&[0.1, 0.3, 1.0])), 1);
+        assert_eq!(clusterer.speaker_count(), 2);
+
+        clusterer.reset();
+        assert_eq!(clusterer.speaker_count(), 0);
+    }
+
+    #[test]
+    fn test_compute_fbank_shape() {
+        let samples: Vec<f32> = (0..16000)
+            .map(|i| (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 16000.0).sin() * 0.3)
+            .collect();
+
+        let features = compute_fbank(&samples, 80).unwrap();
+
+        assert_eq!(features.ncols(), 80);
+        assert_eq!(features.nrows(), 1 + (16000 - FRAME_LENGTH) / FRAME_SHIFT);
+        assert!(features.iter().all(|v| v.is_finite()));
+        assert_eq!(compute_fbank(&samples[..100], 80).unwrap().nrows(), 0);
+    }
+
+    #[test]
+    fn test_format_text_with_speaker() {
+        assert_eq!(format_text_with_speaker(Some(2), " hello "), "[Speaker 2]: hello");
+        assert_eq!(format_text_with_speaker(None, "hello"), "hello");
+    }
+}
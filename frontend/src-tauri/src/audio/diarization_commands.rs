@@ -0,0 +1,214 @@
+// audio/diarization_commands.rs
+//
+// Tauri commands to download and inspect the optional speaker embedding model used
+// for on-device diarization. The download streams to a `.part` file and is renamed
+// into place once complete, so a partial model is never picked up by the workers.
+
+use super::diarization::diarization_model_path;
+use futures_util::StreamExt;
+use log::{error, info, warn};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Runtime};
+use tokio::io::AsyncWriteExt;
+
+/// WeSpeaker ResNet34 (VoxCeleb, large-margin fine-tuned), exported to ONNX with fbank input
+const DIARIZATION_MODEL_URL: &str =
+    "https://huggingface.co/Wespeaker/wespeaker-voxceleb-resnet34-LM/resolve/main/voxceleb_resnet34_LM.onnx";
+
+static DOWNLOAD_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+static CANCEL_DOWNLOAD: AtomicBool = AtomicBool::new(false);
+
+/// Install state of the speaker embedding model
+#[derive(Debug, Clone, Serialize)]
+pub struct DiarizationModelStatus {
+    pub installed: bool,
+    pub downloading: bool,
+    pub path: Option<String>,
+    pub size_mb: f64,
+}
+
+/// Clears the in-progress flag however the download ends
+struct DownloadGuard;
+
+impl Drop for DownloadGuard {
+    fn drop(&mut self) {
+        DOWNLOAD_IN_PROGRESS.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Tauri command to report whether the speaker embedding model is installed
+#[tauri::command]
+pub async fn diarization_get_model_status<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<DiarizationModelStatus, String> {
+    let path = diarization_model_path(&app);
+    let size_bytes = match &path {
+        Some(path) => tokio::fs::metadata(path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0),
+        None => 0,
+    };
+
+    Ok(DiarizationModelStatus {
+        installed: size_bytes > 0,
+        downloading: DOWNLOAD_IN_PROGRESS.load(Ordering::SeqCst),
+        path: path.map(|p| p.to_string_lossy().to_string()),
+        size_mb: size_bytes as f64 / (1024.0 * 1024.0),
+    })
+}
+
+/// Tauri command to download the speaker embedding model
+///
+/// Emits `diarization-model-download-progress` while downloading and
+/// `diarization-model-download-complete` once the model is in place.
+#[tauri::command]
+pub async fn diarization_download_model<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    let model_path = diarization_model_path(&app)
+        .ok_or_else(|| "Failed to resolve the app data directory".to_string())?;
+
+    if DOWNLOAD_IN_PROGRESS.swap(true, Ordering::SeqCst) {
+        return Err("Speaker model download already in progress".to_string());
+    }
+    let _guard = DownloadGuard;
+    CANCEL_DOWNLOAD.store(false, Ordering::SeqCst);
+
+    if let Some(dir) = model_path.parent() {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| format!("Failed to create model directory: {}", e))?;
+    }
+    let part_path = model_path.with_extension("onnx.part");
+
+    info!(
+        "Downloading speaker embedding model from {}",
+        DIARIZATION_MODEL_URL
+    );
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let response = client
+        .get(DIARIZATION_MODEL_URL)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to start download: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Download failed with status: {}",
+            response.status()
+        ));
+    }
+
+    let total_bytes = response.content_length().unwrap_or(0);
+    let mut file = tokio::fs::File::create(&part_path)
+        .await
+        .map_err(|e| format!("Failed to create file: {}", e))?;
+
+    let mut stream = response.bytes_stream();
+    let mut downloaded = 0u64;
+    let mut last_progress = 0u8;
+    let mut last_report = Instant::now();
+    let started = Instant::now();
+    emit_progress(&app, 0, 0, total_bytes, 0.0);
+
+    while let Some(chunk) = stream.next().await {
+        if CANCEL_DOWNLOAD.load(Ordering::SeqCst) {
+            info!("Speaker model download cancelled");
+            drop(file);
+            let _ = tokio::fs::remove_file(&part_path).await;
+            return Err("Download cancelled by user".to_string());
+        }
+
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                drop(file);
+                let _ = tokio::fs::remove_file(&part_path).await;
+                return Err(format!("Failed to read chunk: {}", e));
+            }
+        };
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("Failed to write chunk to file: {}", e))?;
+        downloaded += chunk.len() as u64;
+
+        let progress = if total_bytes > 0 {
+            ((downloaded as f64 / total_bytes as f64) * 100.0).min(99.0) as u8
+        } else {
+            0
+        };
+        if progress > last_progress || last_report.elapsed() >= Duration::from_secs(2) {
+            let elapsed = started.elapsed().as_secs_f64().max(0.001);
+            let speed_mbps = downloaded as f64 / (1024.0 * 1024.0) / elapsed;
+            emit_progress(&app, progress, downloaded, total_bytes, speed_mbps);
+            last_progress = progress;
+            last_report = Instant::now();
+        }
+    }
+
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to flush file: {}", e))?;
+    drop(file);
+
+    if total_bytes > 0 && downloaded != total_bytes {
+        let _ = tokio::fs::remove_file(&part_path).await;
+        return Err(format!(
+            "Download incomplete: {} of {} bytes",
+            downloaded, total_bytes
+        ));
+    }
+
+    tokio::fs::rename(&part_path, &model_path)
+        .await
+        .map_err(|e| format!("Failed to move model into place: {}", e))?;
+    info!(
+        "✅ Speaker embedding model installed at {} ({:.1} MB)",
+        model_path.display(),
+        downloaded as f64 / (1024.0 * 1024.0)
+    );
+
+    emit_progress(&app, 100, downloaded, total_bytes, 0.0);
+    if let Err(e) = app.emit(
+        "diarization-model-download-complete",
+        serde_json::json!({ "path": model_path.to_string_lossy() }),
+    ) {
+        error!("Failed to emit diarization download complete event: {}", e);
+    }
+    Ok(())
+}
+
+/// Tauri command to cancel an in-progress speaker model download
+#[tauri::command]
+pub async fn diarization_cancel_download() -> Result<(), String> {
+    if !DOWNLOAD_IN_PROGRESS.load(Ordering::SeqCst) {
+        warn!("No speaker model download to cancel");
+        return Ok(());
+    }
+    CANCEL_DOWNLOAD.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+fn emit_progress<R: Runtime>(
+    app: &AppHandle<R>,
+    progress: u8,
+    downloaded_bytes: u64,
+    total_bytes: u64,
+    speed_mbps: f64,
+) {
+    if let Err(e) = app.emit(
+        "diarization-model-download-progress",
+        serde_json::json!({
+            "progress": progress,
+            "downloaded_mb": downloaded_bytes as f64 / (1024.0 * 1024.0),
+            "total_mb": total_bytes as f64 / (1024.0 * 1024.0),
+            "speed_mbps": speed_mbps,
+            "status": if progress == 100 { "completed" } else { "downloading" }
+        }),
+    ) {
+        error!("Failed to emit diarization download progress event: {}", e);
+    }
+}
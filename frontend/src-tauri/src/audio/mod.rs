@@ -3,6 +3,8 @@ pub mod audio_processing;
 pub mod encode;
 pub mod ffmpeg;
 pub mod vad;
+pub mod diarization;
+pub mod diarization_commands;
 
 // Modularized device management
 pub mod devices;
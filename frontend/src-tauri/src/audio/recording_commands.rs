@@ -104,6 +104,9 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
     }
     info!("✅ Transcription model validation passed");
 
+    // Deepgram selected but unreachable: record now, transcribe once back online
+    let offline_capture = crate::deepgram::offline_queue::begin_recording(&app).await;
+
     // Async-first approach - no more blocking operations!
     info!("🚀 Starting async recording initialization");
 
@@ -123,6 +126,8 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
                 (true, None, None)
             }
         };
+    // Offline captures need the audio file for the deferred upload
+    let auto_save = auto_save || offline_capture;
 
     // ============================================================================
     // MICROPHONE DEVICE RESOLUTION: Preference → Default → Error
@@ -246,6 +251,7 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
     info!("🔍 Setting IS_RECORDING to true and resetting SPEECH_DETECTED_EMITTED");
     IS_RECORDING.store(true, Ordering::SeqCst);
     reset_speech_detected_flag(); // Reset for new recording session
+    super::diarization::reset_live_diarization(); // Speaker clusters are per recording
 
     // Start optimized parallel transcription task and store handle
     let task_handle = transcription::start_transcription_task(app.clone(), transcription_receiver);
@@ -272,6 +278,7 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
                     display_time: update.timestamp.clone(), // Use wall-clock timestamp for display
                     confidence: update.confidence,
                     sequence_id: update.sequence_id,
+                    provider: update.provider.clone(),
                 };
 
                 // Save to recording manager
@@ -347,6 +354,9 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
     }
     info!("✅ Transcription model validation passed");
 
+    // Deepgram selected but unreachable: record now, transcribe once back online
+    let offline_capture = crate::deepgram::offline_queue::begin_recording(&app).await;
+
     // Parse devices
     let mic_device = if let Some(ref name) = mic_device_name {
         Some(Arc::new(parse_audio_device(name).map_err(|e| {
@@ -381,6 +391,8 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
             true // Default to saving if preferences can't be loaded
         }
     };
+    // Offline captures need the audio file for the deferred upload
+    let auto_save = auto_save || offline_capture;
 
     // Always ensure a meeting name is set so incremental saver initializes
     let effective_meeting_name = meeting_name.clone().unwrap_or_else(|| {
@@ -414,6 +426,7 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
     info!("🔍 Setting IS_RECORDING to true and resetting SPEECH_DETECTED_EMITTED");
     IS_RECORDING.store(true, Ordering::SeqCst);
     reset_speech_detected_flag(); // Reset for new recording session
+    super::diarization::reset_live_diarization(); // Speaker clusters are per recording
 
     // Start optimized parallel transcription task and store handle
     let task_handle = transcription::start_transcription_task(app.clone(), transcription_receiver);
@@ -440,6 +453,7 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
                     display_time: update.timestamp.clone(), // Use wall-clock timestamp for display
                     confidence: update.confidence,
                     sequence_id: update.sequence_id,
+                    provider: update.provider.clone(),
                 };
 
                 // Save to recording manager
@@ -855,6 +869,14 @@ pub async fn stop_recording<R: Runtime>(
         _ => (None, None),
     };
 
+    // Offline captures are queued for Deepgram once the connection is back
+    let pending_transcription = crate::deepgram::offline_queue::finish_recording(
+        &app,
+        meeting_folder.as_deref(),
+        meeting_name.as_deref(),
+    )
+    .await;
+
     info!("📤 Preparing recording metadata for frontend save");
     info!("   folder_path: {:?}", folder_path_str);
     info!("   meeting_name: {:?}", meeting_name_str);
@@ -878,7 +900,8 @@ pub async fn stop_recording<R: Runtime>(
         serde_json::json!({
             "message": "Recording stopped - frontend will save after all transcripts received",
             "folder_path": folder_path_str,
-            "meeting_name": meeting_name_str
+            "meeting_name": meeting_name_str,
+            "pending_transcription": pending_transcription
         }),
     )
     .map_err(|e| e.to_string())?;
@@ -5,12 +5,13 @@ use log::{info, warn, error};
 use tauri::{AppHandle, Runtime, Emitter};
 use tokio::sync::mpsc;
 use serde::{Serialize, Deserialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::recording_state::AudioChunk;
 use super::audio_processing::create_meeting_folder;
 use super::incremental_saver::IncrementalAudioSaver;
-use crate::obsidian::{MarkdownTranscriptEntry, MeetingMarkdownData, save_meeting_markdown_to_folder};
+use crate::obsidian::speakers::split_speaker_turns;
+use crate::obsidian::{MarkdownTranscriptEntry, MeetingMarkdownData, NoteMetadata, save_meeting_markdown_to_folder};
 
 /// Structured transcript segment for JSON export
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +24,9 @@ pub struct TranscriptSegment {
     pub display_time: String,   // Formatted time for display like "[02:15]"
     pub confidence: f32,
     pub sequence_id: u64,
+    /// Engine that produced the text, e.g. a local fallback during a Deepgram outage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
 }
 
 /// Meeting metadata structure
@@ -130,6 +134,7 @@ impl RecordingSaver {
             display_time: "[00:00]".to_string(),
             confidence: 1.0,
             sequence_id: 0,
+            provider: None,
         };
         self.add_transcript_segment(segment);
     }
@@ -341,12 +346,13 @@ impl RecordingSaver {
     /// Write meeting markdown file with transcript (Phase 1 implementation)
     ///
     /// Creates a standalone markdown file with:
-    /// - Frontmatter with date, time, duration, recording filename
+    /// - Frontmatter with date, time and the recording metadata (duration,
+    ///   end time, speakers, recording link, transcript provider)
     /// - Transcript section formatted with timestamps
     async fn write_meeting_markdown(
         &self,
         folder: &PathBuf,
-        recording_filename: Option<&str>,
+        recording_path: Option<&Path>,
         duration_seconds: Option<f64>,
     ) -> Result<PathBuf> {
         // Clone segments to avoid holding lock during I/O
@@ -371,38 +377,40 @@ impl RecordingSaver {
             now.format("%H:%M:%S").to_string()
         };
 
-        // Format duration
-        let duration = if let Some(secs) = duration_seconds {
-            let hours = (secs / 3600.0).floor() as u32;
-            let mins = ((secs % 3600.0) / 60.0).floor() as u32;
-            let secs_remainder = (secs % 60.0).floor() as u32;
-            if hours > 0 {
-                format!("{:02}:{:02}:{:02}", hours, mins, secs_remainder)
-            } else {
-                format!("{:02}:{:02}", mins, secs_remainder)
-            }
-        } else {
-            "00:00".to_string()
-        };
-
-        // Convert transcript segments to markdown entries
+        // Convert transcript segments to markdown entries, one per diarized speaker turn
         let entries: Vec<MarkdownTranscriptEntry> = segments_clone
             .iter()
-            .map(|seg| MarkdownTranscriptEntry {
-                display_time: seg.display_time.clone(),
-                text: seg.text.clone(),
-                speaker: None, // TODO: Add speaker diarization support
+            .flat_map(|seg| {
+                split_speaker_turns(&seg.text)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(turn, (speaker, text))| MarkdownTranscriptEntry {
+                        display_time: seg.display_time.clone(),
+                        text,
+                        speaker,
+                        // Citation ids are per segment, so only its first turn gets one
+                        audio_start_time: (turn == 0).then_some(seg.audio_start_time),
+                    })
             })
             .collect();
 
+        // Same recording metadata as transcripts saved into existing notes
+        let metadata = NoteMetadata {
+            time_end: Some(now.format("%Y-%m-%dT%H:%M:%S").to_string()),
+            duration_seconds,
+            recording: recording_path.map(|p| p.to_string_lossy().to_string()),
+            transcript_provider: main_provider(&segments_clone),
+            summary_template: None,
+        };
+
         // Create markdown data
         let markdown_data = MeetingMarkdownData {
             date: date.clone(),
             time_start,
-            duration,
-            recording_filename: recording_filename.map(|s| s.to_string()),
+            metadata,
             entries,
             summary: None, // Summary is added later via update_meeting_summary command
+            speaker_names: Default::default(), // Named later via save_meeting_speakers
         };
 
         // Generate filename from meeting name (sanitized) or date
@@ -537,11 +545,9 @@ impl RecordingSaver {
             info!("✅ Metadata updated with duration: {:?}s", metadata.duration_seconds);
 
             // Generate meeting markdown file (Phase 1)
-            let recording_filename = final_audio_path.file_name()
-                .map(|n| n.to_string_lossy().to_string());
             match self.write_meeting_markdown(
                 folder,
-                recording_filename.as_deref(),
+                Some(final_audio_path.as_path()),
                 metadata.duration_seconds,
             ).await {
                 Ok(md_path) => {
@@ -601,3 +607,19 @@ impl Default for RecordingSaver {
         Self::new()
     }
 }
+
+/// Provider that produced most of the transcript (ties go to the first one heard)
+fn main_provider(segments: &[TranscriptSegment]) -> Option<String> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for provider in segments.iter().filter_map(|s| s.provider.as_deref()) {
+        match counts.iter_mut().find(|(p, _)| *p == provider) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((provider, 1)),
+        }
+    }
+    counts
+        .iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(provider, _)| provider.to_string())
+}
@@ -0,0 +1,376 @@
+// audio/transcription/cloud.rs
+//
+// Shared plumbing for the upload-based providers (OpenAI, Groq, ElevenLabs,
+// remote whisper.cpp servers): splitting long VAD segments into upload-sized chunks
+// and joining their transcripts, language hints and HTTP error handling.
+//
+// Each upload is diarized on its own, so vendor speaker labels ("A",
+// "speaker_0") name the first voice of every VAD segment alike. They can't be
+// matched across segments, so the providers return unlabelled text and the
+// transcription worker labels speakers with the on-device diarization model.
+
+use super::provider::{TranscriptionError, TranscriptResult};
+use crate::deepgram::prerecorded::encode_wav_pcm16;
+use crate::deepgram::websocket::is_valid_language_code;
+use log::{error, info, warn};
+use reqwest::multipart::{Form, Part};
+use std::future::Future;
+use std::time::Duration;
+
+/// Sample rate of the audio handed to providers
+pub const SAMPLE_RATE: u32 = 16000;
+
+/// Shortest audio the cloud APIs accept (0.1 seconds at 16kHz)
+pub const MIN_SAMPLES: usize = 1600;
+
+/// Per-request timeout, long enough for a full-size chunk
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Attempts per chunk when the API is rate limited or briefly unavailable
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Analysis frame used to find a quiet place to cut (20ms at 16kHz)
+const CUT_FRAME_SAMPLES: usize = 320;
+
+// ============================================================================
+// CHUNKING
+// ============================================================================
+
+/// Split audio into chunks of at most `max_samples` (plus a possible short tail).
+///
+/// Cuts are placed in the quietest 20ms frame of the last 10% before each
+/// limit so words are not split in half. A tail shorter than `MIN_SAMPLES` is
+/// merged into the previous chunk instead of being uploaded on its own.
+pub fn split_into_chunks(audio: &[f32], max_samples: usize) -> Vec<&[f32]> {
+    let max_samples = max_samples.max(CUT_FRAME_SAMPLES * 10);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while audio.len() - start > max_samples {
+        let search_from = start + max_samples - max_samples / 10;
+        let cut = search_from + quietest_frame_offset(&audio[search_from..start + max_samples]);
+        chunks.push(&audio[start..cut]);
+        start = cut;
+    }
+
+    if start < audio.len() {
+        let tail = &audio[start..];
+        match chunks.pop() {
+            Some(previous) if tail.len() < MIN_SAMPLES => {
+                let previous_start = start - previous.len();
+                chunks.push(&audio[previous_start..]);
+            }
+            Some(previous) => {
+                chunks.push(previous);
+                chunks.push(tail);
+            }
+            None => chunks.push(tail),
+        }
+    }
+    chunks
+}
+
+/// Offset of the middle of the lowest-energy frame in `window`
+fn quietest_frame_offset(window: &[f32]) -> usize {
+    window
+        .chunks(CUT_FRAME_SAMPLES)
+        .enumerate()
+        .map(|(i, frame)| {
+            let energy: f32 = frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32;
+            (i * CUT_FRAME_SAMPLES + frame.len() / 2, energy)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(offset, _)| offset)
+        .unwrap_or(window.len() / 2)
+        .max(1)
+}
+
+/// Wrap a chunk as a 16-bit PCM WAV file part
+pub fn wav_part(samples: &[f32]) -> Result<Part, String> {
+    Part::bytes(encode_wav_pcm16(samples, SAMPLE_RATE))
+        .file_name("audio.wav")
+        .mime_str("audio/wav")
+        .map_err(|e| format!("Failed to build upload: {}", e))
+}
+
+/// Transcribe chunks in order with `transcribe_chunk` and join their text.
+///
+/// The first chunk that fails fails the whole VAD segment.
+pub async fn transcribe_chunks<'a, F, Fut>(
+    vendor: &str,
+    chunks: Vec<&'a [f32]>,
+    mut transcribe_chunk: F,
+) -> Result<TranscriptResult, TranscriptionError>
+where
+    F: FnMut(&'a [f32]) -> Fut,
+    Fut: Future<Output = Result<TranscriptResult, String>>,
+{
+    let mut results = Vec::with_capacity(chunks.len());
+    for chunk in chunks {
+        let result = transcribe_chunk(chunk).await.map_err(|e| {
+            error!("{} transcription failed: {}", vendor, e);
+            TranscriptionError::EngineFailed(e)
+        })?;
+        results.push(result);
+    }
+
+    if results.len() > 1 {
+        info!("{}: joined {} chunks", vendor, results.len());
+    }
+    Ok(join_results(results))
+}
+
+/// Join chunk results; confidence is the mean of the chunks that report one
+fn join_results(results: Vec<TranscriptResult>) -> TranscriptResult {
+    let confidences: Vec<f32> = results.iter().filter_map(|r| r.confidence).collect();
+    let text = results
+        .iter()
+        .map(|r| r.text.trim())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    TranscriptResult {
+        text,
+        confidence: (!confidences.is_empty())
+            .then(|| confidences.iter().sum::<f32>() / confidences.len() as f32),
+        is_partial: false,
+        words: Vec::new(),
+    }
+}
+
+// ============================================================================
+// LANGUAGE
+// ============================================================================
+
+/// ISO-639-1 language hint for the app's language preference.
+///
+/// "auto", "auto-translate", "multi" and similar return None so the API auto-detects;
+/// regional codes are reduced to the base language (`en-US` -> `en`).
+pub fn language_hint(language: Option<&str>) -> Option<String> {
+    let language = language?.trim();
+    if !is_valid_language_code(language) || language.eq_ignore_ascii_case("multi") {
+        return None;
+    }
+    language
+        .split(['-', '_'])
+        .next()
+        .map(str::to_lowercase)
+        .filter(|code| code.len() >= 2)
+}
+
+/// Average probability of a list of log probabilities
+pub fn mean_probability(logprobs: impl IntoIterator<Item = f64>) -> Option<f32> {
+    let (sum, count) = logprobs
+        .into_iter()
+        .fold((0.0, 0usize), |(sum, count), lp| (sum + lp.exp(), count + 1));
+    (count > 0).then(|| (sum / count as f64).clamp(0.0, 1.0) as f32)
+}
+
+// ============================================================================
+// HTTP
+// ============================================================================
+
+/// HTTP client with the cloud request timeout
+pub fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new())
+}
+
+/// Send a multipart upload, retrying rate limits and server errors with backoff.
+///
+/// `build` is called once per attempt since multipart forms can't be reused.
+pub async fn send_with_retry(
+    vendor: &str,
+    build: impl Fn() -> Result<(reqwest::RequestBuilder, Form), String>,
+) -> Result<String, String> {
+    let mut attempt = 1;
+    loop {
+        let (request, form) = build()?;
+        let response = request
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach {}: {}", vendor, e))?;
+
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if status.is_success() {
+            return Ok(body);
+        }
+
+        let retryable = status.as_u16() == 429 || status.is_server_error();
+        if retryable && attempt < MAX_ATTEMPTS {
+            let delay = Duration::from_secs(1 << (attempt - 1));
+            warn!(
+                "{} returned {} (attempt {}/{}), retrying in {:?}",
+                vendor, status, attempt, MAX_ATTEMPTS, delay
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+            continue;
+        }
+        return Err(api_error(vendor, status.as_u16(), &body));
+    }
+}
+
+/// User-facing message for a failed API call
+pub fn api_error(vendor: &str, status: u16, body: &str) -> String {
+    match status {
+        401 | 403 => format!(
+            "{} rejected the API key. Please check it in Settings > Transcription.",
+            vendor
+        ),
+        429 => format!("{} rate limit reached. Please try again shortly.", vendor),
+        _ => format!("{} API error ({}): {}", vendor, status, error_message(body)),
+    }
+}
+
+/// Error message from a JSON error body (`{"error": {"message"}}`,
+/// `{"detail": {"message"}}` or `{"error": "..."}`), or the raw body
+fn error_message(body: &str) -> String {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return body.trim().to_string();
+    };
+    let message = value
+        .pointer("/error/message")
+        .or_else(|| value.pointer("/detail/message"))
+        .or_else(|| value.get("detail"))
+        .or_else(|| value.get("error"))
+        .and_then(|v| v.as_str());
+    message.map(str::to_string).unwrap_or_else(|| body.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{spawn_mock_server, spawn_mock_server_sequence};
+
+    #[test]
+    fn test_split_into_chunks() {
+        // Short audio stays in one chunk
+        let audio = vec![0.1f32; 8000];
+        assert_eq!(split_into_chunks(&audio, 16000).len(), 1);
+
+        // A silent stretch near the limit becomes the cut point
+        let mut audio = vec![0.5f32; 40000];
+        audio[15000..15400].iter_mut().for_each(|s| *s = 0.0);
+        let chunks = split_into_chunks(&audio, 16000);
+        assert!(chunks.len() >= 2);
+        assert!(chunks[0].len() > 15000 && chunks[0].len() <= 15400);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), audio.len());
+
+        // A tiny tail is merged into the previous chunk
+        let audio = vec![0.5f32; 16000 + 100];
+        let chunks = split_into_chunks(&audio, 16000);
+        assert!(chunks.iter().all(|c| c.len() >= MIN_SAMPLES));
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), audio.len());
+    }
+
+    fn result(text: &str, confidence: Option<f32>) -> TranscriptResult {
+        TranscriptResult {
+            text: text.to_string(),
+            confidence,
+            is_partial: false,
+            words: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_join_results() {
+        let joined = join_results(vec![result("one", Some(0.8)), result(" ", None), result("two", Some(0.6))]);
+        assert_eq!(joined.text, "one two");
+        assert!((joined.confidence.unwrap() - 0.7).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_chunks_stops_at_first_failure() {
+        let audio = [0.0f32; 4];
+        let chunks = vec![&audio[..2], &audio[2..]];
+
+        let joined = transcribe_chunks("OpenAI", chunks.clone(), |chunk| async move {
+            Ok(result(&format!("{} samples", chunk.len()), Some(0.5)))
+        })
+        .await
+        .unwrap();
+        assert_eq!(joined.text, "2 samples 2 samples");
+
+        let mut calls = 0;
+        let error = transcribe_chunks("Groq", chunks, |_| {
+            calls += 1;
+            async { Err("rate limited".to_string()) }
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(error, TranscriptionError::EngineFailed(e) if e == "rate limited"));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_language_hint() {
+        assert_eq!(language_hint(Some("en")), Some("en".to_string()));
+        assert_eq!(language_hint(Some("pt-BR")), Some("pt".to_string()));
+        assert_eq!(language_hint(Some("auto")), None);
+        assert_eq!(language_hint(Some("auto-translate")), None);
+        assert_eq!(language_hint(Some("multi")), None);
+        assert_eq!(language_hint(None), None);
+    }
+
+    #[test]
+    fn test_api_error() {
+        assert!(api_error("OpenAI", 401, "").contains("rejected the API key"));
+        let body = r#"{"error": {"message": "Invalid file format."}}"#;
+        assert_eq!(api_error("Groq", 400, body), "Groq API error (400): Invalid file format.");
+        let body = r#"{"detail": {"status": "invalid", "message": "Bad language"}}"#;
+        assert_eq!(api_error("ElevenLabs", 422, body), "ElevenLabs API error (422): Bad language");
+        let body = r#"{"error": "no audio data"}"#;
+        assert_eq!(api_error("whisper.cpp server", 500, body), "whisper.cpp server API error (500): no audio data");
+    }
+
+    #[test]
+    fn test_mean_probability() {
+        assert_eq!(mean_probability(Vec::new()), None);
+        let p = mean_probability(vec![0.0, 0.0]).unwrap();
+        assert!((p - 1.0).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_send_with_retry_retries_server_errors() {
+        let (base_url, requests) = spawn_mock_server_sequence(vec![
+            ("503 Service Unavailable", r#"{"error": {"message": "busy"}}"#),
+            ("200 OK", r#"{"text": "hello"}"#),
+        ])
+        .await;
+        let client = reqwest::Client::new();
+        let url = format!("{}/audio/transcriptions", base_url);
+
+        let body = send_with_retry("OpenAI", || {
+            Ok((client.post(&url), Form::new().text("model", "whisper-1")))
+        })
+        .await
+        .unwrap();
+        assert_eq!(body, r#"{"text": "hello"}"#);
+
+        // The form is rebuilt for the second attempt
+        let requests = requests.await.unwrap();
+        assert_eq!(requests.len(), 2);
+        for request in requests {
+            assert!(request.starts_with("POST /audio/transcriptions "));
+            assert!(request.contains("name=\"model\"\r\n\r\nwhisper-1"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_with_retry_gives_up_on_client_errors() {
+        let (base_url, request) = spawn_mock_server("401 Unauthorized", r#"{"error": {"message": "Invalid API key"}}"#).await;
+        let client = reqwest::Client::new();
+
+        let error = send_with_retry("Groq", || Ok((client.post(&base_url), Form::new().text("model", "whisper-large-v3"))))
+            .await
+            .unwrap_err();
+        assert!(error.contains("rejected the API key"));
+        request.await.unwrap();
+    }
+}
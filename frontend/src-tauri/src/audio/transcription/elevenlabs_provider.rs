@@ -0,0 +1,217 @@
+// audio/transcription/elevenlabs_provider.rs
+//
+// Cloud transcription through ElevenLabs Scribe (`/v1/speech-to-text`).
+// Diarization is not requested: its labels are per upload and can't be
+// matched across VAD segments (see `cloud`).
+
+use super::cloud::{self, MIN_SAMPLES};
+use super::provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
+use async_trait::async_trait;
+use log::debug;
+use reqwest::multipart::Form;
+use serde::Deserialize;
+
+/// ElevenLabs API base URL
+pub const ELEVENLABS_API_BASE_URL: &str = "https://api.elevenlabs.io/v1";
+
+pub const ELEVENLABS_DEFAULT_MODEL: &str = "scribe_v1";
+
+/// Scribe accepts large files; 30-minute chunks keep long segments to few uploads
+const MAX_CHUNK_SAMPLES: usize = 16000 * 60 * 30;
+
+// ============================================================================
+// RESPONSE TYPES
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct SpeechToTextResponse {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    words: Vec<ScribeWord>,
+}
+
+/// A word, spacing or audio event of the transcript
+#[derive(Debug, Deserialize)]
+struct ScribeWord {
+    text: String,
+    /// "word", "spacing" or "audio_event"
+    #[serde(rename = "type", default)]
+    kind: String,
+    logprob: Option<f64>,
+}
+
+/// Transcript of a response without audio events, scored by word log probabilities
+fn map_response(response: SpeechToTextResponse) -> TranscriptResult {
+    let words: Vec<&ScribeWord> = response.words.iter().filter(|w| w.kind != "audio_event").collect();
+
+    let text = if response.text.trim().is_empty() {
+        // Spacing items carry the whitespace between words
+        words.iter().map(|w| w.text.as_str()).collect::<String>().trim().to_string()
+    } else {
+        response.text.trim().to_string()
+    };
+
+    TranscriptResult {
+        text,
+        confidence: cloud::mean_probability(
+            words.iter().filter(|w| w.kind == "word").filter_map(|w| w.logprob),
+        ),
+        is_partial: false,
+        words: Vec::new(),
+    }
+}
+
+// ============================================================================
+// ELEVENLABS PROVIDER
+// ============================================================================
+
+/// Uploads VAD segments to ElevenLabs Scribe
+pub struct ElevenLabsProvider {
+    client: reqwest::Client,
+    api_key: String,
+    model: String,
+    base_url: String,
+}
+
+impl ElevenLabsProvider {
+    /// Create a provider; an empty model selects scribe_v1
+    pub fn new(api_key: String, model: String) -> Self {
+        Self::with_base_url(api_key, model, ELEVENLABS_API_BASE_URL.to_string())
+    }
+
+    /// Provider against a custom base URL (mock server)
+    pub fn with_base_url(api_key: String, model: String, base_url: String) -> Self {
+        // Older settings stored a TTS model for ElevenLabs; those can't transcribe
+        let model = if model.trim().starts_with("scribe") {
+            model.trim().to_string()
+        } else {
+            ELEVENLABS_DEFAULT_MODEL.to_string()
+        };
+
+        Self {
+            client: cloud::http_client(),
+            api_key,
+            model,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Upload one chunk to Scribe with word timestamps and no diarization
+    async fn transcribe_chunk(&self, samples: &[f32], language: Option<&str>) -> Result<TranscriptResult, String> {
+        let url = format!("{}/speech-to-text", self.base_url);
+
+        let body = cloud::send_with_retry("ElevenLabs", || {
+            let mut form = Form::new()
+                .part("file", cloud::wav_part(samples)?)
+                .text("model_id", self.model.clone())
+                .text("diarize", "false")
+                .text("tag_audio_events", "false")
+                .text("timestamps_granularity", "word");
+            if let Some(language) = language {
+                form = form.text("language_code", language.to_string());
+            }
+            Ok((self.client.post(&url).header("xi-api-key", &self.api_key), form))
+        })
+        .await?;
+
+        let response: SpeechToTextResponse = serde_json::from_str(&body)
+            .map_err(|e| format!("Failed to parse ElevenLabs response: {}", e))?;
+        Ok(map_response(response))
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for ElevenLabsProvider {
+    /// Transcribe a VAD segment, splitting it into upload-sized chunks if needed
+    async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        if self.api_key.is_empty() {
+            return Err(TranscriptionError::EngineFailed(
+                "ElevenLabs API key not configured".to_string(),
+            ));
+        }
+        if audio.len() < MIN_SAMPLES {
+            return Err(TranscriptionError::AudioTooShort {
+                samples: audio.len(),
+                minimum: MIN_SAMPLES,
+            });
+        }
+
+        let language = cloud::language_hint(language.as_deref());
+        let chunks = cloud::split_into_chunks(&audio, MAX_CHUNK_SAMPLES);
+        debug!(
+            "ElevenLabs: transcribing {} samples in {} chunk(s) with '{}', language: {:?}",
+            audio.len(),
+            chunks.len(),
+            self.model,
+            language
+        );
+
+        cloud::transcribe_chunks("ElevenLabs", chunks, |chunk| self.transcribe_chunk(chunk, language.as_deref())).await
+    }
+
+    /// Cloud service - "loaded" when an API key is set
+    async fn is_model_loaded(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+
+    async fn get_current_model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "ElevenLabs"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_fallback() {
+        let provider = ElevenLabsProvider::new("key".to_string(), "eleven_multilingual_v2".to_string());
+        assert_eq!(provider.model, ELEVENLABS_DEFAULT_MODEL);
+        let provider = ElevenLabsProvider::new("key".to_string(), "scribe_v1_experimental".to_string());
+        assert_eq!(provider.model, "scribe_v1_experimental");
+        assert_eq!(provider.provider_name(), "ElevenLabs");
+    }
+
+    #[test]
+    fn test_map_words() {
+        let body = r#"{
+            "language_code": "en",
+            "language_probability": 0.98,
+            "text": "",
+            "words": [
+                {"text": "Hi", "type": "word", "start": 0.0, "end": 0.2, "speaker_id": "speaker_1", "logprob": -0.1},
+                {"text": " ", "type": "spacing", "start": 0.2, "end": 0.3, "speaker_id": "speaker_1"},
+                {"text": "there.", "type": "word", "start": 0.3, "end": 0.6, "speaker_id": "speaker_1", "logprob": -0.1},
+                {"text": " ", "type": "spacing", "start": 0.6, "end": 0.9},
+                {"text": "(laughs)", "type": "audio_event", "start": 0.9, "end": 1.2, "speaker_id": "speaker_0"},
+                {"text": "Hello!", "type": "word", "start": 1.2, "end": 1.6, "speaker_id": "speaker_0", "logprob": -0.1}
+            ]
+        }"#;
+        let result = map_response(serde_json::from_str(body).unwrap());
+        assert_eq!(result.text, "Hi there. Hello!");
+        assert!((result.confidence.unwrap() - (-0.1f64).exp() as f32).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_map_without_speakers() {
+        let body = r#"{"language_code": "de", "text": " Guten Tag. ", "words": []}"#;
+        let result = map_response(serde_json::from_str(body).unwrap());
+        assert_eq!(result.text, "Guten Tag.");
+        assert_eq!(result.confidence, None);
+    }
+
+    #[tokio::test]
+    async fn test_is_model_loaded() {
+        assert!(ElevenLabsProvider::new("key".to_string(), String::new()).is_model_loaded().await);
+        assert!(!ElevenLabsProvider::new(String::new(), String::new()).is_model_loaded().await);
+    }
+}
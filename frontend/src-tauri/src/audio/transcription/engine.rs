@@ -2,10 +2,12 @@
 //
 // TranscriptionEngine enum and model initialization/validation logic.
 
+use super::failover_provider::{FailoverEvent, FailoverProvider, FallbackResolver};
 use super::provider::TranscriptionProvider;
 use log::{info, warn};
 use std::sync::Arc;
-use tauri::{AppHandle, Manager, Runtime};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
 
 // ============================================================================
 // TRANSCRIPTION ENGINE ENUM
@@ -152,16 +154,67 @@ pub async fn validate_transcription_model_ready<R: Runtime>(app: &AppHandle<R>)
                 Err("Deepgram API key is not configured. Please add your API key in Settings > Transcription.".to_string())
             }
         }
+        "openai" | "groq" | "elevenLabs" => {
+            let vendor = cloud_vendor_name(&config.provider);
+            info!("🔍 Validating {} configuration...", vendor);
+            // Upload-based cloud providers only need an API key
+            match config.api_key {
+                Some(api_key) if !api_key.is_empty() => {
+                    info!("✅ {} API key is configured", vendor);
+                    Ok(())
+                }
+                _ => {
+                    warn!("❌ {} API key is not set", vendor);
+                    Err(format!(
+                        "{} API key is not configured. Please add your API key in Settings > Transcription.",
+                        vendor
+                    ))
+                }
+            }
+        }
+        "remoteWhisper" => {
+            info!("🔍 Validating remote Whisper server configuration...");
+            // The server itself is only contacted once audio is sent
+            let remote_config = load_remote_whisper_config(app).await?;
+            remote_config.validate()?;
+            info!("✅ Remote Whisper server configured at {}", remote_config.endpoint);
+            Ok(())
+        }
         other => {
             warn!("❌ Unsupported transcription provider: {}", other);
             Err(format!(
-                "Provider '{}' is not supported. Please select 'deepgram', 'localWhisper', or 'parakeet'.",
+                "Provider '{}' is not supported. Please select 'deepgram', 'openai', 'groq', 'elevenLabs', 'remoteWhisper', 'localWhisper', or 'parakeet'.",
                 other
             ))
         }
     }
 }
 
+/// Display name of an upload-based cloud provider id
+fn cloud_vendor_name(provider: &str) -> &'static str {
+    match provider {
+        "openai" => "OpenAI",
+        "groq" => "Groq",
+        _ => "ElevenLabs",
+    }
+}
+
+/// Saved remote whisper.cpp server configuration
+async fn load_remote_whisper_config<R: Runtime>(
+    app: &AppHandle<R>,
+) -> Result<super::RemoteWhisperConfig, String> {
+    let state = app.state::<crate::state::AppState>();
+    crate::database::repositories::setting::SettingsRepository::get_remote_whisper_config(
+        state.db_manager.pool(),
+    )
+    .await
+    .map_err(|e| format!("Failed to load remote Whisper server configuration: {}", e))?
+    .filter(|config| !config.endpoint.trim().is_empty())
+    .ok_or_else(|| {
+        "Remote Whisper server is not configured. Please set the server URL in Settings > Transcription.".to_string()
+    })
+}
+
 /// Get or initialize the appropriate transcription engine based on provider configuration
 pub async fn get_or_init_transcription_engine<R: Runtime>(
     app: &AppHandle<R>,
@@ -250,12 +303,75 @@ pub async fn get_or_init_transcription_engine<R: Runtime>(
                 config.model
             };
 
-            let provider = crate::deepgram::DeepgramProvider::with_model(api_key, model.clone());
+            // Offline at recording start: the recording is queued for Deepgram
+            // when it stops, a local model (if enabled) gives a live preview
+            if crate::deepgram::offline_queue::is_offline_capture() {
+                let (failover, _) = load_failover_preferences(app);
+                if failover {
+                    if let Some(local) = local_fallback_provider().await {
+                        info!("📴 Deepgram offline, previewing with a local model");
+                        return Ok(TranscriptionEngine::Provider(local));
+                    }
+                }
+                return Err("Deepgram is unreachable; the recording will be transcribed once back online".to_string());
+            }
+
+            let provider = crate::deepgram::DeepgramProvider::with_model(api_key, model.clone())
+                .with_state_listener(crate::deepgram::provider::connection_state_emitter(app.clone()));
             info!("✅ Deepgram provider initialized with model '{}'", model);
 
             // Explicitly coerce to trait object
             let provider: Arc<dyn crate::audio::transcription::provider::TranscriptionProvider> =
                 Arc::new(provider);
+
+            // Fail over to a local engine when Deepgram stays unreachable
+            let (failover, rerun_gaps) = load_failover_preferences(app);
+            if !failover {
+                return Ok(TranscriptionEngine::Provider(provider));
+            }
+            info!("🛟 Local failover enabled (re-run gaps through Deepgram: {})", rerun_gaps);
+            let resolver: FallbackResolver = Arc::new(|| Box::pin(local_fallback_provider()));
+            let emitter = app.clone();
+            let provider = FailoverProvider::new(provider, resolver)
+                .with_gap_rerun(rerun_gaps)
+                .with_listener(Arc::new(move |event: &FailoverEvent| {
+                    if let Err(e) = emitter.emit("transcription-failover", event) {
+                        warn!("Failed to emit failover event: {}", e);
+                    }
+                }));
+            Ok(TranscriptionEngine::Provider(Arc::new(provider)))
+        }
+        "openai" | "groq" | "elevenLabs" => {
+            let vendor = cloud_vendor_name(&config.provider);
+            info!("☁️ Initializing {} transcription engine", vendor);
+
+            let api_key = config.api_key.filter(|key| !key.is_empty()).ok_or_else(|| {
+                format!(
+                    "{} API key is not configured. Please add your API key in Settings > Transcription.",
+                    vendor
+                )
+            })?;
+
+            // An empty model selects the provider's default
+            let provider: Arc<dyn TranscriptionProvider> = match config.provider.as_str() {
+                "openai" => Arc::new(super::OpenAiCompatibleProvider::openai(api_key, config.model)),
+                "groq" => Arc::new(super::OpenAiCompatibleProvider::groq(api_key, config.model)),
+                _ => Arc::new(super::ElevenLabsProvider::new(api_key, config.model)),
+            };
+            info!(
+                "✅ {} provider initialized with model '{}'",
+                vendor,
+                provider.get_current_model().await.unwrap_or_default()
+            );
+            Ok(TranscriptionEngine::Provider(provider))
+        }
+        "remoteWhisper" => {
+            info!("🖧 Initializing remote Whisper server transcription engine");
+            let remote_config = load_remote_whisper_config(app).await?;
+            let provider = super::RemoteWhisperProvider::new(&remote_config, config.model)?;
+            info!("✅ Remote Whisper provider initialized for {}", provider.url());
+
+            let provider: Arc<dyn TranscriptionProvider> = Arc::new(provider);
             Ok(TranscriptionEngine::Provider(provider))
         }
         "localWhisper" => {
@@ -285,6 +401,52 @@ pub async fn get_or_init_transcription_engine<R: Runtime>(
     }
 }
 
+/// Deepgram failover preferences (`preferences.json`): (fail over to a local
+/// engine, re-run the failover gap through Deepgram after it recovers)
+fn load_failover_preferences<R: Runtime>(app: &AppHandle<R>) -> (bool, bool) {
+    let Ok(store) = app.store("preferences.json") else {
+        return (false, false);
+    };
+    let flag = |key: &str, default: bool| store.get(key).and_then(|v| v.as_bool()).unwrap_or(default);
+    (flag("deepgram_local_failover", false), flag("deepgram_rerun_gaps", false))
+}
+
+/// Local engine for Deepgram failover: a loaded or downloaded Parakeet model,
+/// otherwise a Whisper model. Loaded on first failure only.
+async fn local_fallback_provider() -> Option<Arc<dyn TranscriptionProvider>> {
+    use crate::parakeet_engine::commands::{parakeet_init, parakeet_validate_model_ready, PARAKEET_ENGINE};
+    use crate::whisper_engine::commands::{whisper_init, whisper_validate_model_ready, WHISPER_ENGINE};
+
+    if parakeet_init().await.is_ok() {
+        match parakeet_validate_model_ready().await {
+            Ok(model) => {
+                let engine = PARAKEET_ENGINE.lock().unwrap().as_ref().cloned();
+                if let Some(engine) = engine {
+                    info!("🛟 Failing over to Parakeet model '{}'", model);
+                    return Some(Arc::new(super::ParakeetProvider::new(engine)));
+                }
+            }
+            Err(e) => info!("Parakeet not available for failover: {}", e),
+        }
+    }
+
+    if whisper_init().await.is_ok() {
+        match whisper_validate_model_ready().await {
+            Ok(model) => {
+                let engine = WHISPER_ENGINE.lock().unwrap().as_ref().cloned();
+                if let Some(engine) = engine {
+                    info!("🛟 Failing over to Whisper model '{}'", model);
+                    return Some(Arc::new(super::WhisperProvider::new(engine)));
+                }
+            }
+            Err(e) => info!("Whisper not available for failover: {}", e),
+        }
+    }
+
+    warn!("⚠️ No local model available for failover");
+    None
+}
+
 /// Get or initialize transcription engine using API configuration
 /// Returns Whisper engine if provider is localWhisper, otherwise returns error for non-Whisper providers
 pub async fn get_or_init_whisper<R: Runtime>(
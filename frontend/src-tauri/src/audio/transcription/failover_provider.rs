@@ -0,0 +1,508 @@
+// audio/transcription/failover_provider.rs
+//
+// Composite provider that transcribes with a cloud primary (Deepgram) and
+// switches to a local engine (Parakeet or Whisper) when the primary keeps
+// failing, so a dead connection or an exhausted account doesn't end the
+// transcript. While on the local engine the primary is probed periodically;
+// once it answers again, the segments transcribed locally can be re-run
+// through it and are reported as revisions.
+
+use super::provider::{Attribution, GapRevision, TranscriptionError, TranscriptionProvider, TranscriptResult};
+use async_trait::async_trait;
+use futures_util::future::BoxFuture;
+use log::{info, warn};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+/// Consecutive primary failures before the remaining audio goes to the local engine
+const FAILOVER_AFTER_FAILURES: u32 = 2;
+
+/// How often the primary is tried again while on the local engine
+const PROBE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Gap audio kept for re-running through the primary (15 minutes at 16kHz)
+const MAX_GAP_SAMPLES: usize = 16000 * 60 * 15;
+
+/// How long the end of a recording waits for a gap re-run to finish
+const GAP_RERUN_WAIT: Duration = Duration::from_secs(120);
+
+/// Loads the local engine on first use (models are only loaded when needed)
+pub type FallbackResolver =
+    Arc<dyn Fn() -> BoxFuture<'static, Option<Arc<dyn TranscriptionProvider>>> + Send + Sync>;
+
+/// Callback invoked when transcription switches engines
+pub type FailoverListener = Arc<dyn Fn(&FailoverEvent) + Send + Sync>;
+
+/// Engine switch, as emitted to the frontend
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum FailoverEvent {
+    /// The primary keeps failing; audio goes to the local engine
+    Activated { primary: String, fallback: String, reason: String },
+    /// The primary answers again
+    Recovered { primary: String, rerun_segments: usize },
+}
+
+/// Audio transcribed by the local engine, kept for a re-run
+struct GapChunk {
+    id: u64,
+    audio: Vec<f32>,
+    language: Option<String>,
+}
+
+#[derive(Default)]
+struct FailoverState {
+    on_fallback: bool,
+    consecutive_failures: u32,
+    last_probe: Option<Instant>,
+    fallback: Option<Arc<dyn TranscriptionProvider>>,
+    next_gap_id: u64,
+    gap: Vec<GapChunk>,
+    gap_samples: usize,
+}
+
+/// Transcribes with a primary provider and fails over to a local engine
+pub struct FailoverProvider {
+    primary: Arc<dyn TranscriptionProvider>,
+    resolve_fallback: FallbackResolver,
+    rerun_gaps: bool,
+    probe_interval: Duration,
+    listener: Option<FailoverListener>,
+    state: Mutex<FailoverState>,
+    revisions: Arc<Mutex<Vec<GapRevision>>>,
+    rerun_task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl FailoverProvider {
+    /// Wrap `primary`; `resolve_fallback` provides the local engine on first failure
+    pub fn new(primary: Arc<dyn TranscriptionProvider>, resolve_fallback: FallbackResolver) -> Self {
+        Self {
+            primary,
+            resolve_fallback,
+            rerun_gaps: false,
+            probe_interval: PROBE_INTERVAL,
+            listener: None,
+            state: Mutex::new(FailoverState::default()),
+            revisions: Arc::new(Mutex::new(Vec::new())),
+            rerun_task: Mutex::new(None),
+        }
+    }
+
+    /// Re-run locally transcribed segments through the primary once it recovers
+    pub fn with_gap_rerun(mut self, enabled: bool) -> Self {
+        self.rerun_gaps = enabled;
+        self
+    }
+
+    /// Report engine switches to `listener`
+    pub fn with_listener(mut self, listener: FailoverListener) -> Self {
+        self.listener = Some(listener);
+        self
+    }
+
+    /// Whether audio currently goes to the local engine
+    pub async fn is_on_fallback(&self) -> bool {
+        self.state.lock().await.on_fallback
+    }
+
+    fn notify(&self, event: FailoverEvent) {
+        if let Some(listener) = &self.listener {
+            listener(&event);
+        }
+    }
+
+    /// The local engine, resolved on first use
+    async fn fallback(&self) -> Option<Arc<dyn TranscriptionProvider>> {
+        if let Some(fallback) = self.state.lock().await.fallback.clone() {
+            return Some(fallback);
+        }
+        let fallback = (self.resolve_fallback)().await?;
+        info!("Local fallback engine ready: {}", fallback.provider_name());
+        self.state.lock().await.fallback = Some(fallback.clone());
+        Some(fallback)
+    }
+
+    /// Whether this segment should go to the primary
+    async fn should_try_primary(&self) -> bool {
+        let mut state = self.state.lock().await;
+        if !state.on_fallback {
+            return true;
+        }
+        let probe_due = state
+            .last_probe
+            .map_or(true, |last| last.elapsed() >= self.probe_interval);
+        if probe_due {
+            state.last_probe = Some(Instant::now());
+        }
+        probe_due
+    }
+
+    /// The primary answered: leave the local engine and start the gap re-run
+    async fn primary_succeeded(&self) {
+        let mut state = self.state.lock().await;
+        state.consecutive_failures = 0;
+        if !state.on_fallback {
+            return;
+        }
+        state.on_fallback = false;
+
+        let gap = std::mem::take(&mut state.gap);
+        state.gap_samples = 0;
+        drop(state);
+
+        info!(
+            "{} recovered, switching back from the local engine ({} segment(s) to re-run)",
+            self.primary.provider_name(),
+            gap.len()
+        );
+        self.notify(FailoverEvent::Recovered {
+            primary: self.primary.provider_name().to_string(),
+            rerun_segments: gap.len(),
+        });
+        if !gap.is_empty() {
+            self.start_gap_rerun(gap).await;
+        }
+    }
+
+    /// The primary failed; returns true once the remaining audio should fail over
+    async fn primary_failed(&self, error: &TranscriptionError) -> bool {
+        let mut state = self.state.lock().await;
+        state.consecutive_failures += 1;
+        if state.on_fallback {
+            warn!("{} still unavailable: {}", self.primary.provider_name(), error);
+            return false;
+        }
+        let failures = state.consecutive_failures;
+        warn!(
+            "{} failed ({}/{} before failover): {}",
+            self.primary.provider_name(),
+            failures,
+            FAILOVER_AFTER_FAILURES,
+            error
+        );
+        if failures >= FAILOVER_AFTER_FAILURES {
+            state.on_fallback = true;
+            state.last_probe = Some(Instant::now());
+            return true;
+        }
+        false
+    }
+
+    /// Keep a locally transcribed segment for the re-run; returns its gap id
+    async fn record_gap(&self, audio: Vec<f32>, language: Option<String>) -> Option<u64> {
+        if !self.rerun_gaps {
+            return None;
+        }
+        let mut state = self.state.lock().await;
+        if state.gap_samples + audio.len() > MAX_GAP_SAMPLES {
+            warn!("Failover gap exceeds the re-run limit, keeping the local transcript for the rest");
+            return None;
+        }
+        let id = state.next_gap_id;
+        state.next_gap_id += 1;
+        state.gap_samples += audio.len();
+        state.gap.push(GapChunk { id, audio, language });
+        Some(id)
+    }
+
+    /// Re-transcribe the gap with the primary in the background
+    async fn start_gap_rerun(&self, gap: Vec<GapChunk>) {
+        let primary = self.primary.clone();
+        let revisions = self.revisions.clone();
+        let handle = tokio::spawn(async move {
+            let total = gap.len();
+            let mut revised = 0;
+            for chunk in gap {
+                match primary.transcribe(chunk.audio, chunk.language).await {
+                    Ok(result) => {
+                        revised += 1;
+                        revisions.lock().await.push(GapRevision {
+                            gap_id: chunk.id,
+                            provider: primary.provider_name(),
+                            result,
+                        });
+                    }
+                    Err(TranscriptionError::AudioTooShort { .. }) => {}
+                    Err(e) => {
+                        // Keep the local transcript for the rest rather than failing over again
+                        warn!("Gap re-run stopped after {}/{} segments: {}", revised, total, e);
+                        break;
+                    }
+                }
+            }
+            info!("Gap re-run finished: {}/{} segments revised", revised, total);
+        });
+
+        let mut task = self.rerun_task.lock().await;
+        if let Some(previous) = task.replace(handle) {
+            // A previous re-run keeps going; its revisions are still collected
+            drop(previous);
+        }
+    }
+
+    /// Transcribe with the local engine
+    async fn transcribe_fallback(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+        primary_error: TranscriptionError,
+    ) -> Result<(TranscriptResult, Attribution), TranscriptionError> {
+        let Some(fallback) = self.fallback().await else {
+            warn!("No local engine available for failover");
+            return Err(primary_error);
+        };
+
+        let gap_audio = self.rerun_gaps.then(|| (audio.clone(), language.clone()));
+        let result = fallback.transcribe(audio, language).await?;
+        let gap_id = match gap_audio {
+            Some((audio, language)) if !result.text.trim().is_empty() => self.record_gap(audio, language).await,
+            _ => None,
+        };
+        let attribution = Attribution {
+            provider: fallback.provider_name(),
+            gap_id,
+        };
+        Ok((result, attribution))
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for FailoverProvider {
+    async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        self.transcribe_attributed(audio, language, None)
+            .await
+            .map(|(result, _)| result)
+    }
+
+    async fn transcribe_at(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+        recording_time: f64,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        self.transcribe_attributed(audio, language, Some(recording_time))
+            .await
+            .map(|(result, _)| result)
+    }
+
+    async fn finish_recording(&self) {
+        self.primary.finish_recording().await;
+    }
+
+    /// Available while either engine is
+    async fn is_model_loaded(&self) -> bool {
+        if self.primary.is_model_loaded().await {
+            return true;
+        }
+        let fallback = self.state.lock().await.fallback.clone();
+        match fallback {
+            Some(fallback) => fallback.is_model_loaded().await,
+            None => false,
+        }
+    }
+
+    async fn get_current_model(&self) -> Option<String> {
+        self.primary.get_current_model().await
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.primary.provider_name()
+    }
+
+    fn labels_speakers(&self) -> bool {
+        self.primary.labels_speakers()
+    }
+
+    async fn transcribe_attributed(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+        recording_time: Option<f64>,
+    ) -> std::result::Result<(TranscriptResult, Attribution), TranscriptionError> {
+        if !self.should_try_primary().await {
+            let reason = TranscriptionError::EngineFailed(format!(
+                "{} unavailable",
+                self.primary.provider_name()
+            ));
+            return self.transcribe_fallback(audio, language, reason).await;
+        }
+
+        let primary_result = match recording_time {
+            Some(time) => self.primary.transcribe_at(audio.clone(), language.clone(), time).await,
+            None => self.primary.transcribe(audio.clone(), language.clone()).await,
+        };
+        match primary_result {
+            Ok(result) => {
+                self.primary_succeeded().await;
+                let attribution = Attribution {
+                    provider: self.primary.provider_name(),
+                    gap_id: None,
+                };
+                Ok((result, attribution))
+            }
+            // Only engine failures (connection, account) count; short audio is expected
+            Err(e @ TranscriptionError::EngineFailed(_)) => {
+                let switched = self.primary_failed(&e).await;
+                // The failing segment itself goes to the local engine too
+                let result = self.transcribe_fallback(audio, language, e.clone()).await;
+                if switched {
+                    let fallback = match &result {
+                        Ok((_, attribution)) => attribution.provider.to_string(),
+                        Err(_) => "none".to_string(),
+                    };
+                    warn!(
+                        "{} keeps failing, transcribing with {} until it recovers",
+                        self.primary.provider_name(),
+                        fallback
+                    );
+                    self.notify(FailoverEvent::Activated {
+                        primary: self.primary.provider_name().to_string(),
+                        fallback,
+                        reason: e.to_string(),
+                    });
+                }
+                result
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn take_gap_revisions(&self, wait: bool) -> Vec<GapRevision> {
+        if wait {
+            let task = self.rerun_task.lock().await.take();
+            if let Some(task) = task {
+                if tokio::time::timeout(GAP_RERUN_WAIT, task).await.is_err() {
+                    warn!("Gap re-run did not finish within {:?}, keeping the rest local", GAP_RERUN_WAIT);
+                }
+            }
+        }
+        std::mem::take(&mut *self.revisions.lock().await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// Provider that fails while `down` is set
+    struct MockProvider {
+        name: &'static str,
+        down: AtomicBool,
+        calls: AtomicUsize,
+    }
+
+    impl MockProvider {
+        fn new(name: &'static str, down: bool) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                down: AtomicBool::new(down),
+                calls: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl TranscriptionProvider for MockProvider {
+        async fn transcribe(&self, audio: Vec<f32>, _language: Option<String>) -> Result<TranscriptResult, TranscriptionError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.down.load(Ordering::SeqCst) {
+                return Err(TranscriptionError::EngineFailed("connection refused".to_string()));
+            }
+            Ok(TranscriptResult {
+                text: format!("{} {}", self.name, audio.len()),
+                confidence: None,
+                is_partial: false,
+                words: Vec::new(),
+            })
+        }
+
+        async fn is_model_loaded(&self) -> bool {
+            true
+        }
+
+        async fn get_current_model(&self) -> Option<String> {
+            None
+        }
+
+        fn provider_name(&self) -> &'static str {
+            self.name
+        }
+    }
+
+    fn failover(primary: Arc<MockProvider>, local: Arc<MockProvider>) -> FailoverProvider {
+        let resolver: FallbackResolver = Arc::new(move || {
+            let local: Arc<dyn TranscriptionProvider> = local.clone();
+            Box::pin(async move { Some(local) })
+        });
+        FailoverProvider::new(primary, resolver)
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_after_repeated_failures() {
+        let primary = MockProvider::new("Deepgram", true);
+        let local = MockProvider::new("Parakeet", false);
+        let provider = failover(primary.clone(), local.clone());
+
+        // Failing segments are transcribed locally, the switch happens on the second failure
+        let (result, attribution) = provider.transcribe_attributed(vec![0.0; 10], None, None).await.unwrap();
+        assert_eq!(result.text, "Parakeet 10");
+        assert_eq!(attribution.provider, "Parakeet");
+        assert!(!provider.is_on_fallback().await);
+
+        provider.transcribe_attributed(vec![0.0; 10], None, None).await.unwrap();
+        assert!(provider.is_on_fallback().await);
+
+        // Until the next probe the primary is not tried
+        primary.down.store(false, Ordering::SeqCst);
+        let calls = primary.calls.load(Ordering::SeqCst);
+        let (_, attribution) = provider.transcribe_attributed(vec![0.0; 10], None, None).await.unwrap();
+        assert_eq!(attribution.provider, "Parakeet");
+        assert_eq!(primary.calls.load(Ordering::SeqCst), calls);
+    }
+
+    #[tokio::test]
+    async fn test_recovers_and_reruns_gap() {
+        let primary = MockProvider::new("Deepgram", true);
+        let local = MockProvider::new("Whisper", false);
+        let mut provider = failover(primary.clone(), local).with_gap_rerun(true);
+        provider.probe_interval = Duration::ZERO;
+
+        let (_, first) = provider.transcribe_attributed(vec![0.0; 5], None, None).await.unwrap();
+        let (_, second) = provider.transcribe_attributed(vec![0.0; 7], None, None).await.unwrap();
+        assert_eq!(first.gap_id, Some(0));
+        assert_eq!(second.gap_id, Some(1));
+        assert!(provider.is_on_fallback().await);
+
+        // The probe succeeds: back on the primary, the gap is re-run
+        primary.down.store(false, Ordering::SeqCst);
+        let (result, attribution) = provider.transcribe_attributed(vec![0.0; 3], None, None).await.unwrap();
+        assert_eq!(result.text, "Deepgram 3");
+        assert_eq!(attribution, Attribution { provider: "Deepgram", gap_id: None });
+        assert!(!provider.is_on_fallback().await);
+
+        let revisions = provider.take_gap_revisions(true).await;
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].gap_id, 0);
+        assert_eq!(revisions[0].result.text, "Deepgram 5");
+        assert_eq!(revisions[1].result.text, "Deepgram 7");
+        assert!(provider.take_gap_revisions(false).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_without_fallback_returns_primary_error() {
+        let primary = MockProvider::new("Deepgram", true);
+        let resolver: FallbackResolver = Arc::new(|| Box::pin(async { None }));
+        let provider = FailoverProvider::new(primary, resolver);
+
+        let error = provider.transcribe(vec![0.0; 10], None).await.unwrap_err();
+        assert!(error.to_string().contains("connection refused"));
+    }
+}
@@ -5,13 +5,23 @@
 pub mod provider;
 pub mod whisper_provider;
 pub mod parakeet_provider;
+pub mod cloud;
+pub mod openai_provider;
+pub mod elevenlabs_provider;
+pub mod remote_whisper_provider;
+pub mod failover_provider;
 pub mod engine;
 pub mod worker;
+pub mod retranscribe;
 
 // Re-export commonly used types
-pub use provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
+pub use provider::{Attribution, GapRevision, TranscriptionError, TranscriptionProvider, TranscriptResult};
 pub use whisper_provider::WhisperProvider;
 pub use parakeet_provider::ParakeetProvider;
+pub use openai_provider::OpenAiCompatibleProvider;
+pub use elevenlabs_provider::ElevenLabsProvider;
+pub use remote_whisper_provider::{RemoteWhisperConfig, RemoteWhisperProvider};
+pub use failover_provider::{FailoverEvent, FailoverProvider};
 pub use engine::{
     TranscriptionEngine,
     validate_transcription_model_ready,
@@ -23,3 +33,4 @@ pub use worker::{
     reset_speech_detected_flag,
     TranscriptUpdate
 };
+pub use retranscribe::{RetranscribeRequest, RetranscribeResult};
@@ -0,0 +1,282 @@
+// audio/transcription/openai_provider.rs
+//
+// Cloud transcription through OpenAI-compatible `/audio/transcriptions`
+// endpoints: OpenAI (whisper-1, gpt-4o-transcribe, gpt-4o-transcribe-diarize)
+// and Groq (whisper-large-v3, whisper-large-v3-turbo).
+
+use super::cloud::{self, MIN_SAMPLES};
+use super::provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
+use async_trait::async_trait;
+use log::debug;
+use reqwest::multipart::Form;
+use serde::Deserialize;
+
+/// OpenAI API base URL
+pub const OPENAI_API_BASE_URL: &str = "https://api.openai.com/v1";
+
+/// Groq's OpenAI-compatible API base URL
+pub const GROQ_API_BASE_URL: &str = "https://api.groq.com/openai/v1";
+
+pub const OPENAI_DEFAULT_MODEL: &str = "gpt-4o-transcribe";
+pub const GROQ_DEFAULT_MODEL: &str = "whisper-large-v3-turbo";
+
+/// Both APIs cap uploads at 25 MB; 10 minutes of 16-bit 16kHz WAV is ~19 MB
+const MAX_CHUNK_SAMPLES: usize = 16000 * 60 * 10;
+
+// ============================================================================
+// RESPONSE TYPES
+// ============================================================================
+
+/// Response of `json`, `verbose_json` and `diarized_json` formats
+#[derive(Debug, Deserialize)]
+struct TranscriptionResponse {
+    #[serde(default)]
+    text: String,
+    /// verbose_json (Whisper) and diarized_json segments
+    #[serde(default)]
+    segments: Vec<ResponseSegment>,
+    /// Token log probabilities of gpt-4o models (`include[]=logprobs`)
+    #[serde(default)]
+    logprobs: Vec<TokenLogprob>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseSegment {
+    #[serde(default)]
+    text: String,
+    avg_logprob: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenLogprob {
+    logprob: f64,
+}
+
+/// Response format used for a model
+fn response_format(model: &str) -> &'static str {
+    if model.contains("diarize") {
+        "diarized_json"
+    } else if model.starts_with("gpt-") {
+        // gpt-4o models only return json/text
+        "json"
+    } else {
+        // Whisper models: per-segment log probabilities for a confidence score
+        "verbose_json"
+    }
+}
+
+/// Transcript of a response, scored by token or segment log probabilities
+///
+/// Speaker labels of diarized segments are per upload and dropped (see `cloud`).
+fn map_response(response: TranscriptionResponse) -> TranscriptResult {
+    let text = if response.text.trim().is_empty() {
+        response
+            .segments
+            .iter()
+            .map(|s| s.text.trim())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        response.text.trim().to_string()
+    };
+
+    let confidence = if response.logprobs.is_empty() {
+        cloud::mean_probability(response.segments.iter().filter_map(|s| s.avg_logprob))
+    } else {
+        cloud::mean_probability(response.logprobs.iter().map(|t| t.logprob))
+    };
+
+    TranscriptResult {
+        text,
+        confidence,
+        is_partial: false,
+        words: Vec::new(),
+    }
+}
+
+// ============================================================================
+// OPENAI-COMPATIBLE PROVIDER
+// ============================================================================
+
+/// Uploads VAD segments to an OpenAI-compatible transcription endpoint
+pub struct OpenAiCompatibleProvider {
+    /// Vendor name used in logs and errors ("OpenAI", "Groq")
+    vendor: &'static str,
+    client: reqwest::Client,
+    api_key: String,
+    model: String,
+    base_url: String,
+}
+
+impl OpenAiCompatibleProvider {
+    /// OpenAI provider; an empty model selects gpt-4o-transcribe
+    pub fn openai(api_key: String, model: String) -> Self {
+        Self::with_base_url("OpenAI", api_key, model, OPENAI_API_BASE_URL.to_string())
+    }
+
+    /// Groq provider; an empty model selects whisper-large-v3-turbo
+    pub fn groq(api_key: String, model: String) -> Self {
+        Self::with_base_url("Groq", api_key, model, GROQ_API_BASE_URL.to_string())
+    }
+
+    /// Provider against a custom base URL (proxies, self-hosted gateways or a mock server)
+    pub fn with_base_url(vendor: &'static str, api_key: String, model: String, base_url: String) -> Self {
+        let model = if model.trim().is_empty() {
+            match vendor {
+                "Groq" => GROQ_DEFAULT_MODEL.to_string(),
+                _ => OPENAI_DEFAULT_MODEL.to_string(),
+            }
+        } else {
+            model.trim().to_string()
+        };
+
+        Self {
+            vendor,
+            client: cloud::http_client(),
+            api_key,
+            model,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Upload one chunk to `/audio/transcriptions` in the model's response format
+    async fn transcribe_chunk(&self, samples: &[f32], language: Option<&str>) -> Result<TranscriptResult, String> {
+        let url = format!("{}/audio/transcriptions", self.base_url);
+        let format = response_format(&self.model);
+
+        let body = cloud::send_with_retry(self.vendor, || {
+            let mut form = Form::new()
+                .part("file", cloud::wav_part(samples)?)
+                .text("model", self.model.clone())
+                .text("response_format", format)
+                .text("temperature", "0");
+            if let Some(language) = language {
+                form = form.text("language", language.to_string());
+            }
+            match format {
+                "diarized_json" => form = form.text("chunking_strategy", "auto"),
+                "json" => form = form.text("include[]", "logprobs"),
+                _ => {}
+            }
+            Ok((self.client.post(&url).bearer_auth(&self.api_key), form))
+        })
+        .await?;
+
+        let response: TranscriptionResponse = serde_json::from_str(&body)
+            .map_err(|e| format!("Failed to parse {} response: {}", self.vendor, e))?;
+        Ok(map_response(response))
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for OpenAiCompatibleProvider {
+    /// Transcribe a VAD segment, splitting it into upload-sized chunks if needed
+    async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        if self.api_key.is_empty() {
+            return Err(TranscriptionError::EngineFailed(format!(
+                "{} API key not configured",
+                self.vendor
+            )));
+        }
+        if audio.len() < MIN_SAMPLES {
+            return Err(TranscriptionError::AudioTooShort {
+                samples: audio.len(),
+                minimum: MIN_SAMPLES,
+            });
+        }
+
+        let language = cloud::language_hint(language.as_deref());
+        let chunks = cloud::split_into_chunks(&audio, MAX_CHUNK_SAMPLES);
+        debug!(
+            "{}: transcribing {} samples in {} chunk(s) with '{}', language: {:?}",
+            self.vendor,
+            audio.len(),
+            chunks.len(),
+            self.model,
+            language
+        );
+
+        cloud::transcribe_chunks(self.vendor, chunks, |chunk| self.transcribe_chunk(chunk, language.as_deref())).await
+    }
+
+    /// Cloud service - "loaded" when an API key is set
+    async fn is_model_loaded(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+
+    async fn get_current_model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.vendor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_models() {
+        let openai = OpenAiCompatibleProvider::openai("key".to_string(), String::new());
+        assert_eq!(openai.model, OPENAI_DEFAULT_MODEL);
+        assert_eq!(openai.provider_name(), "OpenAI");
+
+        let groq = OpenAiCompatibleProvider::groq("key".to_string(), " whisper-large-v3 ".to_string());
+        assert_eq!(groq.model, "whisper-large-v3");
+        assert_eq!(groq.base_url, GROQ_API_BASE_URL);
+        assert_eq!(groq.provider_name(), "Groq");
+    }
+
+    #[test]
+    fn test_response_format() {
+        assert_eq!(response_format("whisper-1"), "verbose_json");
+        assert_eq!(response_format("whisper-large-v3-turbo"), "verbose_json");
+        assert_eq!(response_format("gpt-4o-transcribe"), "json");
+        assert_eq!(response_format("gpt-4o-transcribe-diarize"), "diarized_json");
+    }
+
+    #[test]
+    fn test_map_verbose_response() {
+        let body = r#"{
+            "text": " Hello there. ",
+            "language": "english",
+            "segments": [
+                {"id": 0, "text": " Hello", "avg_logprob": -0.1},
+                {"id": 1, "text": " there.", "avg_logprob": -0.3}
+            ]
+        }"#;
+        let result = map_response(serde_json::from_str(body).unwrap());
+        assert_eq!(result.text, "Hello there.");
+        let confidence = result.confidence.unwrap();
+        assert!(confidence > 0.7 && confidence < 0.9);
+    }
+
+    #[test]
+    fn test_map_diarized_response() {
+        let body = r#"{
+            "text": "",
+            "segments": [
+                {"type": "transcript.text.segment", "speaker": "A", "text": "Hi.", "start": 0.0, "end": 0.5},
+                {"type": "transcript.text.segment", "speaker": "B", "text": "Hello.", "start": 0.6, "end": 1.0},
+                {"type": "transcript.text.segment", "speaker": "B", "text": "Shall we start?", "start": 1.1, "end": 2.0}
+            ]
+        }"#;
+        let result = map_response(serde_json::from_str(body).unwrap());
+        // Labels are per upload and can't be matched across VAD segments
+        assert_eq!(result.text, "Hi. Hello. Shall we start?");
+        assert_eq!(result.confidence, None);
+    }
+
+    #[tokio::test]
+    async fn test_is_model_loaded() {
+        assert!(OpenAiCompatibleProvider::openai("key".to_string(), String::new()).is_model_loaded().await);
+        assert!(!OpenAiCompatibleProvider::groq(String::new(), String::new()).is_model_loaded().await);
+    }
+}
@@ -43,6 +43,27 @@ pub struct TranscriptResult {
     pub text: String,
     pub confidence: Option<f32>, // None if provider doesn't support confidence scores
     pub is_partial: bool,
+    /// Word timings in seconds from the start of the transcribed audio
+    /// (empty if the provider doesn't report them)
+    pub words: Vec<crate::api::WordTiming>,
+}
+
+/// Which engine produced a result (composite providers pick per segment)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribution {
+    /// `provider_name()` of the engine that transcribed the audio
+    pub provider: &'static str,
+    /// Set when the segment may later be revised by a better engine
+    pub gap_id: Option<u64>,
+}
+
+/// A segment transcribed again after the fact, replacing an earlier result
+#[derive(Debug, Clone)]
+pub struct GapRevision {
+    /// `gap_id` of the original result's attribution
+    pub gap_id: u64,
+    pub provider: &'static str,
+    pub result: TranscriptResult,
 }
 
 /// Trait for transcription providers (Whisper, Parakeet, future providers)
@@ -70,4 +91,50 @@ pub trait TranscriptionProvider: Send + Sync {
 
     /// Get the provider name (for logging/debugging)
     fn provider_name(&self) -> &'static str;
+
+    /// Whether results come with `[Speaker N]:` labels of their own. Providers
+    /// without them get speakers from the on-device diarization model.
+    fn labels_speakers(&self) -> bool {
+        false
+    }
+
+    /// Transcribe a segment that starts `recording_time` seconds into a live
+    /// recording. Streaming providers keep one session for the whole recording;
+    /// others transcribe the segment on its own.
+    async fn transcribe_at(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+        _recording_time: f64,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        self.transcribe(audio, language).await
+    }
+
+    /// The live recording ended; release anything kept open for it
+    async fn finish_recording(&self) {}
+
+    /// Transcribe and report which engine produced the result.
+    /// With `recording_time`, the audio is a segment of the live recording.
+    async fn transcribe_attributed(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+        recording_time: Option<f64>,
+    ) -> std::result::Result<(TranscriptResult, Attribution), TranscriptionError> {
+        let result = match recording_time {
+            Some(time) => self.transcribe_at(audio, language, time).await?,
+            None => self.transcribe(audio, language).await?,
+        };
+        let attribution = Attribution {
+            provider: self.provider_name(),
+            gap_id: None,
+        };
+        Ok((result, attribution))
+    }
+
+    /// Revisions of earlier results that became available since the last call.
+    /// With `wait`, pending revisions are finished first (end of recording).
+    async fn take_gap_revisions(&self, _wait: bool) -> Vec<GapRevision> {
+        Vec::new()
+    }
 }
@@ -0,0 +1,382 @@
+// audio/transcription/remote_whisper_provider.rs
+//
+// Transcription on a whisper.cpp-compatible HTTP server (see
+// backend/whisper-custom/server). VAD segments are uploaded to its
+// `/inference` endpoint, so machines without a GPU can offload to a shared
+// server on the LAN instead of a third-party cloud.
+
+use super::cloud::{self, MIN_SAMPLES};
+use super::provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
+use async_trait::async_trait;
+use log::{debug, info};
+use reqwest::multipart::Form;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// Name used in logs and errors
+const VENDOR: &str = "whisper.cpp server";
+
+/// Path appended when the endpoint is just a server address
+const DEFAULT_INFERENCE_PATH: &str = "/inference";
+
+pub const DEFAULT_TIMEOUT_SECS: u64 = 60;
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 2;
+
+/// Keep single requests well inside the timeout
+const MAX_CHUNK_SAMPLES: usize = 16000 * 60 * 5;
+
+/// Remote whisper.cpp server configuration
+/// Stored as JSON in the database next to the transcript settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteWhisperConfig {
+    /// Server address or full inference URL (e.g., "http://gpu-box:8178" or "https://whisper.lan/inference")
+    pub endpoint: String,
+    /// Token for a proxy in front of the server (optional, whisper.cpp itself has no auth)
+    #[serde(rename = "apiKey")]
+    pub api_key: Option<String>,
+    /// Header carrying the token; "Authorization" (default) sends `Bearer <token>`,
+    /// any other header (e.g., "X-API-Key") the raw token
+    #[serde(rename = "authHeader")]
+    pub auth_header: Option<String>,
+    /// Request timeout in seconds (default 60)
+    #[serde(rename = "timeoutSecs")]
+    pub timeout_secs: Option<u64>,
+    /// Maximum simultaneous requests from this app (default 2)
+    #[serde(rename = "maxConcurrentRequests")]
+    pub max_concurrent_requests: Option<usize>,
+}
+
+impl RemoteWhisperConfig {
+    /// Full inference URL; `/inference` is appended when the endpoint has no path
+    pub fn inference_url(&self) -> Result<String, String> {
+        let mut url = url::Url::parse(self.endpoint.trim())
+            .map_err(|e| format!("Invalid server URL '{}': {}", self.endpoint, e))?;
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err("Server URL must start with http:// or https://".to_string());
+        }
+        if url.path() == "/" || url.path().is_empty() {
+            url.set_path(DEFAULT_INFERENCE_PATH);
+        }
+        Ok(url.to_string())
+    }
+
+    /// Header name and value for the configured token
+    fn auth(&self) -> Option<(String, String)> {
+        let token = self.api_key.as_deref().map(str::trim).filter(|t| !t.is_empty())?;
+        let header = self
+            .auth_header
+            .as_deref()
+            .map(str::trim)
+            .filter(|h| !h.is_empty())
+            .unwrap_or("Authorization");
+        let value = if header.eq_ignore_ascii_case("authorization") && !token.contains(' ') {
+            format!("Bearer {}", token)
+        } else {
+            token.to_string()
+        };
+        Some((header.to_string(), value))
+    }
+
+    /// Check the URL and limits before saving
+    pub fn validate(&self) -> Result<(), String> {
+        self.inference_url()?;
+        if self.timeout_secs == Some(0) {
+            return Err("Timeout must be at least 1 second".to_string());
+        }
+        if self.max_concurrent_requests == Some(0) {
+            return Err("Concurrent requests must be at least 1".to_string());
+        }
+        if let Some(header) = self.auth_header.as_deref().filter(|h| !h.trim().is_empty()) {
+            reqwest::header::HeaderName::from_bytes(header.trim().as_bytes())
+                .map_err(|_| format!("Invalid auth header name '{}'", header))?;
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// RESPONSE TYPES
+// ============================================================================
+
+/// `verbose_json` response; failures come back as `{"error": "..."}`
+#[derive(Debug, Deserialize)]
+struct InferenceResponse {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    segments: Vec<InferenceSegment>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InferenceSegment {
+    #[serde(default)]
+    text: String,
+    avg_logprob: Option<f64>,
+    no_speech_prob: Option<f64>,
+}
+
+impl InferenceSegment {
+    /// Whisper's own silence rule: likely no speech and a low-confidence decode
+    fn is_silence(&self) -> bool {
+        self.no_speech_prob.unwrap_or(0.0) > 0.6 && self.avg_logprob.unwrap_or(0.0) < -1.0
+    }
+}
+
+/// Transcript of an inference response without the segments Whisper marks as silence
+fn map_response(response: InferenceResponse) -> Result<TranscriptResult, String> {
+    if let Some(error) = response.error {
+        return Err(format!("{} error: {}", VENDOR, error));
+    }
+
+    if response.segments.is_empty() {
+        return Ok(TranscriptResult {
+            text: response.text.trim().to_string(),
+            confidence: None,
+            is_partial: false,
+            words: Vec::new(),
+        });
+    }
+
+    let speech: Vec<&InferenceSegment> = response.segments.iter().filter(|s| !s.is_silence()).collect();
+    Ok(TranscriptResult {
+        text: speech
+            .iter()
+            .map(|s| s.text.trim())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" "),
+        confidence: cloud::mean_probability(speech.iter().filter_map(|s| s.avg_logprob)),
+        is_partial: false,
+        words: Vec::new(),
+    })
+}
+
+// ============================================================================
+// REMOTE WHISPER PROVIDER
+// ============================================================================
+
+/// Uploads VAD segments to a whisper.cpp server's inference endpoint
+pub struct RemoteWhisperProvider {
+    client: reqwest::Client,
+    url: String,
+    auth: Option<(String, String)>,
+    /// Label reported as the current model
+    model: String,
+    /// Limits simultaneous requests so a shared server isn't flooded
+    requests: Arc<Semaphore>,
+}
+
+impl RemoteWhisperProvider {
+    /// Create a provider from the saved configuration
+    pub fn new(config: &RemoteWhisperConfig, model: String) -> Result<Self, String> {
+        config.validate()?;
+        let timeout = Duration::from_secs(config.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+        let max_concurrent = config
+            .max_concurrent_requests
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS);
+
+        Ok(Self {
+            client,
+            url: config.inference_url()?,
+            auth: config.auth(),
+            model: if model.trim().is_empty() {
+                "whisper.cpp".to_string()
+            } else {
+                model.trim().to_string()
+            },
+            requests: Arc::new(Semaphore::new(max_concurrent)),
+        })
+    }
+
+    /// Server URL requests are sent to
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Post one chunk to the inference endpoint, waiting for a free request slot
+    async fn transcribe_chunk(
+        &self,
+        samples: &[f32],
+        language: &str,
+        translate: bool,
+    ) -> Result<TranscriptResult, String> {
+        let _permit = self
+            .requests
+            .acquire()
+            .await
+            .map_err(|_| format!("{} provider is shutting down", VENDOR))?;
+
+        let body = cloud::send_with_retry(VENDOR, || {
+            let mut form = Form::new()
+                .part("file", cloud::wav_part(samples)?)
+                .text("response_format", "verbose_json")
+                .text("temperature", "0.0")
+                .text("language", language.to_string());
+            if translate {
+                form = form.text("translate", "true");
+            }
+            let mut request = self.client.post(&self.url);
+            if let Some((header, value)) = &self.auth {
+                request = request.header(header.as_str(), value.as_str());
+            }
+            Ok((request, form))
+        })
+        .await?;
+
+        let response: InferenceResponse = serde_json::from_str(&body)
+            .map_err(|e| format!("Failed to parse {} response: {}", VENDOR, e))?;
+        map_response(response)
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for RemoteWhisperProvider {
+    /// Transcribe a VAD segment on the server
+    ///
+    /// "auto-translate" translates to English like the local Whisper engine.
+    async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        if audio.len() < MIN_SAMPLES {
+            return Err(TranscriptionError::AudioTooShort {
+                samples: audio.len(),
+                minimum: MIN_SAMPLES,
+            });
+        }
+
+        let translate = language.as_deref() == Some("auto-translate");
+        let language = cloud::language_hint(language.as_deref()).unwrap_or_else(|| "auto".to_string());
+        let chunks = cloud::split_into_chunks(&audio, MAX_CHUNK_SAMPLES);
+        debug!(
+            "Remote Whisper: sending {} samples in {} chunk(s) to {}, language: {}, translate: {}",
+            audio.len(),
+            chunks.len(),
+            self.url,
+            language,
+            translate
+        );
+
+        cloud::transcribe_chunks(VENDOR, chunks, |chunk| self.transcribe_chunk(chunk, &language, translate)).await
+    }
+
+    /// The server is only checked when a request is made
+    async fn is_model_loaded(&self) -> bool {
+        true
+    }
+
+    async fn get_current_model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "RemoteWhisper"
+    }
+}
+
+/// Send one second of silence to check the server is reachable and answers
+pub async fn test_connection(config: &RemoteWhisperConfig) -> Result<Duration, String> {
+    let provider = RemoteWhisperProvider::new(config, String::new())?;
+    info!("Testing remote Whisper server at {}", provider.url());
+    let started = std::time::Instant::now();
+    provider
+        .transcribe_chunk(&vec![0.0; 16000], "auto", false)
+        .await?;
+    Ok(started.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(endpoint: &str) -> RemoteWhisperConfig {
+        RemoteWhisperConfig {
+            endpoint: endpoint.to_string(),
+            api_key: None,
+            auth_header: None,
+            timeout_secs: None,
+            max_concurrent_requests: None,
+        }
+    }
+
+    #[test]
+    fn test_inference_url() {
+        assert_eq!(config("http://gpu-box:8178").inference_url().unwrap(), "http://gpu-box:8178/inference");
+        assert_eq!(config("http://gpu-box:8178/").inference_url().unwrap(), "http://gpu-box:8178/inference");
+        assert_eq!(
+            config("https://whisper.lan/api/transcribe").inference_url().unwrap(),
+            "https://whisper.lan/api/transcribe"
+        );
+        assert!(config("gpu-box:8178").inference_url().is_err());
+        assert!(config("ftp://gpu-box").inference_url().is_err());
+    }
+
+    #[test]
+    fn test_auth_header() {
+        let mut cfg = config("http://gpu-box:8178");
+        assert_eq!(cfg.auth(), None);
+
+        cfg.api_key = Some("secret".to_string());
+        assert_eq!(cfg.auth(), Some(("Authorization".to_string(), "Bearer secret".to_string())));
+
+        cfg.api_key = Some("Basic dXNlcjpwYXNz".to_string());
+        assert_eq!(cfg.auth().unwrap().1, "Basic dXNlcjpwYXNz");
+
+        cfg.auth_header = Some("X-API-Key".to_string());
+        cfg.api_key = Some("secret".to_string());
+        assert_eq!(cfg.auth(), Some(("X-API-Key".to_string(), "secret".to_string())));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(config("http://gpu-box:8178").validate().is_ok());
+        let mut cfg = config("http://gpu-box:8178");
+        cfg.max_concurrent_requests = Some(0);
+        assert!(cfg.validate().is_err());
+        let mut cfg = config("http://gpu-box:8178");
+        cfg.auth_header = Some("Bad Header".to_string());
+        assert!(cfg.validate().is_err());
+    }
+
+    #[test]
+    fn test_map_response() {
+        let body = r#"{
+            "task": "transcribe",
+            "language": "english",
+            "text": " Hello there. Thank you.",
+            "segments": [
+                {"id": 0, "text": " Hello there.", "avg_logprob": -0.2, "no_speech_prob": 0.01},
+                {"id": 1, "text": " Thank you.", "avg_logprob": -1.4, "no_speech_prob": 0.9}
+            ]
+        }"#;
+        let result = map_response(serde_json::from_str(body).unwrap()).unwrap();
+        assert_eq!(result.text, "Hello there.");
+        assert!((result.confidence.unwrap() - (-0.2f64).exp() as f32).abs() < 1e-5);
+
+        let plain = map_response(serde_json::from_str(r#"{"text": " Hi. "}"#).unwrap()).unwrap();
+        assert_eq!(plain.text, "Hi.");
+
+        let error = map_response(serde_json::from_str(r#"{"error": "failed to process audio"}"#).unwrap());
+        assert!(error.unwrap_err().contains("failed to process audio"));
+    }
+
+    #[tokio::test]
+    async fn test_provider_basics() {
+        let provider = RemoteWhisperProvider::new(&config("http://gpu-box:8178"), String::new()).unwrap();
+        assert_eq!(provider.provider_name(), "RemoteWhisper");
+        assert!(provider.is_model_loaded().await);
+        assert_eq!(provider.get_current_model().await.as_deref(), Some("whisper.cpp"));
+        assert!(matches!(
+            provider.transcribe(vec![0.0; 100], None).await,
+            Err(TranscriptionError::AudioTooShort { .. })
+        ));
+    }
+}
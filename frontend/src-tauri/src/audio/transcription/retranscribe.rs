@@ -0,0 +1,630 @@
+// audio/transcription/retranscribe.rs
+//
+// Offline re-transcription of a finished meeting. Decodes the merged audio
+// file written by IncrementalAudioSaver::finalize, splits it into speech
+// segments with VAD and runs them through a local provider (or uploads the
+// whole file to Deepgram's prerecorded API), then replaces the meeting's
+// transcript rows and transcripts.json.
+
+use super::provider::TranscriptionProvider;
+use super::worker::format_recording_time;
+use super::{ParakeetProvider, WhisperProvider};
+use crate::api::{TranscriptSegment as DbTranscriptSegment, WordTiming};
+use crate::audio::ffmpeg::find_ffmpeg_path;
+use crate::audio::recording_saver::TranscriptSegment as JsonTranscriptSegment;
+use crate::audio::diarization::{diarize_segments, format_text_with_speaker, get_or_load_diarization_model};
+use crate::audio::vad::get_speech_chunks;
+use crate::database::repositories::{
+    meeting::MeetingsRepository, transcript::TranscriptsRepository,
+};
+use crate::deepgram::prerecorded::to_word_timings;
+use crate::deepgram::provider::format_segment_with_speakers;
+use crate::deepgram::{DeepgramPrerecordedClient, PrerecordedOptions};
+use crate::state::AppState;
+use anyhow::{anyhow, Result};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// VAD redemption time used for offline passes. Matches the live pipeline so
+/// segment boundaries look the same as a live recording.
+const RETRANSCRIBE_REDEMPTION_MS: u32 = 2000;
+
+/// Audio file extensions accepted when metadata.json does not name the file
+const AUDIO_EXTENSIONS: [&str; 5] = ["mp4", "m4a", "wav", "mp3", "ogg"];
+
+// ============================================================================
+// REQUEST / RESULT TYPES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetranscribeRequest {
+    pub meeting_id: String,
+    /// "localWhisper", "parakeet" or "deepgram"
+    pub provider: String,
+    /// Model to use; falls back to the loaded / configured model when None
+    pub model: Option<String>,
+    /// Language hint; falls back to the global language preference when None
+    pub language: Option<String>,
+    /// Keep the previous transcripts.json as a timestamped copy (default true)
+    pub keep_previous_version: Option<bool>,
+    /// Label speakers with the local diarization model when installed (default true).
+    /// Deepgram always diarizes server-side.
+    pub diarize: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetranscribeResult {
+    pub meeting_id: String,
+    pub provider: String,
+    pub model: Option<String>,
+    pub segment_count: usize,
+    pub duration_seconds: f64,
+    pub transcript_file: String,
+    pub previous_version_file: Option<String>,
+}
+
+/// Progress payload for the `retranscription-progress` event
+#[derive(Debug, Clone, Serialize)]
+pub struct RetranscribeProgress {
+    pub meeting_id: String,
+    pub processed_segments: usize,
+    pub total_segments: usize,
+    pub percentage: u32,
+}
+
+// ============================================================================
+// AUDIO LOADING
+// ============================================================================
+
+/// Locate the final audio file of a meeting folder.
+/// Prefers the file named in metadata.json, then audio.mp4, then any audio file.
+pub fn find_meeting_audio_file(folder: &Path) -> Option<PathBuf> {
+    let metadata_path = folder.join("metadata.json");
+    if let Ok(content) = std::fs::read_to_string(&metadata_path) {
+        if let Ok(metadata) = serde_json::from_str::<serde_json::Value>(&content) {
+            if let Some(name) = metadata.get("audio_file").and_then(|v| v.as_str()) {
+                if !name.is_empty() && folder.join(name).is_file() {
+                    return Some(folder.join(name));
+                }
+            }
+        }
+    }
+
+    let default_path = folder.join("audio.mp4");
+    if default_path.is_file() {
+        return Some(default_path);
+    }
+
+    let mut candidates: Vec<PathBuf> = std::fs::read_dir(folder)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .map(|ext| AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+                    .unwrap_or(false)
+        })
+        .collect();
+    candidates.sort();
+    candidates.into_iter().next()
+}
+
+/// Decode any FFmpeg-readable audio file to 16kHz mono f32 samples
+pub fn decode_audio_file_16k(path: &Path) -> Result<Vec<f32>> {
+    let ffmpeg_path = find_ffmpeg_path()
+        .ok_or_else(|| anyhow!("FFmpeg not found. Please install FFmpeg to re-transcribe recordings."))?;
+
+    let input = path
+        .to_str()
+        .ok_or_else(|| anyhow!("Invalid audio path: {}", path.display()))?;
+
+    let mut command = std::process::Command::new(ffmpeg_path);
+    command.args([
+        "-v", "error",
+        "-i", input,
+        "-vn",               // Ignore any video stream
+        "-ac", "1",          // Mono
+        "-ar", "16000",      // 16kHz (transcription engines expect this)
+        "-f", "f32le",       // Raw little-endian f32
+        "pipe:1",
+    ]);
+
+    // Hide console window on Windows
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command.output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        error!("FFmpeg decode failed: {}", stderr);
+        return Err(anyhow!("Failed to decode audio: {}", stderr));
+    }
+
+    let samples: Vec<f32> = output
+        .stdout
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+
+    if samples.is_empty() {
+        return Err(anyhow!("Audio file contains no samples: {}", path.display()));
+    }
+
+    Ok(samples)
+}
+
+// ============================================================================
+// PROVIDER RESOLUTION
+// ============================================================================
+
+/// Build a local provider for an explicit provider/model choice, independent
+/// of the live transcription configuration. A model other than the one the
+/// live engine has loaded gets its own engine instance, so the live engine
+/// keeps its model.
+async fn resolve_local_provider<R: Runtime>(
+    app: &AppHandle<R>,
+    provider: &str,
+    model: Option<&str>,
+) -> Result<Arc<dyn TranscriptionProvider>, String> {
+    match provider {
+        "localWhisper" => {
+            crate::whisper_engine::commands::whisper_init().await?;
+            let live = crate::whisper_engine::commands::WHISPER_ENGINE
+                .lock()
+                .unwrap()
+                .as_ref()
+                .cloned()
+                .ok_or("Whisper engine not initialized")?;
+            let engine = match model {
+                Some(model_name) if live.get_current_model().await.as_deref() != Some(model_name) => {
+                    info!("🔄 Loading Whisper model '{}' in a separate engine for re-transcription", model_name);
+                    let engine = crate::whisper_engine::WhisperEngine::new_with_models_dir(
+                        crate::whisper_engine::commands::get_models_directory(),
+                    )
+                    .map_err(|e| format!("Failed to initialize whisper engine: {}", e))?;
+                    engine
+                        .discover_models()
+                        .await
+                        .map_err(|e| format!("Failed to discover models: {}", e))?;
+                    engine
+                        .load_model(model_name)
+                        .await
+                        .map_err(|e| format!("Failed to load model '{}': {}", model_name, e))?;
+                    Arc::new(engine)
+                }
+                Some(_) => live,
+                None => super::get_or_init_whisper(app).await?,
+            };
+            Ok(Arc::new(WhisperProvider::new(engine)))
+        }
+        "parakeet" => {
+            crate::parakeet_engine::commands::parakeet_init().await?;
+            let live = crate::parakeet_engine::commands::PARAKEET_ENGINE
+                .lock()
+                .unwrap()
+                .as_ref()
+                .cloned()
+                .ok_or("Parakeet engine not initialized")?;
+            let engine = match model {
+                Some(model_name) if live.get_current_model().await.as_deref() != Some(model_name) => {
+                    info!("🔄 Loading Parakeet model '{}' in a separate engine for re-transcription", model_name);
+                    let engine = crate::parakeet_engine::ParakeetEngine::new_with_models_dir(
+                        crate::parakeet_engine::commands::get_models_directory(),
+                    )
+                    .map_err(|e| format!("Failed to initialize Parakeet engine: {}", e))?;
+                    engine
+                        .discover_models()
+                        .await
+                        .map_err(|e| format!("Failed to discover Parakeet models: {}", e))?;
+                    engine
+                        .load_model(model_name)
+                        .await
+                        .map_err(|e| format!("Failed to load Parakeet model '{}': {}", model_name, e))?;
+                    Arc::new(engine)
+                }
+                Some(_) => live,
+                None => {
+                    if !live.is_model_loaded().await {
+                        crate::parakeet_engine::commands::parakeet_validate_model_ready_with_config(app)
+                            .await?;
+                    }
+                    live
+                }
+            };
+            Ok(Arc::new(ParakeetProvider::new(engine)))
+        }
+        other => Err(format!(
+            "Provider '{}' is not supported for re-transcription. Please select 'deepgram', 'localWhisper', or 'parakeet'.",
+            other
+        )),
+    }
+}
+
+// ============================================================================
+// TRANSCRIPTS.JSON VERSIONING
+// ============================================================================
+
+/// Write transcripts.json in the same layout as RecordingSaver.
+/// When `keep_previous` is set, the existing file is first copied to
+/// `transcripts.<timestamp>.json` (it stays in place until the atomic
+/// replace) and that path is returned.
+pub fn write_transcripts_json_versioned(
+    folder: &Path,
+    segments: &[JsonTranscriptSegment],
+    provider: &str,
+    model: Option<&str>,
+    keep_previous: bool,
+) -> Result<Option<PathBuf>> {
+    let transcript_path = folder.join("transcripts.json");
+    let temp_path = folder.join(".transcripts.json.tmp");
+
+    let previous_version = if keep_previous && transcript_path.exists() {
+        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let backup_path = folder.join(format!("transcripts.{}.json", stamp));
+        std::fs::copy(&transcript_path, &backup_path)?;
+        info!("📦 Kept previous transcript as {}", backup_path.display());
+        Some(backup_path)
+    } else {
+        None
+    };
+
+    let json = serde_json::json!({
+        "version": "1.0",
+        "segments": segments,
+        "last_updated": chrono::Utc::now().to_rfc3339(),
+        "total_segments": segments.len(),
+        "transcribed_with": {
+            "provider": provider,
+            "model": model,
+            "offline": true
+        }
+    });
+
+    std::fs::write(&temp_path, serde_json::to_string_pretty(&json)?)?;
+    std::fs::rename(&temp_path, &transcript_path)?; // Atomic
+
+    Ok(previous_version)
+}
+
+// ============================================================================
+// RE-TRANSCRIPTION
+// ============================================================================
+
+/// A transcribed stretch of the recording, in seconds from recording start
+struct TranscribedSpan {
+    start: f64,
+    end: f64,
+    text: String,
+    confidence: Option<f32>,
+    words: Option<Vec<WordTiming>>,
+}
+
+fn emit_progress<R: Runtime>(app: &AppHandle<R>, meeting_id: &str, processed: usize, total: usize) {
+    let progress = RetranscribeProgress {
+        meeting_id: meeting_id.to_string(),
+        processed_segments: processed,
+        total_segments: total,
+        percentage: ((processed * 100) / total.max(1)) as u32,
+    };
+    if let Err(e) = app.emit("retranscription-progress", &progress) {
+        warn!("Failed to emit retranscription-progress event: {}", e);
+    }
+}
+
+/// Decode, split with VAD and transcribe segment by segment with Whisper or Parakeet
+async fn transcribe_with_local_provider<R: Runtime>(
+    app: &AppHandle<R>,
+    request: &RetranscribeRequest,
+    audio_path: &Path,
+) -> Result<(Vec<TranscribedSpan>, f64, Option<String>), String> {
+    let provider = resolve_local_provider(app, &request.provider, request.model.as_deref()).await?;
+    let model = provider.get_current_model().await.or_else(|| request.model.clone());
+
+    let decode_path = audio_path.to_path_buf();
+    let samples = tokio::task::spawn_blocking(move || decode_audio_file_16k(&decode_path))
+        .await
+        .map_err(|e| format!("Audio decode task failed: {}", e))?
+        .map_err(|e| e.to_string())?;
+    let duration_seconds = samples.len() as f64 / 16000.0;
+
+    let speech_segments = tokio::task::spawn_blocking(move || {
+        get_speech_chunks(&samples, RETRANSCRIBE_REDEMPTION_MS)
+    })
+    .await
+    .map_err(|e| format!("VAD task failed: {}", e))?
+    .map_err(|e| format!("VAD failed: {}", e))?;
+
+    let total_segments = speech_segments.len();
+    info!("🗣️ VAD found {} speech segments in {:.1}s of audio", total_segments, duration_seconds);
+
+    // Label VAD segments with speaker clusters before the samples are handed to the provider
+    let diarization_model = if request.diarize.unwrap_or(true) {
+        get_or_load_diarization_model(app)
+    } else {
+        None
+    };
+    let (speech_segments, speakers) = match diarization_model {
+        Some(model) => tokio::task::spawn_blocking(move || {
+            let speakers = diarize_segments(&model, &speech_segments);
+            (speech_segments, speakers)
+        })
+        .await
+        .map_err(|e| format!("Diarization task failed: {}", e))?,
+        None => {
+            let speakers = vec![None; speech_segments.len()];
+            (speech_segments, speakers)
+        }
+    };
+
+    let language = request
+        .language
+        .clone()
+        .or_else(crate::get_language_preference_internal)
+        .filter(|lang| lang != "auto");
+
+    let mut spans = Vec::new();
+    for (index, segment) in speech_segments.into_iter().enumerate() {
+        let start = segment.start_timestamp_ms / 1000.0;
+        let end = segment.end_timestamp_ms / 1000.0;
+
+        match provider.transcribe(segment.samples, language.clone()).await {
+            Ok(result) if !result.text.trim().is_empty() => spans.push(TranscribedSpan {
+                start,
+                end,
+                text: format_text_with_speaker(speakers[index], &result.text),
+                confidence: result.confidence,
+                words: None,
+            }),
+            Ok(_) => {}
+            Err(e) => {
+                // A partial transcript would silently drop speech; keep the existing one instead
+                return Err(format!(
+                    "Segment {} ({:.1}s-{:.1}s) failed to transcribe: {}. The existing transcript was left unchanged",
+                    index + 1,
+                    start,
+                    end,
+                    e
+                ));
+            }
+        }
+
+        emit_progress(app, &request.meeting_id, index + 1, total_segments);
+    }
+
+    Ok((spans, duration_seconds, model))
+}
+
+/// Upload the whole recording to Deepgram's prerecorded API in one request
+async fn transcribe_with_deepgram<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &AppState,
+    request: &RetranscribeRequest,
+    audio_path: &Path,
+) -> Result<(Vec<TranscribedSpan>, f64, Option<String>), String> {
+    let api_key = crate::deepgram::commands::get_deepgram_api_key(state).await?;
+    let client = DeepgramPrerecordedClient::new(api_key);
+
+    let defaults = PrerecordedOptions::default();
+    let options = PrerecordedOptions {
+        model: request.model.clone().unwrap_or(defaults.model.clone()),
+        language: request.language.clone().or_else(crate::get_language_preference_internal),
+        ..defaults
+    };
+
+    emit_progress(app, &request.meeting_id, 0, 1);
+    let transcript = client.transcribe_file(audio_path, &options).await?;
+    emit_progress(app, &request.meeting_id, 1, 1);
+
+    let spans = transcript
+        .segments
+        .iter()
+        .map(|segment| {
+            let start = segment.start_time.unwrap_or(0.0);
+            TranscribedSpan {
+                start,
+                end: segment.end_time.unwrap_or(start),
+                text: format_segment_with_speakers(segment),
+                confidence: segment.confidence,
+                words: Some(to_word_timings(&segment.words)).filter(|w| !w.is_empty()),
+            }
+        })
+        .filter(|span| !span.text.trim().is_empty())
+        .collect();
+
+    let duration_seconds = transcript
+        .duration
+        .or_else(|| transcript.segments.last().and_then(|s| s.end_time))
+        .unwrap_or(0.0);
+
+    Ok((spans, duration_seconds, Some(options.model)))
+}
+
+/// Re-run a finished meeting through the requested provider
+pub async fn retranscribe_meeting_folder<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &AppState,
+    request: &RetranscribeRequest,
+) -> Result<RetranscribeResult, String> {
+    let pool = state.db_manager.pool();
+
+    let meeting = MeetingsRepository::get_meeting_metadata(pool, &request.meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", request.meeting_id))?;
+
+    let folder = meeting
+        .folder_path
+        .map(PathBuf::from)
+        .filter(|path| path.is_dir())
+        .ok_or("Meeting has no recording folder on disk")?;
+
+    let audio_path = find_meeting_audio_file(&folder)
+        .ok_or_else(|| format!("No audio file found in {}", folder.display()))?;
+    info!("🎧 Re-transcribing {} with {}", audio_path.display(), request.provider);
+
+    let (spans, duration_seconds, model) = if request.provider == "deepgram" {
+        transcribe_with_deepgram(app, state, request, &audio_path).await?
+    } else {
+        transcribe_with_local_provider(app, request, &audio_path).await?
+    };
+
+    if spans.is_empty() {
+        return Err("Re-transcription produced no text; the existing transcript was left unchanged".to_string());
+    }
+
+    let mut json_segments: Vec<JsonTranscriptSegment> = Vec::with_capacity(spans.len());
+    let mut db_segments: Vec<DbTranscriptSegment> = Vec::with_capacity(spans.len());
+
+    for (sequence_id, span) in spans.into_iter().enumerate() {
+        let display_time = format_recording_time(span.start);
+
+        json_segments.push(JsonTranscriptSegment {
+            id: format!("seg_{}", sequence_id),
+            text: span.text.clone(),
+            audio_start_time: span.start,
+            audio_end_time: span.end,
+            duration: span.end - span.start,
+            display_time: display_time.clone(),
+            confidence: span.confidence.unwrap_or(1.0),
+            sequence_id: sequence_id as u64,
+            provider: None,
+        });
+        db_segments.push(DbTranscriptSegment {
+            id: format!("transcript-{}", uuid::Uuid::new_v4()),
+            text: span.text,
+            timestamp: display_time,
+            audio_start_time: Some(span.start),
+            audio_end_time: Some(span.end),
+            duration: Some(span.end - span.start),
+            words: span.words,
+        });
+    }
+
+    // Database first: transcripts.json only changes once the rows were replaced
+    TranscriptsRepository::replace_meeting_transcripts(pool, &request.meeting_id, &db_segments)
+        .await
+        .map_err(|e| format!("Failed to save transcripts: {}", e))?;
+
+    let previous_version = write_transcripts_json_versioned(
+        &folder,
+        &json_segments,
+        &request.provider,
+        model.as_deref(),
+        request.keep_previous_version.unwrap_or(true),
+    )
+    .map_err(|e| format!("Failed to write transcripts.json: {}", e))?;
+
+    info!(
+        "✅ Re-transcribed meeting {} with {}: {} segments",
+        request.meeting_id,
+        request.provider,
+        db_segments.len()
+    );
+
+    Ok(RetranscribeResult {
+        meeting_id: request.meeting_id.clone(),
+        provider: request.provider.clone(),
+        model,
+        segment_count: db_segments.len(),
+        duration_seconds,
+        transcript_file: folder.join("transcripts.json").to_string_lossy().to_string(),
+        previous_version_file: previous_version.map(|p| p.to_string_lossy().to_string()),
+    })
+}
+
+/// Re-transcribe a completed meeting with any supported provider
+#[tauri::command]
+pub async fn retranscribe_meeting<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    request: RetranscribeRequest,
+) -> Result<RetranscribeResult, String> {
+    info!(
+        "retranscribe_meeting called for meeting {} (provider: {}, model: {:?})",
+        request.meeting_id, request.provider, request.model
+    );
+
+    if crate::RECORDING_FLAG.load(std::sync::atomic::Ordering::SeqCst) {
+        return Err("Cannot re-transcribe while a recording is in progress".to_string());
+    }
+
+    retranscribe_meeting_folder(&app, &state, &request).await.map_err(|e| {
+        error!("❌ Re-transcription failed for meeting {}: {}", request.meeting_id, e);
+        e
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_find_audio_file_prefers_metadata() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("audio.mp4"), b"x").unwrap();
+        std::fs::write(dir.path().join("import.wav"), b"x").unwrap();
+        std::fs::write(
+            dir.path().join("metadata.json"),
+            r#"{"audio_file": "import.wav"}"#,
+        )
+        .unwrap();
+
+        let found = find_meeting_audio_file(dir.path()).unwrap();
+        assert_eq!(found.file_name().unwrap(), "import.wav");
+    }
+
+    #[test]
+    fn test_find_audio_file_falls_back_to_any_audio() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.md"), b"x").unwrap();
+        std::fs::write(dir.path().join("memo.M4A"), b"x").unwrap();
+
+        let found = find_meeting_audio_file(dir.path()).unwrap();
+        assert_eq!(found.file_name().unwrap(), "memo.M4A");
+
+        let empty = tempdir().unwrap();
+        assert!(find_meeting_audio_file(empty.path()).is_none());
+    }
+
+    #[test]
+    fn test_write_transcripts_json_keeps_previous_version() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("transcripts.json"), r#"{"segments": []}"#).unwrap();
+
+        let segments = vec![JsonTranscriptSegment {
+            id: "seg_0".to_string(),
+            text: "Hello".to_string(),
+            audio_start_time: 0.0,
+            audio_end_time: 1.5,
+            duration: 1.5,
+            display_time: "[00:00]".to_string(),
+            confidence: 0.9,
+            sequence_id: 0,
+            provider: None,
+        }];
+
+        let backup = write_transcripts_json_versioned(dir.path(), &segments, "parakeet", None, true)
+            .unwrap()
+            .expect("previous version should be kept");
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), r#"{"segments": []}"#);
+
+        let written: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(dir.path().join("transcripts.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(written["total_segments"], 1);
+        assert_eq!(written["segments"][0]["text"], "Hello");
+        assert_eq!(written["transcribed_with"]["provider"], "parakeet");
+    }
+}
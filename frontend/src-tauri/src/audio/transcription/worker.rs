@@ -3,10 +3,15 @@
 // Parallel transcription worker pool and chunk processing logic.
 
 use super::engine::TranscriptionEngine;
-use super::provider::TranscriptionError;
+use super::provider::{Attribution, GapRevision, TranscriptionError};
+use crate::audio::diarization::{
+    assign_live_speaker, format_text_with_speaker, get_or_load_diarization_model, live_embedding_window,
+};
+use crate::api::WordTiming;
 use crate::audio::AudioChunk;
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Runtime};
@@ -36,6 +41,15 @@ pub struct TranscriptUpdate {
     pub audio_start_time: f64, // Seconds from recording start (e.g., 125.3)
     pub audio_end_time: f64,   // Seconds from recording start (e.g., 128.6)
     pub duration: f64,          // Segment duration in seconds (e.g., 3.3)
+    /// Engine that produced the text (differs from the selected one after a failover)
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Replaces the earlier update with the same sequence_id (failover gap re-run)
+    #[serde(default)]
+    pub revision: bool,
+    /// Word timings in recording-relative seconds (Deepgram only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<WordTiming>,
 }
 
 // NOTE: get_transcript_history and get_recording_meeting_name functions
@@ -52,6 +66,13 @@ pub fn start_transcription_task<R: Runtime>(
         // Initialize transcription engine (Whisper or Parakeet based on config)
         let transcription_engine = match super::engine::get_or_init_transcription_engine(&app).await {
             Ok(engine) => engine,
+            Err(e) if crate::deepgram::offline_queue::is_offline_capture() => {
+                // Offline capture without a local preview: keep recording, transcribe later
+                info!("📴 No live transcription while offline: {}", e);
+                let mut receiver = transcription_receiver;
+                while receiver.recv().await.is_some() {}
+                return;
+            }
             Err(e) => {
                 error!("Failed to initialize transcription engine: {}", e);
                 let _ = app.emit("transcription-error", serde_json::json!({
@@ -110,6 +131,19 @@ pub fn start_transcription_task<R: Runtime>(
                     warn!("⚠️ Worker {} pre-validation: {} model not loaded - chunks may be skipped", worker_id, engine_name);
                 }
 
+                // Local engines and upload providers have no usable diarization of their
+                // own: use the on-device speaker embedding model when one is installed
+                let diarization_model = match &engine_clone {
+                    TranscriptionEngine::Provider(provider) if provider.labels_speakers() => None,
+                    _ => get_or_load_diarization_model(&app_clone),
+                };
+                if diarization_model.is_some() {
+                    info!("🗣️ Worker {} local speaker diarization enabled", worker_id);
+                }
+
+                // Updates from a failover engine that may still be revised, by gap id
+                let mut gap_updates: HashMap<u64, TranscriptUpdate> = HashMap::new();
+
                 loop {
                     // Try to get a chunk to process
                     let chunk = {
@@ -143,6 +177,18 @@ pub fn start_transcription_task<R: Runtime>(
                             let chunk_timestamp = chunk.timestamp;
                             let chunk_duration = chunk.data.len() as f64 / chunk.sample_rate as f64;
 
+                            // Embed the chunk's speaker alongside transcription rather than before it
+                            let speaker_task = diarization_model.as_ref().map(|model| {
+                                let model = model.clone();
+                                let window = live_embedding_window(&chunk.data, chunk.sample_rate);
+                                let samples_16k = if chunk.sample_rate != 16000 {
+                                    crate::audio::audio_processing::resample_audio(window, chunk.sample_rate, 16000)
+                                } else {
+                                    window.to_vec()
+                                };
+                                tokio::task::spawn_blocking(move || assign_live_speaker(&model, &samples_16k))
+                            });
+
                             // Transcribe with provider-agnostic approach
                             match transcribe_chunk_with_provider(
                                 &engine_clone,
@@ -151,7 +197,12 @@ pub fn start_transcription_task<R: Runtime>(
                             )
                             .await
                             {
-                                Ok((transcript, confidence_opt, is_partial)) => {
+                                Ok((transcript, confidence_opt, is_partial, attribution, words)) => {
+                                    let speaker = match speaker_task {
+                                        Some(task) => task.await.ok().flatten(),
+                                        None => None,
+                                    };
+
                                     // Provider-aware confidence threshold
                                     let confidence_threshold = match &engine_clone {
                                         TranscriptionEngine::Whisper(_) | TranscriptionEngine::Provider(_) => 0.3,
@@ -206,7 +257,7 @@ pub fn start_transcription_task<R: Runtime>(
                                         // Emit transcript update with NEW recording-relative timestamps
 
                                         let update = TranscriptUpdate {
-                                            text: transcript,
+                                            text: format_text_with_speaker(speaker, &transcript),
                                             timestamp: format_current_timestamp(), // Wall-clock for reference
                                             source: "Audio".to_string(),
                                             sequence_id,
@@ -217,7 +268,13 @@ pub fn start_transcription_task<R: Runtime>(
                                             audio_start_time,
                                             audio_end_time,
                                             duration: chunk_duration,
+                                            provider: Some(attribution.provider.to_string()),
+                                            revision: false,
+                                            words: shift_words(words, audio_start_time),
                                         };
+                                        if let Some(gap_id) = attribution.gap_id {
+                                            gap_updates.insert(gap_id, update.clone());
+                                        }
 
                                         if let Err(e) = app_clone.emit("transcript-update", &update)
                                         {
@@ -287,6 +344,11 @@ pub fn start_transcription_task<R: Runtime>(
                                 "progress_percentage": progress_percentage,
                                 "message": format!("Worker {} processing... ({}/{})", worker_id, completed, queued)
                             }));
+
+                            if let TranscriptionEngine::Provider(provider) = &engine_clone {
+                                let revisions = provider.take_gap_revisions(false).await;
+                                emit_gap_revisions(&app_clone, revisions, &mut gap_updates);
+                            }
                         }
                         None => {
                             // No more chunks available
@@ -300,6 +362,14 @@ pub fn start_transcription_task<R: Runtime>(
                                         "👷 Worker {} finishing - all {}/{} chunks processed",
                                         worker_id, final_completed, final_queued
                                     );
+                                    // Let a running failover gap re-run finish before the transcript is saved
+                                    if let TranscriptionEngine::Provider(provider) = &engine_clone {
+                                        if !gap_updates.is_empty() {
+                                            let revisions = provider.take_gap_revisions(true).await;
+                                            emit_gap_revisions(&app_clone, revisions, &mut gap_updates);
+                                        }
+                                        provider.finish_recording().await;
+                                    }
                                     break;
                                 } else {
                                     warn!("👷 Worker {} detected potential chunk loss: {}/{} completed, waiting...", worker_id, final_completed, final_queued);
@@ -404,12 +474,12 @@ pub fn start_transcription_task<R: Runtime>(
 }
 
 /// Transcribe audio chunk using the appropriate provider (Whisper, Parakeet, or trait-based)
-/// Returns: (text, confidence Option, is_partial)
+/// Returns: (text, confidence Option, is_partial, engine that produced the text)
 async fn transcribe_chunk_with_provider<R: Runtime>(
     engine: &TranscriptionEngine,
     chunk: AudioChunk,
     app: &AppHandle<R>,
-) -> std::result::Result<(String, Option<f32>, bool), TranscriptionError> {
+) -> std::result::Result<(String, Option<f32>, bool, Attribution, Vec<WordTiming>), TranscriptionError> {
     // Convert to 16kHz mono for transcription
     let transcription_data = if chunk.sample_rate != 16000 {
         crate::audio::audio_processing::resample_audio(&chunk.data, chunk.sample_rate, 16000)
@@ -455,7 +525,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                 Ok((text, confidence, is_partial)) => {
                     let cleaned_text = text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), Some(confidence), is_partial));
+                        return Ok((String::new(), Some(confidence), is_partial, local_attribution("Whisper"), Vec::new()));
                     }
 
                     info!(
@@ -463,7 +533,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                         chunk.chunk_id, cleaned_text, confidence, is_partial
                     );
 
-                    Ok((cleaned_text, Some(confidence), is_partial))
+                    Ok((cleaned_text, Some(confidence), is_partial, local_attribution("Whisper"), Vec::new()))
                 }
                 Err(e) => {
                     error!(
@@ -490,7 +560,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                 Ok(text) => {
                     let cleaned_text = text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), None, false));
+                        return Ok((String::new(), None, false, local_attribution("Parakeet"), Vec::new()));
                     }
 
                     info!(
@@ -499,7 +569,7 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                     );
 
                     // Parakeet doesn't provide confidence or partial results
-                    Ok((cleaned_text, None, false))
+                    Ok((cleaned_text, None, false, local_attribution("Parakeet"), Vec::new()))
                 }
                 Err(e) => {
                     error!(
@@ -525,11 +595,11 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
             // NEW: Trait-based provider (clean, unified interface)
             let language = crate::get_language_preference_internal();
 
-            match provider.transcribe(speech_samples, language).await {
-                Ok(result) => {
+            match provider.transcribe_attributed(speech_samples, language, Some(chunk.timestamp)).await {
+                Ok((result, attribution)) => {
                     let cleaned_text = result.text.trim().to_string();
                     if cleaned_text.is_empty() {
-                        return Ok((String::new(), result.confidence, result.is_partial));
+                        return Ok((String::new(), result.confidence, result.is_partial, attribution, Vec::new()));
                     }
 
                     let confidence_str = match result.confidence {
@@ -539,14 +609,14 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
 
                     info!(
                         "{} transcription complete for chunk {}: '{}' ({}, partial: {})",
-                        provider.provider_name(),
+                        attribution.provider,
                         chunk.chunk_id,
                         cleaned_text,
                         confidence_str,
                         result.is_partial
                     );
 
-                    Ok((cleaned_text, result.confidence, result.is_partial))
+                    Ok((cleaned_text, result.confidence, result.is_partial, attribution, result.words))
                 }
                 Err(e) => {
                     error!(
@@ -572,6 +642,53 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
     }
 }
 
+/// Attribution of a result from a directly used local engine
+fn local_attribution(provider: &'static str) -> Attribution {
+    Attribution {
+        provider,
+        gap_id: None,
+    }
+}
+
+/// Re-emit updates whose audio was transcribed again after a failover.
+/// Updates keep their sequence_id so listeners replace the earlier text.
+fn emit_gap_revisions<R: Runtime>(
+    app: &AppHandle<R>,
+    revisions: Vec<GapRevision>,
+    gap_updates: &mut HashMap<u64, TranscriptUpdate>,
+) {
+    for revision in revisions {
+        let Some(mut update) = gap_updates.remove(&revision.gap_id) else {
+            continue;
+        };
+        let text = revision.result.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        update.text = text.to_string();
+        update.confidence = revision.result.confidence.unwrap_or(update.confidence);
+        update.is_partial = false;
+        update.provider = Some(revision.provider.to_string());
+        update.revision = true;
+        update.words = shift_words(revision.result.words, update.audio_start_time);
+
+        info!("🔁 Revised segment {} with {}", update.sequence_id, revision.provider);
+        if let Err(e) = app.emit("transcript-update", &update) {
+            error!("Failed to emit revised transcript update: {}", e);
+        }
+    }
+}
+
+/// Move word timings from segment-relative to recording-relative seconds
+fn shift_words(mut words: Vec<WordTiming>, offset: f64) -> Vec<WordTiming> {
+    for word in &mut words {
+        word.start += offset;
+        word.end += offset;
+    }
+    words
+}
+
 /// Format current timestamp (wall-clock time)
 fn format_current_timestamp() -> String {
     let now = std::time::SystemTime::now()
@@ -586,8 +703,7 @@ fn format_current_timestamp() -> String {
 }
 
 /// Format recording-relative time as [MM:SS]
-#[allow(dead_code)]
-fn format_recording_time(seconds: f64) -> String {
+pub(crate) fn format_recording_time(seconds: f64) -> String {
     let total_seconds = seconds.floor() as u64;
     let minutes = total_seconds / 60;
     let secs = total_seconds % 60;
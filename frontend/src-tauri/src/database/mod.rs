@@ -3,3 +3,5 @@ pub mod manager;
 pub mod models;
 pub mod repositories;
 pub mod setup;
+#[cfg(test)]
+pub(crate) mod test_utils;
@@ -37,6 +37,72 @@ pub struct Transcript {
     pub duration: Option<f64>,
 }
 
+/// A single recognized word with timing, stored in the transcript_words table
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TranscriptWord {
+    pub id: i64,
+    pub transcript_id: String,
+    pub meeting_id: String,
+    pub word_index: i64,
+    pub word: String,
+    pub punctuated_word: Option<String>,
+    // Recording-relative timestamps in seconds
+    pub start_time: f64,
+    pub end_time: f64,
+    pub confidence: Option<f64>,
+    pub speaker: Option<i64>,
+}
+
+/// Embedding vector of one chunk of transcript or summary text (semantic search)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct MeetingEmbedding {
+    pub id: i64,
+    pub meeting_id: String,
+    pub source_type: String,
+    pub source_id: String,
+    pub chunk_index: i64,
+    pub content: String,
+    pub timestamp: Option<String>,
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+    pub model: String,
+    pub dimensions: i64,
+    /// Little-endian f32 values
+    #[serde(skip)]
+    pub vector: Vec<u8>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Per-meeting mapping from a diarized speaker label to a person's name
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct MeetingSpeaker {
+    pub meeting_id: String,
+    pub speaker_label: String,
+    pub display_name: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Meeting recorded offline, waiting for Deepgram prerecorded transcription
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct PendingTranscription {
+    pub id: String,
+    pub folder_path: String,
+    pub meeting_name: Option<String>,
+    pub model: Option<String>,
+    pub language: Option<String>,
+    pub status: String,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    pub meeting_id: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// Prep note that was open while recording
+    pub note_path: Option<String>,
+    /// Content of the prep note when recording started
+    pub note_base: Option<String>,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct SummaryProcess {
     pub meeting_id: String,
@@ -89,6 +155,9 @@ pub struct Setting {
     #[sqlx(rename = "openRouterApiKey")]
     #[serde(rename = "openRouterApiKey")]
     pub open_router_api_key: Option<String>,
+    #[sqlx(rename = "geminiApiKey")]
+    #[serde(rename = "geminiApiKey")]
+    pub gemini_api_key: Option<String>,
     #[sqlx(rename = "ollamaEndpoint")]
     #[serde(rename = "ollamaEndpoint")]
     pub ollama_endpoint: Option<String>,
@@ -0,0 +1,300 @@
+use crate::database::models::MeetingEmbedding;
+use chrono::Utc;
+use sqlx::{Error as SqlxError, QueryBuilder, Sqlite, SqlitePool};
+use tracing::info;
+
+/// A chunk of meeting text with its vector, ready to be stored
+#[derive(Debug, Clone)]
+pub struct NewEmbedding {
+    pub source_type: String,
+    pub source_id: String,
+    pub chunk_index: i64,
+    pub content: String,
+    pub timestamp: Option<String>,
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+    pub vector: Vec<f32>,
+}
+
+/// Encode a vector as a little-endian f32 BLOB
+pub fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// Decode a little-endian f32 BLOB
+pub fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+pub struct EmbeddingsRepository;
+
+impl EmbeddingsRepository {
+    /// Replaces the embeddings of a meeting for one model in a single transaction,
+    /// and marks the meeting as indexed for that model (even with no chunks).
+    pub async fn replace_meeting_embeddings(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        model: &str,
+        embeddings: &[NewEmbedding],
+    ) -> Result<usize, SqlxError> {
+        if meeting_id.trim().is_empty() || model.trim().is_empty() {
+            return Err(SqlxError::Protocol(
+                "meeting_id and model cannot be empty".to_string(),
+            ));
+        }
+
+        let mut transaction = pool.begin().await?;
+        let now = Utc::now();
+
+        sqlx::query("DELETE FROM embeddings WHERE meeting_id = ? AND model = ?")
+            .bind(meeting_id)
+            .bind(model)
+            .execute(&mut *transaction)
+            .await?;
+
+        for embedding in embeddings {
+            sqlx::query(
+                "INSERT INTO embeddings (meeting_id, source_type, source_id, chunk_index, content, timestamp, start_time, end_time, model, dimensions, vector, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(meeting_id)
+            .bind(&embedding.source_type)
+            .bind(&embedding.source_id)
+            .bind(embedding.chunk_index)
+            .bind(&embedding.content)
+            .bind(&embedding.timestamp)
+            .bind(embedding.start_time)
+            .bind(embedding.end_time)
+            .bind(model)
+            .bind(embedding.vector.len() as i64)
+            .bind(encode_vector(&embedding.vector))
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        sqlx::query(
+            "INSERT INTO embedding_index_state (meeting_id, model, chunk_count, indexed_at)
+             VALUES (?, ?, ?, ?)
+             ON CONFLICT(meeting_id, model) DO UPDATE SET
+                 chunk_count = excluded.chunk_count,
+                 indexed_at = excluded.indexed_at",
+        )
+        .bind(meeting_id)
+        .bind(model)
+        .bind(embeddings.len() as i64)
+        .bind(now)
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        info!(
+            "Stored {} embeddings for meeting {} (model {})",
+            embeddings.len(),
+            meeting_id,
+            model
+        );
+        Ok(embeddings.len())
+    }
+
+    /// Returns all embeddings of a model, optionally limited to some meetings.
+    pub async fn get_embeddings_for_model(
+        pool: &SqlitePool,
+        model: &str,
+        meeting_ids: Option<&[String]>,
+    ) -> Result<Vec<MeetingEmbedding>, SqlxError> {
+        let mut builder: QueryBuilder<Sqlite> =
+            QueryBuilder::new("SELECT * FROM embeddings WHERE model = ");
+        builder.push_bind(model);
+        if let Some(ids) = meeting_ids.filter(|ids| !ids.is_empty()) {
+            builder.push(" AND meeting_id IN (");
+            let mut separated = builder.separated(", ");
+            for id in ids {
+                separated.push_bind(id);
+            }
+            separated.push_unseparated(")");
+        }
+        builder.push(" ORDER BY meeting_id, source_type, chunk_index");
+
+        builder
+            .build_query_as::<MeetingEmbedding>()
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Returns ids of meetings that were never indexed for `model`, or
+    /// that changed after they were last indexed.
+    pub async fn get_meetings_to_index(
+        pool: &SqlitePool,
+        model: &str,
+    ) -> Result<Vec<String>, SqlxError> {
+        sqlx::query_scalar::<_, String>(
+            "SELECT m.id FROM meetings m
+             LEFT JOIN embedding_index_state s ON s.meeting_id = m.id AND s.model = ?
+             WHERE s.indexed_at IS NULL OR m.updated_at > s.indexed_at
+             ORDER BY m.created_at DESC",
+        )
+        .bind(model)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Deletes all embeddings of a model and its indexed markers (e.g. before a full rebuild).
+    pub async fn delete_embeddings_for_model(
+        pool: &SqlitePool,
+        model: &str,
+    ) -> Result<u64, SqlxError> {
+        let mut transaction = pool.begin().await?;
+        let result = sqlx::query("DELETE FROM embeddings WHERE model = ?")
+            .bind(model)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("DELETE FROM embedding_index_state WHERE model = ?")
+            .bind(model)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{insert_meeting, test_pool};
+
+    fn chunk(source_id: &str, vector: Vec<f32>) -> NewEmbedding {
+        NewEmbedding {
+            source_type: "transcript".to_string(),
+            source_id: source_id.to_string(),
+            chunk_index: 0,
+            content: "We agreed on the budget".to_string(),
+            timestamp: Some("[00:05]".to_string()),
+            start_time: Some(5.0),
+            end_time: Some(9.0),
+            vector,
+        }
+    }
+
+    #[test]
+    fn test_vector_round_trip() {
+        let vector = vec![0.5, -1.25, 3.0];
+        assert_eq!(decode_vector(&encode_vector(&vector)), vector);
+    }
+
+    #[tokio::test]
+    async fn test_meetings_without_chunks_are_indexed_once() {
+        let pool = test_pool().await;
+        insert_meeting(&pool, "empty", "No transcript").await;
+        insert_meeting(&pool, "full", "Budget").await;
+
+        let pending = EmbeddingsRepository::get_meetings_to_index(&pool, "local:test")
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 2);
+
+        EmbeddingsRepository::replace_meeting_embeddings(&pool, "empty", "local:test", &[])
+            .await
+            .unwrap();
+        EmbeddingsRepository::replace_meeting_embeddings(
+            &pool,
+            "full",
+            "local:test",
+            &[chunk("t1", vec![1.0, 0.0])],
+        )
+        .await
+        .unwrap();
+
+        assert!(EmbeddingsRepository::get_meetings_to_index(&pool, "local:test")
+            .await
+            .unwrap()
+            .is_empty());
+        // Indexing state is per model
+        assert_eq!(
+            EmbeddingsRepository::get_meetings_to_index(&pool, "ollama:other")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // A rebuild forgets the markers along with the vectors
+        EmbeddingsRepository::delete_embeddings_for_model(&pool, "local:test")
+            .await
+            .unwrap();
+        assert_eq!(
+            EmbeddingsRepository::get_meetings_to_index(&pool, "local:test")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_embeddings_for_model_filters_meetings() {
+        let pool = test_pool().await;
+        for id in ["m1", "m2", "m3"] {
+            insert_meeting(&pool, id, id).await;
+            EmbeddingsRepository::replace_meeting_embeddings(
+                &pool,
+                id,
+                "local:test",
+                &[chunk(id, vec![1.0, 0.0])],
+            )
+            .await
+            .unwrap();
+        }
+        EmbeddingsRepository::replace_meeting_embeddings(
+            &pool,
+            "m1",
+            "ollama:other",
+            &[chunk("m1", vec![0.0, 1.0])],
+        )
+        .await
+        .unwrap();
+
+        let all = EmbeddingsRepository::get_embeddings_for_model(&pool, "local:test", None)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 3);
+
+        let ids = vec!["m1".to_string(), "m3".to_string()];
+        let some = EmbeddingsRepository::get_embeddings_for_model(&pool, "local:test", Some(&ids))
+            .await
+            .unwrap();
+        let meetings: Vec<_> = some.iter().map(|e| e.meeting_id.as_str()).collect();
+        assert_eq!(meetings, vec!["m1", "m3"]);
+        assert_eq!(decode_vector(&some[0].vector), vec![1.0, 0.0]);
+    }
+
+    #[tokio::test]
+    async fn test_replace_meeting_embeddings_replaces_previous_chunks() {
+        let pool = test_pool().await;
+        insert_meeting(&pool, "m1", "Budget").await;
+        let model = "local:test";
+
+        EmbeddingsRepository::replace_meeting_embeddings(
+            &pool,
+            "m1",
+            model,
+            &[chunk("t1", vec![1.0, 0.0]), chunk("t2", vec![0.0, 1.0])],
+        )
+        .await
+        .unwrap();
+        EmbeddingsRepository::replace_meeting_embeddings(&pool, "m1", model, &[chunk("t3", vec![1.0, 1.0])])
+            .await
+            .unwrap();
+
+        let stored = EmbeddingsRepository::get_embeddings_for_model(&pool, model, None)
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].source_id, "t3");
+        assert_eq!(stored[0].dimensions, 2);
+    }
+}
@@ -128,6 +128,19 @@ impl MeetingsRepository {
         Ok(meeting)
     }
 
+    /// Find the most recent meeting saved from a recording folder
+    pub async fn find_meeting_by_folder(
+        pool: &SqlitePool,
+        folder_path: &str,
+    ) -> Result<Option<MeetingModel>, SqlxError> {
+        sqlx::query_as(
+            "SELECT id, title, created_at, updated_at, folder_path FROM meetings WHERE folder_path = ? ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(folder_path)
+        .fetch_optional(pool)
+        .await
+    }
+
     /// Get meeting transcripts with pagination support
     pub async fn get_meeting_transcripts_paginated(
         pool: &SqlitePool,
@@ -258,7 +271,22 @@ async fn delete_meeting_with_transaction(
         .execute(&mut *transaction)
         .await?;
 
-    // 3. Delete from transcripts
+    // 3. Delete embeddings, speaker names, word timings, then transcripts
+    sqlx::query("DELETE FROM embeddings WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    sqlx::query("DELETE FROM meeting_speakers WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    sqlx::query("DELETE FROM transcript_words WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
     sqlx::query("DELETE FROM transcripts WHERE meeting_id = ?")
         .bind(meeting_id)
         .execute(&mut *transaction)
@@ -1,5 +1,10 @@
+pub mod embedding;
 pub mod meeting;
+pub mod pending_transcription;
+pub mod search;
 pub mod setting;
+pub mod speaker;
 pub mod summary;
 pub mod transcript;
 pub mod transcript_chunk;
+pub mod transcript_word;
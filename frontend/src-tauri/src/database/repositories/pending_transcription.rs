@@ -0,0 +1,179 @@
+use crate::database::models::PendingTranscription;
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqlitePool};
+use tracing::info;
+
+pub struct PendingTranscriptionsRepository;
+
+impl PendingTranscriptionsRepository {
+    /// Queues a recording folder for deferred transcription and returns the job id.
+    pub async fn enqueue(
+        pool: &SqlitePool,
+        folder_path: &str,
+        meeting_name: Option<&str>,
+        model: Option<&str>,
+        language: Option<&str>,
+    ) -> Result<String, SqlxError> {
+        if folder_path.trim().is_empty() {
+            return Err(SqlxError::Protocol(
+                "folder_path cannot be empty".to_string(),
+            ));
+        }
+
+        let id = format!("pending-{}", uuid::Uuid::new_v4());
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO pending_transcriptions (id, folder_path, meeting_name, model, language, status, attempts, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, 'pending', 0, ?, ?)",
+        )
+        .bind(&id)
+        .bind(folder_path)
+        .bind(meeting_name)
+        .bind(model)
+        .bind(language)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        info!("Queued pending transcription {} for {}", id, folder_path);
+        Ok(id)
+    }
+
+    /// Attaches the prep note open while recording to the folder's queued job.
+    /// Returns whether a queued job was found.
+    pub async fn set_note(
+        pool: &SqlitePool,
+        folder_path: &str,
+        note_path: &str,
+        note_base: Option<&str>,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query(
+            "UPDATE pending_transcriptions SET note_path = ?, note_base = ?, updated_at = ? WHERE folder_path = ? AND status = 'pending'",
+        )
+        .bind(note_path)
+        .bind(note_base)
+        .bind(Utc::now())
+        .bind(folder_path)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Returns the jobs still waiting for transcription, oldest first.
+    pub async fn list_pending(pool: &SqlitePool) -> Result<Vec<PendingTranscription>, SqlxError> {
+        sqlx::query_as::<_, PendingTranscription>(
+            "SELECT * FROM pending_transcriptions WHERE status = 'pending' ORDER BY created_at ASC",
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Marks a job as transcribed into the given meeting.
+    pub async fn mark_completed(
+        pool: &SqlitePool,
+        id: &str,
+        meeting_id: &str,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            "UPDATE pending_transcriptions SET status = 'completed', meeting_id = ?, last_error = NULL, updated_at = ? WHERE id = ?",
+        )
+        .bind(meeting_id)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Records a failed attempt. The job stays queued until `max_attempts` is reached.
+    /// Returns whether the job gave up.
+    pub async fn record_failure(
+        pool: &SqlitePool,
+        id: &str,
+        error: &str,
+        max_attempts: i64,
+    ) -> Result<bool, SqlxError> {
+        sqlx::query(
+            "UPDATE pending_transcriptions
+             SET attempts = attempts + 1,
+                 last_error = ?,
+                 status = CASE WHEN attempts + 1 >= ? THEN 'failed' ELSE status END,
+                 updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(error)
+        .bind(max_attempts)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        let attempts: i64 =
+            sqlx::query_scalar("SELECT attempts FROM pending_transcriptions WHERE id = ?")
+                .bind(id)
+                .fetch_one(pool)
+                .await?;
+
+        Ok(attempts >= max_attempts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::test_pool;
+
+    #[tokio::test]
+    async fn test_set_note_attaches_prep_note_to_queued_job() {
+        let pool = test_pool().await;
+        let id = PendingTranscriptionsRepository::enqueue(
+            &pool,
+            "/rec/standup",
+            Some("Standup"),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let found = PendingTranscriptionsRepository::set_note(
+            &pool,
+            "/rec/standup",
+            "/vault/Standup.md",
+            Some("# Standup\n"),
+        )
+        .await
+        .unwrap();
+        assert!(found);
+        assert!(!PendingTranscriptionsRepository::set_note(
+            &pool,
+            "/rec/other",
+            "/vault/Other.md",
+            None
+        )
+        .await
+        .unwrap());
+
+        let jobs = PendingTranscriptionsRepository::list_pending(&pool)
+            .await
+            .unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].id, id);
+        assert_eq!(jobs[0].note_path.as_deref(), Some("/vault/Standup.md"));
+        assert_eq!(jobs[0].note_base.as_deref(), Some("# Standup\n"));
+
+        // Completed jobs keep the note they were written to
+        PendingTranscriptionsRepository::mark_completed(&pool, &id, "meeting-1")
+            .await
+            .unwrap();
+        assert!(!PendingTranscriptionsRepository::set_note(
+            &pool,
+            "/rec/standup",
+            "/vault/Later.md",
+            None
+        )
+        .await
+        .unwrap());
+    }
+}
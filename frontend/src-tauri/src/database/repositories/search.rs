@@ -0,0 +1,432 @@
+use crate::api::{HighlightRange, SearchHit, SearchIndexStats, SearchQuery};
+use sqlx::{Error as SqlxError, FromRow, QueryBuilder, Sqlite, SqlitePool};
+use tracing::{info, warn};
+
+/// Marker characters wrapped around matched terms by FTS5 `snippet()`.
+/// Stripped again when building the highlight offsets.
+const HIGHLIGHT_START: char = '\u{2}';
+const HIGHLIGHT_END: char = '\u{3}';
+const SNIPPET_TOKENS: i64 = 24;
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 500;
+
+pub const SOURCE_TRANSCRIPT: &str = "transcript";
+pub const SOURCE_SUMMARY: &str = "summary";
+pub const SOURCE_NOTES: &str = "notes";
+
+#[derive(Debug, FromRow)]
+struct SearchRow {
+    source: String,
+    source_id: String,
+    meeting_id: String,
+    meeting_title: String,
+    meeting_created_at: String,
+    snippet: String,
+    rank: f64,
+    timestamp: Option<String>,
+    audio_start_time: Option<f64>,
+    speaker: Option<String>,
+}
+
+pub struct SearchRepository;
+
+impl SearchRepository {
+    /// Ranked full-text search over transcripts, summaries and meeting notes.
+    ///
+    /// The query uses FTS5 syntax (phrases in quotes, AND/OR/NOT, prefix*). Input that
+    /// is not valid FTS5 syntax (`10:30`, `note: x`, unbalanced quotes...) is retried
+    /// as a plain all-terms query.
+    pub async fn search(pool: &SqlitePool, query: &SearchQuery) -> Result<Vec<SearchHit>, SqlxError> {
+        if query.query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        match Self::run_search(pool, query, query.query.trim()).await {
+            Ok(hits) => Ok(hits),
+            // The SQL around the MATCH expression is fixed, so a database error here
+            // comes from the user's query: FTS5 reports bad syntax as "fts5: syntax
+            // error", "no such column" (for `col:term` filters), "unterminated string"...
+            Err(SqlxError::Database(e)) => {
+                let fallback = sanitize_fts_query(&query.query);
+                warn!(
+                    "Invalid FTS5 query '{}' ({}), retrying as '{}'",
+                    query.query,
+                    e.message(),
+                    fallback
+                );
+                if fallback.is_empty() {
+                    return Ok(Vec::new());
+                }
+                Self::run_search(pool, query, &fallback).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn run_search(
+        pool: &SqlitePool,
+        query: &SearchQuery,
+        match_expr: &str,
+    ) -> Result<Vec<SearchHit>, SqlxError> {
+        let sources = query.sources.clone().unwrap_or_default();
+        let wants = |source: &str| sources.is_empty() || sources.iter().any(|s| s == source);
+        // Speakers only exist on transcript segments
+        let speaker = query
+            .speaker
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty());
+
+        let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM (");
+        let mut parts = 0;
+
+        if wants(SOURCE_TRANSCRIPT) {
+            builder.push(
+                "SELECT 'transcript' AS source, t.id AS source_id, m.id AS meeting_id, m.title AS meeting_title,
+                        m.created_at AS meeting_created_at,
+                        snippet(transcripts_fts, 0, char(2), char(3), '…', ",
+            );
+            builder.push(SNIPPET_TOKENS.to_string());
+            builder.push(
+                ") AS snippet, bm25(transcripts_fts) AS rank,
+                        t.timestamp AS timestamp, t.audio_start_time AS audio_start_time, t.speaker AS speaker
+                 FROM transcripts_fts
+                 JOIN transcripts t ON t.rowid = transcripts_fts.rowid
+                 JOIN meetings m ON m.id = t.meeting_id
+                 WHERE transcripts_fts MATCH ",
+            );
+            builder.push_bind(match_expr.to_string());
+            Self::push_meeting_filters(&mut builder, query);
+            if let Some(speaker) = speaker {
+                // Matches the audio source column (mic/system), a diarized "[Speaker N]:"
+                // label, or a person named through the meeting's speaker map
+                builder.push(" AND (t.speaker = ");
+                builder.push_bind(speaker.to_string());
+                builder.push(" OR t.transcript LIKE '%[' || ");
+                builder.push_bind(speaker.to_string());
+                builder.push(
+                    " || ']:%' OR EXISTS (SELECT 1 FROM meeting_speakers ms
+                         WHERE ms.meeting_id = t.meeting_id
+                           AND ms.display_name = ",
+                );
+                builder.push_bind(speaker.to_string());
+                builder.push(
+                    " COLLATE NOCASE
+                           AND t.transcript LIKE '%[' || ms.speaker_label || ']:%'))",
+                );
+            }
+            parts += 1;
+        }
+
+        if wants(SOURCE_SUMMARY) && speaker.is_none() {
+            if parts > 0 {
+                builder.push(" UNION ALL ");
+            }
+            builder.push(
+                "SELECT 'summary' AS source, m.id AS source_id, m.id AS meeting_id, m.title AS meeting_title,
+                        m.created_at AS meeting_created_at,
+                        snippet(summaries_fts, 0, char(2), char(3), '…', ",
+            );
+            builder.push(SNIPPET_TOKENS.to_string());
+            builder.push(
+                ") AS snippet, bm25(summaries_fts) AS rank,
+                        NULL AS timestamp, NULL AS audio_start_time, NULL AS speaker
+                 FROM summaries_fts
+                 JOIN meetings m ON m.id = summaries_fts.meeting_id
+                 WHERE summaries_fts MATCH ",
+            );
+            builder.push_bind(match_expr.to_string());
+            Self::push_meeting_filters(&mut builder, query);
+            parts += 1;
+        }
+
+        if wants(SOURCE_NOTES) && speaker.is_none() {
+            if parts > 0 {
+                builder.push(" UNION ALL ");
+            }
+            builder.push(
+                "SELECT 'notes' AS source, m.id AS source_id, m.id AS meeting_id, m.title AS meeting_title,
+                        m.created_at AS meeting_created_at,
+                        snippet(meeting_notes_fts, 0, char(2), char(3), '…', ",
+            );
+            builder.push(SNIPPET_TOKENS.to_string());
+            builder.push(
+                ") AS snippet, bm25(meeting_notes_fts) AS rank,
+                        NULL AS timestamp, NULL AS audio_start_time, NULL AS speaker
+                 FROM meeting_notes_fts
+                 JOIN meetings m ON m.id = meeting_notes_fts.meeting_id
+                 WHERE meeting_notes_fts MATCH ",
+            );
+            builder.push_bind(match_expr.to_string());
+            Self::push_meeting_filters(&mut builder, query);
+            parts += 1;
+        }
+
+        if parts == 0 {
+            return Ok(Vec::new());
+        }
+
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let offset = query.offset.unwrap_or(0).max(0);
+        builder.push(") ORDER BY rank ASC LIMIT ");
+        builder.push_bind(limit);
+        builder.push(" OFFSET ");
+        builder.push_bind(offset);
+
+        let rows = builder.build_query_as::<SearchRow>().fetch_all(pool).await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let (snippet, highlights) = parse_highlighted_snippet(&row.snippet);
+                SearchHit {
+                    source: row.source,
+                    source_id: row.source_id,
+                    meeting_id: row.meeting_id,
+                    meeting_title: row.meeting_title,
+                    meeting_created_at: row.meeting_created_at,
+                    snippet,
+                    highlights,
+                    // bm25() is lower-is-better; expose a higher-is-better score
+                    score: -row.rank,
+                    timestamp: row.timestamp,
+                    audio_start_time: row.audio_start_time,
+                    speaker: row.speaker,
+                }
+            })
+            .collect())
+    }
+
+    fn push_meeting_filters(builder: &mut QueryBuilder<Sqlite>, query: &SearchQuery) {
+        if let Some(date_from) = query.date_from.as_deref().filter(|d| !d.is_empty()) {
+            builder.push(" AND substr(m.created_at, 1, 10) >= ");
+            builder.push_bind(date_from.to_string());
+        }
+        if let Some(date_to) = query.date_to.as_deref().filter(|d| !d.is_empty()) {
+            builder.push(" AND substr(m.created_at, 1, 10) <= ");
+            builder.push_bind(date_to.to_string());
+        }
+        if let Some(title) = query.title.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+            builder.push(" AND m.title LIKE '%' || ");
+            builder.push_bind(title.to_string());
+            builder.push(" || '%'");
+        }
+    }
+
+    /// Rebuilds all search indexes from their source tables.
+    /// Needed for databases created before the FTS migration ran its backfill,
+    /// or after a VACUUM renumbered rowids.
+    pub async fn rebuild_index(pool: &SqlitePool) -> Result<SearchIndexStats, SqlxError> {
+        let mut transaction = pool.begin().await?;
+
+        sqlx::query("DELETE FROM transcripts_fts")
+            .execute(&mut *transaction)
+            .await?;
+        let transcripts = sqlx::query(
+            "INSERT INTO transcripts_fts (rowid, transcript, transcript_id, meeting_id)
+             SELECT rowid, transcript, id, meeting_id FROM transcripts",
+        )
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected();
+
+        sqlx::query("DELETE FROM summaries_fts")
+            .execute(&mut *transaction)
+            .await?;
+        let summaries = sqlx::query(
+            "INSERT INTO summaries_fts (rowid, content, meeting_id)
+             SELECT rowid,
+                    CASE WHEN json_valid(result)
+                         THEN COALESCE(json_extract(result, '$.markdown'), result)
+                         ELSE result END,
+                    meeting_id
+             FROM summary_processes
+             WHERE result IS NOT NULL",
+        )
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected();
+
+        sqlx::query("DELETE FROM meeting_notes_fts")
+            .execute(&mut *transaction)
+            .await?;
+        let notes = sqlx::query(
+            "INSERT INTO meeting_notes_fts (rowid, notes_markdown, meeting_id)
+             SELECT rowid, notes_markdown, meeting_id FROM meeting_notes
+             WHERE notes_markdown IS NOT NULL",
+        )
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected();
+
+        for table in ["transcripts_fts", "summaries_fts", "meeting_notes_fts"] {
+            sqlx::query(&format!("INSERT INTO {table}({table}) VALUES('optimize')"))
+                .execute(&mut *transaction)
+                .await?;
+        }
+
+        transaction.commit().await?;
+
+        info!(
+            "Rebuilt search index: {} transcripts, {} summaries, {} notes",
+            transcripts, summaries, notes
+        );
+
+        Ok(SearchIndexStats {
+            transcripts,
+            summaries,
+            notes,
+        })
+    }
+}
+
+/// Strip FTS5 highlight markers from a snippet and return the highlighted ranges
+/// as UTF-16 offsets into the cleaned snippet (what JavaScript string indexing uses).
+pub fn parse_highlighted_snippet(raw: &str) -> (String, Vec<HighlightRange>) {
+    let mut text = String::with_capacity(raw.len());
+    let mut highlights = Vec::new();
+    let mut offset = 0usize;
+    let mut start: Option<usize> = None;
+
+    for c in raw.chars() {
+        match c {
+            HIGHLIGHT_START => start = Some(offset),
+            HIGHLIGHT_END => {
+                if let Some(s) = start.take() {
+                    highlights.push(HighlightRange { start: s, end: offset });
+                }
+            }
+            _ => {
+                text.push(c);
+                offset += c.len_utf16();
+            }
+        }
+    }
+
+    (text, highlights)
+}
+
+/// Turn free text into a safe FTS5 query: every term quoted, all terms required.
+pub fn sanitize_fts_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|term| term.replace('"', ""))
+        .filter(|term| term.chars().any(char::is_alphanumeric))
+        .map(|term| format!("\"{}\"", term))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Safe FTS5 query for search-as-you-type: every term quoted and matched as a
+/// prefix, so a partly typed word already finds its matches.
+pub fn prefix_fts_query(query: &str) -> String {
+    sanitize_fts_query(query)
+        .split(' ')
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("{}*", term))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{insert_meeting, insert_transcript, test_pool};
+
+    #[test]
+    fn test_parse_highlighted_snippet() {
+        let raw = format!("the {}budget{} review", HIGHLIGHT_START, HIGHLIGHT_END);
+        let (text, highlights) = parse_highlighted_snippet(&raw);
+        assert_eq!(text, "the budget review");
+        assert_eq!(highlights.len(), 1);
+        assert_eq!((highlights[0].start, highlights[0].end), (4, 10));
+    }
+
+    #[test]
+    fn test_parse_highlighted_snippet_uses_utf16_offsets() {
+        // "😀" is two UTF-16 code units
+        let raw = format!("😀 {}café{}", HIGHLIGHT_START, HIGHLIGHT_END);
+        let (text, highlights) = parse_highlighted_snippet(&raw);
+        assert_eq!(text, "😀 café");
+        assert_eq!((highlights[0].start, highlights[0].end), (3, 7));
+
+        // Unbalanced markers are dropped
+        let (text, highlights) = parse_highlighted_snippet(&format!("a{}b", HIGHLIGHT_END));
+        assert_eq!(text, "ab");
+        assert!(highlights.is_empty());
+    }
+
+    #[test]
+    fn test_sanitize_fts_query() {
+        assert_eq!(sanitize_fts_query("budget review"), "\"budget\" \"review\"");
+        assert_eq!(sanitize_fts_query("10:30"), "\"10:30\"");
+        assert_eq!(sanitize_fts_query("note: \"x"), "\"note:\" \"x\"");
+        assert_eq!(sanitize_fts_query(" - ( ) "), "");
+    }
+
+    #[test]
+    fn test_prefix_fts_query() {
+        assert_eq!(prefix_fts_query("budget rev"), "\"budget\"* \"rev\"*");
+        assert_eq!(prefix_fts_query(" - "), "");
+    }
+
+    #[tokio::test]
+    async fn test_transcript_search_matches_partly_typed_words() {
+        let pool = test_pool().await;
+        insert_meeting(&pool, "m1", "Planning").await;
+        insert_transcript(&pool, "t1", "m1", "We reviewed the budget").await;
+
+        let results =
+            crate::database::repositories::transcript::TranscriptsRepository::search_transcripts(&pool, "revie bud")
+                .await
+                .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "m1");
+        assert_eq!(results[0].match_context, "We reviewed the budget");
+    }
+
+    #[tokio::test]
+    async fn test_search_falls_back_on_invalid_fts_syntax() {
+        let pool = test_pool().await;
+        insert_meeting(&pool, "m1", "Planning").await;
+        insert_transcript(&pool, "t1", "m1", "Standup moved to 10:30, see the note: budget").await;
+
+        for query in ["10:30", "note: budget", "\"budget", "budget ("] {
+            let hits = SearchRepository::search(
+                &pool,
+                &SearchQuery {
+                    query: query.to_string(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_or_else(|e| panic!("query {:?} failed: {}", query, e));
+            assert_eq!(hits.len(), 1, "query {:?}", query);
+            assert_eq!(hits[0].source_id, "t1");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_ranks_and_highlights_transcripts() {
+        let pool = test_pool().await;
+        insert_meeting(&pool, "m1", "Planning").await;
+        insert_transcript(&pool, "t1", "m1", "We reviewed the budget").await;
+        insert_transcript(&pool, "t2", "m1", "Lunch plans").await;
+
+        let hits = SearchRepository::search(
+            &pool,
+            &SearchQuery {
+                query: "budget".to_string(),
+                sources: Some(vec![SOURCE_TRANSCRIPT.to_string()]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].meeting_title, "Planning");
+        assert_eq!(hits[0].snippet, "We reviewed the budget");
+        assert_eq!((hits[0].highlights[0].start, hits[0].highlights[0].end), (16, 22));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::database::models::{Setting, TranscriptSetting};
+use crate::audio::transcription::RemoteWhisperConfig;
 use crate::summary::CustomOpenAIConfig;
 use sqlx::SqlitePool;
 
@@ -25,7 +26,7 @@ pub struct SaveTranscriptConfigRequest {
 pub struct SettingsRepository;
 
 // Transcript providers: localWhisper, deepgram, elevenLabs, groq, openai
-// Summary providers: openai, claude, ollama, groq, added openrouter
+// Summary providers: openai, claude, ollama, groq, added openrouter, gemini
 // NOTE: Handle data exclusion in the higher layer as this is database abstraction layer(using SELECT *)
 
 impl SettingsRepository {
@@ -85,6 +86,7 @@ impl SettingsRepository {
             "ollama" => "ollamaApiKey",
             "groq" => "groqApiKey",
             "openrouter" => "openRouterApiKey",
+            "gemini" => "geminiApiKey",
             "builtin-ai" => return Ok(()), // No API key needed
             _ => {
                 return Err(sqlx::Error::Protocol(
@@ -123,6 +125,7 @@ impl SettingsRepository {
             "groq" => "groqApiKey",
             "claude" => "anthropicApiKey",
             "openrouter" => "openRouterApiKey",
+            "gemini" => "geminiApiKey",
             "builtin-ai" => return Ok(None), // No API key needed
             _ => {
                 return Err(sqlx::Error::Protocol(
@@ -210,6 +213,7 @@ impl SettingsRepository {
         let api_key_column = match provider {
             "localWhisper" => "whisperApiKey",
             "parakeet" => return Ok(()), // Parakeet doesn't need an API key, return early
+            "remoteWhisper" => return Ok(()), // Token lives in remoteWhisperConfig
             "deepgram" => "deepgramApiKey",
             "elevenLabs" => "elevenLabsApiKey",
             "groq" => "groqApiKey",
@@ -242,6 +246,7 @@ impl SettingsRepository {
         let api_key_column = match provider {
             "localWhisper" => "whisperApiKey",
             "parakeet" => return Ok(None), // Parakeet doesn't need an API key
+            "remoteWhisper" => return Ok(None), // Token lives in remoteWhisperConfig
             "deepgram" => "deepgramApiKey",
             "elevenLabs" => "elevenLabsApiKey",
             "groq" => "groqApiKey",
@@ -279,6 +284,7 @@ impl SettingsRepository {
             "groq" => "groqApiKey",
             "claude" => "anthropicApiKey",
             "openrouter" => "openRouterApiKey",
+            "gemini" => "geminiApiKey",
             "builtin-ai" => return Ok(()), // No API key needed
             _ => {
                 return Err(sqlx::Error::Protocol(
@@ -375,4 +381,58 @@ impl SettingsRepository {
 
         Ok(())
     }
+
+    // ===== REMOTE WHISPER CONFIG METHODS =====
+
+    /// Gets the remote whisper.cpp server configuration from JSON
+    ///
+    /// # Returns
+    /// * `Ok(Some(RemoteWhisperConfig))` - Config exists and is valid JSON
+    /// * `Ok(None)` - No config stored
+    /// * `Err(sqlx::Error)` - Database error
+    pub async fn get_remote_whisper_config(
+        pool: &SqlitePool,
+    ) -> std::result::Result<Option<RemoteWhisperConfig>, sqlx::Error> {
+        let config_json: Option<Option<String>> = sqlx::query_scalar(
+            "SELECT remoteWhisperConfig FROM transcript_settings WHERE id = '1' LIMIT 1"
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        match config_json.flatten() {
+            Some(json) => serde_json::from_str(&json).map(Some).map_err(|e| {
+                sqlx::Error::Protocol(format!("Invalid JSON in remoteWhisperConfig: {}", e).into())
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Saves the remote whisper.cpp server configuration as JSON
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    /// * `config` - RemoteWhisperConfig to save (endpoint, apiKey, authHeader, timeoutSecs, maxConcurrentRequests)
+    pub async fn save_remote_whisper_config(
+        pool: &SqlitePool,
+        config: &RemoteWhisperConfig,
+    ) -> std::result::Result<(), sqlx::Error> {
+        let config_json = serde_json::to_string(config)
+            .map_err(|e| sqlx::Error::Protocol(
+                format!("Failed to serialize config to JSON: {}", e).into()
+            ))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO transcript_settings (id, provider, model, remoteWhisperConfig)
+            VALUES ('1', 'parakeet', 'parakeet-tdt-0.6b-v3-int8', $1)
+            ON CONFLICT(id) DO UPDATE SET
+                remoteWhisperConfig = excluded.remoteWhisperConfig
+            "#,
+        )
+        .bind(config_json)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
 }
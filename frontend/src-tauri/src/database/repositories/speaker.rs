@@ -0,0 +1,142 @@
+use crate::database::models::MeetingSpeaker;
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqlitePool};
+use std::collections::HashMap;
+use tracing::info;
+
+pub struct MeetingSpeakersRepository;
+
+impl MeetingSpeakersRepository {
+    /// Returns all speaker mappings of a meeting ordered by label.
+    pub async fn list_speakers(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<MeetingSpeaker>, SqlxError> {
+        if meeting_id.trim().is_empty() {
+            return Err(SqlxError::Protocol(
+                "meeting_id cannot be empty".to_string(),
+            ));
+        }
+
+        sqlx::query_as::<_, MeetingSpeaker>(
+            "SELECT * FROM meeting_speakers WHERE meeting_id = ? ORDER BY speaker_label ASC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Returns the speaker map of a meeting as `label -> display name`.
+    pub async fn get_speaker_map(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<HashMap<String, String>, SqlxError> {
+        let speakers = Self::list_speakers(pool, meeting_id).await?;
+        Ok(speakers
+            .into_iter()
+            .map(|speaker| (speaker.speaker_label, speaker.display_name))
+            .collect())
+    }
+
+    /// Replaces the speaker map of a meeting in a single transaction.
+    /// Entries with an empty display name are dropped, which un-names that speaker.
+    pub async fn set_speaker_map(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        speakers: &HashMap<String, String>,
+    ) -> Result<usize, SqlxError> {
+        if meeting_id.trim().is_empty() {
+            return Err(SqlxError::Protocol(
+                "meeting_id cannot be empty".to_string(),
+            ));
+        }
+
+        let mut transaction = pool.begin().await?;
+        let now = Utc::now();
+
+        sqlx::query("DELETE FROM meeting_speakers WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        let mut saved = 0;
+        for (label, name) in speakers {
+            let label = label.trim();
+            let name = name.trim();
+            if label.is_empty() || name.is_empty() {
+                continue;
+            }
+
+            sqlx::query(
+                "INSERT INTO meeting_speakers (meeting_id, speaker_label, display_name, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(meeting_id)
+            .bind(label)
+            .bind(name)
+            .bind(now)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+            saved += 1;
+        }
+
+        sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
+            .bind(now)
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+
+        info!("Saved {} speaker names for meeting {}", saved, meeting_id);
+        Ok(saved)
+    }
+
+    /// Names a single speaker of a meeting, updating an existing mapping.
+    pub async fn set_speaker_name(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        speaker_label: &str,
+        display_name: &str,
+    ) -> Result<(), SqlxError> {
+        if meeting_id.trim().is_empty() || speaker_label.trim().is_empty() {
+            return Err(SqlxError::Protocol(
+                "meeting_id and speaker_label cannot be empty".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO meeting_speakers (meeting_id, speaker_label, display_name, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(meeting_id, speaker_label)
+             DO UPDATE SET display_name = excluded.display_name, updated_at = excluded.updated_at",
+        )
+        .bind(meeting_id)
+        .bind(speaker_label.trim())
+        .bind(display_name.trim())
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Removes the name of a single speaker.
+    pub async fn delete_speaker(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        speaker_label: &str,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query(
+            "DELETE FROM meeting_speakers WHERE meeting_id = ? AND speaker_label = ?",
+        )
+        .bind(meeting_id)
+        .bind(speaker_label)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -151,6 +151,34 @@ impl SummaryProcessesRepository {
         Ok(())
     }
 
+    /// Records progress of a running generation in `metadata` and marks it `processing`
+    ///
+    /// Only applies while the process is still running, so a late progress
+    /// write can't overwrite a completed, failed or cancelled status.
+    pub async fn update_process_progress(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        progress: &Value,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        let progress_str = serde_json::to_string(progress)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize progress: {}", e)))?;
+
+        sqlx::query(
+            r#"
+            UPDATE summary_processes
+            SET status = 'processing', metadata = ?, updated_at = ?
+            WHERE meeting_id = ? AND status IN ('PENDING', 'processing')
+            "#,
+        )
+        .bind(progress_str)
+        .bind(now)
+        .bind(meeting_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn update_process_failed(
         pool: &SqlitePool,
         meeting_id: &str,
@@ -1,4 +1,6 @@
-use crate::api::{TranscriptSearchResult, TranscriptSegment};
+use crate::api::{SearchQuery, TranscriptSearchResult, TranscriptSegment};
+use crate::database::repositories::search::{prefix_fts_query, SearchRepository, SOURCE_TRANSCRIPT};
+use crate::database::repositories::transcript_word::save_words_with_transaction;
 use chrono::Utc;
 use sqlx::{Connection, Error as SqlxError, SqlitePool};
 use tracing::{error, info};
@@ -68,6 +70,20 @@ impl TranscriptsRepository {
                 transaction.rollback().await?;
                 return Err(e);
             }
+
+            // Save word-level timings when the provider supplied them
+            if let Some(words) = &segment.words {
+                if let Err(e) =
+                    save_words_with_transaction(&mut transaction, &meeting_id, &transcript_id, words).await
+                {
+                    error!(
+                        "Failed to save word timings for meeting {}: {}",
+                        meeting_id, e
+                    );
+                    transaction.rollback().await?;
+                    return Err(e);
+                }
+            }
         }
 
         info!(
@@ -82,65 +98,114 @@ impl TranscriptsRepository {
         Ok(meeting_id)
     }
 
-    /// Searches for a query string within the transcripts.
-    /// It returns a list of matching transcripts with context.
-    pub async fn search_transcripts(
+    /// Replaces all transcript segments of an existing meeting.
+    /// Used by offline re-transcription: the old rows are deleted and the new
+    /// segments inserted in a single transaction, so a failed re-pass never
+    /// leaves the meeting with a partial transcript.
+    pub async fn replace_meeting_transcripts(
         pool: &SqlitePool,
-        query: &str,
-    ) -> Result<Vec<TranscriptSearchResult>, SqlxError> {
-        if query.trim().is_empty() {
-            return Ok(Vec::new());
+        meeting_id: &str,
+        transcripts: &[TranscriptSegment],
+    ) -> Result<usize, SqlxError> {
+        if meeting_id.trim().is_empty() {
+            return Err(SqlxError::Protocol(
+                "meeting_id cannot be empty".to_string(),
+            ));
         }
 
-        let search_query = format!("%{}%", query.to_lowercase());
-
-        let rows = sqlx::query_as::<_, (String, String, String, String)>(
-            "SELECT m.id, m.title, t.transcript, t.timestamp
-             FROM meetings m
-             JOIN transcripts t ON m.id = t.meeting_id
-             WHERE LOWER(t.transcript) LIKE ?",
-        )
-        .bind(&search_query)
-        .fetch_all(pool)
-        .await?;
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
 
-        let results = rows
-            .into_iter()
-            .map(|(id, title, transcript, timestamp)| {
-                let match_context = Self::get_match_context(&transcript, query);
-                TranscriptSearchResult {
-                    id,
-                    title,
-                    match_context,
-                    timestamp,
-                }
-            })
-            .collect();
+        sqlx::query("DELETE FROM transcript_words WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
 
-        Ok(results)
-    }
+        let deleted = sqlx::query("DELETE FROM transcripts WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?
+            .rows_affected();
 
-    /// Helper function to extract a snippet of text around the first match of a query.
-    fn get_match_context(transcript: &str, query: &str) -> String {
-        let transcript_lower = transcript.to_lowercase();
-        let query_lower = query.to_lowercase();
+        for segment in transcripts {
+            let result = sqlx::query(
+                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(&segment.id)
+            .bind(meeting_id)
+            .bind(&segment.text)
+            .bind(&segment.timestamp)
+            .bind(segment.audio_start_time)
+            .bind(segment.audio_end_time)
+            .bind(segment.duration)
+            .execute(&mut *transaction)
+            .await;
 
-        match transcript_lower.find(&query_lower) {
-            Some(match_index) => {
-                let start_index = match_index.saturating_sub(100);
-                let end_index = (match_index + query.len() + 100).min(transcript.len());
+            if let Err(e) = result {
+                error!(
+                    "Failed to insert re-transcribed segment for meeting {}: {}",
+                    meeting_id, e
+                );
+                transaction.rollback().await?;
+                return Err(e);
+            }
 
-                let mut context = String::new();
-                if start_index > 0 {
-                    context.push_str("...");
-                }
-                context.push_str(&transcript[start_index..end_index]);
-                if end_index < transcript.len() {
-                    context.push_str("...");
+            if let Some(words) = &segment.words {
+                if let Err(e) =
+                    save_words_with_transaction(&mut transaction, meeting_id, &segment.id, words).await
+                {
+                    error!(
+                        "Failed to save word timings for meeting {}: {}",
+                        meeting_id, e
+                    );
+                    transaction.rollback().await?;
+                    return Err(e);
                 }
-                context
             }
-            None => transcript.chars().take(200).collect(), // Fallback to the start of the transcript
         }
+
+        sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
+            .bind(Utc::now())
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+
+        info!(
+            "Replaced {} transcript segments with {} for meeting {}",
+            deleted,
+            transcripts.len(),
+            meeting_id
+        );
+
+        Ok(transcripts.len())
+    }
+
+    /// Searches for a query string within the transcripts.
+    /// Backed by the FTS5 index for search-as-you-type: every typed word matches
+    /// as a prefix, and matches come ranked with a snippet as context.
+    pub async fn search_transcripts(
+        pool: &SqlitePool,
+        query: &str,
+    ) -> Result<Vec<TranscriptSearchResult>, SqlxError> {
+        let search_query = SearchQuery {
+            query: prefix_fts_query(query),
+            sources: Some(vec![SOURCE_TRANSCRIPT.to_string()]),
+            ..Default::default()
+        };
+
+        let hits = SearchRepository::search(pool, &search_query).await?;
+
+        Ok(hits
+            .into_iter()
+            .map(|hit| TranscriptSearchResult {
+                id: hit.meeting_id,
+                title: hit.meeting_title,
+                match_context: hit.snippet,
+                timestamp: hit.timestamp.unwrap_or_default(),
+            })
+            .collect())
     }
 }
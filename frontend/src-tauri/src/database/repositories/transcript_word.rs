@@ -0,0 +1,288 @@
+use crate::api::WordTiming;
+use crate::database::models::TranscriptWord;
+use sqlx::{Error as SqlxError, SqliteConnection, SqlitePool};
+use tracing::info;
+
+pub struct TranscriptWordsRepository;
+
+impl TranscriptWordsRepository {
+    /// Saves the words of one transcript segment, replacing any existing words.
+    pub async fn save_words(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        transcript_id: &str,
+        words: &[WordTiming],
+    ) -> Result<usize, SqlxError> {
+        let mut transaction = pool.begin().await?;
+        let saved =
+            save_words_with_transaction(&mut transaction, meeting_id, transcript_id, words).await?;
+        transaction.commit().await?;
+        Ok(saved)
+    }
+
+    /// Returns the words of a transcript segment in spoken order.
+    pub async fn get_words_for_transcript(
+        pool: &SqlitePool,
+        transcript_id: &str,
+    ) -> Result<Vec<TranscriptWord>, SqlxError> {
+        if transcript_id.trim().is_empty() {
+            return Err(SqlxError::Protocol(
+                "transcript_id cannot be empty".to_string(),
+            ));
+        }
+
+        sqlx::query_as::<_, TranscriptWord>(
+            "SELECT * FROM transcript_words WHERE transcript_id = ? ORDER BY word_index ASC",
+        )
+        .bind(transcript_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Returns the words of a meeting, optionally limited to a time window
+    /// (for clip extraction) and/or to words at or below a confidence threshold
+    /// (for low-confidence flagging).
+    pub async fn get_words_for_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        start_time: Option<f64>,
+        end_time: Option<f64>,
+        max_confidence: Option<f64>,
+    ) -> Result<Vec<TranscriptWord>, SqlxError> {
+        if meeting_id.trim().is_empty() {
+            return Err(SqlxError::Protocol(
+                "meeting_id cannot be empty".to_string(),
+            ));
+        }
+
+        sqlx::query_as::<_, TranscriptWord>(
+            "SELECT * FROM transcript_words
+             WHERE meeting_id = ?
+               AND (? IS NULL OR end_time > ?)
+               AND (? IS NULL OR start_time < ?)
+               AND (? IS NULL OR (confidence IS NOT NULL AND confidence <= ?))
+             ORDER BY start_time ASC, word_index ASC",
+        )
+        .bind(meeting_id)
+        .bind(start_time)
+        .bind(start_time)
+        .bind(end_time)
+        .bind(end_time)
+        .bind(max_confidence)
+        .bind(max_confidence)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Deletes all word timings of a meeting.
+    pub async fn delete_words_for_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<u64, SqlxError> {
+        let result = sqlx::query("DELETE FROM transcript_words WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// Inserts the words of one transcript segment inside an existing transaction,
+/// replacing any words already stored for that segment.
+pub async fn save_words_with_transaction(
+    conn: &mut SqliteConnection,
+    meeting_id: &str,
+    transcript_id: &str,
+    words: &[WordTiming],
+) -> Result<usize, SqlxError> {
+    sqlx::query("DELETE FROM transcript_words WHERE transcript_id = ?")
+        .bind(transcript_id)
+        .execute(&mut *conn)
+        .await?;
+
+    for (index, word) in words.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO transcript_words (transcript_id, meeting_id, word_index, word, punctuated_word, start_time, end_time, confidence, speaker)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(transcript_id)
+        .bind(meeting_id)
+        .bind(index as i64)
+        .bind(&word.word)
+        .bind(&word.punctuated_word)
+        .bind(word.start)
+        .bind(word.end)
+        .bind(word.confidence)
+        .bind(word.speaker)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    if !words.is_empty() {
+        info!(
+            "Saved {} word timings for transcript {}",
+            words.len(),
+            transcript_id
+        );
+    }
+
+    Ok(words.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{insert_meeting, insert_transcript, test_pool};
+
+    fn word(text: &str, start: f64, end: f64, confidence: Option<f64>) -> WordTiming {
+        WordTiming {
+            word: text.to_lowercase(),
+            punctuated_word: Some(text.to_string()),
+            start,
+            end,
+            confidence,
+            speaker: Some(0),
+        }
+    }
+
+    async fn seeded_pool() -> SqlitePool {
+        let pool = test_pool().await;
+        insert_meeting(&pool, "m1", "Standup").await;
+        insert_transcript(&pool, "t1", "m1", "Hello there").await;
+        insert_transcript(&pool, "t2", "m1", "General Kenobi").await;
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_save_and_get_words_in_spoken_order() {
+        let pool = seeded_pool().await;
+        let words = [
+            word("Hello", 1.0, 1.4, Some(0.99)),
+            word("there.", 1.5, 1.9, Some(0.95)),
+        ];
+
+        let saved = TranscriptWordsRepository::save_words(&pool, "m1", "t1", &words)
+            .await
+            .unwrap();
+        assert_eq!(saved, 2);
+
+        let stored = TranscriptWordsRepository::get_words_for_transcript(&pool, "t1")
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].word_index, 0);
+        assert_eq!(stored[0].punctuated_word.as_deref(), Some("Hello"));
+        assert_eq!(stored[1].word, "there.");
+        assert_eq!(stored[1].start_time, 1.5);
+        assert_eq!(stored[1].speaker, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_resave_replaces_existing_words() {
+        let pool = seeded_pool().await;
+        TranscriptWordsRepository::save_words(&pool, "m1", "t1", &[word("Hello", 1.0, 1.4, None)])
+            .await
+            .unwrap();
+        TranscriptWordsRepository::save_words(
+            &pool,
+            "m1",
+            "t1",
+            &[word("Hi", 1.0, 1.2, None), word("there", 1.3, 1.6, None)],
+        )
+        .await
+        .unwrap();
+
+        let stored = TranscriptWordsRepository::get_words_for_transcript(&pool, "t1")
+            .await
+            .unwrap();
+        let texts: Vec<_> = stored.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(texts, vec!["hi", "there"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_words_for_meeting_filters_window_and_confidence() {
+        let pool = seeded_pool().await;
+        TranscriptWordsRepository::save_words(
+            &pool,
+            "m1",
+            "t1",
+            &[
+                word("Hello", 1.0, 1.4, Some(0.99)),
+                word("there", 1.5, 1.9, Some(0.4)),
+            ],
+        )
+        .await
+        .unwrap();
+        TranscriptWordsRepository::save_words(
+            &pool,
+            "m1",
+            "t2",
+            &[
+                word("General", 5.0, 5.5, Some(0.3)),
+                word("Kenobi", 5.6, 6.2, None),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let all = TranscriptWordsRepository::get_words_for_meeting(&pool, "m1", None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 4);
+
+        // Words overlapping [1.6, 5.2) only
+        let window = TranscriptWordsRepository::get_words_for_meeting(
+            &pool,
+            "m1",
+            Some(1.6),
+            Some(5.2),
+            None,
+        )
+        .await
+        .unwrap();
+        let texts: Vec<_> = window.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(texts, vec!["there", "general"]);
+
+        // Words without a confidence are never flagged
+        let low =
+            TranscriptWordsRepository::get_words_for_meeting(&pool, "m1", None, None, Some(0.5))
+                .await
+                .unwrap();
+        let texts: Vec<_> = low.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(texts, vec!["there", "general"]);
+    }
+
+    #[tokio::test]
+    async fn test_delete_words_for_meeting() {
+        let pool = seeded_pool().await;
+        TranscriptWordsRepository::save_words(&pool, "m1", "t1", &[word("Hello", 1.0, 1.4, None)])
+            .await
+            .unwrap();
+
+        let deleted = TranscriptWordsRepository::delete_words_for_meeting(&pool, "m1")
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+        assert!(
+            TranscriptWordsRepository::get_words_for_transcript(&pool, "t1")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_ids_are_rejected() {
+        let pool = test_pool().await;
+        assert!(
+            TranscriptWordsRepository::get_words_for_transcript(&pool, " ")
+                .await
+                .is_err()
+        );
+        assert!(
+            TranscriptWordsRepository::get_words_for_meeting(&pool, "", None, None, None)
+                .await
+                .is_err()
+        );
+    }
+}
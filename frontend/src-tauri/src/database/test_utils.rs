@@ -0,0 +1,49 @@
+//! Shared helpers for repository tests: an in-memory database with all
+//! migrations applied, plus minimal meeting/transcript fixtures.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::SqlitePool;
+use std::str::FromStr;
+
+/// Opens a fresh in-memory database with every migration applied.
+///
+/// The pool is limited to one connection because each SQLite in-memory
+/// connection is its own database.
+pub(crate) async fn test_pool() -> SqlitePool {
+    let options = SqliteConnectOptions::from_str("sqlite::memory:")
+        .expect("valid in-memory url")
+        .foreign_keys(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .expect("open in-memory database");
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("run migrations");
+    pool
+}
+
+pub(crate) async fn insert_meeting(pool: &SqlitePool, id: &str, title: &str) {
+    sqlx::query(
+        "INSERT INTO meetings (id, title, created_at, updated_at) VALUES (?, ?, '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z')",
+    )
+    .bind(id)
+    .bind(title)
+    .execute(pool)
+    .await
+    .expect("insert meeting");
+}
+
+pub(crate) async fn insert_transcript(pool: &SqlitePool, id: &str, meeting_id: &str, text: &str) {
+    sqlx::query(
+        "INSERT INTO transcripts (id, meeting_id, transcript, timestamp) VALUES (?, ?, ?, '2026-01-01T00:00:00Z')",
+    )
+    .bind(id)
+    .bind(meeting_id)
+    .bind(text)
+    .execute(pool)
+    .await
+    .expect("insert transcript");
+}
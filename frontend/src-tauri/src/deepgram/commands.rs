@@ -0,0 +1,102 @@
+// deepgram/commands.rs
+//
+// Tauri commands for Deepgram prerecorded (batch) transcription of audio files.
+
+use super::prerecorded::{to_note_segments, DeepgramPrerecordedClient, PrerecordedOptions, PrerecordedTranscript};
+use crate::database::repositories::setting::SettingsRepository;
+use crate::obsidian::{NoteMetadata, SaveTranscriptRequest, SpeakerNames};
+use crate::state::AppState;
+use log::{error, info};
+use serde::Deserialize;
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime};
+
+/// Request to transcribe an existing audio file with the prerecorded API
+#[derive(Debug, Clone, Deserialize)]
+pub struct TranscribeFileRequest {
+    /// Audio file to upload (mp4/wav/mp3/...)
+    pub audio_path: String,
+    /// Optional meeting note to write the transcript into
+    pub note_path: Option<String>,
+    pub model: Option<String>,
+    pub language: Option<String>,
+    pub diarize: Option<bool>,
+    /// Speaker label -> person name to apply to the note's transcript
+    #[serde(default)]
+    pub speaker_names: SpeakerNames,
+}
+
+/// Load the Deepgram API key from the transcript settings
+pub async fn get_deepgram_api_key(state: &AppState) -> Result<String, String> {
+    SettingsRepository::get_transcript_api_key(state.db_manager.pool(), "deepgram")
+        .await
+        .map_err(|e| format!("Failed to read Deepgram API key: {}", e))?
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| {
+            "Deepgram API key is not configured. Please add your API key in Settings > Transcription.".to_string()
+        })
+}
+
+/// Transcribe an audio file with Deepgram's prerecorded API.
+/// When `note_path` is given the transcript is also merged into that note.
+#[tauri::command]
+pub async fn deepgram_transcribe_file<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    request: TranscribeFileRequest,
+) -> Result<PrerecordedTranscript, String> {
+    info!("deepgram_transcribe_file called for: {}", request.audio_path);
+
+    let audio_path = PathBuf::from(&request.audio_path);
+    if !audio_path.is_file() {
+        return Err(format!("Audio file not found: {}", request.audio_path));
+    }
+
+    let api_key = get_deepgram_api_key(&state).await?;
+    let client = DeepgramPrerecordedClient::new(api_key);
+
+    let defaults = PrerecordedOptions::default();
+    let options = PrerecordedOptions {
+        model: request.model.clone().unwrap_or(defaults.model.clone()),
+        language: request.language.clone().or_else(crate::get_language_preference_internal),
+        diarize: request.diarize.unwrap_or(defaults.diarize),
+        ..defaults
+    };
+
+    // Snapshot the note before the upload, so edits made while transcribing are merged
+    let base_content = match &request.note_path {
+        Some(note_path) => tokio::fs::read_to_string(note_path)
+            .await
+            .map_err(|e| format!("Failed to read note {}: {}", note_path, e))
+            .map(Some)?,
+        None => None,
+    };
+
+    let transcript = client.transcribe_file(&audio_path, &options).await?;
+
+    if let Some(note_path) = request.note_path {
+        let save_request = SaveTranscriptRequest {
+            file_path: note_path.clone(),
+            segments: to_note_segments(&transcript.segments),
+            update_status: true,
+            speaker_names: request.speaker_names.clone(),
+            action_items: Vec::new(),
+            base_content,
+            metadata: NoteMetadata {
+                duration_seconds: transcript.duration,
+                recording: Some(request.audio_path.clone()),
+                transcript_provider: Some("deepgram".to_string()),
+                ..Default::default()
+            },
+        };
+        crate::obsidian::writer::save_transcript(&save_request)
+            .await
+            .map_err(|e| {
+                error!("Failed to write imported transcript to {}: {}", note_path, e);
+                format!("Failed to write transcript to note: {}", e)
+            })?;
+        info!("Imported transcript written to note: {}", note_path);
+    }
+
+    Ok(transcript)
+}
@@ -1,21 +1,29 @@
 // deepgram/mod.rs
 //
 // Deepgram transcription module providing cloud-based speech-to-text
-// with real-time streaming and speaker diarization support.
+// with real-time streaming, prerecorded (batch) uploads and speaker
+// diarization support.
 //
 // This module implements the TranscriptionProvider trait for integration
 // with the existing audio transcription pipeline.
 
+pub mod commands;
+pub mod offline_queue;
+pub mod prerecorded;
 pub mod provider;
+pub mod replay;
 pub mod websocket;
 
 // Re-export commonly used types
+pub use prerecorded::{DeepgramPrerecordedClient, PrerecordedOptions, PrerecordedTranscript};
 pub use provider::DeepgramProvider;
 pub use websocket::{
     convert_f32_to_linear16,
     ConnectionState,
+    ConnectionStateEvent,
     DeepgramConfig,
     DeepgramWebSocket,
+    ResilienceConfig,
     SpeakerSegment,
     TranscriptionSegment,
 };
@@ -0,0 +1,641 @@
+// deepgram/offline_queue.rs
+//
+// Offline capture queue. When Deepgram is unreachable at recording start the
+// meeting is recorded without live cloud transcription and queued in the
+// `pending_transcriptions` table when it stops. A background task uploads
+// queued recordings to the prerecorded API once Deepgram is reachable again
+// and writes transcript and summary to the meeting note (the prep note open
+// while recording, if any), as a live recording would have.
+
+use crate::api::MeetingTranscript;
+use crate::audio::transcription::retranscribe::{retranscribe_meeting_folder, RetranscribeRequest};
+use crate::database::models::PendingTranscription;
+use crate::database::repositories::{
+    meeting::MeetingsRepository, pending_transcription::PendingTranscriptionsRepository,
+    setting::SettingsRepository, speaker::MeetingSpeakersRepository,
+    summary::SummaryProcessesRepository, transcript_chunk::TranscriptChunksRepository,
+};
+use crate::obsidian::speakers::split_speaker_turns;
+use crate::obsidian::{ActionItem, NoteMetadata, SaveTranscriptRequest, TranscriptSegment};
+use crate::state::AppState;
+use crate::summary::SummaryService;
+use log::{debug, error, info, warn};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::net::TcpStream;
+use tokio::sync::Notify;
+
+/// Host probed to decide whether Deepgram is reachable
+const DEEPGRAM_HOST: &str = "api.deepgram.com";
+
+/// Connect timeout of the reachability probe
+const CONNECTIVITY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often queued recordings are retried while offline
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Failed uploads before a queued recording is given up
+const MAX_ATTEMPTS: i64 = 5;
+
+/// How long a queued recording waits for its meeting to be saved
+const MEETING_SAVE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Template for the summary; the note's frontmatter may pick another one
+const DEFAULT_SUMMARY_TEMPLATE: &str = "standard_meeting";
+
+/// Event emitted when a recording starts without a connection to Deepgram
+pub const OFFLINE_CAPTURE_EVENT: &str = "offline-capture-started";
+
+/// Event emitted when a queued recording has been transcribed
+pub const PENDING_COMPLETE_EVENT: &str = "pending-transcription-complete";
+
+/// Event emitted when a queued recording failed to transcribe
+pub const PENDING_FAILED_EVENT: &str = "pending-transcription-failed";
+
+/// Whether the current recording is captured for deferred transcription
+static OFFLINE_CAPTURE: AtomicBool = AtomicBool::new(false);
+
+/// Wakes the queue task when a recording was queued
+fn queue_wake() -> &'static Notify {
+    static WAKE: OnceLock<Notify> = OnceLock::new();
+    WAKE.get_or_init(Notify::new)
+}
+
+/// Payload of `pending-transcription-complete`
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingTranscriptionComplete {
+    pub meeting_id: String,
+    pub meeting_name: Option<String>,
+    pub segment_count: usize,
+    pub summarized: bool,
+}
+
+/// Payload of `pending-transcription-failed`
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingTranscriptionFailed {
+    pub meeting_name: Option<String>,
+    pub folder_path: String,
+    pub error: String,
+    /// The recording stays queued unless this is set
+    pub gave_up: bool,
+}
+
+/// Whether the current recording has no live Deepgram transcription
+pub fn is_offline_capture() -> bool {
+    OFFLINE_CAPTURE.load(Ordering::SeqCst)
+}
+
+/// Whether a TCP connection to the Deepgram API can be opened
+pub async fn deepgram_reachable() -> bool {
+    matches!(
+        tokio::time::timeout(
+            CONNECTIVITY_TIMEOUT,
+            TcpStream::connect((DEEPGRAM_HOST, 443))
+        )
+        .await,
+        Ok(Ok(_))
+    )
+}
+
+/// Called when a recording starts. With Deepgram selected but unreachable the
+/// recording becomes an offline capture. Returns whether it is one.
+pub async fn begin_recording<R: Runtime>(app: &AppHandle<R>) -> bool {
+    let provider = match app.try_state::<AppState>() {
+        Some(state) => SettingsRepository::get_transcript_config(state.db_manager.pool())
+            .await
+            .ok()
+            .flatten()
+            .map(|config| config.provider),
+        None => None,
+    };
+
+    let offline = provider.as_deref() == Some("deepgram") && !deepgram_reachable().await;
+    OFFLINE_CAPTURE.store(offline, Ordering::SeqCst);
+
+    if offline {
+        warn!("📴 Deepgram is unreachable - recording now, transcribing once back online");
+        let _ = app.emit(
+            OFFLINE_CAPTURE_EVENT,
+            serde_json::json!({
+                "message": "You're offline. Recording anyway; Deepgram will transcribe this meeting once you're back online."
+            }),
+        );
+    }
+    offline
+}
+
+/// Called when a recording stops. Queues an offline capture for deferred
+/// transcription. Returns whether the recording was queued.
+pub async fn finish_recording<R: Runtime>(
+    app: &AppHandle<R>,
+    folder: Option<&Path>,
+    meeting_name: Option<&str>,
+) -> bool {
+    if !OFFLINE_CAPTURE.swap(false, Ordering::SeqCst) {
+        return false;
+    }
+
+    let Some(folder) = folder else {
+        error!("❌ Offline recording has no meeting folder; it can't be transcribed later");
+        return false;
+    };
+    let Some(state) = app.try_state::<AppState>() else {
+        error!("❌ Database not available; offline recording was not queued");
+        return false;
+    };
+    let pool = state.db_manager.pool();
+
+    let model = SettingsRepository::get_transcript_config(pool)
+        .await
+        .ok()
+        .flatten()
+        .map(|config| config.model)
+        .filter(|model| !model.is_empty());
+    let language = crate::get_language_preference_internal();
+
+    match PendingTranscriptionsRepository::enqueue(
+        pool,
+        &folder.to_string_lossy(),
+        meeting_name,
+        model.as_deref(),
+        language.as_deref(),
+    )
+    .await
+    {
+        Ok(_) => {
+            queue_wake().notify_one();
+            true
+        }
+        Err(e) => {
+            error!(
+                "❌ Failed to queue offline recording {}: {}",
+                folder.display(),
+                e
+            );
+            false
+        }
+    }
+}
+
+/// Attach the prep note that was open while an offline recording was made, so
+/// its deferred transcript and summary go to that note as they would have live.
+/// `base_content` is the note as it was when recording started.
+#[tauri::command]
+pub async fn set_pending_transcription_note<R: Runtime>(
+    app: AppHandle<R>,
+    folder_path: String,
+    note_path: String,
+    base_content: Option<String>,
+) -> Result<(), String> {
+    let state = app
+        .try_state::<AppState>()
+        .ok_or("Database not available")?;
+    let found = PendingTranscriptionsRepository::set_note(
+        state.db_manager.pool(),
+        &folder_path,
+        &note_path,
+        base_content.as_deref(),
+    )
+    .await
+    .map_err(|e| format!("Failed to attach meeting note: {}", e))?;
+    if !found {
+        warn!(
+            "No queued recording for {}, meeting note not attached",
+            folder_path
+        );
+    }
+    Ok(())
+}
+
+/// Background task processing queued recordings whenever Deepgram is reachable
+pub async fn run_offline_queue<R: Runtime>(app: AppHandle<R>) {
+    loop {
+        process_queue(&app).await;
+        let _ = tokio::time::timeout(QUEUE_POLL_INTERVAL, queue_wake().notified()).await;
+    }
+}
+
+async fn process_queue<R: Runtime>(app: &AppHandle<R>) {
+    // Leave the connection to a live recording
+    if crate::audio::recording_commands::is_recording().await {
+        return;
+    }
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let pool = state.db_manager.pool();
+
+    let jobs = match PendingTranscriptionsRepository::list_pending(pool).await {
+        Ok(jobs) if !jobs.is_empty() => jobs,
+        Ok(_) => return,
+        Err(e) => {
+            warn!("Failed to load pending transcriptions: {}", e);
+            return;
+        }
+    };
+
+    if !deepgram_reachable().await {
+        debug!(
+            "Deepgram still unreachable, {} recording(s) queued",
+            jobs.len()
+        );
+        return;
+    }
+    info!(
+        "🌐 Deepgram is reachable, processing {} queued recording(s)",
+        jobs.len()
+    );
+
+    for job in jobs {
+        if crate::audio::recording_commands::is_recording().await {
+            break;
+        }
+
+        // The frontend saves the meeting shortly after the recording stops
+        let meeting_id =
+            match MeetingsRepository::find_meeting_by_folder(pool, &job.folder_path).await {
+                Ok(Some(meeting)) => meeting.id,
+                Ok(None) => {
+                    let waited = (chrono::Utc::now() - job.created_at)
+                        .to_std()
+                        .unwrap_or_default();
+                    if waited < MEETING_SAVE_TIMEOUT {
+                        debug!(
+                            "Meeting for {} not saved yet, retrying later",
+                            job.folder_path
+                        );
+                    } else {
+                        // The meeting was discarded; don't keep the recording queued forever
+                        warn!("Meeting for {} was never saved", job.folder_path);
+                        report_failure(
+                            app,
+                            pool,
+                            &job,
+                            "The meeting was never saved".to_string(),
+                            1, // nothing to retry
+                        )
+                        .await;
+                    }
+                    continue;
+                }
+                Err(e) => {
+                    warn!("Failed to look up meeting for {}: {}", job.folder_path, e);
+                    continue;
+                }
+            };
+
+        match process_job(app, &state, &job, &meeting_id).await {
+            Ok(complete) => {
+                if let Err(e) =
+                    PendingTranscriptionsRepository::mark_completed(pool, &job.id, &meeting_id)
+                        .await
+                {
+                    error!(
+                        "Failed to mark pending transcription {} completed: {}",
+                        job.id, e
+                    );
+                }
+                info!(
+                    "✅ Queued recording {} transcribed: {} segments (summary: {})",
+                    job.folder_path, complete.segment_count, complete.summarized
+                );
+                let _ = app.emit(PENDING_COMPLETE_EVENT, &complete);
+            }
+            Err(e) => {
+                error!("❌ Queued recording {} failed: {}", job.folder_path, e);
+                report_failure(app, pool, &job, e, MAX_ATTEMPTS).await;
+            }
+        }
+    }
+}
+
+/// Count a failed attempt and tell the frontend; the recording is given up
+/// after `max_attempts`
+async fn report_failure<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    job: &PendingTranscription,
+    error: String,
+    max_attempts: i64,
+) {
+    let gave_up =
+        PendingTranscriptionsRepository::record_failure(pool, &job.id, &error, max_attempts)
+            .await
+            .unwrap_or(false);
+    let _ = app.emit(
+        PENDING_FAILED_EVENT,
+        PendingTranscriptionFailed {
+            meeting_name: job.meeting_name.clone(),
+            folder_path: job.folder_path.clone(),
+            error,
+            gave_up,
+        },
+    );
+}
+
+/// Transcribe a queued recording with the prerecorded API, then write the
+/// transcript and a summary into the meeting note
+async fn process_job<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &AppState,
+    job: &PendingTranscription,
+    meeting_id: &str,
+) -> Result<PendingTranscriptionComplete, String> {
+    let pool = state.db_manager.pool();
+    info!(
+        "☁️ Transcribing queued recording {} (meeting {})",
+        job.folder_path, meeting_id
+    );
+
+    let folder = Path::new(&job.folder_path);
+    let note = meeting_note(job, folder).await;
+
+    // Replaces the meeting's transcript rows and transcripts.json
+    let request = RetranscribeRequest {
+        meeting_id: meeting_id.to_string(),
+        provider: "deepgram".to_string(),
+        model: job.model.clone(),
+        language: job.language.clone(),
+        keep_previous_version: Some(true),
+        diarize: None,
+    };
+    let result = retranscribe_meeting_folder(app, state, &request).await?;
+
+    let mut transcripts = MeetingsRepository::get_meeting(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcripts: {}", e))?
+        .map(|meeting| meeting.transcripts)
+        .unwrap_or_default();
+    transcripts.sort_by(|a, b| {
+        a.audio_start_time
+            .unwrap_or(0.0)
+            .total_cmp(&b.audio_start_time.unwrap_or(0.0))
+    });
+
+    match note {
+        Some((note_path, base_content)) => {
+            let speaker_names = MeetingSpeakersRepository::get_speaker_map(pool, meeting_id)
+                .await
+                .map_err(|e| format!("Failed to load speaker names: {}", e))?;
+            let save_request = SaveTranscriptRequest {
+                file_path: note_path.to_string_lossy().to_string(),
+                segments: note_segments(&transcripts),
+                update_status: true,
+                speaker_names,
+                action_items: Vec::new(),
+                base_content: Some(base_content),
+                metadata: NoteMetadata {
+                    duration_seconds: Some(result.duration_seconds),
+                    transcript_provider: Some("deepgram".to_string()),
+                    ..Default::default()
+                },
+            };
+            crate::obsidian::writer::save_transcript(&save_request)
+                .await
+                .map_err(|e| format!("Failed to write transcript to note: {}", e))?;
+        }
+        None => warn!(
+            "No meeting note in {}, transcript kept in the database only",
+            folder.display()
+        ),
+    }
+
+    // The transcript is done at this point; a failed summary can be regenerated by hand
+    let prep_note = job.note_path.as_deref().map(Path::new);
+    let summarized =
+        match summarize_meeting(app, pool, meeting_id, &transcripts, folder, prep_note).await {
+            Ok(summarized) => summarized,
+            Err(e) => {
+                warn!(
+                    "⚠️ Summary for queued recording {} failed: {}",
+                    meeting_id, e
+                );
+                false
+            }
+        };
+
+    Ok(PendingTranscriptionComplete {
+        meeting_id: meeting_id.to_string(),
+        meeting_name: job.meeting_name.clone(),
+        segment_count: result.segment_count,
+        summarized,
+    })
+}
+
+/// Note the transcript goes into and the content edits are merged against: the
+/// prep note open while recording with its content at recording start, or the
+/// note in the recording folder
+async fn meeting_note(job: &PendingTranscription, folder: &Path) -> Option<(PathBuf, String)> {
+    let note_path = match &job.note_path {
+        Some(note_path) => PathBuf::from(note_path),
+        None => crate::obsidian::find_markdown_file_in_folder(folder)?,
+    };
+    if let Some(base) = &job.note_base {
+        return Some((note_path, base.clone()));
+    }
+
+    // Snapshot the note before transcribing, so edits made meanwhile are merged
+    match tokio::fs::read_to_string(&note_path).await {
+        Ok(content) => Some((note_path, content)),
+        Err(e) => {
+            warn!("Failed to read meeting note {}: {}", note_path.display(), e);
+            None
+        }
+    }
+}
+
+/// Generate the summary with the configured model and write it to the meeting
+/// note, like a live recording's summary: guided by the prep note's agenda,
+/// with the template picked by its frontmatter and its action items merged
+/// into it. Returns false when no summary model is configured.
+async fn summarize_meeting<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: &str,
+    transcripts: &[MeetingTranscript],
+    folder: &Path,
+    prep_note: Option<&Path>,
+) -> Result<bool, String> {
+    let config = SettingsRepository::get_model_config(pool)
+        .await
+        .map_err(|e| format!("Failed to load model config: {}", e))?
+        .filter(|config| !config.provider.is_empty() && !config.model.is_empty());
+    let Some(config) = config else {
+        info!(
+            "No summary model configured, skipping summary for {}",
+            meeting_id
+        );
+        return Ok(false);
+    };
+
+    let text = summary_text(transcripts);
+    if text.trim().is_empty() {
+        return Ok(false);
+    }
+
+    SummaryProcessesRepository::create_or_reset_process(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to initialize process: {}", e))?;
+    TranscriptChunksRepository::save_transcript_data(
+        pool,
+        meeting_id,
+        &text,
+        &config.provider,
+        &config.model,
+        40000,
+        1000,
+    )
+    .await
+    .map_err(|e| format!("Failed to save transcript data: {}", e))?;
+
+    SummaryService::process_transcript_background(
+        app.clone(),
+        pool.clone(),
+        meeting_id.to_string(),
+        text,
+        config.provider,
+        config.model,
+        String::new(),
+        DEFAULT_SUMMARY_TEMPLATE.to_string(),
+        prep_note.map(|path| path.to_string_lossy().to_string()),
+        true,
+    )
+    .await;
+
+    let process = SummaryProcessesRepository::get_summary_data(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to read summary: {}", e))?
+        .ok_or("Summary process not found")?;
+    if process.status != "completed" {
+        return Err(process
+            .error
+            .unwrap_or_else(|| format!("Summary {}", process.status)));
+    }
+
+    let result: serde_json::Value = process
+        .result
+        .as_deref()
+        .and_then(|result| serde_json::from_str(result).ok())
+        .unwrap_or_default();
+    let markdown = result["markdown"].as_str().unwrap_or_default();
+    if markdown.trim().is_empty() {
+        return Ok(false);
+    }
+    let action_items =
+        serde_json::from_value::<Vec<ActionItem>>(result["action_items"].clone()).ok();
+    let template = result["template"].as_str().map(str::to_string);
+
+    crate::obsidian::update_meeting_summary(
+        folder.to_string_lossy().to_string(),
+        markdown.to_string(),
+        action_items.clone(),
+        template,
+    )
+    .await?;
+
+    if let (Some(prep_note), Some(action_items)) = (prep_note, action_items) {
+        if !action_items.is_empty() {
+            crate::obsidian::merge_meeting_action_items(
+                prep_note.to_string_lossy().to_string(),
+                action_items,
+            )
+            .await?;
+        }
+    }
+    Ok(true)
+}
+
+/// Transcript as sent to the summary model, one `[MM:SS] text` line per segment
+fn summary_text(transcripts: &[MeetingTranscript]) -> String {
+    transcripts
+        .iter()
+        .map(|t| match t.audio_start_time {
+            Some(start) => format!(
+                "{} {}",
+                crate::audio::transcription::worker::format_recording_time(start),
+                t.text
+            ),
+            None => format!("{} {}", t.timestamp, t.text),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Transcript rows as meeting note segments, one per diarized speaker turn
+fn note_segments(transcripts: &[MeetingTranscript]) -> Vec<TranscriptSegment> {
+    transcripts
+        .iter()
+        .flat_map(|t| {
+            let timestamp = super::provider::format_timestamp(t.audio_start_time.unwrap_or(0.0));
+            split_speaker_turns(&t.text).into_iter().enumerate().map(
+                move |(turn, (speaker, text))| TranscriptSegment {
+                    timestamp: timestamp.clone(),
+                    speaker,
+                    text,
+                    // Citation ids are per transcript row, so only its first turn gets one
+                    audio_start_time: t.audio_start_time.filter(|_| turn == 0),
+                },
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript(text: &str, start: Option<f64>) -> MeetingTranscript {
+        MeetingTranscript {
+            id: "t".to_string(),
+            text: text.to_string(),
+            timestamp: "[00:00]".to_string(),
+            audio_start_time: start,
+            audio_end_time: None,
+            duration: None,
+        }
+    }
+
+    #[test]
+    fn test_summary_text_uses_recording_time() {
+        let transcripts = vec![
+            transcript("Hello", Some(5.2)),
+            transcript("Bye", Some(65.0)),
+        ];
+        assert_eq!(summary_text(&transcripts), "[00:05] Hello\n[01:05] Bye");
+    }
+
+    #[test]
+    fn test_note_segments_skip_empty_text() {
+        let transcripts = vec![
+            transcript("  ", Some(1.0)),
+            transcript("[Speaker 0]: Hi ", Some(62.0)),
+        ];
+        let segments = note_segments(&transcripts);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].timestamp, "01:02");
+        assert_eq!(segments[0].speaker.as_deref(), Some("Speaker 0"));
+        assert_eq!(segments[0].text, "Hi");
+    }
+
+    #[test]
+    fn test_note_segments_split_speaker_turns() {
+        let transcripts = vec![
+            transcript("[Speaker 0]: Hi\n[Speaker 1]: Hello there", Some(5.0)),
+            transcript("No label", Some(9.0)),
+        ];
+        let segments = note_segments(&transcripts);
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[1].timestamp, "00:05");
+        assert_eq!(segments[1].speaker.as_deref(), Some("Speaker 1"));
+        assert_eq!(segments[1].text, "Hello there");
+        assert_eq!(segments[0].audio_start_time, Some(5.0));
+        assert_eq!(segments[1].audio_start_time, None);
+        assert_eq!(segments[2].speaker, None);
+        assert_eq!(segments[2].text, "No label");
+    }
+}
@@ -0,0 +1,632 @@
+// deepgram/prerecorded.rs
+//
+// Batch client for Deepgram's prerecorded `/v1/listen` REST endpoint.
+// Uploads a complete audio file (recordings folder, phone memos, Zoom exports)
+// and maps the response into the same TranscriptionSegment/SpeakerSegment
+// types used by the streaming path.
+
+use super::provider::format_timestamp;
+use super::websocket::{
+    extract_speaker_segments, is_valid_language_code, DeepgramWord, SpeakerSegment,
+    TranscriptionSegment,
+};
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+/// Production Deepgram REST endpoint
+pub const DEEPGRAM_API_BASE_URL: &str = "https://api.deepgram.com";
+
+/// Uploads of long meetings can take a while to process
+const PRERECORDED_TIMEOUT: Duration = Duration::from_secs(600);
+
+// ============================================================================
+// REQUEST OPTIONS
+// ============================================================================
+
+/// Query options for a prerecorded request
+#[derive(Debug, Clone)]
+pub struct PrerecordedOptions {
+    pub model: String,
+    pub language: Option<String>,
+    pub diarize: bool,
+    pub utterances: bool,
+    pub paragraphs: bool,
+    pub punctuate: bool,
+    pub smart_format: bool,
+}
+
+impl Default for PrerecordedOptions {
+    fn default() -> Self {
+        Self {
+            model: "nova-2".to_string(),
+            language: None,
+            diarize: true,
+            utterances: true,
+            paragraphs: true,
+            punctuate: true,
+            smart_format: true,
+        }
+    }
+}
+
+impl PrerecordedOptions {
+    /// Build the query parameters for `/v1/listen`
+    pub fn query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("model", self.model.clone())];
+
+        match self.language.as_deref() {
+            Some(lang) if is_valid_language_code(lang) => {
+                params.push(("language", lang.to_string()));
+            }
+            // No usable language code - let Deepgram detect it
+            _ => params.push(("detect_language", "true".to_string())),
+        }
+
+        for (key, enabled) in [
+            ("diarize", self.diarize),
+            ("utterances", self.utterances),
+            ("paragraphs", self.paragraphs),
+            ("punctuate", self.punctuate),
+            ("smart_format", self.smart_format),
+        ] {
+            if enabled {
+                params.push((key, "true".to_string()));
+            }
+        }
+
+        params
+    }
+}
+
+// ============================================================================
+// DEEPGRAM PRERECORDED RESPONSE TYPES
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrerecordedResponse {
+    pub metadata: Option<PrerecordedMetadata>,
+    pub results: PrerecordedResults,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrerecordedMetadata {
+    pub request_id: Option<String>,
+    pub duration: Option<f64>,
+    pub channels: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrerecordedResults {
+    pub channels: Vec<PrerecordedChannel>,
+    pub utterances: Option<Vec<PrerecordedUtterance>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrerecordedChannel {
+    pub alternatives: Vec<PrerecordedAlternative>,
+    pub detected_language: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrerecordedAlternative {
+    pub transcript: String,
+    pub confidence: Option<f64>,
+    pub words: Option<Vec<DeepgramWord>>,
+    pub paragraphs: Option<PrerecordedParagraphs>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrerecordedParagraphs {
+    pub paragraphs: Vec<PrerecordedParagraph>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrerecordedParagraph {
+    pub sentences: Vec<PrerecordedSentence>,
+    pub speaker: Option<i32>,
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrerecordedSentence {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrerecordedUtterance {
+    pub start: f64,
+    pub end: f64,
+    pub confidence: Option<f64>,
+    pub transcript: String,
+    pub speaker: Option<i32>,
+    pub words: Option<Vec<DeepgramWord>>,
+}
+
+// ============================================================================
+// MAPPED RESULT
+// ============================================================================
+
+/// A finished prerecorded transcription
+#[derive(Debug, Clone, Serialize)]
+pub struct PrerecordedTranscript {
+    pub request_id: Option<String>,
+    pub duration: Option<f64>,
+    pub detected_language: Option<String>,
+    /// Full transcript text of the first channel
+    pub transcript: String,
+    /// Segments built from utterances, paragraphs or the whole channel (in that order of preference)
+    pub segments: Vec<TranscriptionSegment>,
+    /// Word timings of the first channel
+    pub words: Vec<DeepgramWord>,
+}
+
+/// Map a prerecorded response into TranscriptionSegments.
+/// Utterances are preferred, then paragraphs, then a single segment per channel.
+pub fn map_prerecorded_response(response: &PrerecordedResponse) -> PrerecordedTranscript {
+    let channel = response.results.channels.first();
+    let alternative = channel.and_then(|c| c.alternatives.first());
+    let words = alternative
+        .and_then(|a| a.words.clone())
+        .unwrap_or_default();
+
+    let segments = match response.results.utterances.as_deref() {
+        Some(utterances) if !utterances.is_empty() => utterances
+            .iter()
+            .filter(|u| !u.transcript.trim().is_empty())
+            .map(map_utterance)
+            .collect(),
+        _ => match alternative.and_then(|a| a.paragraphs.as_ref()) {
+            Some(paragraphs) if !paragraphs.paragraphs.is_empty() => paragraphs
+                .paragraphs
+                .iter()
+                .map(|p| map_paragraph(p, alternative.and_then(|a| a.confidence), &words))
+                .filter(|s| !s.text.is_empty())
+                .collect(),
+            _ => alternative
+                .filter(|a| !a.transcript.trim().is_empty())
+                .map(|a| {
+                    vec![TranscriptionSegment {
+                        text: a.transcript.clone(),
+                        confidence: a.confidence.map(|c| c as f32),
+                        is_final: true,
+                        start_time: words.first().map(|w| w.start),
+                        end_time: words.last().map(|w| w.end),
+                        speakers: extract_speaker_segments(&words),
+                        words: words.clone(),
+                        from_finalize: false,
+                    }]
+                })
+                .unwrap_or_default(),
+        },
+    };
+
+    PrerecordedTranscript {
+        request_id: response.metadata.as_ref().and_then(|m| m.request_id.clone()),
+        duration: response.metadata.as_ref().and_then(|m| m.duration),
+        detected_language: channel.and_then(|c| c.detected_language.clone()),
+        transcript: alternative.map(|a| a.transcript.clone()).unwrap_or_default(),
+        segments,
+        words,
+    }
+}
+
+fn map_utterance(utterance: &PrerecordedUtterance) -> TranscriptionSegment {
+    let speakers = match (&utterance.words, utterance.speaker) {
+        (Some(words), _) if !words.is_empty() => extract_speaker_segments(words),
+        (_, Some(speaker_id)) => vec![SpeakerSegment {
+            speaker_id,
+            text: utterance.transcript.clone(),
+            start_time: utterance.start,
+            end_time: utterance.end,
+        }],
+        _ => Vec::new(),
+    };
+
+    TranscriptionSegment {
+        text: utterance.transcript.clone(),
+        confidence: utterance.confidence.map(|c| c as f32),
+        is_final: true,
+        start_time: Some(utterance.start),
+        end_time: Some(utterance.end),
+        speakers,
+        words: utterance.words.clone().unwrap_or_default(),
+        from_finalize: false,
+    }
+}
+
+fn map_paragraph(
+    paragraph: &PrerecordedParagraph,
+    confidence: Option<f64>,
+    words: &[DeepgramWord],
+) -> TranscriptionSegment {
+    let text = paragraph
+        .sentences
+        .iter()
+        .map(|s| s.text.trim())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let speakers = paragraph
+        .speaker
+        .map(|speaker_id| {
+            vec![SpeakerSegment {
+                speaker_id,
+                text: text.clone(),
+                start_time: paragraph.start,
+                end_time: paragraph.end,
+            }]
+        })
+        .unwrap_or_default();
+
+    // Paragraphs carry no words of their own; take the channel words inside the paragraph
+    let paragraph_words = words
+        .iter()
+        .filter(|w| w.start >= paragraph.start && w.end <= paragraph.end)
+        .cloned()
+        .collect();
+
+    TranscriptionSegment {
+        text,
+        confidence: confidence.map(|c| c as f32),
+        is_final: true,
+        start_time: Some(paragraph.start),
+        end_time: Some(paragraph.end),
+        speakers,
+        words: paragraph_words,
+        from_finalize: false,
+    }
+}
+
+// ============================================================================
+// PRERECORDED CLIENT
+// ============================================================================
+
+/// REST client for Deepgram prerecorded transcription
+pub struct DeepgramPrerecordedClient {
+    client: reqwest::Client,
+    api_key: String,
+    base_url: String,
+}
+
+impl DeepgramPrerecordedClient {
+    /// Create a client against the production Deepgram API
+    pub fn new(api_key: String) -> Self {
+        Self::with_base_url(api_key, DEEPGRAM_API_BASE_URL.to_string())
+    }
+
+    /// Create a client against a custom base URL (self-hosted Deepgram or a mock server)
+    pub fn with_base_url(api_key: String, base_url: String) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(PRERECORDED_TIMEOUT)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self {
+            client,
+            api_key,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Upload an audio file from disk
+    pub async fn transcribe_file(
+        &self,
+        path: &Path,
+        options: &PrerecordedOptions,
+    ) -> Result<PrerecordedTranscript, String> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| format!("Failed to read audio file {}: {}", path.display(), e))?;
+
+        info!(
+            "Uploading {} ({:.1} MB) to Deepgram prerecorded API",
+            path.display(),
+            bytes.len() as f64 / (1024.0 * 1024.0)
+        );
+
+        self.transcribe_bytes(bytes, content_type_for_path(path), options)
+            .await
+    }
+
+    /// Upload 16-bit PCM samples wrapped in a WAV container
+    pub async fn transcribe_samples(
+        &self,
+        samples: &[f32],
+        sample_rate: u32,
+        options: &PrerecordedOptions,
+    ) -> Result<PrerecordedTranscript, String> {
+        self.transcribe_bytes(encode_wav_pcm16(samples, sample_rate), "audio/wav", options)
+            .await
+    }
+
+    /// Upload raw audio bytes with the given content type
+    pub async fn transcribe_bytes(
+        &self,
+        bytes: Vec<u8>,
+        content_type: &str,
+        options: &PrerecordedOptions,
+    ) -> Result<PrerecordedTranscript, String> {
+        if self.api_key.is_empty() {
+            return Err("Deepgram API key not configured".to_string());
+        }
+        if bytes.is_empty() {
+            return Err("Audio data is empty".to_string());
+        }
+
+        let url = format!("{}/v1/listen", self.base_url);
+        let response = self
+            .client
+            .post(&url)
+            .query(&options.query_params())
+            .header("Authorization", format!("Token {}", self.api_key))
+            .header("Content-Type", content_type)
+            .body(bytes)
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Deepgram prerecorded request failed: {}", e);
+                format!("Failed to reach Deepgram: {}", e)
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            error!("Deepgram prerecorded API returned {}: {}", status, body);
+            return Err(match status.as_u16() {
+                401 | 403 => "Deepgram rejected the API key. Please check it in Settings > Transcription.".to_string(),
+                _ => format!("Deepgram API error ({}): {}", status, body),
+            });
+        }
+
+        let parsed: PrerecordedResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Deepgram response: {}", e))?;
+
+        let transcript = map_prerecorded_response(&parsed);
+        info!(
+            "Deepgram prerecorded transcription complete: {} segments, {:?}s",
+            transcript.segments.len(),
+            transcript.duration
+        );
+        Ok(transcript)
+    }
+}
+
+// ============================================================================
+// UTILITY FUNCTIONS
+// ============================================================================
+
+/// Convert transcription segments into Obsidian note transcript segments.
+/// Segments with several speakers are split into one entry per speaker turn.
+pub fn to_note_segments(segments: &[TranscriptionSegment]) -> Vec<crate::obsidian::TranscriptSegment> {
+    segments
+        .iter()
+        .flat_map(|segment| {
+            if segment.speakers.is_empty() {
+                vec![crate::obsidian::TranscriptSegment {
+                    timestamp: format_timestamp(segment.start_time.unwrap_or(0.0)),
+                    speaker: None,
+                    text: segment.text.trim().to_string(),
+                    audio_start_time: segment.start_time,
+                }]
+            } else {
+                segment
+                    .speakers
+                    .iter()
+                    .enumerate()
+                    .map(|(turn, s)| crate::obsidian::TranscriptSegment {
+                        timestamp: format_timestamp(s.start_time),
+                        speaker: Some(format!("Speaker {}", s.speaker_id)),
+                        text: s.text.clone(),
+                        audio_start_time: if turn == 0 { segment.start_time } else { None },
+                    })
+                    .collect()
+            }
+        })
+        .filter(|s| !s.text.is_empty())
+        .collect()
+}
+
+/// Convert Deepgram words into word timings for the transcript_words table
+pub fn to_word_timings(words: &[DeepgramWord]) -> Vec<crate::api::WordTiming> {
+    words
+        .iter()
+        .map(|w| crate::api::WordTiming {
+            word: w.word.clone(),
+            punctuated_word: w.punctuated_word.clone(),
+            start: w.start,
+            end: w.end,
+            confidence: Some(w.confidence),
+            speaker: w.speaker.map(i64::from),
+        })
+        .collect()
+}
+
+/// Content type for an audio file, based on its extension
+pub fn content_type_for_path(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .as_deref()
+    {
+        Some("mp4") | Some("m4a") => "audio/mp4",
+        Some("wav") => "audio/wav",
+        Some("mp3") => "audio/mpeg",
+        Some("ogg") | Some("opus") => "audio/ogg",
+        Some("webm") => "audio/webm",
+        Some("flac") => "audio/flac",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Wrap mono f32 samples in a 16-bit PCM WAV container
+pub fn encode_wav_pcm16(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    let pcm = super::websocket::convert_f32_to_linear16(samples);
+    let mut wav = Vec::with_capacity(44 + pcm.len());
+
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + pcm.len() as u32).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(pcm.len() as u32).to_le_bytes());
+    wav.extend_from_slice(&pcm);
+
+    wav
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::spawn_mock_server;
+
+    const UTTERANCE_RESPONSE: &str = r#"{
+        "metadata": {"request_id": "req-1", "duration": 4.2, "channels": 1},
+        "results": {
+            "channels": [{
+                "alternatives": [{
+                    "transcript": "Hello there. Hi Bob.",
+                    "confidence": 0.97,
+                    "words": [
+                        {"word": "hello", "start": 0.1, "end": 0.4, "confidence": 0.99, "speaker": 0, "punctuated_word": "Hello"},
+                        {"word": "there", "start": 0.4, "end": 0.8, "confidence": 0.98, "speaker": 0, "punctuated_word": "there."},
+                        {"word": "hi", "start": 2.0, "end": 2.2, "confidence": 0.95, "speaker": 1, "punctuated_word": "Hi"},
+                        {"word": "bob", "start": 2.2, "end": 2.6, "confidence": 0.9, "speaker": 1, "punctuated_word": "Bob."}
+                    ]
+                }]
+            }],
+            "utterances": [
+                {"start": 0.1, "end": 0.8, "confidence": 0.98, "transcript": "Hello there.", "speaker": 0},
+                {"start": 2.0, "end": 2.6, "confidence": 0.92, "transcript": "Hi Bob.", "speaker": 1}
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_query_params() {
+        let options = PrerecordedOptions {
+            language: Some("nl".to_string()),
+            paragraphs: false,
+            ..Default::default()
+        };
+        let params = options.query_params();
+        assert!(params.contains(&("language", "nl".to_string())));
+        assert!(params.contains(&("diarize", "true".to_string())));
+        assert!(params.contains(&("utterances", "true".to_string())));
+        assert!(!params.iter().any(|(k, _)| *k == "paragraphs"));
+
+        let auto = PrerecordedOptions {
+            language: Some("auto-translate".to_string()),
+            ..Default::default()
+        };
+        let params = auto.query_params();
+        assert!(!params.iter().any(|(k, _)| *k == "language"));
+        assert!(params.contains(&("detect_language", "true".to_string())));
+    }
+
+    #[test]
+    fn test_map_utterances() {
+        let response: PrerecordedResponse = serde_json::from_str(UTTERANCE_RESPONSE).unwrap();
+        let transcript = map_prerecorded_response(&response);
+
+        assert_eq!(transcript.request_id.as_deref(), Some("req-1"));
+        assert_eq!(transcript.words.len(), 4);
+        assert_eq!(transcript.segments.len(), 2);
+        assert_eq!(transcript.segments[1].text, "Hi Bob.");
+        assert_eq!(transcript.segments[1].speakers[0].speaker_id, 1);
+        assert_eq!(transcript.segments[1].start_time, Some(2.0));
+    }
+
+    #[test]
+    fn test_map_paragraphs_without_utterances() {
+        let json = r#"{
+            "results": {
+                "channels": [{
+                    "alternatives": [{
+                        "transcript": "First point. Second point.",
+                        "confidence": 0.9,
+                        "paragraphs": {"paragraphs": [
+                            {"speaker": 2, "start": 0.0, "end": 3.0, "sentences": [
+                                {"text": "First point.", "start": 0.0, "end": 1.0},
+                                {"text": "Second point.", "start": 1.5, "end": 3.0}
+                            ]}
+                        ]}
+                    }]
+                }]
+            }
+        }"#;
+        let response: PrerecordedResponse = serde_json::from_str(json).unwrap();
+        let transcript = map_prerecorded_response(&response);
+
+        assert_eq!(transcript.segments.len(), 1);
+        assert_eq!(transcript.segments[0].text, "First point. Second point.");
+        assert_eq!(transcript.segments[0].speakers[0].speaker_id, 2);
+    }
+
+    #[test]
+    fn test_encode_wav_header() {
+        let wav = encode_wav_pcm16(&[0.0, 0.5, -0.5], 16000);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..12], b"WAVE");
+        assert_eq!(wav.len(), 44 + 6);
+        assert_eq!(u32::from_le_bytes([wav[24], wav[25], wav[26], wav[27]]), 16000);
+    }
+
+    #[test]
+    fn test_to_note_segments_splits_speakers() {
+        let response: PrerecordedResponse = serde_json::from_str(UTTERANCE_RESPONSE).unwrap();
+        let mut transcript = map_prerecorded_response(&response);
+        transcript.segments[0].speakers.clear();
+
+        let note_segments = to_note_segments(&transcript.segments);
+        assert_eq!(note_segments.len(), 2);
+        assert_eq!(note_segments[0].speaker, None);
+        assert_eq!(note_segments[1].timestamp, "00:02");
+        assert_eq!(note_segments[1].speaker.as_deref(), Some("Speaker 1"));
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_bytes_against_mock_server() {
+        let (base_url, handle) = spawn_mock_server("200 OK", UTTERANCE_RESPONSE).await;
+        let client = DeepgramPrerecordedClient::with_base_url("test_key".to_string(), base_url);
+
+        let transcript = client
+            .transcribe_bytes(vec![1, 2, 3, 4], "audio/wav", &PrerecordedOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(transcript.segments.len(), 2);
+
+        let request = handle.await.unwrap();
+        assert!(request.starts_with("POST /v1/listen?"));
+        assert!(request.contains("diarize=true"));
+        assert!(request.to_lowercase().contains("authorization: token test_key"));
+        assert!(request.to_lowercase().contains("content-type: audio/wav"));
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_bytes_maps_auth_error() {
+        let (base_url, _handle) = spawn_mock_server("401 Unauthorized", r#"{"err_code":"INVALID_AUTH"}"#).await;
+        let client = DeepgramPrerecordedClient::with_base_url("bad".to_string(), base_url);
+
+        let err = client
+            .transcribe_bytes(vec![1, 2, 3, 4], "audio/wav", &PrerecordedOptions::default())
+            .await
+            .unwrap_err();
+        assert!(err.contains("API key"));
+    }
+}
@@ -4,18 +4,93 @@
 // Implements the TranscriptionProvider trait for streaming cloud-based transcription
 // with speaker diarization support.
 
-use super::websocket::{DeepgramConfig, DeepgramWebSocket, TranscriptionSegment};
+use super::websocket::{
+    ConnectionState, ConnectionStateEvent, DeepgramConfig, DeepgramWebSocket, TranscriptionSegment,
+};
 use crate::audio::transcription::provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
 use async_trait::async_trait;
 use log::{debug, error, info, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Runtime};
 use tokio::sync::{Mutex, RwLock};
-use tokio::time::{timeout, Duration};
+use tokio::time::{timeout_at, Duration, Instant};
+
+/// How long a transcription waits for the next result before giving up
+const RESULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Upper bound on the wait for one transcription's results, reconnects included
+const MAX_RESULT_WAIT: Duration = Duration::from_secs(30);
+
+/// Results ending this close to a segment's start belong to an earlier segment
+const STALE_RESULT_TOLERANCE_SECS: f64 = 0.05;
+
+/// Event carrying Deepgram connection state changes to the frontend
+pub const CONNECTION_STATE_EVENT: &str = "deepgram-connection-state";
+
+/// Callback invoked on every WebSocket connection state change
+pub type ConnectionStateListener = Arc<dyn Fn(&ConnectionState) + Send + Sync>;
 
 // ============================================================================
 // DEEPGRAM PROVIDER
 // ============================================================================
 
+/// Streaming session shared by the segments of a live recording
+struct LiveSession {
+    receiver: tokio::sync::mpsc::Receiver<TranscriptionSegment>,
+    language: Option<String>,
+}
+
+/// Final results of one transcription, in arrival order
+#[derive(Default)]
+struct FinalResults {
+    text: String,
+    confidence: Option<f32>,
+    words: Vec<crate::api::WordTiming>,
+}
+
+impl FinalResults {
+    /// Append a final segment; word times are made relative to `offset`
+    fn add(&mut self, segment: &TranscriptionSegment, offset: f64) {
+        // Build the final text with speaker labels if available
+        if !segment.speakers.is_empty() {
+            for speaker_seg in &segment.speakers {
+                if !self.text.is_empty() {
+                    self.text.push('\n');
+                }
+                self.text.push_str(&format!(
+                    "[Speaker {}]: {}",
+                    speaker_seg.speaker_id, speaker_seg.text
+                ));
+            }
+        } else if !segment.text.trim().is_empty() {
+            if !self.text.is_empty() {
+                self.text.push(' ');
+            }
+            self.text.push_str(&segment.text);
+        }
+
+        if segment.confidence.is_some() {
+            self.confidence = segment.confidence;
+        }
+        self.words
+            .extend(super::prerecorded::to_word_timings(&segment.words).into_iter().map(|mut word| {
+                word.start -= offset;
+                word.end -= offset;
+                word
+            }));
+    }
+
+    fn into_result(self, is_partial: bool) -> TranscriptResult {
+        TranscriptResult {
+            text: self.text.trim().to_string(),
+            confidence: self.confidence,
+            is_partial,
+            words: self.words,
+        }
+    }
+}
+
 /// Deepgram cloud transcription provider
 /// Provides real-time streaming transcription with speaker diarization
 pub struct DeepgramProvider {
@@ -29,6 +104,10 @@ pub struct DeepgramProvider {
     result_buffer: Arc<Mutex<Vec<TranscriptionSegment>>>,
     /// Whether a streaming session is active
     is_streaming: Arc<RwLock<bool>>,
+    /// Notified of connection state changes (reconnects) of every connection
+    state_listener: Option<ConnectionStateListener>,
+    /// Session of the live recording, opened by its first segment
+    live: Mutex<Option<LiveSession>>,
 }
 
 impl DeepgramProvider {
@@ -41,6 +120,8 @@ impl DeepgramProvider {
             websocket: Arc::new(RwLock::new(None)),
             result_buffer: Arc::new(Mutex::new(Vec::new())),
             is_streaming: Arc::new(RwLock::new(false)),
+            state_listener: None,
+            live: Mutex::new(None),
         }
     }
 
@@ -52,9 +133,32 @@ impl DeepgramProvider {
             websocket: Arc::new(RwLock::new(None)),
             result_buffer: Arc::new(Mutex::new(Vec::new())),
             is_streaming: Arc::new(RwLock::new(false)),
+            state_listener: None,
+            live: Mutex::new(None),
         }
     }
 
+    /// Report connection state changes (dropped connections, reconnects) to `listener`
+    pub fn with_state_listener(mut self, listener: ConnectionStateListener) -> Self {
+        self.state_listener = Some(listener);
+        self
+    }
+
+    /// Forward the state changes of a connection to the state listener
+    fn forward_state_changes(&self, websocket: &DeepgramWebSocket) {
+        let Some(listener) = self.state_listener.clone() else {
+            return;
+        };
+        let mut states = websocket.subscribe_state();
+        // Ends when the connection and its session task are gone
+        tokio::spawn(async move {
+            while states.changed().await.is_ok() {
+                let state = states.borrow_and_update().clone();
+                listener(&state);
+            }
+        });
+    }
+
     /// Start a streaming transcription session
     /// Returns a receiver for transcription segments
     pub async fn start_streaming(
@@ -71,18 +175,19 @@ impl DeepgramProvider {
 
         let config = DeepgramConfig {
             api_key: self.api_key.clone(),
-            model: self.model.clone(),
+            model: self.get_model_for_language(language.as_deref()),
             language,
             sample_rate: 16000,
             channels: 1,
             encoding: "linear16".to_string(),
             diarize: true,
             punctuate: true,
-            interim_results: true,
+            interim_results: false, // Audio is finalized segment by segment
             smart_format: true,
         };
 
         let websocket = DeepgramWebSocket::new(config);
+        self.forward_state_changes(&websocket);
         let receiver = websocket.connect().await?;
 
         // Store the websocket connection
@@ -112,6 +217,38 @@ impl DeepgramProvider {
         }
     }
 
+    /// Send audio that starts at `recording_time` seconds into the recording,
+    /// so segment times stay recording-relative across pauses and reconnects
+    pub async fn send_audio_stream_at(&self, audio: &[f32], recording_time: f64) -> Result<(), String> {
+        let ws = self.websocket.read().await;
+
+        if let Some(ref websocket) = *ws {
+            websocket.send_audio_at(audio, Some(recording_time)).await
+        } else {
+            Err("Not connected - call start_streaming first".to_string())
+        }
+    }
+
+    /// Ask for the final results of all audio sent so far, keeping the session open
+    pub async fn finalize_stream(&self) -> Result<(), String> {
+        let ws = self.websocket.read().await;
+
+        if let Some(ref websocket) = *ws {
+            websocket.finalize().await
+        } else {
+            Err("Not connected - call start_streaming first".to_string())
+        }
+    }
+
+    /// Connection state of the streaming session, if one is open
+    async fn stream_state(&self) -> Option<ConnectionState> {
+        let ws = self.websocket.read().await;
+        match *ws {
+            Some(ref websocket) => Some(websocket.get_state().await),
+            None => None,
+        }
+    }
+
     /// Stop the streaming session
     pub async fn stop_streaming(&self) {
         // Disconnect websocket
@@ -228,6 +365,7 @@ impl DeepgramProvider {
         };
 
         let websocket = DeepgramWebSocket::new(config);
+        self.forward_state_changes(&websocket);
 
         // Connect and get receiver
         let mut receiver = websocket.connect().await.map_err(|e| {
@@ -248,75 +386,182 @@ impl DeepgramProvider {
 
         // Wait for results with timeout
         // The receiver will close when Deepgram sends its Close frame back
-        let mut final_text = String::new();
-        let mut confidence: Option<f32> = None;
-        let mut is_partial = true;
+        let mut results = FinalResults::default();
         let mut segment_count = 0;
 
-        // Collect results with a timeout (10 seconds should be plenty for Deepgram)
+        // Collect results with a timeout (10 seconds should be plenty for Deepgram).
+        // The deadline restarts while a dropped connection is being re-established,
+        // since the audio is replayed once it's back, but never beyond MAX_RESULT_WAIT.
         debug!("Waiting for transcription results from Deepgram...");
-        let result = timeout(Duration::from_secs(10), async {
-            while let Some(segment) = receiver.recv().await {
-                segment_count += 1;
-                debug!(
-                    "Received segment {}: is_final={}, text='{}', confidence={:?}",
-                    segment_count, segment.is_final, segment.text, segment.confidence
-                );
-
-                if segment.is_final {
-                    // Build the final text with speaker labels if available
-                    if !segment.speakers.is_empty() {
-                        for speaker_seg in &segment.speakers {
-                            if !final_text.is_empty() {
-                                final_text.push('\n');
-                            }
-                            final_text.push_str(&format!(
-                                "[Speaker {}]: {}",
-                                speaker_seg.speaker_id, speaker_seg.text
-                            ));
-                        }
-                    } else {
-                        if !final_text.is_empty() {
-                            final_text.push(' ');
-                        }
-                        final_text.push_str(&segment.text);
-                    }
-
-                    confidence = segment.confidence;
-                    is_partial = false;
+        let wait_limit = Instant::now() + MAX_RESULT_WAIT;
+        let mut deadline = Instant::now() + RESULT_TIMEOUT;
+        let mut timed_out = false;
+        loop {
+            let segment = match timeout_at(deadline, receiver.recv()).await {
+                Ok(Some(segment)) => segment,
+                Ok(None) => break,
+                Err(_)
+                    if Instant::now() < wait_limit
+                        && matches!(websocket.get_state().await, ConnectionState::Reconnecting { .. }) =>
+                {
+                    deadline = (Instant::now() + RESULT_TIMEOUT).min(wait_limit);
+                    continue;
                 }
+                Err(_) => {
+                    timed_out = true;
+                    break;
+                }
+            };
+            segment_count += 1;
+            debug!(
+                "Received segment {}: is_final={}, text='{}', confidence={:?}",
+                segment_count, segment.is_final, segment.text, segment.confidence
+            );
+
+            if segment.is_final {
+                results.add(&segment, 0.0);
             }
-            debug!("Receiver channel closed after {} segments", segment_count);
-        })
-        .await;
+        }
+        debug!("Received {} segments", segment_count);
+
+        // A connection that could not be re-established ends the session
+        let final_state = websocket.get_state().await;
 
         // Now disconnect to clean up
         websocket.disconnect().await;
 
-        if result.is_err() {
-            warn!("Transcription timed out after 10 seconds");
-            // If we got some results before timeout, use them
-            if !final_text.is_empty() {
-                return Ok(TranscriptResult {
-                    text: final_text.trim().to_string(),
-                    confidence,
-                    is_partial: true, // Mark as partial since we timed out
-                });
-            }
+        if timed_out {
+            warn!("Transcription timed out after {:?}", RESULT_TIMEOUT);
         }
 
         // If we didn't get any final results, return what we have
-        if final_text.is_empty() {
+        if results.text.is_empty() {
+            if let ConnectionState::Error(message) = final_state {
+                return Err(TranscriptionError::EngineFailed(message));
+            }
             return Err(TranscriptionError::EngineFailed(
                 "No transcription results received".to_string(),
             ));
         }
 
-        Ok(TranscriptResult {
-            text: final_text.trim().to_string(),
-            confidence,
-            is_partial,
-        })
+        // Mark as partial if we timed out
+        Ok(results.into_result(timed_out))
+    }
+
+    /// Transcribe a segment of the live recording over the recording's streaming session.
+    ///
+    /// The session is opened by the first segment and kept until the recording
+    /// ends, so KeepAlives bridge the pauses between segments and a dropped
+    /// connection is re-established with the unconfirmed audio replayed. Each
+    /// segment is sent at its recording time and finalized; its final results
+    /// are collected with word times relative to the segment.
+    async fn transcribe_live(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+        recording_time: f64,
+    ) -> Result<TranscriptResult, TranscriptionError> {
+        if audio.len() < 1600 {
+            return Err(TranscriptionError::AudioTooShort {
+                samples: audio.len(),
+                minimum: 1600,
+            });
+        }
+
+        // Held for the whole segment: segments go over the session one at a time
+        let mut live = self.live.lock().await;
+        let session = match live.take() {
+            Some(session) if session.language == language => session,
+            previous => {
+                if previous.is_some() {
+                    // Language changed mid-recording
+                    self.stop_streaming().await;
+                }
+                let receiver = self.start_streaming(language.clone()).await.map_err(|e| {
+                    error!("Failed to connect to Deepgram: {}", e);
+                    TranscriptionError::EngineFailed(e)
+                })?;
+                LiveSession { receiver, language }
+            }
+        };
+        let session = live.insert(session);
+
+        let sent = match self.send_audio_stream_at(&audio, recording_time).await {
+            Ok(()) => self.finalize_stream().await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = sent {
+            error!("Failed to send audio to Deepgram: {}", e);
+            *live = None;
+            self.stop_streaming().await;
+            return Err(TranscriptionError::EngineFailed(e));
+        }
+
+        let mut results = FinalResults::default();
+        let wait_limit = Instant::now() + MAX_RESULT_WAIT;
+        let mut deadline = Instant::now() + RESULT_TIMEOUT;
+        let mut finalized = false;
+        let mut session_ended = false;
+        loop {
+            let segment = match timeout_at(deadline, session.receiver.recv()).await {
+                Ok(Some(segment)) => segment,
+                Ok(None) => {
+                    session_ended = true;
+                    break;
+                }
+                Err(_)
+                    if Instant::now() < wait_limit
+                        && matches!(self.stream_state().await, Some(ConnectionState::Reconnecting { .. })) =>
+                {
+                    deadline = (Instant::now() + RESULT_TIMEOUT).min(wait_limit);
+                    continue;
+                }
+                Err(_) => break,
+            };
+
+            // Late results of an earlier segment that timed out
+            if segment
+                .end_time
+                .map_or(false, |end| end <= recording_time + STALE_RESULT_TOLERANCE_SECS)
+            {
+                debug!("Dropping late Deepgram result ending at {:?}", segment.end_time);
+                continue;
+            }
+
+            if segment.is_final {
+                results.add(&segment, recording_time);
+            }
+            if segment.from_finalize {
+                finalized = true;
+                break;
+            }
+        }
+
+        if session_ended {
+            // Rejected or out of reconnect attempts; the next segment starts a new session
+            let message = match self.stream_state().await {
+                Some(ConnectionState::Error(message)) => message,
+                _ => "Deepgram session ended".to_string(),
+            };
+            *live = None;
+            self.stop_streaming().await;
+            if results.text.is_empty() {
+                return Err(TranscriptionError::EngineFailed(message));
+            }
+        } else if !finalized {
+            warn!(
+                "Live transcription of the segment at {} timed out",
+                format_timestamp(recording_time)
+            );
+            if results.text.is_empty() {
+                return Err(TranscriptionError::EngineFailed(
+                    "No transcription results received".to_string(),
+                ));
+            }
+        }
+
+        // A finalized segment may be silent; an empty result is not an error then
+        Ok(results.into_result(!finalized))
     }
 }
 
@@ -351,6 +596,30 @@ impl TranscriptionProvider for DeepgramProvider {
         self.transcribe_single_shot(audio, language).await
     }
 
+    /// Segments of a live recording share one streaming session
+    async fn transcribe_at(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+        recording_time: f64,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        if self.api_key.is_empty() {
+            return Err(TranscriptionError::EngineFailed(
+                "Deepgram API key not configured".to_string(),
+            ));
+        }
+
+        self.transcribe_live(audio, language, recording_time).await
+    }
+
+    /// Close the live recording's streaming session
+    async fn finish_recording(&self) {
+        let mut live = self.live.lock().await;
+        if live.take().is_some() {
+            self.stop_streaming().await;
+        }
+    }
+
     /// Deepgram is a cloud service - always "loaded"
     async fn is_model_loaded(&self) -> bool {
         // Deepgram is cloud-based, so model is always "loaded"
@@ -367,6 +636,11 @@ impl TranscriptionProvider for DeepgramProvider {
     fn provider_name(&self) -> &'static str {
         "Deepgram"
     }
+
+    /// Deepgram diarizes server-side
+    fn labels_speakers(&self) -> bool {
+        true
+    }
 }
 
 // ============================================================================
@@ -387,6 +661,29 @@ pub fn format_segment_with_speakers(segment: &TranscriptionSegment) -> String {
     }
 }
 
+/// State listener that emits `deepgram-connection-state` events to the frontend.
+///
+/// One connection streams the whole recording, so opening and closing it stay
+/// silent: only reconnect attempts and how they ended are reported.
+pub fn connection_state_emitter<R: Runtime>(app: AppHandle<R>) -> ConnectionStateListener {
+    let reconnecting = AtomicBool::new(false);
+    Arc::new(move |state: &ConnectionState| {
+        let notify = match state {
+            ConnectionState::Reconnecting { .. } => {
+                reconnecting.store(true, Ordering::SeqCst);
+                true
+            }
+            ConnectionState::Connected | ConnectionState::Error(_) => reconnecting.swap(false, Ordering::SeqCst),
+            ConnectionState::Disconnected | ConnectionState::Connecting => false,
+        };
+        if notify {
+            if let Err(e) = app.emit(CONNECTION_STATE_EVENT, ConnectionStateEvent::from(state)) {
+                warn!("Failed to emit Deepgram connection state: {}", e);
+            }
+        }
+    })
+}
+
 /// Format timestamp in HH:MM:SS format
 pub fn format_timestamp(seconds: f64) -> String {
     let total_secs = seconds as u64;
@@ -404,6 +701,7 @@ pub fn format_timestamp(seconds: f64) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::deepgram::websocket::{extract_speaker_segments, DeepgramWord};
 
     #[test]
     fn test_format_timestamp() {
@@ -436,4 +734,47 @@ mod tests {
         let provider = DeepgramProvider::with_model("test_key".to_string(), "nova-2-general".to_string());
         assert_eq!(provider.get_current_model().await, Some("nova-2-general".to_string()));
     }
+
+    #[test]
+    fn test_final_results_are_segment_relative() {
+        let word = |word: &str, start: f64, speaker: i32| DeepgramWord {
+            word: word.to_string(),
+            start,
+            end: start + 0.3,
+            confidence: 0.9,
+            speaker: Some(speaker),
+            punctuated_word: None,
+        };
+        let words = vec![word("hi", 12.0, 0), word("hello", 12.5, 1)];
+        let segment = TranscriptionSegment {
+            text: "hi hello".to_string(),
+            confidence: Some(0.9),
+            is_final: true,
+            start_time: Some(12.0),
+            end_time: Some(13.0),
+            speakers: extract_speaker_segments(&words),
+            words,
+            from_finalize: false,
+        };
+        let silence = TranscriptionSegment {
+            text: String::new(),
+            confidence: None,
+            is_final: true,
+            start_time: Some(13.0),
+            end_time: Some(13.5),
+            speakers: Vec::new(),
+            words: Vec::new(),
+            from_finalize: true,
+        };
+
+        let mut results = FinalResults::default();
+        results.add(&segment, 10.0);
+        results.add(&silence, 10.0);
+        let result = results.into_result(false);
+
+        assert_eq!(result.text, "[Speaker 0]: hi\n[Speaker 1]: hello");
+        assert_eq!(result.confidence, Some(0.9));
+        assert!((result.words[1].start - 2.5).abs() < 1e-9);
+        assert!(!result.is_partial);
+    }
 }
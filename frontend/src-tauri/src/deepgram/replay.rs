@@ -0,0 +1,192 @@
+// deepgram/replay.rs
+//
+// Bounded ring buffer of recently streamed audio, replayed to Deepgram after a
+// reconnect, and the mapping from stream time back to recording time.
+//
+// Deepgram timestamps are relative to the start of each WebSocket connection.
+// After a reconnect the new connection starts at the replayed audio, and
+// callers may stream non-contiguous audio (pauses, VAD segments), so result
+// times are mapped: connection time -> stream time -> recording time.
+
+use std::collections::VecDeque;
+
+/// Bytes per linear16 sample
+const BYTES_PER_SAMPLE: usize = 2;
+
+/// Gap between expected and given recording time treated as a discontinuity
+const TIMELINE_TOLERANCE_SECS: f64 = 0.001;
+
+/// Recent linear16 audio and the stream/recording timeline of a streaming session
+#[derive(Debug)]
+pub struct ReplayBuffer {
+    sample_rate: u32,
+    capacity_bytes: usize,
+    /// Linear16 bytes of the most recent audio
+    pcm: VecDeque<u8>,
+    /// Stream sample index of the first buffered sample
+    first_sample: u64,
+    /// Stream samples pushed so far
+    total_samples: u64,
+    /// End of the audio covered by final results, in stream samples
+    confirmed_sample: u64,
+    /// (stream sample, recording seconds) at every discontinuity, ascending
+    timeline: Vec<(u64, f64)>,
+}
+
+impl ReplayBuffer {
+    /// Create a buffer holding up to `capacity_secs` of audio
+    pub fn new(sample_rate: u32, capacity_secs: f64) -> Self {
+        let capacity_samples = (capacity_secs.max(0.0) * sample_rate as f64) as usize;
+        Self {
+            sample_rate,
+            capacity_bytes: capacity_samples * BYTES_PER_SAMPLE,
+            pcm: VecDeque::new(),
+            first_sample: 0,
+            total_samples: 0,
+            confirmed_sample: 0,
+            timeline: Vec::new(),
+        }
+    }
+
+    /// Append streamed linear16 audio.
+    ///
+    /// `recording_time` is where the audio starts in the recording; None
+    /// continues right after the previous audio.
+    pub fn push(&mut self, pcm: &[u8], recording_time: Option<f64>) {
+        if let Some(time) = recording_time {
+            let expected = self.to_recording_time(self.samples_to_secs(self.total_samples));
+            if self.timeline.is_empty() || (time - expected).abs() > TIMELINE_TOLERANCE_SECS {
+                self.timeline.push((self.total_samples, time));
+            }
+        }
+
+        self.total_samples += (pcm.len() / BYTES_PER_SAMPLE) as u64;
+        self.pcm.extend(pcm);
+
+        // Drop the oldest whole samples beyond capacity
+        let overflow = self.pcm.len().saturating_sub(self.capacity_bytes);
+        let overflow = overflow + overflow % BYTES_PER_SAMPLE;
+        if overflow > 0 {
+            self.pcm.drain(..overflow.min(self.pcm.len()));
+            self.first_sample += (overflow / BYTES_PER_SAMPLE) as u64;
+        }
+    }
+
+    /// Mark audio up to `stream_secs` as covered by a final result
+    pub fn confirm_until(&mut self, stream_secs: f64) {
+        let sample = ((stream_secs.max(0.0) * self.sample_rate as f64).round() as u64).min(self.total_samples);
+        self.confirmed_sample = self.confirmed_sample.max(sample);
+
+        // Results never refer to audio before the next replay start;
+        // keep the last timeline entry at or before it
+        let keep_from = self.replay_start();
+        if let Some(last_before) = self.timeline.iter().rposition(|(s, _)| *s <= keep_from) {
+            self.timeline.drain(..last_before);
+        }
+    }
+
+    /// Stream sample a replay starts at: the first unconfirmed sample still buffered
+    pub fn replay_start(&self) -> u64 {
+        self.confirmed_sample.max(self.first_sample)
+    }
+
+    /// Unconfirmed audio to replay after a reconnect, with its stream start sample
+    pub fn unconfirmed(&self) -> (u64, Vec<u8>) {
+        let start = self.replay_start();
+        let skip = ((start - self.first_sample) as usize * BYTES_PER_SAMPLE).min(self.pcm.len());
+        (start, self.pcm.iter().skip(skip).copied().collect())
+    }
+
+    /// Whether any streamed audio is still waiting for a final result
+    pub fn has_unconfirmed(&self) -> bool {
+        self.total_samples > self.replay_start()
+    }
+
+    /// Seconds of unconfirmed audio that fell out of the buffer and can't be replayed
+    pub fn lost_secs(&self) -> f64 {
+        self.samples_to_secs(self.first_sample.saturating_sub(self.confirmed_sample))
+    }
+
+    /// Stream position of a sample in seconds
+    pub fn samples_to_secs(&self, samples: u64) -> f64 {
+        samples as f64 / self.sample_rate as f64
+    }
+
+    /// Map a stream time to recording time using the timeline
+    pub fn to_recording_time(&self, stream_secs: f64) -> f64 {
+        let sample = (stream_secs.max(0.0) * self.sample_rate as f64) as u64;
+        match self.timeline.iter().rev().find(|(s, _)| *s <= sample).or(self.timeline.first()) {
+            Some((anchor_sample, anchor_time)) => {
+                anchor_time + stream_secs - self.samples_to_secs(*anchor_sample)
+            }
+            None => stream_secs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Linear16 silence of the given length
+    fn pcm(samples: usize) -> Vec<u8> {
+        vec![0u8; samples * BYTES_PER_SAMPLE]
+    }
+
+    #[test]
+    fn test_ring_buffer_keeps_recent_audio() {
+        let mut buffer = ReplayBuffer::new(100, 2.0); // 200 samples
+        buffer.push(&pcm(150), None);
+        buffer.push(&pcm(150), None);
+
+        let (start, audio) = buffer.unconfirmed();
+        assert_eq!(start, 100);
+        assert_eq!(audio.len(), 200 * BYTES_PER_SAMPLE);
+        assert!((buffer.lost_secs() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_replay_starts_after_confirmed_audio() {
+        let mut buffer = ReplayBuffer::new(100, 10.0);
+        buffer.push(&pcm(300), None);
+        buffer.confirm_until(1.5);
+
+        let (start, audio) = buffer.unconfirmed();
+        assert_eq!(start, 150);
+        assert_eq!(audio.len(), 150 * BYTES_PER_SAMPLE);
+        assert_eq!(buffer.lost_secs(), 0.0);
+        assert!(buffer.has_unconfirmed());
+
+        // Confirmation never moves backwards
+        buffer.confirm_until(0.5);
+        assert_eq!(buffer.replay_start(), 150);
+
+        buffer.confirm_until(3.0);
+        assert!(!buffer.has_unconfirmed());
+    }
+
+    #[test]
+    fn test_recording_time_mapping() {
+        let mut buffer = ReplayBuffer::new(100, 60.0);
+        // Without anchors stream time is recording time
+        assert_eq!(buffer.to_recording_time(3.0), 3.0);
+
+        // Speech segment at 10s, then one at 25s (gap from VAD or a pause)
+        buffer.push(&pcm(200), Some(10.0));
+        buffer.push(&pcm(100), None);
+        buffer.push(&pcm(200), Some(25.0));
+
+        assert!((buffer.to_recording_time(0.5) - 10.5).abs() < 1e-9);
+        assert!((buffer.to_recording_time(2.5) - 12.5).abs() < 1e-9);
+        assert!((buffer.to_recording_time(3.5) - 25.5).abs() < 1e-9);
+
+        // Continuous audio with matching times adds no anchors
+        buffer.push(&pcm(100), Some(27.0));
+        assert_eq!(buffer.timeline.len(), 2);
+
+        // Pruning keeps the anchor needed for the replay start
+        buffer.confirm_until(4.0);
+        assert_eq!(buffer.timeline.len(), 1);
+        assert!((buffer.to_recording_time(4.5) - 26.5).abs() < 1e-9);
+    }
+}
@@ -2,20 +2,48 @@
 //
 // WebSocket connection management for Deepgram streaming transcription API.
 // Handles connection establishment, audio streaming, and response parsing.
+// A streaming session sends KeepAlives while no audio flows, reconnects with
+// backoff after a dropped connection and replays the audio that has no final
+// result yet (see replay.rs).
 
+use super::replay::ReplayBuffer;
 use futures_util::{SinkExt, StreamExt};
 use log::{debug, error, info, warn};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, watch, Mutex};
+use tokio::time::{Instant, MissedTickBehavior};
 use tokio_tungstenite::{
     connect_async,
     tungstenite::{
+        self,
         http::Request,
         Message,
     },
+    MaybeTlsStream,
+    WebSocketStream,
 };
 
+/// Deepgram streaming API base URL
+pub const DEEPGRAM_WS_BASE_URL: &str = "wss://api.deepgram.com";
+
+/// Deepgram control message that flushes final results and closes the stream
+const CLOSE_STREAM_MESSAGE: &str = r#"{"type": "CloseStream"}"#;
+
+/// Deepgram control message that keeps an idle stream open
+/// (Deepgram closes streams that receive no data for 10 seconds)
+const KEEPALIVE_MESSAGE: &str = r#"{"type": "KeepAlive"}"#;
+
+/// Deepgram control message that flushes final results but keeps the stream open
+const FINALIZE_MESSAGE: &str = r#"{"type": "Finalize"}"#;
+
+/// Replayed audio is sent in 1 second frames (16kHz linear16)
+const REPLAY_CHUNK_BYTES: usize = 32000;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
 // ============================================================================
 // DEEPGRAM API RESPONSE TYPES
 // ============================================================================
@@ -30,6 +58,8 @@ pub struct DeepgramResponse {
     pub start: Option<f64>,
     pub is_final: Option<bool>,
     pub speech_final: Option<bool>,
+    /// Set on the result that answers a Finalize message
+    pub from_finalize: Option<bool>,
     pub channel: Option<DeepgramChannel>,
     pub metadata: Option<DeepgramMetadata>,
 }
@@ -46,7 +76,7 @@ pub struct DeepgramAlternative {
     pub words: Option<Vec<DeepgramWord>>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeepgramWord {
     pub word: String,
     pub start: f64,
@@ -75,7 +105,7 @@ pub struct DeepgramModelInfo {
 // ============================================================================
 
 /// Result from a transcription segment
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TranscriptionSegment {
     pub text: String,
     pub confidence: Option<f32>,
@@ -83,10 +113,15 @@ pub struct TranscriptionSegment {
     pub start_time: Option<f64>,
     pub end_time: Option<f64>,
     pub speakers: Vec<SpeakerSegment>,
+    /// Word-level timings and confidences (empty if the response had none)
+    pub words: Vec<DeepgramWord>,
+    /// Answers a Finalize: all audio sent before it has its final results
+    /// (forwarded even when the transcript is empty)
+    pub from_finalize: bool,
 }
 
 /// Speaker segment within a transcription
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SpeakerSegment {
     pub speaker_id: i32,
     pub text: String,
@@ -104,9 +139,62 @@ pub enum ConnectionState {
     Disconnected,
     Connecting,
     Connected,
+    /// Connection dropped; waiting before reconnect attempt `attempt`
+    Reconnecting { attempt: u32 },
     Error(String),
 }
 
+/// Connection state as emitted to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionStateEvent {
+    /// "disconnected", "connecting", "connected", "reconnecting" or "error"
+    pub state: &'static str,
+    pub attempt: Option<u32>,
+    pub message: Option<String>,
+}
+
+impl From<&ConnectionState> for ConnectionStateEvent {
+    fn from(state: &ConnectionState) -> Self {
+        let (name, attempt, message) = match state {
+            ConnectionState::Disconnected => ("disconnected", None, None),
+            ConnectionState::Connecting => ("connecting", None, None),
+            ConnectionState::Connected => ("connected", None, None),
+            ConnectionState::Reconnecting { attempt } => ("reconnecting", Some(*attempt), None),
+            ConnectionState::Error(message) => ("error", None, Some(message.clone())),
+        };
+        Self {
+            state: name,
+            attempt,
+            message,
+        }
+    }
+}
+
+/// KeepAlive and reconnect behaviour of a streaming session
+#[derive(Debug, Clone)]
+pub struct ResilienceConfig {
+    /// Send a KeepAlive when no audio was sent for this long (paused, muted)
+    pub keepalive_interval: Duration,
+    /// Reconnect attempts after a dropped connection before giving up
+    pub max_reconnect_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Recent audio kept for replay after a reconnect
+    pub replay_buffer_secs: f64,
+}
+
+impl Default for ResilienceConfig {
+    fn default() -> Self {
+        Self {
+            keepalive_interval: Duration::from_secs(4),
+            max_reconnect_attempts: 10,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            replay_buffer_secs: 30.0,
+        }
+    }
+}
+
 /// Configuration for Deepgram connection
 #[derive(Debug, Clone)]
 pub struct DeepgramConfig {
@@ -142,23 +230,24 @@ impl Default for DeepgramConfig {
 impl DeepgramConfig {
     /// Build the WebSocket URL with query parameters
     pub fn build_url(&self) -> String {
+        self.build_url_for(DEEPGRAM_WS_BASE_URL)
+    }
+
+    /// Build the WebSocket URL against a custom base URL (self-hosted Deepgram or a mock server)
+    pub fn build_url_for(&self, base_url: &str) -> String {
         let mut url = format!(
-            "wss://api.deepgram.com/v1/listen?model={}&encoding={}&sample_rate={}&channels={}",
-            self.model, self.encoding, self.sample_rate, self.channels
+            "{}/v1/listen?model={}&encoding={}&sample_rate={}&channels={}",
+            base_url.trim_end_matches('/'),
+            self.model,
+            self.encoding,
+            self.sample_rate,
+            self.channels
         );
 
         // Only add language parameter if it's a valid Deepgram language code
         // Skip "auto-translate" and other special values - let Deepgram auto-detect
         if let Some(ref lang) = self.language {
-            // Valid language codes are typically 2-letter (en, es) or locale (en-US, en-GB)
-            // Skip special values like "auto-translate", "auto", "detect" etc.
-            let is_valid_language_code = lang.len() >= 2
-                && lang.len() <= 10
-                && !lang.contains("auto")
-                && !lang.contains("translate")
-                && !lang.contains("detect");
-
-            if is_valid_language_code {
+            if is_valid_language_code(lang) {
                 url.push_str(&format!("&language={}", lang));
             }
             // If not a valid code, omit language parameter - Deepgram will auto-detect
@@ -184,6 +273,18 @@ impl DeepgramConfig {
     }
 }
 
+/// Check whether a language value can be passed to Deepgram as-is.
+/// Valid language codes are typically 2-letter (en, es) or locale (en-US, en-GB);
+/// special values like "auto-translate", "auto", "detect" are skipped so
+/// Deepgram auto-detects instead.
+pub(crate) fn is_valid_language_code(lang: &str) -> bool {
+    lang.len() >= 2
+        && lang.len() <= 10
+        && !lang.contains("auto")
+        && !lang.contains("translate")
+        && !lang.contains("detect")
+}
+
 // ============================================================================
 // WEBSOCKET CONNECTION MANAGER
 // ============================================================================
@@ -191,8 +292,13 @@ impl DeepgramConfig {
 /// Internal message type for the audio send channel
 #[derive(Debug)]
 enum AudioMessage {
-    /// Audio data to send
-    Audio(Vec<u8>),
+    /// Linear16 audio to send, with its start in the recording if known
+    Audio {
+        pcm: Vec<u8>,
+        recording_time: Option<f64>,
+    },
+    /// Signal to send Finalize message (Deepgram protocol)
+    Finalize,
     /// Signal to send CloseStream message (Deepgram protocol)
     CloseStream,
 }
@@ -200,7 +306,9 @@ enum AudioMessage {
 /// Manages the WebSocket connection to Deepgram
 pub struct DeepgramWebSocket {
     config: DeepgramConfig,
-    state: Arc<RwLock<ConnectionState>>,
+    resilience: ResilienceConfig,
+    base_url: String,
+    state: Arc<watch::Sender<ConnectionState>>,
     sender: Arc<Mutex<Option<mpsc::Sender<AudioMessage>>>>,
     transcript_receiver: Arc<Mutex<Option<mpsc::Receiver<TranscriptionSegment>>>>,
 }
@@ -208,70 +316,78 @@ pub struct DeepgramWebSocket {
 impl DeepgramWebSocket {
     /// Create a new DeepgramWebSocket instance
     pub fn new(config: DeepgramConfig) -> Self {
+        Self::with_resilience(config, ResilienceConfig::default())
+    }
+
+    /// Create a DeepgramWebSocket with custom KeepAlive and reconnect behaviour
+    pub fn with_resilience(config: DeepgramConfig, resilience: ResilienceConfig) -> Self {
+        let (state, _) = watch::channel(ConnectionState::Disconnected);
         Self {
             config,
-            state: Arc::new(RwLock::new(ConnectionState::Disconnected)),
+            resilience,
+            base_url: DEEPGRAM_WS_BASE_URL.to_string(),
+            state: Arc::new(state),
             sender: Arc::new(Mutex::new(None)),
             transcript_receiver: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Connect to a custom base URL (self-hosted Deepgram or a mock server)
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
     /// Get the current connection state
     pub async fn get_state(&self) -> ConnectionState {
-        self.state.read().await.clone()
+        self.state.borrow().clone()
     }
 
     /// Check if connected
     pub async fn is_connected(&self) -> bool {
-        matches!(*self.state.read().await, ConnectionState::Connected)
+        matches!(*self.state.borrow(), ConnectionState::Connected)
+    }
+
+    /// Receiver notified on every connection state change (including reconnects)
+    pub fn subscribe_state(&self) -> watch::Receiver<ConnectionState> {
+        self.state.subscribe()
     }
 
     /// Connect to Deepgram WebSocket API
+    ///
+    /// The initial connection error is returned directly. Once connected, a
+    /// dropped connection is re-established in the background with backoff and
+    /// the unconfirmed audio is replayed, so the returned receiver keeps
+    /// delivering segments with times continuing where they left off.
     pub async fn connect(&self) -> Result<mpsc::Receiver<TranscriptionSegment>, String> {
         // Check if already connected
-        {
-            let state = self.state.read().await;
-            if *state == ConnectionState::Connected {
-                return Err("Already connected".to_string());
-            }
+        if matches!(
+            *self.state.borrow(),
+            ConnectionState::Connected | ConnectionState::Reconnecting { .. }
+        ) {
+            return Err("Already connected".to_string());
         }
 
         // Update state to connecting
-        {
-            let mut state = self.state.write().await;
-            *state = ConnectionState::Connecting;
-        }
+        self.state.send_replace(ConnectionState::Connecting);
 
-        let url = self.config.build_url();
+        let url = self.config.build_url_for(&self.base_url);
         info!("Connecting to Deepgram: {}", url);
 
-        // Build the WebSocket request with authentication
-        let request = Request::builder()
-            .uri(&url)
-            .header("Authorization", format!("Token {}", self.config.api_key))
-            .header("Host", "api.deepgram.com")
-            .header("Upgrade", "websocket")
-            .header("Connection", "Upgrade")
-            .header("Sec-WebSocket-Version", "13")
-            .header("Sec-WebSocket-Key", generate_websocket_key())
-            .body(())
-            .map_err(|e| format!("Failed to build request: {}", e))?;
-
-        // Connect to WebSocket
-        let (ws_stream, _response) = connect_async(request)
-            .await
-            .map_err(|e| {
-                error!("WebSocket connection failed: {}", e);
-                format!("Failed to connect to Deepgram: {}", e)
-            })?;
+        let socket = match open_socket(&url, &self.config.api_key).await {
+            Ok(socket) => socket,
+            Err(e) => {
+                let message = e.message().to_string();
+                error!("WebSocket connection failed: {}", message);
+                self.state.send_replace(ConnectionState::Error(message.clone()));
+                return Err(message);
+            }
+        };
 
         info!("Connected to Deepgram WebSocket");
 
-        // Split the WebSocket stream
-        let (mut ws_sender, mut ws_receiver) = ws_stream.split();
-
         // Create channels for communication
-        let (audio_tx, mut audio_rx) = mpsc::channel::<AudioMessage>(100);
+        let (audio_tx, audio_rx) = mpsc::channel::<AudioMessage>(100);
         let (transcript_tx, transcript_rx) = mpsc::channel::<TranscriptionSegment>(100);
 
         // Store the audio sender
@@ -281,111 +397,22 @@ impl DeepgramWebSocket {
         }
 
         // Update state to connected
-        {
-            let mut state = self.state.write().await;
-            *state = ConnectionState::Connected;
-        }
-
-        let state_clone = self.state.clone();
-        let sender_clone = self.sender.clone();
-
-        // Spawn task to send audio data
-        tokio::spawn(async move {
-            while let Some(msg) = audio_rx.recv().await {
-                match msg {
-                    AudioMessage::Audio(audio_data) => {
-                        if let Err(e) = ws_sender.send(Message::Binary(audio_data)).await {
-                            error!("Failed to send audio data: {}", e);
-                            break;
-                        }
-                    }
-                    AudioMessage::CloseStream => {
-                        // Send Deepgram CloseStream message to signal end of audio
-                        // This tells Deepgram to finish processing and send final results
-                        let close_stream_msg = r#"{"type": "CloseStream"}"#;
-                        debug!("Sending CloseStream message to Deepgram");
-                        if let Err(e) = ws_sender.send(Message::Text(close_stream_msg.to_string())).await {
-                            error!("Failed to send CloseStream message: {}", e);
-                        }
-                        // Don't break - wait for channel to close
-                    }
-                }
-            }
-
-            // Only send WebSocket close frame after channel is fully closed
-            debug!("Audio channel closed, sending WebSocket close frame");
-            let _ = ws_sender.send(Message::Close(None)).await;
-            debug!("Audio sender task completed");
-        });
-
-        // Spawn task to receive transcription responses
-        tokio::spawn(async move {
-            while let Some(msg) = ws_receiver.next().await {
-                match msg {
-                    Ok(Message::Text(text)) => {
-                        match serde_json::from_str::<DeepgramResponse>(&text) {
-                            Ok(response) => {
-                                // Log response type for debugging
-                                if let Some(ref response_type) = response.response_type {
-                                    debug!("Deepgram response type: {}", response_type);
-                                }
-
-                                if let Some(segment) = parse_deepgram_response(&response) {
-                                    debug!(
-                                        "Parsed segment: is_final={}, text='{}', speakers={}",
-                                        segment.is_final,
-                                        &segment.text[..segment.text.len().min(50)],
-                                        segment.speakers.len()
-                                    );
-                                    if let Err(e) = transcript_tx.send(segment).await {
-                                        warn!("Failed to send transcript segment: {}", e);
-                                        break;
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                debug!("Failed to parse Deepgram response: {} - Raw: {}", e, text);
-                            }
-                        }
-                    }
-                    Ok(Message::Close(_)) => {
-                        info!("Deepgram connection closed");
-                        break;
-                    }
-                    Ok(Message::Ping(data)) => {
-                        debug!("Received ping from Deepgram");
-                        // Pong is handled automatically by tungstenite
-                    }
-                    Ok(Message::Pong(_)) => {
-                        debug!("Received pong from Deepgram");
-                    }
-                    Ok(Message::Binary(_)) => {
-                        debug!("Received unexpected binary message from Deepgram");
-                    }
-                    Ok(Message::Frame(_)) => {
-                        // Internal frame, can be ignored
-                    }
-                    Err(e) => {
-                        error!("WebSocket error: {}", e);
-                        break;
-                    }
-                }
-            }
-
-            // Update state to disconnected
-            {
-                let mut state = state_clone.write().await;
-                *state = ConnectionState::Disconnected;
-            }
-
-            // Clear the sender
-            {
-                let mut sender = sender_clone.lock().await;
-                *sender = None;
-            }
-
-            debug!("Transcript receiver task completed");
-        });
+        self.state.send_replace(ConnectionState::Connected);
+
+        let session = StreamSession {
+            url,
+            api_key: self.config.api_key.clone(),
+            buffer: ReplayBuffer::new(self.config.sample_rate, self.resilience.replay_buffer_secs),
+            resilience: self.resilience.clone(),
+            state: self.state.clone(),
+            sender: self.sender.clone(),
+            audio_rx,
+            transcript_tx,
+            connection_start: 0.0,
+            closing: false,
+            finalizing: false,
+        };
+        tokio::spawn(session.run(socket));
 
         Ok(transcript_rx)
     }
@@ -393,13 +420,22 @@ impl DeepgramWebSocket {
     /// Send audio data to Deepgram
     /// Audio should be f32 samples at 16kHz mono - will be converted to linear16
     pub async fn send_audio(&self, audio: &[f32]) -> Result<(), String> {
+        self.send_audio_at(audio, None).await
+    }
+
+    /// Send audio that starts at `recording_time` seconds into the recording.
+    ///
+    /// Segment times are reported relative to the recording even when the
+    /// streamed audio has gaps (pauses, skipped silence) or was replayed after
+    /// a reconnect. Audio sent with `send_audio` continues the previous timeline.
+    pub async fn send_audio_at(&self, audio: &[f32], recording_time: Option<f64>) -> Result<(), String> {
         let sender = self.sender.lock().await;
 
         if let Some(ref tx) = *sender {
             // Convert f32 audio to linear16 (i16)
-            let linear16_data = convert_f32_to_linear16(audio);
+            let pcm = convert_f32_to_linear16(audio);
 
-            tx.send(AudioMessage::Audio(linear16_data))
+            tx.send(AudioMessage::Audio { pcm, recording_time })
                 .await
                 .map_err(|e| format!("Failed to queue audio data: {}", e))
         } else {
@@ -424,21 +460,383 @@ impl DeepgramWebSocket {
         // The sender will be cleared in disconnect()
     }
 
+    /// Ask Deepgram for the final results of all audio sent so far while
+    /// keeping the stream open; the last of them has `from_finalize` set
+    pub async fn finalize(&self) -> Result<(), String> {
+        let sender = self.sender.lock().await;
+        match *sender {
+            Some(ref tx) => tx
+                .send(AudioMessage::Finalize)
+                .await
+                .map_err(|e| format!("Failed to queue Finalize message: {}", e)),
+            None => Err("Not connected".to_string()),
+        }
+    }
+
     /// Disconnect from Deepgram
     pub async fn disconnect(&self) {
-        // Clear the sender to signal the send task to close
+        // Clear the sender to signal the session task to close
         {
             let mut sender = self.sender.lock().await;
             *sender = None;
         }
 
         // Update state
+        self.state.send_replace(ConnectionState::Disconnected);
+
+        info!("Disconnected from Deepgram");
+    }
+}
+
+impl Drop for DeepgramWebSocket {
+    /// The session task shares the sender; clear it so the session ends with its owner
+    fn drop(&mut self) {
+        if let Ok(mut sender) = self.sender.try_lock() {
+            *sender = None;
+        }
+    }
+}
+
+// ============================================================================
+// STREAMING SESSION
+// ============================================================================
+
+/// Why a connection could not be opened
+#[derive(Debug)]
+enum OpenError {
+    /// Rejected by Deepgram (bad API key, invalid parameters) - retrying won't help
+    Fatal(String),
+    /// Network or server problem - worth retrying
+    Retryable(String),
+}
+
+impl OpenError {
+    fn message(&self) -> &str {
+        match self {
+            OpenError::Fatal(message) | OpenError::Retryable(message) => message,
+        }
+    }
+}
+
+/// How a connection ended
+enum SessionEnd {
+    /// Closed on purpose (disconnect, CloseStream answered, consumer gone)
+    Finished,
+    /// Lost unexpectedly; reconnect and replay
+    Dropped(String),
+}
+
+/// Background task owning the socket of a streaming session across reconnects
+struct StreamSession {
+    url: String,
+    api_key: String,
+    resilience: ResilienceConfig,
+    buffer: ReplayBuffer,
+    state: Arc<watch::Sender<ConnectionState>>,
+    sender: Arc<Mutex<Option<mpsc::Sender<AudioMessage>>>>,
+    audio_rx: mpsc::Receiver<AudioMessage>,
+    transcript_tx: mpsc::Sender<TranscriptionSegment>,
+    /// Stream time (seconds) at which the current connection's audio starts
+    connection_start: f64,
+    /// CloseStream was requested; the server closing the socket is expected
+    closing: bool,
+    /// A Finalize was sent and its result has not arrived yet
+    finalizing: bool,
+}
+
+impl StreamSession {
+    async fn run(mut self, socket: Socket) {
+        let mut socket = Some(socket);
+        while let Some(current) = socket.take() {
+            match self.stream(current).await {
+                SessionEnd::Finished => {}
+                SessionEnd::Dropped(reason) => {
+                    warn!("Deepgram connection dropped: {}", reason);
+                    socket = self.reconnect().await;
+                }
+            }
+        }
+
+        // Clear the sender
         {
-            let mut state = self.state.write().await;
-            *state = ConnectionState::Disconnected;
+            let mut sender = self.sender.lock().await;
+            *sender = None;
         }
 
-        info!("Disconnected from Deepgram");
+        // Update state to disconnected unless the session failed
+        let failed = matches!(*self.state.borrow(), ConnectionState::Error(_));
+        if !failed {
+            self.state.send_replace(ConnectionState::Disconnected);
+        }
+
+        debug!("Deepgram session task completed");
+    }
+
+    /// Pump audio, results and KeepAlives over one connection until it ends
+    async fn stream(&mut self, socket: Socket) -> SessionEnd {
+        let (mut ws_sender, mut ws_receiver) = socket.split();
+        let interval = self.resilience.keepalive_interval;
+        let mut keepalive = tokio::time::interval_at(Instant::now() + interval, interval);
+        keepalive.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut last_sent = Instant::now();
+
+        loop {
+            tokio::select! {
+                message = self.audio_rx.recv() => match message {
+                    Some(AudioMessage::Audio { pcm, recording_time }) => {
+                        self.buffer.push(&pcm, recording_time);
+                        if let Err(e) = ws_sender.send(Message::Binary(pcm)).await {
+                            return SessionEnd::Dropped(format!("failed to send audio: {}", e));
+                        }
+                        last_sent = Instant::now();
+                    }
+                    Some(AudioMessage::Finalize) => {
+                        self.finalizing = true;
+                        debug!("Sending Finalize message to Deepgram");
+                        if let Err(e) = ws_sender.send(Message::Text(FINALIZE_MESSAGE.to_string())).await {
+                            return SessionEnd::Dropped(format!("failed to send Finalize: {}", e));
+                        }
+                    }
+                    Some(AudioMessage::CloseStream) => {
+                        // Tells Deepgram to finish processing and send final results
+                        self.closing = true;
+                        debug!("Sending CloseStream message to Deepgram");
+                        if let Err(e) = ws_sender.send(Message::Text(CLOSE_STREAM_MESSAGE.to_string())).await {
+                            return SessionEnd::Dropped(format!("failed to send CloseStream: {}", e));
+                        }
+                    }
+                    None => {
+                        debug!("Audio channel closed, sending WebSocket close frame");
+                        let _ = ws_sender.send(Message::Close(None)).await;
+                        return SessionEnd::Finished;
+                    }
+                },
+                message = ws_receiver.next() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        if !self.handle_response(&text).await {
+                            return SessionEnd::Finished;
+                        }
+                    }
+                    Some(Ok(Message::Close(frame))) => {
+                        if self.closing {
+                            info!("Deepgram connection closed");
+                            return SessionEnd::Finished;
+                        }
+                        return SessionEnd::Dropped(format!("closed by server: {:?}", frame));
+                    }
+                    Some(Ok(_)) => {
+                        // Ping/pong are handled by tungstenite; binary frames are unexpected
+                    }
+                    Some(Err(e)) => return self.lost(format!("WebSocket error: {}", e)),
+                    None => return self.lost("connection closed".to_string()),
+                },
+                _ = keepalive.tick() => {
+                    if !self.closing && last_sent.elapsed() >= interval {
+                        debug!("Sending KeepAlive to Deepgram");
+                        if let Err(e) = ws_sender.send(Message::Text(KEEPALIVE_MESSAGE.to_string())).await {
+                            return SessionEnd::Dropped(format!("failed to send KeepAlive: {}", e));
+                        }
+                        last_sent = Instant::now();
+                    }
+                }
+            }
+        }
+    }
+
+    /// A connection lost without a close frame; after CloseStream only worth
+    /// reconnecting when audio is still waiting for final results
+    fn lost(&self, reason: String) -> SessionEnd {
+        if self.closing && !self.buffer.has_unconfirmed() {
+            SessionEnd::Finished
+        } else {
+            SessionEnd::Dropped(reason)
+        }
+    }
+
+    /// Forward a Deepgram message as a recording-relative segment.
+    /// Returns false once the consumer dropped the receiver.
+    async fn handle_response(&mut self, text: &str) -> bool {
+        let response = match serde_json::from_str::<DeepgramResponse>(text) {
+            Ok(response) => response,
+            Err(e) => {
+                debug!("Failed to parse Deepgram response: {} - Raw: {}", e, text);
+                return true;
+            }
+        };
+
+        // Log response type for debugging
+        if let Some(ref response_type) = response.response_type {
+            debug!("Deepgram response type: {}", response_type);
+        }
+
+        let segment = parse_deepgram_response(&response).map(|mut segment| {
+            let connection_start = self.connection_start;
+            let buffer = &self.buffer;
+            shift_segment_times(&mut segment, |t| buffer.to_recording_time(connection_start + t));
+            segment
+        });
+
+        if response.from_finalize == Some(true) {
+            self.finalizing = false;
+        }
+
+        // Final results cover their audio; it no longer needs replaying
+        if response.is_final == Some(true) {
+            if let (Some(start), Some(duration)) = (response.start, response.duration) {
+                self.buffer.confirm_until(self.connection_start + start + duration);
+            }
+        }
+
+        let Some(segment) = segment else {
+            return true;
+        };
+        debug!(
+            "Parsed segment: is_final={}, text='{}', speakers={}",
+            segment.is_final,
+            segment.text.chars().take(50).collect::<String>(),
+            segment.speakers.len()
+        );
+        if let Err(e) = self.transcript_tx.send(segment).await {
+            warn!("Failed to send transcript segment: {}", e);
+            return false;
+        }
+        true
+    }
+
+    /// Re-open the connection with backoff and replay unconfirmed audio.
+    /// Returns None when giving up or when the session was closed meanwhile.
+    async fn reconnect(&mut self) -> Option<Socket> {
+        let max_attempts = self.resilience.max_reconnect_attempts;
+        for attempt in 1..=max_attempts {
+            self.state.send_replace(ConnectionState::Reconnecting { attempt });
+            let delay = backoff_delay(attempt, self.resilience.initial_backoff, self.resilience.max_backoff);
+            info!(
+                "Reconnecting to Deepgram in {:?} (attempt {}/{})",
+                delay, attempt, max_attempts
+            );
+            if !self.buffer_audio_for(delay).await {
+                debug!("Audio channel closed while reconnecting");
+                return None;
+            }
+
+            match open_socket(&self.url, &self.api_key).await {
+                Ok(socket) => match self.replay(socket).await {
+                    Ok(socket) => {
+                        self.state.send_replace(ConnectionState::Connected);
+                        return Some(socket);
+                    }
+                    Err(e) => warn!("Failed to replay audio after reconnect: {}", e),
+                },
+                Err(OpenError::Fatal(message)) => {
+                    error!("Deepgram rejected the reconnect: {}", message);
+                    self.state.send_replace(ConnectionState::Error(message));
+                    return None;
+                }
+                Err(OpenError::Retryable(message)) => warn!("Reconnect attempt {} failed: {}", attempt, message),
+            }
+        }
+
+        error!("Giving up on Deepgram after {} reconnect attempts", max_attempts);
+        self.state.send_replace(ConnectionState::Error(format!(
+            "Lost connection to Deepgram and could not reconnect after {} attempts",
+            max_attempts
+        )));
+        None
+    }
+
+    /// Keep buffering queued audio while waiting; false if the channel closed
+    async fn buffer_audio_for(&mut self, delay: Duration) -> bool {
+        let deadline = tokio::time::sleep(delay);
+        tokio::pin!(deadline);
+        loop {
+            tokio::select! {
+                _ = &mut deadline => return true,
+                message = self.audio_rx.recv() => match message {
+                    Some(AudioMessage::Audio { pcm, recording_time }) => self.buffer.push(&pcm, recording_time),
+                    Some(AudioMessage::Finalize) => self.finalizing = true,
+                    Some(AudioMessage::CloseStream) => self.closing = true,
+                    None => return false,
+                },
+            }
+        }
+    }
+
+    /// Send the audio that has no final result yet over a fresh connection
+    async fn replay(&mut self, mut socket: Socket) -> Result<Socket, tungstenite::Error> {
+        let lost = self.buffer.lost_secs();
+        if lost > 0.0 {
+            warn!("{:.1}s of audio exceeded the replay buffer and won't be transcribed", lost);
+        }
+
+        let (start, pcm) = self.buffer.unconfirmed();
+        for chunk in pcm.chunks(REPLAY_CHUNK_BYTES) {
+            socket.send(Message::Binary(chunk.to_vec())).await?;
+        }
+        // A Finalize lost with the old connection still needs its answer
+        if self.finalizing {
+            socket.send(Message::Text(FINALIZE_MESSAGE.to_string())).await?;
+        }
+        if self.closing {
+            socket.send(Message::Text(CLOSE_STREAM_MESSAGE.to_string())).await?;
+        }
+
+        // Deepgram times on the new connection start at the replayed audio
+        self.connection_start = self.buffer.samples_to_secs(start);
+        info!(
+            "Reconnected to Deepgram, replayed {:.1}s of audio from {:.1}s",
+            self.buffer.samples_to_secs(pcm.len() as u64 / 2),
+            self.connection_start
+        );
+        Ok(socket)
+    }
+}
+
+/// Delay before reconnect attempt `attempt` (1-based): exponential from
+/// `initial`, capped at `max`
+pub(crate) fn backoff_delay(attempt: u32, initial: Duration, max: Duration) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    initial.saturating_mul(factor).min(max)
+}
+
+/// Build the authenticated WebSocket upgrade request
+fn build_request(url: &str, api_key: &str) -> Result<Request<()>, String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid Deepgram URL: {}", e))?;
+    let host = match (parsed.host_str(), parsed.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => return Err(format!("Invalid Deepgram URL: {}", url)),
+    };
+
+    Request::builder()
+        .uri(url)
+        .header("Authorization", format!("Token {}", api_key))
+        .header("Host", host)
+        .header("Upgrade", "websocket")
+        .header("Connection", "Upgrade")
+        .header("Sec-WebSocket-Version", "13")
+        .header("Sec-WebSocket-Key", generate_websocket_key())
+        .body(())
+        .map_err(|e| format!("Failed to build request: {}", e))
+}
+
+/// Open a WebSocket connection, classifying failures as fatal or retryable
+async fn open_socket(url: &str, api_key: &str) -> Result<Socket, OpenError> {
+    let request = build_request(url, api_key).map_err(OpenError::Fatal)?;
+
+    match connect_async(request).await {
+        Ok((socket, _response)) => Ok(socket),
+        Err(tungstenite::Error::Http(response)) => {
+            let status = response.status().as_u16();
+            match status {
+                401 | 403 => Err(OpenError::Fatal(
+                    "Deepgram rejected the API key. Please check it in Settings > Transcription.".to_string(),
+                )),
+                408 | 429 => Err(OpenError::Retryable(format!("Deepgram returned HTTP {}", status))),
+                400..=499 => Err(OpenError::Fatal(format!("Deepgram refused the connection (HTTP {})", status))),
+                _ => Err(OpenError::Retryable(format!("Deepgram returned HTTP {}", status))),
+            }
+        }
+        Err(e) => Err(OpenError::Retryable(format!("Failed to connect to Deepgram: {}", e))),
     }
 }
 
@@ -473,9 +871,10 @@ fn parse_deepgram_response(response: &DeepgramResponse) -> Option<TranscriptionS
 
     let channel = response.channel.as_ref()?;
     let alternative = channel.alternatives.first()?;
+    let from_finalize = response.from_finalize.unwrap_or(false);
 
-    // Skip empty transcripts
-    if alternative.transcript.trim().is_empty() {
+    // Skip empty transcripts, except the answer to a Finalize
+    if alternative.transcript.trim().is_empty() && !from_finalize {
         return None;
     }
 
@@ -483,7 +882,8 @@ fn parse_deepgram_response(response: &DeepgramResponse) -> Option<TranscriptionS
     let confidence = alternative.confidence.map(|c| c as f32);
 
     // Extract speaker segments from words
-    let speakers = extract_speaker_segments(alternative.words.as_deref().unwrap_or(&[]));
+    let words = alternative.words.clone().unwrap_or_default();
+    let speakers = extract_speaker_segments(&words);
 
     Some(TranscriptionSegment {
         text: alternative.transcript.clone(),
@@ -492,11 +892,27 @@ fn parse_deepgram_response(response: &DeepgramResponse) -> Option<TranscriptionS
         start_time: response.start,
         end_time: response.start.map(|s| s + response.duration.unwrap_or(0.0)),
         speakers,
+        words,
+        from_finalize,
     })
 }
 
+/// Map all times of a segment (segment, speakers, words) through `map`
+fn shift_segment_times(segment: &mut TranscriptionSegment, map: impl Fn(f64) -> f64) {
+    segment.start_time = segment.start_time.map(&map);
+    segment.end_time = segment.end_time.map(&map);
+    for speaker in &mut segment.speakers {
+        speaker.start_time = map(speaker.start_time);
+        speaker.end_time = map(speaker.end_time);
+    }
+    for word in &mut segment.words {
+        word.start = map(word.start);
+        word.end = map(word.end);
+    }
+}
+
 /// Extract speaker segments from word array
-fn extract_speaker_segments(words: &[DeepgramWord]) -> Vec<SpeakerSegment> {
+pub(crate) fn extract_speaker_segments(words: &[DeepgramWord]) -> Vec<SpeakerSegment> {
     if words.is_empty() {
         return Vec::new();
     }
@@ -724,4 +1140,152 @@ mod tests {
         assert_eq!(segments[1].speaker_id, 1);
         assert_eq!(segments[1].text, "Hi!");
     }
+
+    #[test]
+    fn test_backoff_delay() {
+        let initial = Duration::from_millis(500);
+        let max = Duration::from_secs(30);
+        assert_eq!(backoff_delay(1, initial, max), Duration::from_millis(500));
+        assert_eq!(backoff_delay(2, initial, max), Duration::from_secs(1));
+        assert_eq!(backoff_delay(4, initial, max), Duration::from_secs(4));
+        assert_eq!(backoff_delay(10, initial, max), max);
+        assert_eq!(backoff_delay(100, initial, max), max);
+    }
+
+    #[test]
+    fn test_connection_state_event() {
+        let event = ConnectionStateEvent::from(&ConnectionState::Reconnecting { attempt: 3 });
+        assert_eq!(event.state, "reconnecting");
+        assert_eq!(event.attempt, Some(3));
+
+        let event = ConnectionStateEvent::from(&ConnectionState::Error("boom".to_string()));
+        assert_eq!(event.state, "error");
+        assert_eq!(event.message.as_deref(), Some("boom"));
+    }
+
+    /// Final Results message for `start`/`duration` with one word at `word_start`
+    fn final_result(start: f64, duration: f64, word_start: f64) -> String {
+        serde_json::json!({
+            "type": "Results",
+            "start": start,
+            "duration": duration,
+            "is_final": true,
+            "channel": {"alternatives": [{
+                "transcript": "hello",
+                "confidence": 0.9,
+                "words": [{"word": "hello", "start": word_start, "end": word_start + 0.2, "confidence": 0.9, "speaker": 0}]
+            }]}
+        })
+        .to_string()
+    }
+
+    /// Read binary frames until `bytes` of audio arrived
+    async fn read_audio(socket: &mut WebSocketStream<TcpStream>, bytes: usize) -> usize {
+        let mut received = 0;
+        while received < bytes {
+            match socket.next().await {
+                Some(Ok(Message::Binary(data))) => received += data.len(),
+                Some(Ok(_)) => {}
+                _ => break,
+            }
+        }
+        received
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_replays_unconfirmed_audio() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("ws://{}", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            // First connection: confirm the first half second, then drop without a close frame
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            read_audio(&mut socket, 32000).await;
+            socket.send(Message::Text(final_result(0.0, 0.5, 0.1))).await.unwrap();
+            socket.flush().await.unwrap();
+            drop(socket);
+
+            // Second connection: only the unconfirmed half second is replayed
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let replayed = read_audio(&mut socket, 16000).await;
+            socket.send(Message::Text(final_result(0.0, 0.5, 0.1))).await.unwrap();
+            socket.next().await;
+            replayed
+        });
+
+        let resilience = ResilienceConfig {
+            initial_backoff: Duration::from_millis(10),
+            ..ResilienceConfig::default()
+        };
+        let websocket = DeepgramWebSocket::with_resilience(DeepgramConfig::default(), resilience)
+            .with_base_url(base_url);
+        let mut states = websocket.subscribe_state();
+        let mut receiver = websocket.connect().await.unwrap();
+
+        // One second of audio that starts 10 seconds into the recording
+        websocket.send_audio_at(&vec![0.1f32; 16000], Some(10.0)).await.unwrap();
+
+        let first = receiver.recv().await.unwrap();
+        assert_eq!(first.start_time, Some(10.0));
+        assert!((first.words[0].start - 10.1).abs() < 1e-9);
+
+        // After the reconnect, times continue from the replayed audio
+        let second = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!((second.start_time.unwrap() - 10.5).abs() < 1e-9);
+        assert!((second.words[0].start - 10.6).abs() < 1e-9);
+        assert!((second.speakers[0].end_time - 10.8).abs() < 1e-9);
+        assert_eq!(*states.borrow_and_update(), ConnectionState::Connected);
+        assert!(websocket.is_connected().await);
+
+        websocket.disconnect().await;
+        assert_eq!(server.await.unwrap(), 16000);
+    }
+    #[tokio::test]
+    async fn test_finalize_forwards_empty_answer() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("ws://{}", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            read_audio(&mut socket, 16000).await;
+            // Silence: Deepgram answers the Finalize with an empty transcript
+            let finalize = match socket.next().await {
+                Some(Ok(Message::Text(text))) => text,
+                other => panic!("expected Finalize, got {:?}", other),
+            };
+            let answer = serde_json::json!({
+                "type": "Results",
+                "start": 0.0,
+                "duration": 0.5,
+                "is_final": true,
+                "from_finalize": true,
+                "channel": {"alternatives": [{"transcript": "", "words": []}]}
+            });
+            socket.send(Message::Text(answer.to_string())).await.unwrap();
+            socket.next().await;
+            finalize
+        });
+
+        let websocket = DeepgramWebSocket::new(DeepgramConfig::default()).with_base_url(base_url);
+        let mut receiver = websocket.connect().await.unwrap();
+        websocket.send_audio_at(&vec![0.0f32; 8000], Some(4.0)).await.unwrap();
+        websocket.finalize().await.unwrap();
+
+        let answer = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(answer.from_finalize);
+        assert!(answer.text.is_empty());
+        assert_eq!(answer.end_time, Some(4.5));
+
+        websocket.disconnect().await;
+        assert_eq!(server.await.unwrap(), FINALIZE_MESSAGE);
+    }
 }
@@ -46,8 +46,11 @@ pub mod ollama;
 pub mod onboarding;
 pub mod openrouter;
 pub mod parakeet_engine;
+pub mod semantic;
 pub mod state;
 pub mod summary;
+#[cfg(test)]
+pub(crate) mod test_utils;
 pub mod tray;
 pub mod utils;
 #[cfg(not(target_os = "windows"))]
@@ -516,6 +519,13 @@ pub fn run() {
                 log::warn!("Failed to resolve resource directory for templates");
             }
 
+            // Index and watch the configured Obsidian vault
+            obsidian::labels::init_section_labels(_app.handle().clone());
+            tauri::async_runtime::spawn(obsidian::vault::init_vault_index(_app.handle().clone()));
+
+            // Transcribe recordings captured while Deepgram was unreachable
+            tauri::async_runtime::spawn(deepgram::offline_queue::run_offline_queue(_app.handle().clone()));
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -615,6 +625,16 @@ pub fn run() {
             audio::incremental_saver::recover_audio_from_checkpoints,
             audio::incremental_saver::cleanup_checkpoints,
             audio::incremental_saver::has_audio_checkpoints,
+            // Offline re-transcription of finished meetings
+            audio::transcription::retranscribe::retranscribe_meeting,
+            // On-device speaker diarization model
+            audio::diarization_commands::diarization_get_model_status,
+            audio::diarization_commands::diarization_download_model,
+            audio::diarization_commands::diarization_cancel_download,
+            // Deepgram prerecorded (batch) transcription of audio files
+            deepgram::commands::deepgram_transcribe_file,
+            // Offline capture queue
+            deepgram::offline_queue::set_pending_transcription_note,
             console_utils::show_console,
             console_utils::hide_console,
             console_utils::toggle_console,
@@ -624,6 +644,11 @@ pub fn run() {
             ollama::get_ollama_model_context,
             api::api_get_meetings,
             api::api_search_transcripts,
+            api::api_search_meetings,
+            api::api_reindex_search,
+            semantic::commands::api_semantic_index,
+            semantic::commands::api_semantic_search,
+            semantic::commands::api_ask_meetings,
             api::api_get_profile,
             api::api_save_profile,
             api::api_update_profile,
@@ -639,6 +664,8 @@ pub fn run() {
             api::api_get_meeting,
             api::api_get_meeting_metadata,
             api::api_get_meeting_transcripts,
+            api::api_get_transcript_words,
+            api::api_get_meeting_words,
             api::api_save_meeting_title,
             api::api_save_transcript,
             api::open_meeting_folder,
@@ -649,15 +676,29 @@ pub fn run() {
             api::api_save_custom_openai_config,
             api::api_get_custom_openai_config,
             api::api_test_custom_openai_connection,
+            api::api_save_remote_whisper_config,
+            api::api_get_remote_whisper_config,
+            api::api_test_remote_whisper_connection,
             // Summary commands
             summary::api_process_transcript,
             summary::api_get_summary,
             summary::api_save_meeting_summary,
             summary::api_cancel_summary,
+            summary::api_get_gemini_models,
             // Template commands
             summary::api_list_templates,
             summary::api_get_template_details,
             summary::api_validate_template,
+            summary::api_get_template,
+            summary::api_save_template,
+            summary::api_delete_template,
+            summary::api_duplicate_template,
+            summary::api_list_template_versions,
+            summary::api_restore_template_version,
+            summary::api_import_template,
+            summary::api_export_template,
+            summary::api_get_template_rules,
+            summary::api_save_template_rules,
             // Built-in AI commands
             summary::summary_engine::builtin_ai_list_models,
             summary::summary_engine::builtin_ai_get_model_info,
@@ -731,6 +772,24 @@ pub fn run() {
             obsidian::save_meeting_transcript,
             obsidian::save_meeting_markdown_file,
             obsidian::update_meeting_summary,
+            obsidian::merge_meeting_action_items,
+            // Speaker naming (per-meeting speaker map + wikilinks)
+            obsidian::speakers::get_meeting_speakers,
+            obsidian::speakers::save_meeting_speakers,
+            // Vault meeting index
+            obsidian::vault::get_obsidian_vault,
+            obsidian::vault::set_obsidian_vault,
+            obsidian::vault::rescan_obsidian_vault,
+            obsidian::vault::list_vault_meetings,
+            obsidian::vault::get_todays_meetings,
+            // Prep note creation from a template
+            obsidian::prep::create_prep_note,
+            obsidian::prep::get_prep_note_settings,
+            obsidian::prep::set_prep_note_settings,
+            // Section label overrides
+            obsidian::labels::get_section_labels_status,
+            obsidian::labels::reload_section_labels,
+            obsidian::labels::validate_section_labels,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
@@ -0,0 +1,320 @@
+//! Line-preserving frontmatter editor
+//!
+//! Re-serializing the YAML with serde would drop comments and reorder keys,
+//! which users notice in their vault. The editor instead works on the lines
+//! between the `---` markers: a field is its top-level `key:` line plus any
+//! indented or list lines below it, and only fields that are set are rewritten.
+//! Comments, unknown keys and key order are left untouched.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+/// Matches a top-level frontmatter key
+static FRONTMATTER_KEY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^([A-Za-z_][\w-]*)\s*:(.*)$").unwrap());
+
+/// Plain scalars YAML reads as numbers: ints (incl. hex/octal/binary and `_`
+/// separators), floats, `.inf`/`.nan` and YAML 1.1 sexagesimals (`10:30`)
+static YAML_NUMBER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^[-+]?(0x[0-9a-fA-F_]+|0o[0-7_]+|0b[01_]+|[0-9][0-9_]*(\.[0-9_]*)?([eE][-+]?[0-9]+)?|\.[0-9][0-9_]*([eE][-+]?[0-9]+)?|\.(inf|Inf|INF|nan|NaN|NAN)|[0-9][0-9_]*(:[0-5]?[0-9])+(\.[0-9_]*)?)$",
+    )
+    .unwrap()
+});
+
+/// Plain scalars YAML reads as booleans or null
+const YAML_KEYWORDS: &[&str] = &["y", "n", "yes", "no", "true", "false", "on", "off", "null", "~"];
+
+/// A frontmatter value to write
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrontmatterValue {
+    Scalar(String),
+    List(Vec<String>),
+}
+
+impl FrontmatterValue {
+    fn render(&self, key: &str) -> Vec<String> {
+        match self {
+            FrontmatterValue::Scalar(value) => vec![format!("{}: {}", key, yaml_scalar(value))],
+            FrontmatterValue::List(items) => std::iter::once(format!("{}:", key))
+                .chain(items.iter().map(|item| format!("  - {}", yaml_quoted(item))))
+                .collect(),
+        }
+    }
+}
+
+/// Quote a scalar when plain YAML would misread it (links, `: `, leading
+/// indicators, or text that would load as a number, boolean or null)
+fn yaml_scalar(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || YAML_KEYWORDS.contains(&value.to_ascii_lowercase().as_str())
+        || YAML_NUMBER_RE.is_match(value)
+        || value != value.trim()
+        || value == "-"
+        || value.starts_with("- ")
+        || value.contains(": ")
+        || value.contains(" #")
+        || value.ends_with(':')
+        || value.starts_with(['[', ']', '{', '}', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`', '#', ',', '?']);
+    if needs_quotes {
+        yaml_quoted(value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Double-quoted YAML scalar
+fn yaml_quoted(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Editable view of a note's frontmatter
+#[derive(Debug, Clone)]
+pub struct FrontmatterEditor {
+    lines: Vec<String>,
+    body: String,
+    has_frontmatter: bool,
+}
+
+impl FrontmatterEditor {
+    /// Split a note into frontmatter lines and body
+    pub fn parse(content: &str) -> Self {
+        match split_frontmatter(content) {
+            Some((frontmatter, body)) => Self {
+                lines: frontmatter.lines().map(str::to_string).collect(),
+                body: body.to_string(),
+                has_frontmatter: true,
+            },
+            None => Self {
+                lines: Vec::new(),
+                body: content.to_string(),
+                has_frontmatter: false,
+            },
+        }
+    }
+
+    /// Whether the note had a frontmatter block
+    pub fn has_frontmatter(&self) -> bool {
+        self.has_frontmatter
+    }
+
+    /// Line range of a field: its key line plus nested lines
+    fn field_range(&self, key: &str) -> Option<(usize, usize)> {
+        let start = self.lines.iter().position(|line| {
+            FRONTMATTER_KEY_RE
+                .captures(line)
+                .map_or(false, |caps| &caps[1] == key)
+        })?;
+        let nested = self.lines[start + 1..]
+            .iter()
+            .take_while(|line| line.starts_with([' ', '\t']) || line.starts_with('-'))
+            .count();
+        Some((start, start + nested + 1))
+    }
+
+    /// Whether a field is missing or has no value (`key:`, `key: ""`, `key: []`)
+    pub fn is_empty(&self, key: &str) -> bool {
+        let Some((start, end)) = self.field_range(key) else {
+            return true;
+        };
+        let inline_value = FRONTMATTER_KEY_RE.captures(&self.lines[start]).unwrap()[2].trim().to_string();
+        let is_blank = inline_value.is_empty() || inline_value == "\"\"" || inline_value == "[]";
+        is_blank && end == start + 1
+    }
+
+    /// Set a field, replacing its current value in place or appending it
+    pub fn set(&mut self, key: &str, value: &FrontmatterValue) {
+        match self.field_range(key) {
+            Some((start, end)) => {
+                self.lines.splice(start..end, value.render(key));
+            }
+            None => self.lines.extend(value.render(key)),
+        }
+    }
+
+    /// Set a field only when it is missing or empty
+    pub fn set_if_empty(&mut self, key: &str, value: &FrontmatterValue) {
+        if self.is_empty(key) {
+            self.set(key, value);
+        }
+    }
+
+    /// Remove a field and its nested lines
+    pub fn remove(&mut self, key: &str) {
+        if let Some((start, end)) = self.field_range(key) {
+            self.lines.drain(start..end);
+        }
+    }
+
+    /// Note content with the edited frontmatter
+    pub fn render(&self) -> String {
+        if self.lines.is_empty() {
+            return format!("---\n---\n{}", self.body);
+        }
+        format!("---\n{}\n---\n{}", self.lines.join("\n"), self.body)
+    }
+}
+
+/// Set frontmatter fields, creating the frontmatter block if needed
+pub fn set_frontmatter_fields(content: &str, fields: &[(&str, FrontmatterValue)]) -> String {
+    let mut editor = FrontmatterEditor::parse(content);
+    for (key, value) in fields {
+        editor.set(key, value);
+    }
+    editor.render()
+}
+
+/// Fill frontmatter fields that are missing or left empty
+///
+/// Keys that already have a value are kept, so user templates win.
+/// Other lines (comments, unknown keys) are preserved in order.
+pub fn ensure_frontmatter_fields(content: &str, fields: &[(&str, FrontmatterValue)]) -> String {
+    let mut editor = FrontmatterEditor::parse(content);
+    for (key, value) in fields {
+        editor.set_if_empty(key, value);
+    }
+    editor.render()
+}
+
+/// Split content into (frontmatter without markers, body after the closing marker)
+pub fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let content = content.trim_start();
+    let after_first_marker = content.strip_prefix("---")?;
+    let end_pos = after_first_marker.find("\n---")?;
+    let frontmatter = after_first_marker[..end_pos].trim_matches(['\r', '\n']);
+    let rest = &after_first_marker[end_pos + 4..];
+    // Skip the remainder of the closing marker line
+    let body = rest.find('\n').map_or("", |i| &rest[i + 1..]);
+    Some((frontmatter, body))
+}
+
+/// Frontmatter link to a recording: a vault-relative `[[wikilink]]` when the
+/// file is inside the vault, a `file://` URL for other absolute paths, and a
+/// relative path (bare file name) as given
+pub fn recording_link(recording: &Path, vault_root: Option<&Path>) -> String {
+    match vault_root.and_then(|root| recording.strip_prefix(root).ok()) {
+        Some(relative) => format!(
+            "[[{}]]",
+            relative.to_string_lossy().replace('\\', "/")
+        ),
+        None => url::Url::from_file_path(recording)
+            .map(String::from)
+            .unwrap_or_else(|_| recording.to_string_lossy().to_string()),
+    }
+}
+
+/// Dataview-friendly duration to the second (`45 minutes, 12 seconds`,
+/// `1 hour, 5 minutes`, `20 seconds`)
+pub fn format_duration(seconds: f64) -> String {
+    let plural = |count: u64, unit: &str| format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" });
+    let total = seconds.max(0.0).round() as u64;
+    let parts: Vec<String> = [(total / 3600, "hour"), (total % 3600 / 60, "minute"), (total % 60, "second")]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, unit)| plural(count, unit))
+        .collect();
+    if parts.is_empty() {
+        plural(0, "second")
+    } else {
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTE: &str = "---\n# managed by the team\ntags: meeting\nstatus: scheduled\nattendees:\n  - Alice\n  - Bob\nproject: Apollo\n---\n\n# Sync\n";
+
+    #[test]
+    fn test_set_replaces_in_place_and_keeps_other_lines() {
+        let content = set_frontmatter_fields(
+            NOTE,
+            &[
+                ("status", FrontmatterValue::Scalar("completed".to_string())),
+                ("attendees", FrontmatterValue::List(vec!["[[Carol]]".to_string()])),
+                ("time_end", FrontmatterValue::Scalar("2026-01-23T11:00:00".to_string())),
+            ],
+        );
+
+        assert_eq!(
+            content,
+            "---\n# managed by the team\ntags: meeting\nstatus: completed\nattendees:\n  - \"[[Carol]]\"\nproject: Apollo\ntime_end: 2026-01-23T11:00:00\n---\n\n# Sync\n"
+        );
+    }
+
+    #[test]
+    fn test_ensure_only_fills_empty_fields() {
+        let content = ensure_frontmatter_fields(
+            "---\nstatus: draft\ntime_end:\n---\nbody",
+            &[
+                ("status", FrontmatterValue::Scalar("scheduled".to_string())),
+                ("time_end", FrontmatterValue::Scalar("2026-01-23T11:00:00".to_string())),
+            ],
+        );
+        assert_eq!(content, "---\nstatus: draft\ntime_end: 2026-01-23T11:00:00\n---\nbody");
+    }
+
+    #[test]
+    fn test_remove_and_create_frontmatter() {
+        let mut editor = FrontmatterEditor::parse(NOTE);
+        editor.remove("attendees");
+        assert!(!editor.render().contains("Alice"));
+
+        let editor = FrontmatterEditor::parse("# No frontmatter\n");
+        assert!(!editor.has_frontmatter());
+        assert_eq!(editor.render(), "---\n---\n# No frontmatter\n");
+    }
+
+    #[test]
+    fn test_yaml_scalar_quoting() {
+        assert_eq!(yaml_scalar("deepgram"), "deepgram");
+        assert_eq!(yaml_scalar("[[Recordings/call.mp4]]"), "\"[[Recordings/call.mp4]]\"");
+        assert_eq!(yaml_scalar("Standup: daily"), "\"Standup: daily\"");
+        assert_eq!(yaml_scalar("C:\\notes \"q\""), "C:\\notes \"q\"");
+        assert_eq!(yaml_scalar("path: C:\\notes"), "\"path: C:\\\\notes\"");
+    }
+
+    #[test]
+    fn test_yaml_scalar_quotes_non_strings() {
+        for value in ["yes", "No", "TRUE", "false", "on", "null", "~", "-5", "42", "3.14", "1e3", "0x1F", "1_000", ".inf", "10:30"] {
+            assert_eq!(yaml_scalar(value), format!("\"{}\"", value), "{}", value);
+        }
+        for value in ["yesterday", "v1.2", "2026-01-23", "2026-01-23T11:00:00", "1 hour, 5 minutes", "45 minutes"] {
+            assert_eq!(yaml_scalar(value), value, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_list_items_escape_backslashes() {
+        let value = FrontmatterValue::List(vec!["C:\\Users\\ana".to_string(), "say \"hi\"".to_string()]);
+        assert_eq!(
+            value.render("speakers"),
+            vec!["speakers:", "  - \"C:\\\\Users\\\\ana\"", "  - \"say \\\"hi\\\"\""]
+        );
+    }
+
+    #[test]
+    fn test_recording_link_and_duration() {
+        let root = Path::new("/vault");
+        assert_eq!(
+            recording_link(Path::new("/vault/Recordings/call 1.mp4"), Some(root)),
+            "[[Recordings/call 1.mp4]]"
+        );
+        assert_eq!(
+            recording_link(Path::new("/tmp/call 1.mp4"), Some(root)),
+            "file:///tmp/call%201.mp4"
+        );
+        assert_eq!(
+            recording_link(Path::new("call.mp4"), Some(root)),
+            "call.mp4"
+        );
+        assert_eq!(format_duration(2700.0), "45 minutes");
+        assert_eq!(format_duration(2712.4), "45 minutes, 12 seconds");
+        assert_eq!(format_duration(3900.0), "1 hour, 5 minutes");
+        assert_eq!(format_duration(3601.0), "1 hour, 1 second");
+        assert_eq!(format_duration(20.0), "20 seconds");
+        assert_eq!(format_duration(0.2), "0 seconds");
+    }
+}
@@ -0,0 +1,246 @@
+//! User and vault overrides for section labels
+//!
+//! The built-in `section_labels.yaml` can be extended by two optional files,
+//! applied in order (later wins):
+//! - `<app data>/section_labels.yaml` - user-level labels for every vault
+//! - `<vault>/.seshlog/section_labels.yaml` - labels shared with a vault
+//!
+//! Both use the built-in file's format. They are watched and hot-reloaded; an
+//! invalid file keeps the previous labels active and reports the error.
+
+use super::parser::{self, reload_section_config};
+use super::vault::load_vault_root;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// File name of override files
+pub const SECTION_LABELS_FILE: &str = "section_labels.yaml";
+/// Folder inside the vault holding app config
+pub const VAULT_CONFIG_DIR: &str = ".seshlog";
+/// Event emitted after every reload attempt
+pub const SECTION_LABELS_RELOADED_EVENT: &str = "obsidian-section-labels-reloaded";
+/// Quiet period before reloading (editors write in bursts)
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Result of the last reload, returned to the UI and sent with the reload event
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SectionLabelsStatus {
+    /// Override files that are looked for, in the order they are applied
+    pub override_files: Vec<String>,
+    /// Override files currently applied
+    pub applied_files: Vec<String>,
+    /// All configured section fields
+    pub fields: Vec<String>,
+    /// Fields surfaced in `MeetingPrep.custom_fields`
+    pub custom_fields: Vec<String>,
+    /// Error from the last reload (the previous labels stay active)
+    pub error: Option<String>,
+}
+
+static LAST_STATUS: Lazy<Mutex<SectionLabelsStatus>> = Lazy::new(|| Mutex::new(SectionLabelsStatus::default()));
+static LABELS_WATCHER: Lazy<Mutex<Option<RecommendedWatcher>>> = Lazy::new(|| Mutex::new(None));
+
+/// Override file locations, in the order they are applied
+pub fn override_files<R: Runtime>(app: &AppHandle<R>) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    if let Ok(dir) = app.path().app_data_dir() {
+        files.push(dir.join(SECTION_LABELS_FILE));
+    }
+    if let Some(root) = load_vault_root(app) {
+        files.push(root.join(VAULT_CONFIG_DIR).join(SECTION_LABELS_FILE));
+    }
+    files
+}
+
+/// Reload the section config from the override files, record the outcome and notify the UI
+fn reload<R: Runtime>(app: &AppHandle<R>) -> SectionLabelsStatus {
+    let status = reload_files(&override_files(app));
+    if let Err(e) = app.emit(SECTION_LABELS_RELOADED_EVENT, &status) {
+        log::warn!("Failed to emit {}: {}", SECTION_LABELS_RELOADED_EVENT, e);
+    }
+    status
+}
+
+/// Reload the section config from `files` and record the outcome
+fn reload_files(files: &[PathBuf]) -> SectionLabelsStatus {
+    let to_strings = |paths: &[PathBuf]| -> Vec<String> {
+        paths.iter().map(|p| p.to_string_lossy().to_string()).collect()
+    };
+
+    let mut status = SectionLabelsStatus {
+        override_files: to_strings(files),
+        ..Default::default()
+    };
+    match reload_section_config(files) {
+        Ok(applied) => {
+            log::info!("Loaded section labels ({} override file(s))", applied.len());
+            status.applied_files = to_strings(&applied);
+        }
+        Err(e) => {
+            log::warn!("Invalid section label overrides, keeping previous labels: {}", e);
+            status.applied_files = LAST_STATUS.lock().unwrap().applied_files.clone();
+            status.error = Some(e.to_string());
+        }
+    }
+    status.fields = parser::get_configured_fields();
+    status.custom_fields = parser::get_custom_fields();
+
+    *LAST_STATUS.lock().unwrap() = status.clone();
+    status
+}
+
+/// Watch the folders of the override files (the files themselves may not exist yet)
+fn start_watcher<R: Runtime>(app: AppHandle<R>, files: Vec<PathBuf>) -> Result<(), String> {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+    let watched: HashSet<PathBuf> = files.iter().cloned().collect();
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+        Ok(event) => {
+            if event.paths.iter().any(|p| watched.contains(p)) {
+                let _ = tx.send(());
+            }
+        }
+        Err(e) => log::warn!("Section labels watcher error: {}", e),
+    })
+    .map_err(|e| format!("Failed to create section labels watcher: {}", e))?;
+
+    for dir in files.iter().filter_map(|f| f.parent()).filter(|d| d.is_dir()) {
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Failed to watch {}: {}", dir.display(), e))?;
+    }
+
+    // The previous watcher's reload task ends once its channel closes; a reload
+    // it still runs is harmless since reload() reads the current override files
+    *LABELS_WATCHER.lock().unwrap() = Some(watcher);
+
+    tauri::async_runtime::spawn(async move {
+        while rx.recv().await.is_some() {
+            while let Ok(Some(())) = tokio::time::timeout(WATCH_DEBOUNCE, rx.recv()).await {}
+            reload(&app);
+        }
+    });
+
+    Ok(())
+}
+
+/// Load overrides and start watching them (called at startup and when the vault changes)
+pub fn init_section_labels<R: Runtime>(app: AppHandle<R>) {
+    reload(&app);
+    if let Err(e) = start_watcher(app.clone(), override_files(&app)) {
+        log::error!("{}", e);
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Tauri command to get the outcome of the last section labels reload
+#[tauri::command]
+pub async fn get_section_labels_status() -> Result<SectionLabelsStatus, String> {
+    Ok(LAST_STATUS.lock().unwrap().clone())
+}
+
+/// Tauri command to reload section label overrides from disk
+///
+/// Also restarts the watcher, e.g. after the `.seshlog` folder was created.
+#[tauri::command]
+pub async fn reload_section_labels<R: Runtime>(app: AppHandle<R>) -> Result<SectionLabelsStatus, String> {
+    let status = reload(&app);
+    start_watcher(app.clone(), override_files(&app))?;
+    Ok(status)
+}
+
+/// Tauri command to validate override YAML without applying it
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - Fields the merged configuration would contain
+/// * `Err(String)` - What is wrong with the YAML, and where
+#[tauri::command]
+pub async fn validate_section_labels(yaml: String) -> Result<Vec<String>, String> {
+    parser::validate_section_overrides(&yaml).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_LABELS: &str = r#"
+sections:
+  - field: agenda
+    labels:
+      nl:
+        - Bespreekpunten
+  - field: open_risks
+    content_type: bullet
+    labels:
+      en:
+        - Open Risks
+frontmatter_aliases:
+  attendees:
+    - aanwezig
+"#;
+
+    #[tokio::test]
+    async fn test_validate_section_labels() {
+        let fields = validate_section_labels(USER_LABELS.to_string()).await.unwrap();
+        assert!(fields.contains(&"open_risks".to_string()));
+
+        let err = validate_section_labels("sections:\n  - field: open_risks\n".to_string())
+            .await
+            .unwrap_err();
+        assert!(err.contains("content_type"), "{}", err);
+    }
+
+    // One test, since the section config is process-wide
+    #[test]
+    fn test_reload_applies_overrides_and_keeps_labels_on_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let user_file = dir.path().join(SECTION_LABELS_FILE);
+        let vault_file = dir.path().join(VAULT_CONFIG_DIR).join(SECTION_LABELS_FILE);
+        let files = vec![user_file.clone(), vault_file.clone()];
+
+        // Missing files are skipped
+        let status = reload_files(&files);
+        assert!(status.error.is_none());
+        assert!(status.applied_files.is_empty());
+        assert_eq!(status.override_files.len(), 2);
+
+        std::fs::write(&user_file, USER_LABELS).unwrap();
+        let status = reload_files(&files);
+        assert!(status.error.is_none(), "{:?}", status.error);
+        assert_eq!(status.applied_files, vec![user_file.to_string_lossy().to_string()]);
+        assert!(status.custom_fields.contains(&"open_risks".to_string()));
+        assert!(parser::get_labels_for_field("agenda").contains(&"bespreekpunten".to_string()));
+
+        let note = "---\naanwezig: [Anna]\n---\n# Sync\n\n## Bespreekpunten\n- Budget\n\n## Open Risks\n- Hiring\n";
+        let prep = parser::parse_meeting_file(std::path::Path::new("sync.md"), note).unwrap();
+        assert_eq!(prep.agenda, vec!["Budget".to_string()]);
+        assert_eq!(prep.custom_fields.get("open_risks"), Some(&vec!["Hiring".to_string()]));
+        assert_eq!(prep.frontmatter.attendees, Some(vec!["Anna".to_string()]));
+
+        // An invalid vault file keeps the labels from the last good reload
+        std::fs::create_dir_all(vault_file.parent().unwrap()).unwrap();
+        std::fs::write(&vault_file, "sections:\n  - field: agenda\n    levels: [9]\n").unwrap();
+        let status = reload_files(&files);
+        assert!(status.error.is_some());
+        assert_eq!(status.applied_files, vec![user_file.to_string_lossy().to_string()]);
+        assert!(status.custom_fields.contains(&"open_risks".to_string()));
+        assert_eq!(LAST_STATUS.lock().unwrap().error, status.error);
+
+        // Back to the built-in labels
+        std::fs::remove_file(&vault_file).unwrap();
+        std::fs::remove_file(&user_file).unwrap();
+        let status = reload_files(&files);
+        assert!(status.error.is_none());
+        assert!(!status.custom_fields.contains(&"open_risks".to_string()));
+        assert!(!parser::get_labels_for_field("agenda").contains(&"bespreekpunten".to_string()));
+    }
+}
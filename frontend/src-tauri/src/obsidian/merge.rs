@@ -0,0 +1,324 @@
+//! Conflict-safe merging of app output into notes the user may have edited
+//!
+//! A recording can run for an hour while the prep note stays open in Obsidian
+//! (or gets synced from another device). Before writing, the note on disk is
+//! compared with the content loaded when the meeting started. If it changed,
+//! our edits (applied to the loaded content) and the user's edits are merged
+//! three-way, block by block:
+//! - frontmatter is split into top-level keys
+//! - the body is split into heading sections
+//!
+//! A block changed on only one side takes that side; a block changed on both
+//! sides is a conflict. Conflicts keep the user's text in the note, and the
+//! caller writes our full version to a sibling conflict file.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Key of the body text before the first heading
+const PREAMBLE_KEY: &str = "preamble";
+/// Key of frontmatter lines before the first `key:` line
+const FRONTMATTER_PREAMBLE_KEY: &str = "fm";
+
+/// Outcome of a three-way merge
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeResult {
+    /// Every change could be applied
+    Clean(String),
+    /// Some blocks were changed on both sides; `content` keeps the user's version of them
+    Conflict { content: String, blocks: Vec<String> },
+}
+
+/// Stable content hash (FNV-1a, 64 bit) used to detect edits since a note was loaded
+pub fn content_hash(content: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in content.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Sibling path for a conflict copy: `Note.conflict-20260123-101500.md`
+pub fn conflict_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("meeting");
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let mut candidate = path.with_file_name(format!("{}.conflict-{}.md", stem, stamp));
+    let mut counter = 2;
+    while candidate.exists() {
+        candidate = path.with_file_name(format!("{}.conflict-{}-{}.md", stem, stamp, counter));
+        counter += 1;
+    }
+    candidate
+}
+
+/// Three-way merge of a note: `base` is what was loaded, `ours` is `base` with
+/// the app's edits, `theirs` is what is on disk now
+pub fn three_way_merge(base: &str, ours: &str, theirs: &str) -> MergeResult {
+    let (base_fm, base_body) = split_document(base);
+    let (ours_fm, ours_body) = split_document(ours);
+    let (theirs_fm, theirs_body) = split_document(theirs);
+
+    let mut conflicts = Vec::new();
+
+    let frontmatter = match (&ours_fm, &theirs_fm) {
+        (None, None) => None,
+        _ => {
+            let (blocks, fm_conflicts) = merge_blocks(
+                base_fm.as_deref().unwrap_or_default(),
+                ours_fm.as_deref().unwrap_or_default(),
+                theirs_fm.as_deref().unwrap_or_default(),
+            );
+            conflicts.extend(fm_conflicts.into_iter().map(|key| format!("frontmatter: {}", key)));
+            Some(blocks)
+        }
+    };
+
+    let (body, body_conflicts) = merge_blocks(&base_body, &ours_body, &theirs_body);
+    conflicts.extend(body_conflicts.into_iter().map(|key| {
+        body.iter()
+            .chain(ours_body.iter())
+            .find(|(k, _)| *k == key)
+            .and_then(|(_, text)| text.lines().next())
+            .map(|heading| heading.trim().to_string())
+            .unwrap_or(key)
+    }));
+
+    let mut content = String::new();
+    if let Some(frontmatter) = frontmatter {
+        content.push_str("---\n");
+        for (_, text) in &frontmatter {
+            content.push_str(text);
+            content.push('\n');
+        }
+        content.push_str("---\n");
+    }
+    content.push_str(&join_blocks(&body));
+    if theirs.ends_with('\n') && !content.ends_with('\n') {
+        content.push('\n');
+    }
+
+    if conflicts.is_empty() {
+        MergeResult::Clean(content)
+    } else {
+        MergeResult::Conflict { content, blocks: conflicts }
+    }
+}
+
+type Blocks = Vec<(String, String)>;
+
+/// Split a note into frontmatter entries (if any) and body sections
+fn split_document(content: &str) -> (Option<Blocks>, Blocks) {
+    let trimmed = content.trim_start();
+    if let Some(rest) = trimmed.strip_prefix("---") {
+        if let Some(end) = rest.find("\n---") {
+            let yaml = rest[..end].trim_start_matches(['\r', '\n']);
+            let after = &rest[end + 4..];
+            let body = after.strip_prefix("\r\n").or_else(|| after.strip_prefix('\n')).unwrap_or(after);
+            return (Some(split_frontmatter_entries(yaml)), split_sections(body));
+        }
+    }
+    (None, split_sections(content))
+}
+
+/// One block per top-level key; indented lines and list items stay with their key
+fn split_frontmatter_entries(yaml: &str) -> Blocks {
+    let mut blocks: Blocks = Vec::new();
+    let mut key = FRONTMATTER_PREAMBLE_KEY.to_string();
+    let mut lines: Vec<&str> = Vec::new();
+
+    for line in yaml.lines() {
+        let top_level_key = (!line.starts_with([' ', '\t', '-', '#']))
+            .then(|| line.split_once(':'))
+            .flatten()
+            .map(|(k, _)| k.trim())
+            .filter(|k| !k.is_empty());
+        if let Some(new_key) = top_level_key {
+            if !lines.is_empty() {
+                blocks.push((key, lines.join("\n")));
+                lines.clear();
+            }
+            key = new_key.to_string();
+        }
+        lines.push(line);
+    }
+    if !lines.is_empty() {
+        blocks.push((key, lines.join("\n")));
+    }
+    blocks
+}
+
+/// One block per heading (with its content up to the next heading), plus the preamble
+fn split_sections(body: &str) -> Blocks {
+    let mut blocks: Blocks = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut key = PREAMBLE_KEY.to_string();
+    let mut lines: Vec<&str> = Vec::new();
+    let mut in_code_block = false;
+
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+        }
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        let is_heading = !in_code_block && level > 0 && trimmed[level..].starts_with(' ');
+
+        if is_heading {
+            if !lines.is_empty() || key != PREAMBLE_KEY {
+                blocks.push((key, lines.join("\n")));
+                lines.clear();
+            }
+            let base_key = format!("{}:{}", level, trimmed[level..].trim().to_lowercase());
+            let count = seen.entry(base_key.clone()).or_insert(0);
+            *count += 1;
+            key = if *count == 1 { base_key } else { format!("{}#{}", base_key, count) };
+        }
+        lines.push(line);
+    }
+    if !lines.is_empty() || key != PREAMBLE_KEY {
+        blocks.push((key, lines.join("\n")));
+    }
+    blocks
+}
+
+fn join_blocks(blocks: &Blocks) -> String {
+    blocks
+        .iter()
+        .map(|(_, text)| text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn same(a: Option<&str>, b: Option<&str>) -> bool {
+    a.map(str::trim_end) == b.map(str::trim_end)
+}
+
+/// Merge keyed blocks; the result follows the user's order with our new blocks
+/// placed after the block that precedes them in our version
+fn merge_blocks(base: &[(String, String)], ours: &[(String, String)], theirs: &[(String, String)]) -> (Blocks, Vec<String>) {
+    let lookup = |blocks: &[(String, String)], key: &str| -> Option<String> {
+        blocks.iter().find(|(k, _)| k == key).map(|(_, text)| text.clone())
+    };
+
+    let mut result: Blocks = Vec::new();
+    let mut conflicts = Vec::new();
+
+    for (key, their_text) in theirs {
+        let base_text = lookup(base, key);
+        let our_text = lookup(ours, key);
+        let (b, o, t) = (base_text.as_deref(), our_text.as_deref(), Some(their_text.as_str()));
+
+        let merged = if same(o, t) || same(o, b) {
+            // Unchanged by us (or same change on both sides)
+            Some(their_text.clone())
+        } else if same(t, b) {
+            // Only we changed it (None = we removed it)
+            our_text
+        } else {
+            conflicts.push(key.clone());
+            Some(their_text.clone())
+        };
+        if let Some(text) = merged {
+            result.push((key.clone(), text));
+        }
+    }
+
+    for (index, (key, our_text)) in ours.iter().enumerate() {
+        if theirs.iter().any(|(k, _)| k == key) {
+            continue;
+        }
+        match lookup(base, key) {
+            // Removed by the user and untouched by us
+            Some(base_text) if same(Some(&base_text), Some(our_text)) => continue,
+            // Removed by the user but changed by us
+            Some(_) => {
+                conflicts.push(key.clone());
+                continue;
+            }
+            None => {}
+        }
+
+        // Added by us: insert after the closest preceding block that made it into the result
+        let position = ours[..index]
+            .iter()
+            .rev()
+            .find_map(|(prev, _)| result.iter().position(|(k, _)| k == prev))
+            .map_or(0, |pos| pos + 1);
+        let mut text = our_text.clone();
+        // Keep a blank line between the previous section and ours
+        if position > 0 && result[position - 1].1.lines().last().map_or(false, |l| !l.trim().is_empty()) {
+            text = format!("\n{}", text);
+        }
+        result.insert(position, (key.clone(), text));
+    }
+
+    (result, conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "---\ntags: meeting\nstatus: scheduled\nattendees:\n  - Alice\n---\n\n# Sync\n\n## Agenda\n- Budget\n\n## Notes\n- Bring numbers\n";
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        assert_eq!(content_hash("abc"), content_hash("abc"));
+        assert_ne!(content_hash("abc"), content_hash("abd"));
+        assert_eq!(content_hash(""), "cbf29ce484222325");
+    }
+
+    #[test]
+    fn test_merge_keeps_concurrent_user_edits() {
+        let ours = BASE.replace("status: scheduled", "status: completed")
+            + "\n## Transcript\n[00:00] Hello\n";
+        let theirs = BASE
+            .replace("- Bring numbers", "- Bring numbers\n- Ask about Q3")
+            .replace("  - Alice", "  - Alice\n  - Bob");
+
+        let MergeResult::Clean(merged) = three_way_merge(BASE, &ours, &theirs) else {
+            panic!("expected a clean merge");
+        };
+        assert!(merged.contains("status: completed"));
+        assert!(merged.contains("  - Bob"));
+        assert!(merged.contains("- Ask about Q3"));
+        assert!(merged.contains("## Transcript\n[00:00] Hello"));
+        assert!(merged.find("- Ask about Q3").unwrap() < merged.find("## Transcript").unwrap());
+    }
+
+    #[test]
+    fn test_merge_reports_conflict_and_keeps_user_text() {
+        let base = "# Sync\n\n## Summary\nTBD\n";
+        let ours = "# Sync\n\n## Summary\nWe agreed to ship.\n";
+        let theirs = "# Sync\n\n## Summary\nMy own summary\n";
+
+        match three_way_merge(base, ours, theirs) {
+            MergeResult::Conflict { content, blocks } => {
+                assert!(content.contains("My own summary"));
+                assert!(!content.contains("We agreed to ship."));
+                assert_eq!(blocks, vec!["## Summary".to_string()]);
+            }
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_merge_respects_user_deleted_section() {
+        let theirs = BASE.replace("## Agenda\n- Budget\n\n", "");
+        let ours = BASE.to_string() + "\n## Transcript\n[00:00] Hello\n";
+
+        let MergeResult::Clean(merged) = three_way_merge(BASE, &ours, &theirs) else {
+            panic!("expected a clean merge");
+        };
+        assert!(!merged.contains("## Agenda"));
+        assert!(merged.contains("## Transcript"));
+    }
+
+    #[test]
+    fn test_split_sections_ignores_headings_in_code_blocks() {
+        let blocks = split_sections("Intro\n## Notes\n```\n# not a heading\n```\n## Notes\nagain");
+        let keys: Vec<&str> = blocks.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, vec!["preamble", "2:notes", "2:notes#2"]);
+    }
+}
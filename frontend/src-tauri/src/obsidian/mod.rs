@@ -4,16 +4,27 @@
 //! This module enables the app to:
 //! - Load meeting prep content from `.md` files
 //! - Parse YAML frontmatter and structured sections
-//! - Write transcripts back to the source file
+//! - Set frontmatter fields without losing comments or key order
+//! - Write transcripts back to the source file, merging edits made meanwhile
+//! - Name diarized speakers as `[[Person Name]]` wikilinks
+//! - Index meeting notes across a vault and list today's meetings
+//! - Create new prep notes from an Obsidian template
+//! - Extend section labels with user/vault override files
 
+pub mod frontmatter;
+pub mod labels;
+pub mod merge;
 pub mod parser;
+pub mod prep;
+pub mod speakers;
 pub mod types;
+pub mod vault;
 pub mod writer;
 
 pub use parser::{parse_meeting_file, ParseError};
-pub use types::{MeetingFrontmatter, MeetingPrep};
+pub use types::{ActionItem, MeetingFrontmatter, MeetingPrep};
 pub use writer::{
-    MarkdownTranscriptEntry, MeetingMarkdownData, SaveTranscriptRequest, TranscriptSegment,
+    MarkdownTranscriptEntry, MeetingMarkdownData, NoteMetadata, SaveOutcome, SaveTranscriptRequest, SpeakerNames, TranscriptSegment,
     generate_meeting_markdown, save_meeting_markdown, save_meeting_markdown_to_folder,
     update_meeting_markdown_with_summary,
 };
@@ -61,10 +72,10 @@ pub async fn open_meeting_file(path: String) -> Result<MeetingPrep, String> {
 /// * `request` - Contains file path, transcript segments, and options
 ///
 /// # Returns
-/// * `Ok(())` - Success
+/// * `Ok(SaveOutcome)` - Whether concurrent edits were merged and where a conflict copy went
 /// * `Err(String)` - Error message if save fails
 #[tauri::command]
-pub async fn save_meeting_transcript(request: SaveTranscriptRequest) -> Result<(), String> {
+pub async fn save_meeting_transcript(request: SaveTranscriptRequest) -> Result<SaveOutcome, String> {
     log::info!("Saving transcript to: {}", request.file_path);
 
     let path = std::path::Path::new(&request.file_path);
@@ -121,7 +132,7 @@ pub async fn save_meeting_markdown_file(
 /// Find the markdown file in a meeting folder
 ///
 /// Looks for a .md file in the folder, excluding common system files
-fn find_markdown_file_in_folder(folder_path: &std::path::Path) -> Option<std::path::PathBuf> {
+pub(crate) fn find_markdown_file_in_folder(folder_path: &std::path::Path) -> Option<std::path::PathBuf> {
     if !folder_path.is_dir() {
         return None;
     }
@@ -146,12 +157,19 @@ fn find_markdown_file_in_folder(folder_path: &std::path::Path) -> Option<std::pa
 /// # Arguments
 /// * `path` - Path to the existing markdown file OR folder containing the markdown file
 /// * `summary` - Summary markdown content to insert
+/// * `action_items` - Optional structured action items to merge as Obsidian Tasks checkboxes
+/// * `summary_template` - Optional template id, written to the `summary_template` frontmatter field
 ///
 /// # Returns
 /// * `Ok(())` - Success
 /// * `Err(String)` - Error message if update fails
 #[tauri::command]
-pub async fn update_meeting_summary(path: String, summary: String) -> Result<(), String> {
+pub async fn update_meeting_summary(
+    path: String,
+    summary: String,
+    action_items: Option<Vec<ActionItem>>,
+    summary_template: Option<String>,
+) -> Result<(), String> {
     log::info!("Updating meeting markdown with summary: {}", path);
 
     let input_path = std::path::Path::new(&path);
@@ -182,9 +200,48 @@ pub async fn update_meeting_summary(path: String, summary: String) -> Result<(),
 
     log::info!("Found markdown file: {}", md_path.display());
 
-    update_meeting_markdown_with_summary(&md_path, &summary)
+    let frontmatter: Vec<(&str, frontmatter::FrontmatterValue)> = summary_template
+        .filter(|t| !t.trim().is_empty())
+        .map(|t| ("summary_template", frontmatter::FrontmatterValue::Scalar(t)))
+        .into_iter()
+        .collect();
+    update_meeting_markdown_with_summary(&md_path, &summary, &frontmatter)
+        .await
+        .map_err(|e| format!("Failed to update summary: {}", e))?;
+
+    if let Some(items) = action_items {
+        writer::update_action_items_in_file(&md_path, &items)
+            .await
+            .map_err(|e| format!("Failed to update action items: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Tauri command to merge action items into a meeting note
+///
+/// Items are written to the note's Action Items section as Obsidian Tasks
+/// checkboxes, de-duplicated against existing tasks (checked state is kept).
+///
+/// # Arguments
+/// * `path` - Path to the meeting markdown file (e.g. the prep note)
+/// * `action_items` - Structured action items from the summary
+///
+/// # Returns
+/// * `Ok(usize)` - Number of newly added tasks
+/// * `Err(String)` - Error message if the merge fails
+#[tauri::command]
+pub async fn merge_meeting_action_items(path: String, action_items: Vec<ActionItem>) -> Result<usize, String> {
+    log::info!("Merging {} action items into: {}", action_items.len(), path);
+
+    let path_buf = std::path::PathBuf::from(&path);
+    if !path_buf.exists() {
+        return Err(format!("File not found: {}", path));
+    }
+
+    writer::update_action_items_in_file(&path_buf, &action_items)
         .await
-        .map_err(|e| format!("Failed to update summary: {}", e))
+        .map_err(|e| format!("Failed to merge action items: {}", e))
 }
 
 #[cfg(test)]
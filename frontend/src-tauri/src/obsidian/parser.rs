@@ -6,13 +6,15 @@
 //! - Configurable sections via section_labels.yaml
 //!
 //! Section labels are loaded from an embedded YAML config file,
-//! supporting multiple languages and alternative labels.
+//! supporting multiple languages and alternative labels. User and vault
+//! override files can add labels and custom fields at runtime (see `labels`).
 
 use crate::obsidian::types::{MeetingFrontmatter, MeetingPrep};
 use once_cell::sync::Lazy;
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
 
 // Embed the section labels config at compile time
 const SECTION_LABELS_YAML: &str = include_str!("section_labels.yaml");
@@ -51,6 +53,28 @@ struct SectionDefinition {
     labels: HashMap<String, Vec<String>>,
 }
 
+/// User/vault override file: same shape as section_labels.yaml, but every
+/// section key except `field` is optional. Labels are added to the built-in
+/// ones; `levels` and `content_type` replace them.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SectionOverrideConfig {
+    #[serde(default)]
+    sections: Vec<SectionOverride>,
+    #[serde(default)]
+    frontmatter_aliases: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SectionOverride {
+    field: String,
+    levels: Option<Vec<usize>>,
+    content_type: Option<ContentType>,
+    #[serde(default)]
+    labels: HashMap<String, Vec<String>>,
+}
+
 /// How to parse section content
 #[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -70,15 +94,25 @@ struct SectionMatcher {
     patterns: Vec<String>,
 }
 
-/// Lazy-loaded configuration
-static CONFIG: Lazy<Result<Vec<SectionMatcher>, String>> = Lazy::new(|| {
-    load_and_compile_config()
+/// Fields with a dedicated `MeetingPrep` member; everything else goes to `custom_fields`
+const BUILT_IN_FIELDS: &[&str] = &["agenda", "notes", "action_items", "goals", "context"];
+
+/// Active section matchers: the built-in config merged with any override files.
+/// Swapped as a whole on reload, so parsing never sees a half-applied config.
+static CONFIG: Lazy<RwLock<Arc<Vec<SectionMatcher>>>> = Lazy::new(|| {
+    RwLock::new(Arc::new(compile_config(builtin_config())))
+});
+
+/// Active frontmatter key aliases (field -> accepted keys), swapped together with `CONFIG`
+static FRONTMATTER_ALIASES: Lazy<RwLock<Arc<HashMap<String, Vec<String>>>>> = Lazy::new(|| {
+    RwLock::new(Arc::new(builtin_config().frontmatter_aliases))
 });
 
-fn load_and_compile_config() -> Result<Vec<SectionMatcher>, String> {
-    let config: SectionConfig = serde_yaml::from_str(SECTION_LABELS_YAML)
-        .map_err(|e| format!("Failed to parse section_labels.yaml: {}", e))?;
+fn builtin_config() -> SectionConfig {
+    serde_yaml::from_str(SECTION_LABELS_YAML).expect("Failed to parse built-in section_labels.yaml")
+}
 
+fn compile_config(config: SectionConfig) -> Vec<SectionMatcher> {
     let mut matchers = Vec::new();
 
     for section in config.sections {
@@ -90,7 +124,10 @@ fn load_and_compile_config() -> Result<Vec<SectionMatcher>, String> {
                 for level in &section.levels {
                     let prefix = "#".repeat(*level);
                     // Store lowercase for case-insensitive matching
-                    patterns.push(format!("{} {}", prefix, label.to_lowercase()));
+                    let pattern = format!("{} {}", prefix, label.trim().to_lowercase());
+                    if !patterns.contains(&pattern) {
+                        patterns.push(pattern);
+                    }
                 }
             }
         }
@@ -103,17 +140,145 @@ fn load_and_compile_config() -> Result<Vec<SectionMatcher>, String> {
         });
     }
 
-    Ok(matchers)
+    matchers
+}
+
+/// Parse and validate an override file, with errors that point at the offending entry
+fn parse_override_config(yaml: &str, source: &str) -> Result<SectionOverrideConfig, ParseError> {
+    if yaml.trim().is_empty() {
+        return Ok(SectionOverrideConfig::default());
+    }
+
+    let config: SectionOverrideConfig = serde_yaml::from_str(yaml)
+        .map_err(|e| ParseError::ConfigError(format!("{}: {}", source, e)))?;
+
+    let builtin = builtin_config();
+    for (i, section) in config.sections.iter().enumerate() {
+        let context = format!("{}: sections[{}] (field '{}')", source, i, section.field);
+        let valid_name = section
+            .field
+            .chars()
+            .next()
+            .map_or(false, |c| c.is_ascii_lowercase())
+            && section
+                .field
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !valid_name {
+            return Err(ParseError::ConfigError(format!(
+                "{}: field names must be lowercase snake_case (e.g. 'parking_lot')",
+                context
+            )));
+        }
+        if let Some(levels) = &section.levels {
+            if levels.is_empty() || levels.iter().any(|level| !(1..=6).contains(level)) {
+                return Err(ParseError::ConfigError(format!(
+                    "{}: levels must be a non-empty list of heading levels between 1 and 6",
+                    context
+                )));
+            }
+        }
+        for (lang, labels) in &section.labels {
+            if labels.iter().any(|label| label.trim().is_empty()) {
+                return Err(ParseError::ConfigError(format!(
+                    "{}: labels.{} contains an empty label",
+                    context, lang
+                )));
+            }
+        }
+
+        let is_new = !builtin.sections.iter().any(|s| s.field == section.field);
+        if is_new {
+            if section.content_type.is_none() {
+                return Err(ParseError::ConfigError(format!(
+                    "{}: new fields need a content_type (bullet, numbered, checkbox or raw)",
+                    context
+                )));
+            }
+            if section.labels.values().all(|labels| labels.is_empty()) {
+                return Err(ParseError::ConfigError(format!(
+                    "{}: new fields need at least one label",
+                    context
+                )));
+            }
+        }
+    }
+
+    Ok(config)
+}
+
+/// Merge an override file into the config
+fn merge_override_config(config: &mut SectionConfig, overrides: SectionOverrideConfig) {
+    for section in overrides.sections {
+        match config.sections.iter_mut().find(|s| s.field == section.field) {
+            Some(existing) => {
+                if let Some(levels) = section.levels {
+                    existing.levels = levels;
+                }
+                if let Some(content_type) = section.content_type {
+                    existing.content_type = content_type;
+                }
+                for (lang, labels) in section.labels {
+                    existing.labels.entry(lang).or_default().extend(labels);
+                }
+            }
+            None => config.sections.push(SectionDefinition {
+                field: section.field,
+                levels: section.levels.unwrap_or_else(|| vec![2, 3]),
+                // Presence is checked by parse_override_config
+                content_type: section.content_type.unwrap_or(ContentType::Bullet),
+                labels: section.labels,
+            }),
+        }
+    }
+
+    for (field, aliases) in overrides.frontmatter_aliases {
+        config.frontmatter_aliases.entry(field).or_default().extend(aliases);
+    }
 }
 
-/// Get the section matchers, panicking if config failed to load
-fn get_matchers() -> &'static Vec<SectionMatcher> {
-    CONFIG.as_ref().expect("Failed to load section config")
+/// Validate override YAML without applying it
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - All fields the merged configuration would contain
+/// * `Err(ParseError)` - Describes the first problem found
+pub fn validate_section_overrides(yaml: &str) -> Result<Vec<String>, ParseError> {
+    let overrides = parse_override_config(yaml, "section labels")?;
+    let mut config = builtin_config();
+    merge_override_config(&mut config, overrides);
+    Ok(config.sections.into_iter().map(|s| s.field).collect())
+}
+
+/// Rebuild the active section config from the built-in labels and override files
+///
+/// Files are applied in order (later files win); missing files are skipped.
+/// If any file is invalid the current config is kept and the error returned.
+///
+/// # Returns
+/// * `Ok(Vec<PathBuf>)` - Override files that were applied
+pub fn reload_section_config(override_files: &[PathBuf]) -> Result<Vec<PathBuf>, ParseError> {
+    let mut config = builtin_config();
+    let mut applied = Vec::new();
+
+    for path in override_files {
+        if !path.is_file() {
+            continue;
+        }
+        let yaml = std::fs::read_to_string(path)?;
+        let overrides = parse_override_config(&yaml, &path.display().to_string())?;
+        merge_override_config(&mut config, overrides);
+        applied.push(path.clone());
+    }
+
+    let aliases = std::mem::take(&mut config.frontmatter_aliases);
+    *CONFIG.write().unwrap() = Arc::new(compile_config(config));
+    *FRONTMATTER_ALIASES.write().unwrap() = Arc::new(aliases);
+    Ok(applied)
 }
 
-/// Find a matcher by field name
-fn get_matcher_for_field(field: &str) -> Option<&'static SectionMatcher> {
-    get_matchers().iter().find(|m| m.field == field)
+/// Get the active section matchers
+fn get_matchers() -> Arc<Vec<SectionMatcher>> {
+    CONFIG.read().unwrap().clone()
 }
 
 // ============================================================================
@@ -140,21 +305,20 @@ pub fn parse_meeting_file(path: &Path, content: &str) -> Result<MeetingPrep, Par
     let body = get_body_after_frontmatter(content);
 
     // Extract each configured section
-    if let Some(matcher) = get_matcher_for_field("agenda") {
-        prep.agenda = extract_section_items(&body, matcher);
-    }
-    if let Some(matcher) = get_matcher_for_field("notes") {
-        prep.notes = extract_section_items(&body, matcher);
-    }
-    if let Some(matcher) = get_matcher_for_field("action_items") {
-        prep.action_items = extract_section_items(&body, matcher);
-    }
-    if let Some(matcher) = get_matcher_for_field("goals") {
-        prep.goals = extract_section_items(&body, matcher);
-    }
-    if let Some(matcher) = get_matcher_for_field("context") {
+    for matcher in get_matchers().iter() {
         let items = extract_section_items(&body, matcher);
-        prep.context = items.join("\n");
+        match matcher.field.as_str() {
+            "agenda" => prep.agenda = items,
+            "notes" => prep.notes = items,
+            "action_items" => prep.action_items = items,
+            "goals" => prep.goals = items,
+            "context" => prep.context = items.join("\n"),
+            field => {
+                if !items.is_empty() {
+                    prep.custom_fields.insert(field.to_string(), items);
+                }
+            }
+        }
     }
 
     Ok(prep)
@@ -283,7 +447,13 @@ fn parse_frontmatter(content: &str) -> Result<MeetingFrontmatter, ParseError> {
             }
 
             // Parse YAML
-            serde_yaml::from_str(yaml_content).map_err(|e| {
+            let mut value: serde_yaml::Value = serde_yaml::from_str(yaml_content).map_err(|e| {
+                ParseError::FrontmatterError(format!("YAML parse error: {}", e))
+            })?;
+            if let Some(mapping) = value.as_mapping_mut() {
+                apply_frontmatter_aliases(mapping, &FRONTMATTER_ALIASES.read().unwrap());
+            }
+            serde_yaml::from_value(value).map_err(|e| {
                 ParseError::FrontmatterError(format!("YAML parse error: {}", e))
             })
         }
@@ -294,6 +464,27 @@ fn parse_frontmatter(content: &str) -> Result<MeetingFrontmatter, ParseError> {
     }
 }
 
+/// Rename aliased frontmatter keys (e.g. `datum`, `participants`) to their field name
+///
+/// Keys match case-insensitively. A key already named after the field wins over
+/// its aliases; otherwise the first alias present in the alias list is used.
+fn apply_frontmatter_aliases(mapping: &mut serde_yaml::Mapping, aliases: &HashMap<String, Vec<String>>) {
+    for (field, keys) in aliases {
+        let field_key = serde_yaml::Value::String(field.clone());
+        if mapping.contains_key(&field_key) {
+            continue;
+        }
+        let found = keys.iter().find_map(|alias| {
+            mapping.iter().map(|(key, _)| key).find(|key| key.as_str().map_or(false, |k| k.eq_ignore_ascii_case(alias.trim()))).cloned()
+        });
+        if let Some(key) = found {
+            if let Some(value) = mapping.remove(&key) {
+                mapping.insert(field_key, value);
+            }
+        }
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -415,7 +606,8 @@ fn extract_numbered_item(line: &str) -> Option<String> {
 
 /// Get all supported labels for a field (useful for documentation/UI)
 pub fn get_labels_for_field(field: &str) -> Vec<String> {
-    if let Some(matcher) = get_matcher_for_field(field) {
+    let matchers = get_matchers();
+    if let Some(matcher) = matchers.iter().find(|m| m.field == field) {
         // Extract unique labels from patterns (remove heading prefixes)
         let mut labels: Vec<String> = matcher.patterns
             .iter()
@@ -437,6 +629,15 @@ pub fn get_configured_fields() -> Vec<String> {
     get_matchers().iter().map(|m| m.field.clone()).collect()
 }
 
+/// Get configured fields that are surfaced in `MeetingPrep.custom_fields`
+pub fn get_custom_fields() -> Vec<String> {
+    get_matchers()
+        .iter()
+        .map(|m| m.field.clone())
+        .filter(|field| !BUILT_IN_FIELDS.contains(&field.as_str()))
+        .collect()
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -653,6 +854,30 @@ attendees:
 
     // ===== Edge Case Tests =====
 
+    #[test]
+    fn test_parse_frontmatter_aliases() {
+        let content = "---\nDatum: 2026-01-22\ndeelnemers:\n  - Anna\n  - Bram\nstart_time: \"10:00\"\n---\n# Overleg\n";
+        let fm = parse_frontmatter(content).unwrap();
+        assert_eq!(fm.date, Some("2026-01-22".to_string()));
+        assert_eq!(fm.attendees, Some(vec!["Anna".to_string(), "Bram".to_string()]));
+        assert_eq!(fm.time_start, Some("10:00".to_string()));
+    }
+
+    #[test]
+    fn test_frontmatter_field_name_wins_over_alias() {
+        let mut mapping: serde_yaml::Mapping =
+            serde_yaml::from_str("date: 2026-01-22\nfecha: 2025-12-31\nWho: [Ana]\n").unwrap();
+        let aliases = HashMap::from([
+            ("date".to_string(), vec!["fecha".to_string()]),
+            ("attendees".to_string(), vec!["who".to_string()]),
+        ]);
+        apply_frontmatter_aliases(&mut mapping, &aliases);
+
+        let fm: MeetingFrontmatter = serde_yaml::from_value(serde_yaml::Value::Mapping(mapping)).unwrap();
+        assert_eq!(fm.date, Some("2026-01-22".to_string()));
+        assert_eq!(fm.attendees, Some(vec!["Ana".to_string()]));
+    }
+
     #[test]
     fn test_parse_frontmatter_missing() {
         let content = "# Just a title\nSome content";
@@ -741,5 +966,70 @@ date: 2026-01-22
         assert_eq!(extract_numbered_item("Regular text"), None);
         assert_eq!(extract_numbered_item("- Bullet point"), None);
     }
-}
 
+    // ===== Override Config Tests =====
+
+    const OVERRIDE_YAML: &str = r#"
+sections:
+  - field: agenda
+    labels:
+      en:
+        - Talking Points
+  - field: parking_lot
+    content_type: bullet
+    labels:
+      en:
+        - Parking Lot
+"#;
+
+    #[test]
+    fn test_override_merges_labels_and_adds_custom_field() {
+        let mut config = builtin_config();
+        merge_override_config(&mut config, parse_override_config(OVERRIDE_YAML, "test").unwrap());
+        let matchers = compile_config(config);
+
+        let agenda = matchers.iter().find(|m| m.field == "agenda").unwrap();
+        assert!(agenda.patterns.contains(&"## talking points".to_string()));
+        assert!(agenda.patterns.contains(&"## agenda".to_string()), "built-in labels are kept");
+
+        let parking_lot = matchers.iter().find(|m| m.field == "parking_lot").unwrap();
+        assert_eq!(parking_lot.levels, vec![2, 3]);
+        let body = "## Parking Lot\n- Hiring plan\n- Office move\n";
+        assert_eq!(
+            extract_section_items(body, parking_lot),
+            vec!["Hiring plan".to_string(), "Office move".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_override_validation_errors() {
+        let err = parse_override_config("sections:\n  - field: Parking Lot\n    content_type: raw\n    labels: {en: [PL]}\n", "test.yaml")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("sections[0]") && err.contains("snake_case"), "{}", err);
+
+        let err = parse_override_config("sections:\n  - field: agenda\n    levels: [7]\n", "test.yaml")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("between 1 and 6"), "{}", err);
+
+        let err = parse_override_config("sections:\n  - field: risks\n    labels: {en: [Risks]}\n", "test.yaml")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("content_type"), "{}", err);
+
+        let err = parse_override_config("sections:\n  - field: agenda\n    lables: {}\n", "test.yaml")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("test.yaml"), "{}", err);
+        assert!(err.contains("lables"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_section_overrides_lists_fields() {
+        let fields = validate_section_overrides(OVERRIDE_YAML).unwrap();
+        assert!(fields.contains(&"agenda".to_string()));
+        assert!(fields.contains(&"parking_lot".to_string()));
+        assert!(validate_section_overrides("").is_ok());
+    }
+}
@@ -0,0 +1,426 @@
+//! Prep note creation from an Obsidian template
+//!
+//! Creates a new meeting note in the configured vault folder so ad-hoc calls get
+//! a proper prep file instead of ending up as orphan recordings. Templates use the
+//! Obsidian core template variables (`{{date}}`, `{{time}}`, `{{title}}`, with
+//! optional moment.js formats such as `{{date:YYYY-MM-DD}}`) plus `{{attendees}}`.
+//! The frontmatter fields the parser relies on are filled in afterwards.
+
+use super::frontmatter::{ensure_frontmatter_fields, FrontmatterValue};
+use super::parser::parse_meeting_file;
+use super::speakers::normalize_person_name;
+use super::types::MeetingPrep;
+use super::vault::{load_vault_root, VAULT_STORE};
+use super::writer::{atomic_write, to_wikilink};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const MEETINGS_FOLDER_KEY: &str = "meetings_folder";
+const PREP_TEMPLATE_KEY: &str = "prep_template";
+
+/// Used when no template is configured
+pub const DEFAULT_PREP_TEMPLATE: &str = "---
+tags: meeting
+date: {{date}}
+time_start:
+time_end:
+attendees:
+status: scheduled
+---
+
+# {{title}}
+
+## Agenda
+-
+
+## Notes
+-
+
+## Action Items
+- [ ]
+";
+
+/// Matches `{{name}}` and `{{name:format}}`
+static TEMPLATE_VAR_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*(\w+)\s*(?::([^}]*))?\}\}").unwrap());
+
+/// Where prep notes are created and which template they use
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PrepNoteSettings {
+    /// Folder for new notes (absolute, or relative to the vault root)
+    pub meetings_folder: Option<String>,
+    /// Template file (absolute, or relative to the vault root)
+    pub template_path: Option<String>,
+}
+
+/// Request to create a prep note
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreatePrepNoteRequest {
+    pub title: String,
+    /// `YYYY-MM-DD`, defaults to today
+    pub date: Option<String>,
+    /// `HH:MM`, defaults to now
+    pub time: Option<String>,
+    #[serde(default)]
+    pub attendees: Vec<String>,
+    /// Overrides the configured meetings folder
+    pub folder: Option<String>,
+    /// Overrides the configured template
+    pub template_path: Option<String>,
+}
+
+/// Values substituted into the template
+#[derive(Debug, Clone)]
+pub struct PrepNoteValues {
+    pub title: String,
+    pub start: NaiveDateTime,
+    pub attendees: Vec<String>,
+}
+
+// ============================================================================
+// Template rendering
+// ============================================================================
+
+/// Convert a moment.js format (as used by Obsidian templates) to a chrono format
+pub fn moment_to_chrono(format: &str) -> String {
+    const TOKENS: &[(&str, &str)] = &[
+        ("YYYY", "%Y"),
+        ("YY", "%y"),
+        ("MMMM", "%B"),
+        ("MMM", "%b"),
+        ("MM", "%m"),
+        ("M", "%-m"),
+        ("DD", "%d"),
+        ("D", "%-d"),
+        ("dddd", "%A"),
+        ("ddd", "%a"),
+        ("HH", "%H"),
+        ("H", "%-H"),
+        ("hh", "%I"),
+        ("h", "%-I"),
+        ("mm", "%M"),
+        ("ss", "%S"),
+        ("A", "%p"),
+        ("a", "%P"),
+    ];
+
+    let mut result = String::new();
+    let mut rest = format;
+    'outer: while !rest.is_empty() {
+        // [literal text] is copied as-is
+        if let Some(stripped) = rest.strip_prefix('[') {
+            if let Some(end) = stripped.find(']') {
+                result.push_str(&stripped[..end].replace('%', "%%"));
+                rest = &stripped[end + 1..];
+                continue;
+            }
+        }
+        for (token, chrono_format) in TOKENS {
+            if let Some(stripped) = rest.strip_prefix(token) {
+                result.push_str(chrono_format);
+                rest = stripped;
+                continue 'outer;
+            }
+        }
+        let c = rest.chars().next().unwrap();
+        if c == '%' {
+            result.push_str("%%");
+        } else {
+            result.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    result
+}
+
+fn attendee_links(attendees: &[String]) -> Vec<String> {
+    attendees
+        .iter()
+        .map(|attendee| normalize_person_name(attendee))
+        .filter(|attendee| !attendee.is_empty())
+        .map(|attendee| to_wikilink(&attendee))
+        .collect()
+}
+
+/// Substitute template variables; unknown variables are left untouched
+pub fn render_template(template: &str, values: &PrepNoteValues) -> String {
+    TEMPLATE_VAR_RE
+        .replace_all(template, |caps: &regex::Captures| {
+            let format = caps.get(2).map(|m| m.as_str().trim()).filter(|f| !f.is_empty());
+            match caps[1].to_ascii_lowercase().as_str() {
+                "title" => values.title.clone(),
+                "date" => values
+                    .start
+                    .format(&moment_to_chrono(format.unwrap_or("YYYY-MM-DD")))
+                    .to_string(),
+                "time" => values
+                    .start
+                    .format(&moment_to_chrono(format.unwrap_or("HH:mm")))
+                    .to_string(),
+                "attendees" => attendee_links(&values.attendees).join(", "),
+                _ => caps[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Render the template and fill in the frontmatter the parser expects
+pub fn build_prep_note(template: &str, values: &PrepNoteValues) -> String {
+    let rendered = render_template(template, values);
+    let mut fields = vec![
+        ("tags", FrontmatterValue::Scalar("meeting".to_string())),
+        ("date", FrontmatterValue::Scalar(values.start.format("%Y-%m-%d").to_string())),
+        ("time_start", FrontmatterValue::Scalar(values.start.format("%Y-%m-%dT%H:%M:%S").to_string())),
+        ("status", FrontmatterValue::Scalar("scheduled".to_string())),
+    ];
+    let attendees = attendee_links(&values.attendees);
+    if !attendees.is_empty() {
+        fields.push(("attendees", FrontmatterValue::List(attendees)));
+    }
+    ensure_frontmatter_fields(&rendered, &fields)
+}
+
+/// File name for a new note: `YYYY-MM-DD Title.md` without characters Obsidian rejects
+pub fn prep_note_filename(values: &PrepNoteValues) -> String {
+    let title: String = values
+        .title
+        .chars()
+        .filter(|c| !matches!(c, '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']'))
+        .collect();
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    let title = if title.is_empty() { "Meeting".to_string() } else { title };
+    format!("{} {}.md", values.start.format("%Y-%m-%d"), title)
+}
+
+/// First free path in `folder` for `filename`, appending ` 2`, ` 3`, ... if taken
+fn unique_path(folder: &Path, filename: &str) -> PathBuf {
+    let candidate = folder.join(filename);
+    if !candidate.exists() {
+        return candidate;
+    }
+    let stem = filename.trim_end_matches(".md");
+    (2..)
+        .map(|n| folder.join(format!("{} {}.md", stem, n)))
+        .find(|path| !path.exists())
+        .unwrap()
+}
+
+// ============================================================================
+// Settings
+// ============================================================================
+
+fn resolve_in_vault(vault_root: Option<&Path>, path: &str) -> PathBuf {
+    let path = PathBuf::from(path.trim());
+    match vault_root {
+        Some(root) if path.is_relative() => root.join(path),
+        _ => path,
+    }
+}
+
+pub fn load_prep_note_settings<R: Runtime>(app: &AppHandle<R>) -> PrepNoteSettings {
+    let Ok(store) = app.store(VAULT_STORE) else {
+        return PrepNoteSettings::default();
+    };
+    let get = |key: &str| {
+        store
+            .get(key)
+            .and_then(|value| value.as_str().map(str::to_string))
+            .filter(|value| !value.trim().is_empty())
+    };
+    PrepNoteSettings {
+        meetings_folder: get(MEETINGS_FOLDER_KEY),
+        template_path: get(PREP_TEMPLATE_KEY),
+    }
+}
+
+/// Tauri command to get the prep note settings
+#[tauri::command]
+pub async fn get_prep_note_settings<R: Runtime>(app: AppHandle<R>) -> Result<PrepNoteSettings, String> {
+    Ok(load_prep_note_settings(&app))
+}
+
+/// Tauri command to save the prep note settings
+#[tauri::command]
+pub async fn set_prep_note_settings<R: Runtime>(
+    app: AppHandle<R>,
+    settings: PrepNoteSettings,
+) -> Result<(), String> {
+    let store = app
+        .store(VAULT_STORE)
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    for (key, value) in [
+        (MEETINGS_FOLDER_KEY, &settings.meetings_folder),
+        (PREP_TEMPLATE_KEY, &settings.template_path),
+    ] {
+        match value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            Some(value) => store.set(key, value.to_string()),
+            None => {
+                store.delete(key);
+            }
+        }
+    }
+    store
+        .save()
+        .map_err(|e| format!("Failed to save store to disk: {}", e))
+}
+
+// ============================================================================
+// Tauri command
+// ============================================================================
+
+/// Tauri command to create a meeting prep note from the configured template
+///
+/// # Returns
+/// * `Ok(MeetingPrep)` - The parsed new note (its `file_path` is the created file)
+/// * `Err(String)` - Error message if the folder, template or values are invalid
+#[tauri::command]
+pub async fn create_prep_note<R: Runtime>(
+    app: AppHandle<R>,
+    request: CreatePrepNoteRequest,
+) -> Result<MeetingPrep, String> {
+    let title = request.title.trim().to_string();
+    if title.is_empty() {
+        return Err("Meeting title cannot be empty".to_string());
+    }
+
+    let now = chrono::Local::now().naive_local();
+    let date = match request.date.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid date '{}': {}", date, e))?,
+        None => now.date(),
+    };
+    let time = match request.time.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        Some(time) => NaiveTime::parse_from_str(time, "%H:%M")
+            .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M:%S"))
+            .map_err(|e| format!("Invalid time '{}': {}", time, e))?,
+        None => now.time().with_nanosecond(0).unwrap_or(now.time()),
+    };
+    let values = PrepNoteValues {
+        title,
+        start: date.and_time(time),
+        attendees: request.attendees,
+    };
+
+    let vault_root = load_vault_root(&app);
+    let settings = load_prep_note_settings(&app);
+
+    let folder = request
+        .folder
+        .or(settings.meetings_folder)
+        .map(|folder| resolve_in_vault(vault_root.as_deref(), &folder))
+        .or_else(|| vault_root.clone())
+        .ok_or_else(|| "No meetings folder or Obsidian vault configured".to_string())?;
+    tokio::fs::create_dir_all(&folder)
+        .await
+        .map_err(|e| format!("Failed to create folder {}: {}", folder.display(), e))?;
+
+    let template = match request.template_path.or(settings.template_path) {
+        Some(path) => {
+            let path = resolve_in_vault(vault_root.as_deref(), &path);
+            tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|e| format!("Failed to read template {}: {}", path.display(), e))?
+        }
+        None => DEFAULT_PREP_TEMPLATE.to_string(),
+    };
+
+    let content = build_prep_note(&template, &values);
+    let path = unique_path(&folder, &prep_note_filename(&values));
+
+    atomic_write(&path, &content)
+        .await
+        .map_err(|e| format!("Failed to write prep note: {}", e))?;
+    log::info!("Created prep note: {}", path.display());
+
+    parse_meeting_file(&path, &content).map_err(|e| format!("Failed to parse prep note: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> PrepNoteValues {
+        PrepNoteValues {
+            title: "Roadmap Review".to_string(),
+            start: NaiveDate::from_ymd_opt(2026, 1, 23)
+                .unwrap()
+                .and_hms_opt(14, 30, 0)
+                .unwrap(),
+            attendees: vec!["Attendee A".to_string(), "[[Attendee B]]".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_moment_to_chrono() {
+        assert_eq!(moment_to_chrono("YYYY-MM-DD"), "%Y-%m-%d");
+        assert_eq!(moment_to_chrono("dddd, MMMM D"), "%A, %B %-d");
+        assert_eq!(moment_to_chrono("HH:mm [uur]"), "%H:%M uur");
+    }
+
+    #[test]
+    fn test_render_template() {
+        let rendered = render_template(
+            "{{title}} on {{date}} at {{time}} ({{date:dddd}}) with {{attendees}} {{unknown}}",
+            &values(),
+        );
+        assert_eq!(
+            rendered,
+            "Roadmap Review on 2026-01-23 at 14:30 (Friday) with [[Attendee A]], [[Attendee B]] {{unknown}}"
+        );
+    }
+
+    #[test]
+    fn test_build_prep_note_default_template() {
+        let content = build_prep_note(DEFAULT_PREP_TEMPLATE, &values());
+        let prep = parse_meeting_file(Path::new("note.md"), &content).unwrap();
+
+        assert_eq!(prep.title, "Roadmap Review");
+        assert_eq!(prep.frontmatter.date.as_deref(), Some("2026-01-23"));
+        assert_eq!(prep.frontmatter.time_start.as_deref(), Some("2026-01-23T14:30:00"));
+        assert_eq!(prep.frontmatter.status.as_deref(), Some("scheduled"));
+        assert_eq!(
+            prep.frontmatter.attendees,
+            Some(vec!["[[Attendee A]]".to_string(), "[[Attendee B]]".to_string()])
+        );
+        assert!(content.contains("## Agenda"));
+    }
+
+    #[test]
+    fn test_build_prep_note_keeps_template_values() {
+        let template = "---\n# team template\nstatus: draft\nproject: Apollo\n---\n# {{title}}\n";
+        let content = build_prep_note(template, &values());
+
+        assert!(content.contains("# team template"));
+        assert!(content.contains("status: draft"));
+        assert!(content.contains("project: Apollo"));
+        assert!(content.contains("tags: meeting"));
+        assert!(content.ends_with("---\n# Roadmap Review\n"));
+    }
+
+    #[test]
+    fn test_build_prep_note_without_frontmatter() {
+        let content = build_prep_note("# {{title}}\n", &values());
+        assert!(content.starts_with("---\ntags: meeting\ndate: 2026-01-23\n"));
+        assert!(content.ends_with("---\n# Roadmap Review\n"));
+    }
+
+    #[test]
+    fn test_prep_note_filename() {
+        let mut v = values();
+        v.title = "Q1: plan / review?".to_string();
+        assert_eq!(prep_note_filename(&v), "2026-01-23 Q1 plan review.md");
+    }
+
+    #[test]
+    fn test_unique_path() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.md"), "").unwrap();
+        assert_eq!(unique_path(dir.path(), "a.md"), dir.path().join("a 2.md"));
+        assert_eq!(unique_path(dir.path(), "b.md"), dir.path().join("b.md"));
+    }
+}
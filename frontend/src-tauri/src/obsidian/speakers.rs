@@ -0,0 +1,424 @@
+//! Speaker naming for meeting transcripts
+//!
+//! Diarized transcripts only know anonymous labels ("Speaker 1"). This module
+//! lets the user map those labels to people, persists the map per meeting in
+//! SQLite, and rewrites the transcript sections of both the standalone meeting
+//! markdown and the merged prep file so named speakers become `[[Person Name]]`
+//! wikilinks.
+
+use super::parser::parse_meeting_file;
+use super::writer::{extract_transcript_speakers, to_wikilink, update_speaker_names_in_file};
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::speaker::MeetingSpeakersRepository;
+use crate::state::AppState;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Matches the speaker prefix stored in diarized transcript text: `[Speaker 1]: ...`
+static DB_SPEAKER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^\[(Speaker [^\]]+)\]:").unwrap());
+
+/// A speaker label of a meeting and its assigned name, if any
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerEntry {
+    /// Diarized label (e.g., "Speaker 1")
+    pub label: String,
+    /// Assigned person name (without `[[...]]`)
+    pub display_name: Option<String>,
+}
+
+/// Speakers of a meeting together with name candidates for the picker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingSpeakers {
+    pub meeting_id: String,
+    pub speakers: Vec<SpeakerEntry>,
+    /// Attendees from the prep note frontmatter
+    pub candidates: Vec<String>,
+}
+
+/// Request to save the speaker map of a meeting
+#[derive(Debug, Clone, Deserialize)]
+pub struct SaveMeetingSpeakersRequest {
+    pub meeting_id: String,
+    /// Speaker label -> person name; an empty name removes the mapping
+    pub speakers: HashMap<String, String>,
+    /// Additional notes to rewrite (e.g. the Obsidian prep file the transcript was merged into)
+    #[serde(default)]
+    pub note_paths: Vec<String>,
+}
+
+/// Outcome of saving a speaker map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveMeetingSpeakersResult {
+    pub saved: usize,
+    /// Markdown files whose transcript section was rewritten
+    pub updated_files: Vec<String>,
+    /// Markdown files that could not be rewritten, with the error
+    pub failed_files: Vec<(String, String)>,
+}
+
+/// Split diarized transcript text into speaker turns
+///
+/// `[Speaker 0]: Hi\n[Speaker 1]: Hello` becomes `(Some("Speaker 0"), "Hi")`,
+/// `(Some("Speaker 1"), "Hello")`. Unlabeled lines continue the previous turn;
+/// text without any label is a single turn without a speaker.
+pub fn split_speaker_turns(text: &str) -> Vec<(Option<String>, String)> {
+    let mut turns: Vec<(Option<String>, String)> = Vec::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        match DB_SPEAKER_RE.captures(line) {
+            Some(caps) => {
+                let rest = line[caps.get(0).unwrap().end()..].trim();
+                turns.push((Some(caps[1].to_string()), rest.to_string()));
+            }
+            None => match turns.last_mut() {
+                Some((_, turn)) => {
+                    if !turn.is_empty() {
+                        turn.push(' ');
+                    }
+                    turn.push_str(line);
+                }
+                None => turns.push((None, line.to_string())),
+            },
+        }
+    }
+    turns.retain(|(_, turn)| !turn.is_empty());
+    turns
+}
+
+/// Normalize a person name: strip `[[...]]`, aliases (`[[Name|Alias]]`) and whitespace
+pub fn normalize_person_name(name: &str) -> String {
+    let name = name.trim();
+    let inner = name
+        .strip_prefix("[[")
+        .and_then(|n| n.strip_suffix("]]"))
+        .unwrap_or(name);
+    inner.split('|').next().unwrap_or(inner).trim().to_string()
+}
+
+/// Build the label rewrites needed to go from the old to the new speaker map
+///
+/// - newly named: `Speaker 1` -> `[[Name]]`
+/// - renamed: `[[Old]]` -> `[[New]]` (and the raw label, in case the note was never named)
+/// - un-named: `[[Old]]` -> `Speaker 1`
+///
+/// Several labels may share a name (diarization split one person). Their
+/// transcript lines are indistinguishable once rewritten, so `[[Old]]` is only
+/// rewritten when every label that carried it moves to the same new value;
+/// otherwise those lines keep the old name.
+pub fn build_speaker_replacements(
+    old_names: &HashMap<String, String>,
+    new_names: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut replacements = HashMap::new();
+
+    for (label, name) in new_names {
+        replacements.insert(label.clone(), to_wikilink(name));
+    }
+
+    // Old link -> the value each label that carried it should now show
+    let mut old_link_targets: HashMap<String, Vec<String>> = HashMap::new();
+    for (label, old_name) in old_names {
+        let target = new_names
+            .get(label)
+            .map(|name| to_wikilink(name))
+            .unwrap_or_else(|| label.clone());
+        old_link_targets
+            .entry(to_wikilink(old_name))
+            .or_default()
+            .push(target);
+    }
+
+    for (old_link, mut targets) in old_link_targets {
+        targets.sort();
+        targets.dedup();
+        match targets.as_slice() {
+            [target] if *target != old_link => {
+                replacements.insert(old_link, target.clone());
+            }
+            [_] => {}
+            _ => log::warn!(
+                "Speakers sharing {} were given different names; their existing lines keep the old name",
+                old_link
+            ),
+        }
+    }
+
+    replacements
+}
+
+/// Read attendees from a meeting note's frontmatter
+async fn read_attendee_candidates(note_path: &Path) -> Vec<String> {
+    let content = match tokio::fs::read_to_string(note_path).await {
+        Ok(content) => content,
+        Err(e) => {
+            log::warn!("Failed to read note {}: {}", note_path.display(), e);
+            return Vec::new();
+        }
+    };
+
+    match parse_meeting_file(note_path, &content) {
+        Ok(prep) => prep
+            .frontmatter
+            .attendees
+            .unwrap_or_default()
+            .iter()
+            .map(|attendee| normalize_person_name(attendee))
+            .filter(|attendee| !attendee.is_empty())
+            .collect(),
+        Err(e) => {
+            log::warn!("Failed to parse note {}: {}", note_path.display(), e);
+            Vec::new()
+        }
+    }
+}
+
+/// Resolve the standalone meeting markdown written into the recording folder
+async fn find_meeting_markdown(state: &AppState, meeting_id: &str) -> Result<Option<PathBuf>, String> {
+    let meeting = MeetingsRepository::get_meeting_metadata(state.db_manager.pool(), meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+
+    Ok(meeting
+        .folder_path
+        .as_deref()
+        .and_then(|folder| super::find_markdown_file_in_folder(Path::new(folder))))
+}
+
+/// Tauri command to list the speakers of a meeting
+///
+/// Speaker labels are collected from the saved map, the diarized transcript
+/// rows and the transcript sections of the meeting markdown / `note_path`.
+/// Attendees from the note's frontmatter are offered as name candidates.
+#[tauri::command]
+pub async fn get_meeting_speakers(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    note_path: Option<String>,
+) -> Result<MeetingSpeakers, String> {
+    log::info!("Loading speakers for meeting: {}", meeting_id);
+
+    let pool = state.db_manager.pool();
+    let names = MeetingSpeakersRepository::get_speaker_map(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load speaker names: {}", e))?;
+
+    let mut labels: Vec<String> = names.keys().cloned().collect();
+    let mut push_label = |label: String| {
+        if !labels.contains(&label) {
+            labels.push(label);
+        }
+    };
+
+    let transcripts = MeetingsRepository::get_meeting(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting transcripts: {}", e))?
+        .map(|details| details.transcripts)
+        .unwrap_or_default();
+    for transcript in &transcripts {
+        for caps in DB_SPEAKER_RE.captures_iter(&transcript.text) {
+            push_label(caps[1].to_string());
+        }
+    }
+
+    // Labels already rewritten to a wikilink belong to a named speaker
+    let named_links: Vec<String> = names.values().map(|name| to_wikilink(name)).collect();
+    let mut note_files: Vec<PathBuf> = Vec::new();
+    if let Some(path) = find_meeting_markdown(&state, &meeting_id).await? {
+        note_files.push(path);
+    }
+    if let Some(ref path) = note_path {
+        note_files.push(PathBuf::from(path));
+    }
+    for file in &note_files {
+        if let Ok(content) = tokio::fs::read_to_string(file).await {
+            for label in extract_transcript_speakers(&content) {
+                if !label.starts_with("[[") && !named_links.contains(&label) {
+                    push_label(label);
+                }
+            }
+        }
+    }
+
+    labels.sort();
+
+    let mut candidates: Vec<String> = Vec::new();
+    if let Some(ref path) = note_path {
+        for attendee in read_attendee_candidates(Path::new(path)).await {
+            if !candidates.contains(&attendee) {
+                candidates.push(attendee);
+            }
+        }
+    }
+
+    let speakers = labels
+        .into_iter()
+        .map(|label| SpeakerEntry {
+            display_name: names.get(&label).cloned(),
+            label,
+        })
+        .collect();
+
+    Ok(MeetingSpeakers {
+        meeting_id,
+        speakers,
+        candidates,
+    })
+}
+
+/// Tauri command to save the speaker map of a meeting
+///
+/// Persists the map, then rewrites the transcript section of the meeting
+/// markdown in the recording folder and of every note in `note_paths`.
+#[tauri::command]
+pub async fn save_meeting_speakers(
+    state: tauri::State<'_, AppState>,
+    request: SaveMeetingSpeakersRequest,
+) -> Result<SaveMeetingSpeakersResult, String> {
+    log::info!("Saving speaker names for meeting: {}", request.meeting_id);
+
+    let pool = state.db_manager.pool();
+    let old_names = MeetingSpeakersRepository::get_speaker_map(pool, &request.meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load speaker names: {}", e))?;
+
+    let new_names: HashMap<String, String> = request
+        .speakers
+        .iter()
+        .map(|(label, name)| (label.trim().to_string(), normalize_person_name(name)))
+        .filter(|(label, name)| !label.is_empty() && !name.is_empty())
+        .collect();
+
+    let saved = MeetingSpeakersRepository::set_speaker_map(pool, &request.meeting_id, &new_names)
+        .await
+        .map_err(|e| format!("Failed to save speaker names: {}", e))?;
+
+    let mut files: Vec<PathBuf> = Vec::new();
+    if let Some(path) = find_meeting_markdown(&state, &request.meeting_id).await? {
+        files.push(path);
+    }
+    for note_path in &request.note_paths {
+        let path = PathBuf::from(note_path);
+        if !files.contains(&path) {
+            files.push(path);
+        }
+    }
+
+    let replacements = build_speaker_replacements(&old_names, &new_names);
+    let mut updated_files = Vec::new();
+    let mut failed_files = Vec::new();
+
+    for file in files {
+        let display = file.to_string_lossy().to_string();
+        match update_speaker_names_in_file(&file, &replacements).await {
+            Ok(0) => {}
+            Ok(_) => updated_files.push(display),
+            Err(e) => {
+                log::error!("Failed to update speaker names in {}: {}", display, e);
+                failed_files.push((display, e.to_string()));
+            }
+        }
+    }
+
+    Ok(SaveMeetingSpeakersResult {
+        saved,
+        updated_files,
+        failed_files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_speaker_turns() {
+        assert_eq!(
+            split_speaker_turns("[Speaker 0]: Hello.\n[Speaker 1]: Hi,\nthere.\n"),
+            vec![
+                (Some("Speaker 0".to_string()), "Hello.".to_string()),
+                (Some("Speaker 1".to_string()), "Hi, there.".to_string()),
+            ]
+        );
+        assert_eq!(split_speaker_turns("  No labels here "), vec![(None, "No labels here".to_string())]);
+        assert_eq!(split_speaker_turns("[Speaker 2]:   "), vec![]);
+    }
+
+    #[test]
+    fn test_normalize_person_name() {
+        assert_eq!(normalize_person_name("  Attendee A "), "Attendee A");
+        assert_eq!(normalize_person_name("[[Attendee A]]"), "Attendee A");
+        assert_eq!(normalize_person_name("[[People/Attendee A|A]]"), "People/Attendee A");
+    }
+
+    #[test]
+    fn test_build_speaker_replacements() {
+        let mut old_names = HashMap::new();
+        old_names.insert("Speaker 1".to_string(), "Attendee A".to_string());
+        old_names.insert("Speaker 2".to_string(), "Attendee B".to_string());
+
+        let mut new_names = HashMap::new();
+        new_names.insert("Speaker 1".to_string(), "Attendee C".to_string());
+        new_names.insert("Speaker 3".to_string(), "Attendee D".to_string());
+
+        let replacements = build_speaker_replacements(&old_names, &new_names);
+
+        assert_eq!(replacements.get("Speaker 1").unwrap(), "[[Attendee C]]");
+        assert_eq!(replacements.get("[[Attendee A]]").unwrap(), "[[Attendee C]]");
+        assert_eq!(replacements.get("Speaker 3").unwrap(), "[[Attendee D]]");
+        assert_eq!(replacements.get("[[Attendee B]]").unwrap(), "Speaker 2");
+    }
+
+    #[test]
+    fn test_build_speaker_replacements_with_shared_names() {
+        // Two labels merged into one person, then renamed together
+        let mut old_names = HashMap::new();
+        old_names.insert("Speaker 1".to_string(), "Attendee A".to_string());
+        old_names.insert("Speaker 2".to_string(), "Attendee A".to_string());
+        let mut new_names = HashMap::new();
+        new_names.insert("Speaker 1".to_string(), "Attendee B".to_string());
+        new_names.insert("Speaker 2".to_string(), "Attendee B".to_string());
+
+        let replacements = build_speaker_replacements(&old_names, &new_names);
+        assert_eq!(replacements.get("[[Attendee A]]").unwrap(), "[[Attendee B]]");
+
+        // Split apart again: the shared lines can't be attributed, leave them
+        new_names.insert("Speaker 2".to_string(), "Attendee C".to_string());
+        let replacements = build_speaker_replacements(&old_names, &new_names);
+        assert!(!replacements.contains_key("[[Attendee A]]"));
+        assert_eq!(replacements.get("Speaker 2").unwrap(), "[[Attendee C]]");
+
+        // Un-naming one of them is just as ambiguous
+        new_names.remove("Speaker 2");
+        new_names.insert("Speaker 1".to_string(), "Attendee A".to_string());
+        let replacements = build_speaker_replacements(&old_names, &new_names);
+        assert!(!replacements.contains_key("[[Attendee A]]"));
+    }
+
+    #[test]
+    fn test_build_speaker_replacements_swap() {
+        let mut old_names = HashMap::new();
+        old_names.insert("Speaker 1".to_string(), "Attendee A".to_string());
+        old_names.insert("Speaker 2".to_string(), "Attendee B".to_string());
+        let mut new_names = HashMap::new();
+        new_names.insert("Speaker 1".to_string(), "Attendee B".to_string());
+        new_names.insert("Speaker 2".to_string(), "Attendee A".to_string());
+
+        let replacements = build_speaker_replacements(&old_names, &new_names);
+        assert_eq!(replacements.get("[[Attendee A]]").unwrap(), "[[Attendee B]]");
+        assert_eq!(replacements.get("[[Attendee B]]").unwrap(), "[[Attendee A]]");
+    }
+
+    #[test]
+    fn test_db_speaker_prefix() {
+        let text = "[Speaker 0]: Hello.\n[Speaker 1]: Hi.";
+        let labels: Vec<String> = DB_SPEAKER_RE
+            .captures_iter(text)
+            .map(|caps| caps[1].to_string())
+            .collect();
+        assert_eq!(labels, vec!["Speaker 0".to_string(), "Speaker 1".to_string()]);
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Parsed meeting preparation data from an Obsidian markdown file
@@ -31,6 +32,9 @@ pub struct MeetingPrep {
     pub goals: Vec<String>,
     /// Context content (from ### Context section, raw markdown) - legacy support
     pub context: String,
+    /// Sections for user-configured fields (from section label overrides), keyed by field
+    #[serde(default)]
+    pub custom_fields: HashMap<String, Vec<String>>,
     /// Raw file content (preserved for later merge operations)
     pub raw_content: String,
 }
@@ -62,6 +66,38 @@ pub struct MeetingFrontmatter {
     pub attendees: Option<Vec<String>>,
     /// Meeting status (e.g., "scheduled", "completed")
     pub status: Option<String>,
+    /// Recording length as a Dataview duration (e.g., "45 minutes")
+    #[serde(default)]
+    pub duration: Option<String>,
+    /// Speakers heard in the transcript (wikilinks for named speakers)
+    #[serde(default)]
+    pub speakers: Option<Vec<String>>,
+    /// Link to the recorded audio
+    #[serde(default)]
+    pub recording: Option<String>,
+    /// Transcription provider used for the transcript
+    #[serde(default)]
+    pub transcript_provider: Option<String>,
+    /// Summary template to use (set by hand to pick one, or written after summarizing)
+    #[serde(default)]
+    pub summary_template: Option<String>,
+}
+
+/// A structured action item, written to notes as an Obsidian Tasks checkbox:
+/// `- [ ] text 📅 2026-10-20 [[Owner]]`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActionItem {
+    /// What needs to be done
+    pub text: String,
+    /// Person responsible (without `[[...]]`)
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Due date (`YYYY-MM-DD`)
+    #[serde(default)]
+    pub due: Option<String>,
+    /// Whether the checkbox is ticked
+    #[serde(default)]
+    pub done: bool,
 }
 
 /// Helper type to handle tags that can be either a string or array
@@ -93,6 +129,7 @@ impl MeetingPrep {
             action_items: Vec::new(),
             goals: Vec::new(),
             context: String::new(),
+            custom_fields: HashMap::new(),
             raw_content,
         }
     }
@@ -0,0 +1,584 @@
+//! Vault-wide meeting index
+//!
+//! Scans a configured Obsidian vault for notes whose frontmatter is tagged
+//! `meeting`, keeps a lightweight index of them (title, date, start time,
+//! status, attendees) and watches the vault so the index stays current.
+//! The app uses it to list today's scheduled meetings and start recording
+//! against the matching prep file.
+
+use super::parser::parse_meeting_file;
+use super::speakers::normalize_person_name;
+use super::types::MeetingFrontmatter;
+use chrono::NaiveDate;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Runtime};
+use tauri_plugin_store::StoreExt;
+
+pub(super) const VAULT_STORE: &str = "obsidian_vault.json";
+const VAULT_ROOT_KEY: &str = "vault_root";
+/// Event emitted whenever the index changes
+pub const VAULT_UPDATED_EVENT: &str = "obsidian-vault-updated";
+/// Quiet period before applying file changes (editors write in bursts)
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// A meeting note found in the vault
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VaultMeeting {
+    pub file_path: PathBuf,
+    pub title: String,
+    /// Meeting date (`date`, or the date part of `time_start`)
+    pub date: Option<String>,
+    pub time_start: Option<String>,
+    pub status: Option<String>,
+    pub attendees: Vec<String>,
+}
+
+/// Payload of [`VAULT_UPDATED_EVENT`]
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultUpdatedPayload {
+    pub vault_root: String,
+    pub meeting_count: usize,
+}
+
+#[derive(Default)]
+struct VaultIndex {
+    root: Option<PathBuf>,
+    meetings: HashMap<PathBuf, VaultMeeting>,
+}
+
+static VAULT_INDEX: Lazy<RwLock<VaultIndex>> = Lazy::new(|| RwLock::new(VaultIndex::default()));
+static VAULT_WATCHER: Lazy<Mutex<Option<VaultWatcher>>> = Lazy::new(|| Mutex::new(None));
+
+/// File watcher of the indexed vault and the task applying its events
+struct VaultWatcher {
+    _watcher: RecommendedWatcher,
+    task: JoinHandle<()>,
+}
+
+impl Drop for VaultWatcher {
+    /// A debounced change still pending in the task would re-index the old vault
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+// ============================================================================
+// Indexing
+// ============================================================================
+
+/// Whether the frontmatter tags the note as a meeting (`meeting` or `#meeting`)
+pub fn is_meeting_note(frontmatter: &MeetingFrontmatter) -> bool {
+    frontmatter.tags.as_ref().map_or(false, |tags| {
+        tags.as_vec()
+            .iter()
+            .flat_map(|tag| tag.split([',', ' ']))
+            .any(|tag| tag.trim().trim_start_matches('#').eq_ignore_ascii_case("meeting"))
+    })
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .map_or(false, |ext| ext == "md" || ext == "markdown")
+}
+
+/// Skip `.obsidian`, `.trash` and other hidden folders/files inside the vault
+fn is_hidden(root: &Path, path: &Path) -> bool {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+}
+
+/// Parse a single note; `None` if it can't be read or isn't a meeting note
+pub fn index_file(path: &Path) -> Option<VaultMeeting> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let prep = match parse_meeting_file(path, &content) {
+        Ok(prep) => prep,
+        Err(e) => {
+            log::debug!("Skipping {} in vault index: {}", path.display(), e);
+            return None;
+        }
+    };
+    if !is_meeting_note(&prep.frontmatter) {
+        return None;
+    }
+
+    let frontmatter = prep.frontmatter;
+    let date = frontmatter
+        .date
+        .clone()
+        .filter(|d| !d.trim().is_empty())
+        .or_else(|| {
+            frontmatter
+                .time_start
+                .as_deref()
+                .and_then(|t| t.get(..10))
+                .map(str::to_string)
+        });
+
+    Some(VaultMeeting {
+        file_path: path.to_path_buf(),
+        title: prep.title,
+        date,
+        time_start: frontmatter.time_start.filter(|t| !t.trim().is_empty()),
+        status: frontmatter.status,
+        attendees: frontmatter
+            .attendees
+            .unwrap_or_default()
+            .iter()
+            .map(|attendee| normalize_person_name(attendee))
+            .filter(|attendee| !attendee.is_empty())
+            .collect(),
+    })
+}
+
+/// Recursively collect all meeting notes below `root`
+pub fn scan_vault(root: &Path) -> Vec<VaultMeeting> {
+    let mut meetings = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                log::warn!("Failed to read vault folder {}: {}", dir.display(), e);
+                continue;
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if is_hidden(root, &path) {
+                continue;
+            }
+            if path.is_dir() {
+                dirs.push(path);
+            } else if is_markdown(&path) {
+                if let Some(meeting) = index_file(&path) {
+                    meetings.push(meeting);
+                }
+            }
+        }
+    }
+
+    meetings
+}
+
+/// Sort key: start time first, then title
+fn sort_meetings(meetings: &mut [VaultMeeting]) {
+    meetings.sort_by(|a, b| {
+        (a.date.as_deref(), a.time_start.as_deref(), a.title.as_str())
+            .cmp(&(b.date.as_deref(), b.time_start.as_deref(), b.title.as_str()))
+    });
+}
+
+/// Meetings whose date falls within `[from, to]` (inclusive), ordered by start time
+pub fn meetings_between(meetings: &[VaultMeeting], from: Option<NaiveDate>, to: Option<NaiveDate>) -> Vec<VaultMeeting> {
+    let mut selected: Vec<VaultMeeting> = meetings
+        .iter()
+        .filter(|meeting| {
+            let date = meeting
+                .date
+                .as_deref()
+                .and_then(|d| NaiveDate::parse_from_str(d.trim(), "%Y-%m-%d").ok());
+            match date {
+                Some(date) => from.map_or(true, |f| date >= f) && to.map_or(true, |t| date <= t),
+                None => from.is_none() && to.is_none(),
+            }
+        })
+        .cloned()
+        .collect();
+    sort_meetings(&mut selected);
+    selected
+}
+
+fn snapshot() -> (Option<PathBuf>, Vec<VaultMeeting>) {
+    let index = VAULT_INDEX.read().unwrap();
+    (index.root.clone(), index.meetings.values().cloned().collect())
+}
+
+fn emit_updated<R: Runtime>(app: &AppHandle<R>) {
+    let index = VAULT_INDEX.read().unwrap();
+    if let Some(root) = &index.root {
+        let _ = app.emit(
+            VAULT_UPDATED_EVENT,
+            VaultUpdatedPayload {
+                vault_root: root.to_string_lossy().to_string(),
+                meeting_count: index.meetings.len(),
+            },
+        );
+    }
+}
+
+/// Scan a vault off the async runtime, keyed by file path
+async fn scan_vault_in_background(root: &Path) -> Result<HashMap<PathBuf, VaultMeeting>, String> {
+    let scan_root = root.to_path_buf();
+    let meetings = tokio::task::spawn_blocking(move || scan_vault(&scan_root))
+        .await
+        .map_err(|e| format!("Vault scan task failed: {}", e))?;
+    Ok(meetings
+        .into_iter()
+        .map(|meeting| (meeting.file_path.clone(), meeting))
+        .collect())
+}
+
+/// Rebuild the whole index from disk and make `root` the indexed vault
+async fn rebuild_index(root: PathBuf) -> Result<usize, String> {
+    let meetings = scan_vault_in_background(&root).await?;
+
+    let count = meetings.len();
+    let mut index = VAULT_INDEX.write().unwrap();
+    index.meetings = meetings;
+    index.root = Some(root.clone());
+
+    log::info!("Indexed {} meeting notes in vault {}", count, root.display());
+    Ok(count)
+}
+
+/// Rescan the vault of a watcher, unless another vault was configured meanwhile
+async fn rescan_watched(root: &Path) -> Result<(), String> {
+    let meetings = scan_vault_in_background(root).await?;
+
+    let mut index = VAULT_INDEX.write().unwrap();
+    if index.root.as_deref() != Some(root) {
+        log::debug!("Vault changed during rescan of {}, dropping it", root.display());
+        return Ok(());
+    }
+    log::info!("Rescanned vault {}: {} meeting notes", root.display(), meetings.len());
+    index.meetings = meetings;
+    Ok(())
+}
+
+/// Apply changed paths from the watcher; returns true if a full rescan is needed
+fn apply_changes(root: &Path, paths: &HashSet<PathBuf>) -> bool {
+    let mut updates = Vec::new();
+    for path in paths {
+        if is_hidden(root, path) {
+            continue;
+        }
+        if path.is_dir() || (!path.exists() && !is_markdown(path)) {
+            // Folder created, moved or removed: individual file events aren't reliable
+            return true;
+        }
+        if is_markdown(path) {
+            updates.push((path.clone(), index_file(path)));
+        }
+    }
+
+    let mut index = VAULT_INDEX.write().unwrap();
+    if index.root.as_deref() != Some(root) {
+        // Events of a vault that is no longer indexed
+        return false;
+    }
+    for (path, meeting) in updates {
+        match meeting {
+            Some(meeting) => {
+                index.meetings.insert(path, meeting);
+            }
+            None => {
+                index.meetings.remove(&path);
+            }
+        }
+    }
+    false
+}
+
+// ============================================================================
+// Watcher
+// ============================================================================
+
+fn stop_watcher() {
+    if VAULT_WATCHER.lock().unwrap().take().is_some() {
+        log::info!("Stopped vault watcher");
+    }
+}
+
+/// Watch the vault recursively and keep the index in sync
+fn start_watcher<R: Runtime>(app: AppHandle<R>, root: PathBuf) -> Result<(), String> {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Vec<PathBuf>>();
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+        Ok(event) => {
+            let _ = tx.send(event.paths);
+        }
+        Err(e) => log::warn!("Vault watcher error: {}", e),
+    })
+    .map_err(|e| format!("Failed to create vault watcher: {}", e))?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch vault {}: {}", root.display(), e))?;
+
+    log::info!("Watching vault for changes: {}", root.display());
+
+    let task = tauri::async_runtime::spawn(async move {
+        while let Some(paths) = rx.recv().await {
+            let mut changed: HashSet<PathBuf> = paths.into_iter().collect();
+            while let Ok(Some(more)) = tokio::time::timeout(WATCH_DEBOUNCE, rx.recv()).await {
+                changed.extend(more);
+            }
+
+            let apply_root = root.clone();
+            let needs_rescan = tokio::task::spawn_blocking(move || apply_changes(&apply_root, &changed))
+                .await
+                .unwrap_or(true);
+            if needs_rescan {
+                if let Err(e) = rescan_watched(&root).await {
+                    log::error!("Failed to rescan vault: {}", e);
+                    continue;
+                }
+            }
+            emit_updated(&app);
+        }
+    });
+
+    // Any previous watcher is dropped here, aborting its task
+    *VAULT_WATCHER.lock().unwrap() = Some(VaultWatcher {
+        _watcher: watcher,
+        task,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Settings
+// ============================================================================
+
+/// Root of the currently indexed vault
+pub fn vault_root() -> Option<PathBuf> {
+    VAULT_INDEX.read().unwrap().root.clone()
+}
+
+/// Load the configured vault root from the store
+pub fn load_vault_root<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    let store = app.store(VAULT_STORE).ok()?;
+    store
+        .get(VAULT_ROOT_KEY)
+        .and_then(|value| value.as_str().map(PathBuf::from))
+        .filter(|path| !path.as_os_str().is_empty())
+}
+
+fn save_vault_root<R: Runtime>(app: &AppHandle<R>, root: Option<&Path>) -> Result<(), String> {
+    let store = app
+        .store(VAULT_STORE)
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    match root {
+        Some(root) => store.set(VAULT_ROOT_KEY, root.to_string_lossy().to_string()),
+        None => {
+            store.delete(VAULT_ROOT_KEY);
+        }
+    }
+    store
+        .save()
+        .map_err(|e| format!("Failed to save store to disk: {}", e))
+}
+
+/// Index and watch the configured vault (called at startup)
+pub async fn init_vault_index<R: Runtime>(app: AppHandle<R>) {
+    let Some(root) = load_vault_root(&app) else {
+        return;
+    };
+    if !root.is_dir() {
+        log::warn!("Configured vault folder not found: {}", root.display());
+        return;
+    }
+    if let Err(e) = rebuild_index(root.clone()).await {
+        log::error!("Failed to index vault: {}", e);
+        return;
+    }
+    if let Err(e) = start_watcher(app.clone(), root) {
+        log::error!("{}", e);
+    }
+    emit_updated(&app);
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Tauri command to get the configured vault root
+#[tauri::command]
+pub async fn get_obsidian_vault<R: Runtime>(app: AppHandle<R>) -> Result<Option<String>, String> {
+    Ok(load_vault_root(&app).map(|root| root.to_string_lossy().to_string()))
+}
+
+/// Tauri command to configure (or clear) the vault root
+///
+/// Rebuilds the index and restarts the watcher. Returns the number of meeting notes found.
+#[tauri::command]
+pub async fn set_obsidian_vault<R: Runtime>(app: AppHandle<R>, path: Option<String>) -> Result<usize, String> {
+    stop_watcher();
+
+    let Some(path) = path.filter(|p| !p.trim().is_empty()) else {
+        log::info!("Clearing Obsidian vault");
+        save_vault_root(&app, None)?;
+        *VAULT_INDEX.write().unwrap() = VaultIndex::default();
+        super::labels::init_section_labels(app.clone());
+        return Ok(0);
+    };
+
+    let root = PathBuf::from(path.trim());
+    if !root.is_dir() {
+        return Err(format!("Folder not found: {}", root.display()));
+    }
+
+    log::info!("Setting Obsidian vault: {}", root.display());
+    save_vault_root(&app, Some(&root))?;
+    // Vault-level section labels affect how notes are parsed
+    super::labels::init_section_labels(app.clone());
+    let count = rebuild_index(root.clone()).await?;
+    start_watcher(app.clone(), root)?;
+    emit_updated(&app);
+    Ok(count)
+}
+
+/// Tauri command to rescan the vault from disk
+#[tauri::command]
+pub async fn rescan_obsidian_vault<R: Runtime>(app: AppHandle<R>) -> Result<usize, String> {
+    let root = load_vault_root(&app).ok_or_else(|| "No Obsidian vault configured".to_string())?;
+    let count = rebuild_index(root).await?;
+    emit_updated(&app);
+    Ok(count)
+}
+
+/// Tauri command to list indexed meetings, optionally within a date range (`YYYY-MM-DD`)
+#[tauri::command]
+pub async fn list_vault_meetings(
+    date_from: Option<String>,
+    date_to: Option<String>,
+) -> Result<Vec<VaultMeeting>, String> {
+    let parse = |value: Option<String>| -> Result<Option<NaiveDate>, String> {
+        value
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| {
+                NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d")
+                    .map_err(|e| format!("Invalid date '{}': {}", v, e))
+            })
+            .transpose()
+    };
+    let (from, to) = (parse(date_from)?, parse(date_to)?);
+
+    let (_, meetings) = snapshot();
+    Ok(meetings_between(&meetings, from, to))
+}
+
+/// Tauri command to list today's meetings from the vault, ordered by start time
+#[tauri::command]
+pub async fn get_todays_meetings() -> Result<Vec<VaultMeeting>, String> {
+    let (root, meetings) = snapshot();
+    if root.is_none() {
+        return Err("No Obsidian vault configured".to_string());
+    }
+    let today = chrono::Local::now().date_naive();
+    Ok(meetings_between(&meetings, Some(today), Some(today)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::obsidian::types::StringOrVec;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn meeting(title: &str, date: Option<&str>, time_start: Option<&str>) -> VaultMeeting {
+        VaultMeeting {
+            file_path: PathBuf::from(format!("{}.md", title)),
+            title: title.to_string(),
+            date: date.map(str::to_string),
+            time_start: time_start.map(str::to_string),
+            status: None,
+            attendees: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_is_meeting_note() {
+        let mut fm = MeetingFrontmatter::default();
+        assert!(!is_meeting_note(&fm));
+
+        fm.tags = Some(StringOrVec::Single("meeting".to_string()));
+        assert!(is_meeting_note(&fm));
+
+        fm.tags = Some(StringOrVec::Single("project, #Meeting".to_string()));
+        assert!(is_meeting_note(&fm));
+
+        fm.tags = Some(StringOrVec::Multiple(vec!["daily".to_string(), "meetings".to_string()]));
+        assert!(!is_meeting_note(&fm));
+    }
+
+    #[test]
+    fn test_scan_vault() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+
+        write(
+            &root.join("Meetings/2026-01-23 Sync.md"),
+            "---\ntags: meeting\ndate: 2026-01-23\ntime_start: 2026-01-23T10:00:00\nstatus: scheduled\nattendees:\n  - \"[[Attendee A]]\"\n---\n\n# Weekly Sync\n",
+        );
+        write(
+            &root.join("Meetings/Planning.md"),
+            "---\ntags:\n  - meeting\ntime_start: 2026-01-24T09:30:00\n---\n\n# Planning\n",
+        );
+        write(&root.join("Notes/Idea.md"), "---\ntags: idea\n---\n\n# Idea\n");
+        write(&root.join(".trash/Old.md"), "---\ntags: meeting\n---\n\n# Old\n");
+
+        let mut meetings = scan_vault(root);
+        sort_meetings(&mut meetings);
+
+        assert_eq!(meetings.len(), 2);
+        assert_eq!(meetings[0].title, "Weekly Sync");
+        assert_eq!(meetings[0].date.as_deref(), Some("2026-01-23"));
+        assert_eq!(meetings[0].status.as_deref(), Some("scheduled"));
+        assert_eq!(meetings[0].attendees, vec!["Attendee A".to_string()]);
+        assert_eq!(meetings[1].title, "Planning");
+        assert_eq!(meetings[1].date.as_deref(), Some("2026-01-24"));
+    }
+
+    #[test]
+    fn test_meetings_between() {
+        let meetings = vec![
+            meeting("Late", Some("2026-01-23"), Some("2026-01-23T15:00:00")),
+            meeting("Early", Some("2026-01-23"), Some("2026-01-23T09:00:00")),
+            meeting("Tomorrow", Some("2026-01-24"), None),
+            meeting("Undated", None, None),
+        ];
+        let day = NaiveDate::from_ymd_opt(2026, 1, 23);
+
+        let today = meetings_between(&meetings, day, day);
+        let titles: Vec<&str> = today.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, vec!["Early", "Late"]);
+
+        assert_eq!(meetings_between(&meetings, None, None).len(), 4);
+    }
+
+    #[test]
+    fn test_apply_changes_updates_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let note = root.join("Standup.md");
+        write(&note, "---\ntags: meeting\ndate: 2026-01-23\n---\n\n# Standup\n");
+
+        let changed: HashSet<PathBuf> = [note.clone()].into_iter().collect();
+        // Events of a vault that is no longer indexed are ignored
+        VAULT_INDEX.write().unwrap().root = Some(root.join("other"));
+        assert!(!apply_changes(root, &changed));
+        assert!(!VAULT_INDEX.read().unwrap().meetings.contains_key(&note));
+
+        VAULT_INDEX.write().unwrap().root = Some(root.to_path_buf());
+        assert!(!apply_changes(root, &changed));
+        assert!(VAULT_INDEX.read().unwrap().meetings.contains_key(&note));
+
+        std::fs::remove_file(&note).unwrap();
+        assert!(!apply_changes(root, &changed));
+        assert!(!VAULT_INDEX.read().unwrap().meetings.contains_key(&note));
+    }
+}
@@ -8,11 +8,24 @@
 //! - Preserves existing content when merging
 //! - Atomic writes to prevent data loss
 //! - Formats transcripts with timestamps and speaker labels
+//! - Resolves diarized speaker labels to `[[Person Name]]` wikilinks
+//! - Merges action items as Obsidian Tasks checkboxes (`- [ ] text 📅 date [[Owner]]`)
+//! - Merges concurrent edits made while recording instead of overwriting them
 //! - Generates frontmatter with meeting metadata
 
+use super::frontmatter::{format_duration, recording_link, set_frontmatter_fields, FrontmatterEditor, FrontmatterValue};
+use super::merge::{conflict_path, content_hash, three_way_merge, MergeResult};
+use super::types::ActionItem;
+use crate::summary::citations::{self, SegmentIds};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+/// Per-meeting speaker map: diarized label (e.g. "Speaker 1") -> person name
+pub type SpeakerNames = HashMap<String, String>;
+
 /// A single transcript segment with timestamp, speaker, and text
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptSegment {
@@ -22,6 +35,10 @@ pub struct TranscriptSegment {
     pub speaker: Option<String>,
     /// The transcribed text
     pub text: String,
+    /// Recording-relative start in seconds of the transcript row this line opens.
+    /// Further speaker lines of the same row leave it unset and get no block id.
+    #[serde(default)]
+    pub audio_start_time: Option<f64>,
 }
 
 /// Request to save transcript to a meeting file
@@ -34,12 +51,80 @@ pub struct SaveTranscriptRequest {
     /// Whether to update frontmatter status to "completed"
     #[serde(default = "default_update_status")]
     pub update_status: bool,
+    /// Optional speaker names; mapped labels are written as `[[Name]]` wikilinks
+    #[serde(default)]
+    pub speaker_names: SpeakerNames,
+    /// Action items to merge into the note's Action Items section
+    #[serde(default)]
+    pub action_items: Vec<ActionItem>,
+    /// Note content as loaded when the meeting started (`MeetingPrep.raw_content`).
+    /// When the file changed since, edits are merged three-way instead of overwritten.
+    #[serde(default)]
+    pub base_content: Option<String>,
+    /// Recording metadata written to the frontmatter
+    #[serde(default)]
+    pub metadata: NoteMetadata,
+}
+
+/// Recording metadata written to a note's frontmatter (all optional)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NoteMetadata {
+    /// Meeting end time (ISO format)
+    #[serde(default)]
+    pub time_end: Option<String>,
+    /// Recording length in seconds, written as a Dataview duration
+    #[serde(default)]
+    pub duration_seconds: Option<f64>,
+    /// Audio file; linked from the note
+    #[serde(default)]
+    pub recording: Option<String>,
+    /// Transcription provider (e.g. "deepgram", "localWhisper")
+    #[serde(default)]
+    pub transcript_provider: Option<String>,
+    /// Summary template id
+    #[serde(default)]
+    pub summary_template: Option<String>,
+}
+
+/// What happened when saving into a note
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SaveOutcome {
+    /// The note was edited since it was loaded and the edits were merged
+    pub merged_concurrent_edits: bool,
+    /// Sibling file holding our full version, written when edits conflicted
+    pub conflict_path: Option<String>,
+    /// Sections/frontmatter keys edited on both sides (the note keeps the user's version)
+    pub conflicting_sections: Vec<String>,
 }
 
+/// How often to redo a save when the file changes between reading and writing
+const MAX_SAVE_ATTEMPTS: usize = 3;
+
 fn default_update_status() -> bool {
     true
 }
 
+/// Resolve a speaker label for display in a transcript
+///
+/// Named speakers become Obsidian wikilinks (`[[Person Name]]`) so the
+/// transcript links to the person's note; unnamed labels are kept as-is.
+pub fn format_speaker_name(speaker: &str, speaker_names: &SpeakerNames) -> String {
+    match speaker_names.get(speaker).map(|name| name.trim()) {
+        Some(name) if !name.is_empty() => to_wikilink(name),
+        _ => speaker.to_string(),
+    }
+}
+
+/// Wrap a person name in `[[...]]`, leaving existing wikilinks untouched
+pub fn to_wikilink(name: &str) -> String {
+    let name = name.trim();
+    if name.starts_with("[[") && name.ends_with("]]") {
+        name.to_string()
+    } else {
+        format!("[[{}]]", name)
+    }
+}
+
 // ============================================================================
 // PHASE 1: Standalone Meeting Markdown Generation
 // ============================================================================
@@ -54,6 +139,10 @@ pub struct MarkdownTranscriptEntry {
     pub text: String,
     /// Optional speaker label (e.g., "Speaker 1")
     pub speaker: Option<String>,
+    /// Recording-relative start in seconds of the transcript row this entry opens;
+    /// unset for further speaker turns of the row. Citation block ids derive from it.
+    #[serde(default)]
+    pub audio_start_time: Option<f64>,
 }
 
 /// Data for generating a standalone meeting markdown file
@@ -63,14 +152,16 @@ pub struct MeetingMarkdownData {
     pub date: String,
     /// Start time in HH:MM:SS format
     pub time_start: String,
-    /// Duration in MM:SS or HH:MM:SS format
-    pub duration: String,
-    /// Recording filename (just the filename, not full path)
-    pub recording_filename: Option<String>,
+    /// Recording metadata (duration, end time, audio file, provider)
+    #[serde(default)]
+    pub metadata: NoteMetadata,
     /// Transcript entries for markdown
     pub entries: Vec<MarkdownTranscriptEntry>,
     /// Optional AI-generated summary markdown
     pub summary: Option<String>,
+    /// Optional speaker names; mapped labels are written as `[[Name]]` wikilinks
+    #[serde(default)]
+    pub speaker_names: SpeakerNames,
 }
 
 /// Generate markdown content for a meeting file
@@ -86,10 +177,6 @@ pub fn generate_meeting_markdown(data: &MeetingMarkdownData) -> String {
     content.push_str("---\n");
     content.push_str(&format!("date: {}\n", data.date));
     content.push_str(&format!("time_start: {}\n", data.time_start));
-    content.push_str(&format!("duration: {}\n", data.duration));
-    if let Some(ref recording) = data.recording_filename {
-        content.push_str(&format!("recording: {}\n", recording));
-    }
     content.push_str("status: completed\n");
     content.push_str("---\n\n");
 
@@ -104,35 +191,62 @@ pub fn generate_meeting_markdown(data: &MeetingMarkdownData) -> String {
     }
 
     // Transcript section
-    content.push_str(&format_markdown_transcript(&data.entries));
+    content.push_str(&format_markdown_transcript(&data.entries, &data.speaker_names));
+
+    // Recording metadata, written like for transcripts saved into existing notes
+    set_frontmatter_fields(&content, &markdown_metadata_fields(data))
+}
 
-    content
+/// Recording frontmatter fields of a standalone meeting note
+fn markdown_metadata_fields(data: &MeetingMarkdownData) -> Vec<(&'static str, FrontmatterValue)> {
+    note_metadata_fields(
+        &data.metadata,
+        data.entries.iter().filter_map(|entry| entry.speaker.as_deref()),
+        &data.speaker_names,
+    )
 }
 
 /// Format transcript entries into markdown
-fn format_markdown_transcript(entries: &[MarkdownTranscriptEntry]) -> String {
+fn format_markdown_transcript(
+    entries: &[MarkdownTranscriptEntry],
+    speaker_names: &SpeakerNames,
+) -> String {
     if entries.is_empty() {
         return "## Transcript\n\n_No transcript recorded._\n".to_string();
     }
 
     let mut lines = vec!["## Transcript".to_string(), String::new()];
+    let mut ids = SegmentIds::new();
 
     for entry in entries {
         let line = match &entry.speaker {
             Some(speaker) => {
-                format!("{} **{}:** {}", entry.display_time, speaker, entry.text)
+                format!(
+                    "{} **{}:** {}",
+                    entry.display_time,
+                    format_speaker_name(speaker, speaker_names),
+                    entry.text
+                )
             }
             None => {
                 format!("{} {}", entry.display_time, entry.text)
             }
         };
-        lines.push(line);
+        lines.push(with_block_id(line, entry.audio_start_time.map(|start| ids.next_for_start(start))));
         lines.push(String::new()); // Blank line between entries
     }
 
     lines.join("\n")
 }
 
+/// Append an Obsidian block id (` ^t-0215`) so summary citations can link to the line
+fn with_block_id(line: String, id: Option<String>) -> String {
+    match id {
+        Some(id) => format!("{} ^{}", line, id),
+        None => line,
+    }
+}
+
 /// Save meeting markdown to a file alongside the recording
 ///
 /// # Arguments
@@ -161,6 +275,10 @@ pub async fn save_meeting_markdown(
 
 /// Save meeting markdown to a specific folder
 ///
+/// A new file gets the full generated note. If the note already exists (e.g.
+/// a re-save after editing it in Obsidian), only its transcript section and
+/// recording frontmatter are replaced, so edits made meanwhile are kept.
+///
 /// # Arguments
 /// * `folder` - Folder to save the markdown file in
 /// * `filename` - Name of the markdown file (without extension)
@@ -176,6 +294,18 @@ pub async fn save_meeting_markdown_to_folder(
 ) -> Result<PathBuf, WriteError> {
     let md_path = folder.join(format!("{}.md", filename));
 
+    if md_path.is_file() {
+        let transcript = format_markdown_transcript(&data.entries, &data.speaker_names);
+        let mut fields = vec![("status", FrontmatterValue::Scalar("completed".to_string()))];
+        fields.extend(markdown_metadata_fields(data));
+        update_file_guarded(&md_path, |content| {
+            set_frontmatter_fields(&insert_transcript_section(content, &transcript), &fields)
+        })
+        .await?;
+        log::info!("Updated meeting markdown in: {}", md_path.display());
+        return Ok(md_path);
+    }
+
     // Generate content
     let content = generate_meeting_markdown(data);
 
@@ -191,6 +321,7 @@ pub async fn save_meeting_markdown_to_folder(
 /// # Arguments
 /// * `md_path` - Path to the existing markdown file
 /// * `summary` - Summary markdown to insert
+/// * `frontmatter` - Frontmatter fields to set (e.g. `summary_template`)
 ///
 /// # Returns
 /// * `Ok(())` - Success
@@ -198,17 +329,21 @@ pub async fn save_meeting_markdown_to_folder(
 pub async fn update_meeting_markdown_with_summary(
     md_path: &Path,
     summary: &str,
+    frontmatter: &[(&str, FrontmatterValue)],
 ) -> Result<(), WriteError> {
-    // Read existing content
-    let content = tokio::fs::read_to_string(md_path)
-        .await
-        .map_err(|e| WriteError::ReadError(format!("Failed to read file: {}", e)))?;
-
-    // Insert or replace summary section
-    let updated_content = insert_summary_section(&content, summary);
-
-    // Atomic write
-    atomic_write(md_path, &updated_content).await?;
+    // Citation links become block links to the cited transcript lines
+    let summary = citations::to_obsidian_links(summary);
+
+    // Insert or replace summary section, without clobbering edits made meanwhile
+    update_file_guarded(md_path, |content| {
+        let updated = insert_summary_section(content, &summary);
+        if frontmatter.is_empty() {
+            updated
+        } else {
+            set_frontmatter_fields(&updated, frontmatter)
+        }
+    })
+    .await?;
 
     log::info!("Updated meeting markdown with summary: {}", md_path.display());
     Ok(())
@@ -294,6 +429,241 @@ fn insert_summary_section(content: &str, summary: &str) -> String {
     result_lines.join("\n")
 }
 
+// ============================================================================
+// Action Items (Obsidian Tasks format)
+// ============================================================================
+
+/// Tasks plugin due date signifier
+const DUE_DATE_SIGNIFIER: &str = "📅";
+
+/// Emoji signifiers the Tasks plugin appends after the task description
+const TASK_SIGNIFIERS: &[&str] = &["📅", "⏳", "🛫", "✅", "➕", "❌", "🔁", "⏫", "🔼", "🔽", "🔺", "⏬", "🆔", "⛔"];
+
+/// Matches a checkbox list item: indent, status character, rest of line
+static TASK_LINE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\s*)[-*+]\s+\[(.)\]\s*(.*)$").unwrap());
+
+static DUE_DATE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"📅\s*(\d{4}-\d{2}-\d{2})").unwrap());
+
+/// Trailing `[[Owner]]` (or `[[Owner|alias]]`) wikilink of a task line
+static TRAILING_OWNER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[\[([^\]|]+)(?:\|[^\]]*)?\]\]\s*$").unwrap());
+
+/// Format an action item as an Obsidian Tasks line: `- [ ] text 📅 2026-10-20 [[Owner]]`
+pub fn format_action_item(item: &ActionItem) -> String {
+    let mut line = format!("- [{}] {}", if item.done { "x" } else { " " }, item.text.trim());
+    if let Some(due) = item.due.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        line.push_str(&format!(" {} {}", DUE_DATE_SIGNIFIER, due));
+    }
+    if let Some(owner) = item.owner.as_deref().map(str::trim).filter(|o| !o.is_empty()) {
+        line.push(' ');
+        line.push_str(&to_wikilink(owner));
+    }
+    line
+}
+
+/// Parse an Obsidian Tasks checkbox line back into an action item
+pub fn parse_action_item(line: &str) -> Option<ActionItem> {
+    let caps = TASK_LINE_RE.captures(line)?;
+    let done = !matches!(&caps[2], " ");
+    let mut rest = caps[3].trim().to_string();
+
+    let owner = TRAILING_OWNER_RE.captures(&rest).map(|owner| owner[1].trim().to_string());
+    if owner.is_some() {
+        let without_owner = TRAILING_OWNER_RE.replace(&rest, "").trim().to_string();
+        rest = without_owner;
+    }
+    let due = DUE_DATE_RE.captures(&rest).map(|due| due[1].to_string());
+
+    let text_end = TASK_SIGNIFIERS
+        .iter()
+        .filter_map(|signifier| rest.find(signifier))
+        .min()
+        .unwrap_or(rest.len());
+    let text = rest[..text_end].trim().to_string();
+
+    Some(ActionItem {
+        text,
+        owner,
+        due,
+        done,
+    })
+}
+
+/// Key used to recognise the same task across runs (case, punctuation and spacing ignored)
+fn action_item_key(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether a heading line is one of the configured Action Items labels
+fn is_action_items_heading(line: &str) -> bool {
+    if !is_heading(line) {
+        return false;
+    }
+    let label = line.trim().trim_start_matches('#').trim().to_lowercase();
+    label == "action items" || super::parser::get_labels_for_field("action_items").contains(&label)
+}
+
+/// Index of the note's top-level Action Items heading
+///
+/// Only a section at the top of the outline counts; a lone leading `# Title`
+/// is the note title, and an "Action Items" heading nested under another
+/// section (e.g. `### Action Items` inside `## Summary`) is left alone.
+fn find_action_items_section(lines: &[String]) -> Option<usize> {
+    let headings: Vec<(usize, usize)> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| is_heading(line))
+        .map(|(i, line)| (i, count_heading_level(line.trim())))
+        .collect();
+    let has_title = matches!(headings.first(), Some((_, 1)))
+        && headings.iter().filter(|(_, level)| *level == 1).count() == 1;
+
+    // Levels of the headings enclosing the current one
+    let mut open: Vec<usize> = Vec::new();
+    for (n, &(i, level)) in headings.iter().enumerate() {
+        if n == 0 && has_title {
+            continue;
+        }
+        while open.last().map_or(false, |&parent| parent >= level) {
+            open.pop();
+        }
+        if open.is_empty() && is_action_items_heading(&lines[i]) {
+            return Some(i);
+        }
+        open.push(level);
+    }
+    None
+}
+
+/// Merge action items into the note's Action Items section
+///
+/// Existing tasks are matched by their text; matched lines keep their checked
+/// state and only gain a missing due date or owner. New items are appended to
+/// the section, which is created (before the transcript, or at the end) if missing.
+/// Returns the new content and the number of added items.
+pub fn merge_action_items(content: &str, items: &[ActionItem]) -> (String, usize) {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    // Locate the section body: [start, end)
+    let heading = find_action_items_section(&lines);
+    let (start, end) = match heading {
+        Some(h) => {
+            let level = count_heading_level(lines[h].trim());
+            let end = lines[h + 1..]
+                .iter()
+                .position(|line| is_heading(line) && count_heading_level(line.trim()) <= level)
+                .map(|i| h + 1 + i)
+                .unwrap_or(lines.len());
+            (h + 1, end)
+        }
+        None => {
+            let insert_at = lines
+                .iter()
+                .position(|line| line.trim().eq_ignore_ascii_case("## Transcript"))
+                .unwrap_or(lines.len());
+            let mut section = vec!["## Action Items".to_string(), String::new()];
+            if insert_at > 0 && !lines[insert_at - 1].trim().is_empty() {
+                section.insert(0, String::new());
+            }
+            let body_start = insert_at + section.len();
+            lines.splice(insert_at..insert_at, section);
+            (body_start, body_start)
+        }
+    };
+
+    let mut section: Vec<String> = lines[start..end].to_vec();
+    // Drop empty template placeholders ("- [ ]", "-")
+    section.retain(|line| !matches!(line.trim(), "- [ ]" | "-"));
+
+    let mut added = 0;
+    let mut seen: Vec<String> = Vec::new();
+    for item in items {
+        let key = action_item_key(&item.text);
+        if key.is_empty() || seen.contains(&key) {
+            continue;
+        }
+        seen.push(key.clone());
+
+        let existing = section.iter().position(|line| {
+            parse_action_item(line).map_or(false, |task| action_item_key(&task.text) == key)
+        });
+        match existing {
+            Some(i) => {
+                let current = parse_action_item(&section[i]).unwrap();
+                if (current.due.is_none() && item.due.is_some()) || (current.owner.is_none() && item.owner.is_some()) {
+                    let mut line = section[i].trim_end().to_string();
+                    if current.owner.is_some() {
+                        // Keep the owner last; insert the due date before it
+                        line = TRAILING_OWNER_RE.replace(&section[i], "").trim_end().to_string();
+                    }
+                    if current.due.is_none() {
+                        if let Some(due) = item.due.as_deref().filter(|d| !d.trim().is_empty()) {
+                            line.push_str(&format!(" {} {}", DUE_DATE_SIGNIFIER, due.trim()));
+                        }
+                    }
+                    if let Some(owner) = current.owner.as_deref().or(item.owner.as_deref()) {
+                        line.push(' ');
+                        line.push_str(&to_wikilink(owner));
+                    }
+                    section[i] = line;
+                }
+            }
+            None => {
+                // Append after the last task, before trailing blank lines
+                let insert_at = section
+                    .iter()
+                    .rposition(|line| !line.trim().is_empty())
+                    .map(|i| i + 1)
+                    .unwrap_or(0);
+                section.insert(insert_at, format_action_item(&ActionItem { done: false, ..item.clone() }));
+                added += 1;
+            }
+        }
+    }
+
+    if end < lines.len() && section.last().map_or(true, |line| !line.trim().is_empty()) {
+        section.push(String::new());
+    }
+    lines.splice(start..end, section);
+
+    let mut merged = lines.join("\n");
+    if content.ends_with('\n') {
+        merged.push('\n');
+    }
+    (merged, added)
+}
+
+/// Merge action items into a markdown file
+///
+/// # Returns
+/// * `Ok(usize)` - Number of newly added tasks
+/// * `Err(WriteError)` - If reading or writing fails
+pub async fn update_action_items_in_file(path: &Path, items: &[ActionItem]) -> Result<usize, WriteError> {
+    if items.is_empty() {
+        return Ok(0);
+    }
+
+    let mut added = 0;
+    update_file_guarded(path, |content| {
+        let (merged, count) = merge_action_items(content, items);
+        added = count;
+        merged
+    })
+    .await?;
+    if added > 0 {
+        log::info!("Merged {} new action items into: {}", added, path.display());
+    }
+    Ok(added)
+}
+
 // ============================================================================
 // PHASE 2: Merge into Existing Obsidian File (existing implementation)
 // ============================================================================
@@ -316,6 +686,7 @@ pub fn merge_transcript(
     original_content: &str,
     segments: &[TranscriptSegment],
     update_status: bool,
+    speaker_names: &SpeakerNames,
 ) -> Result<String, WriteError> {
     let mut content = original_content.to_string();
 
@@ -325,7 +696,7 @@ pub fn merge_transcript(
     }
 
     // Format the transcript section
-    let transcript_section = format_transcript_section(segments);
+    let transcript_section = format_transcript_section(segments, speaker_names);
 
     // Find or create the Transcript section
     content = insert_transcript_section(&content, &transcript_section);
@@ -335,62 +706,80 @@ pub fn merge_transcript(
 
 /// Update the frontmatter status field
 fn update_frontmatter_status(content: &str, new_status: &str) -> String {
-    let content = content.trim_start();
-
-    // Check if frontmatter exists
-    if !content.starts_with("---") {
+    let mut editor = FrontmatterEditor::parse(content);
+    // Notes without frontmatter are left alone
+    if !editor.has_frontmatter() {
         return content.to_string();
     }
+    editor.set("status", &FrontmatterValue::Scalar(new_status.to_string()));
+    editor.render()
+}
 
-    // Find the closing marker
-    let after_first_marker = &content[3..];
-    let Some(end_pos) = after_first_marker.find("\n---") else {
-        return content.to_string();
-    };
+/// Frontmatter fields describing the recording, for Dataview queries
+///
+/// `speakers` are the transcript's speaker labels, in order of appearance.
+pub fn note_metadata_fields<'a>(
+    metadata: &NoteMetadata,
+    speakers: impl IntoIterator<Item = &'a str>,
+    speaker_names: &SpeakerNames,
+) -> Vec<(&'static str, FrontmatterValue)> {
+    let mut fields = Vec::new();
+    if let Some(time_end) = metadata.time_end.as_ref().filter(|t| !t.trim().is_empty()) {
+        fields.push(("time_end", FrontmatterValue::Scalar(time_end.clone())));
+    }
+    if let Some(seconds) = metadata.duration_seconds.filter(|s| *s > 0.0) {
+        fields.push(("duration", FrontmatterValue::Scalar(format_duration(seconds))));
+    }
 
-    let frontmatter = &after_first_marker[..end_pos];
-    let rest_of_file = &after_first_marker[end_pos + 4..];
-
-    // Update or add status field in frontmatter
-    let updated_frontmatter = if frontmatter.contains("status:") {
-        // Replace existing status
-        let lines: Vec<String> = frontmatter
-            .lines()
-            .map(|line| {
-                if line.trim_start().starts_with("status:") {
-                    format!("status: {}", new_status)
-                } else {
-                    line.to_string()
-                }
-            })
-            .collect();
-        lines.join("\n")
-    } else {
-        // Add status field
-        format!("{}\nstatus: {}", frontmatter.trim_end(), new_status)
-    };
+    // Speakers in order of appearance, named ones as wikilinks
+    let mut names: Vec<String> = Vec::new();
+    for speaker in speakers {
+        let name = format_speaker_name(speaker, speaker_names);
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    if !names.is_empty() {
+        fields.push(("speakers", FrontmatterValue::List(names)));
+    }
 
-    format!("---\n{}\n---{}", updated_frontmatter, rest_of_file)
+    if let Some(recording) = metadata.recording.as_ref().filter(|r| !r.trim().is_empty()) {
+        let link = recording_link(Path::new(recording), super::vault::vault_root().as_deref());
+        fields.push(("recording", FrontmatterValue::Scalar(link)));
+    }
+    if let Some(provider) = metadata.transcript_provider.as_ref().filter(|p| !p.trim().is_empty()) {
+        fields.push(("transcript_provider", FrontmatterValue::Scalar(provider.clone())));
+    }
+    if let Some(template) = metadata.summary_template.as_ref().filter(|t| !t.trim().is_empty()) {
+        fields.push(("summary_template", FrontmatterValue::Scalar(template.clone())));
+    }
+    fields
 }
 
 /// Format transcript segments into markdown
-fn format_transcript_section(segments: &[TranscriptSegment]) -> String {
+fn format_transcript_section(segments: &[TranscriptSegment], speaker_names: &SpeakerNames) -> String {
     if segments.is_empty() {
         return "## Transcript\n\n_No transcript recorded._\n".to_string();
     }
 
     let mut lines = vec!["## Transcript".to_string(), String::new()];
+    let mut ids = SegmentIds::new();
 
     for segment in segments {
         let line = match &segment.speaker {
             Some(speaker) => {
-                format!("[{}] **{}:** {}", segment.timestamp, speaker, segment.text)
+                format!(
+                    "[{}] **{}:** {}",
+                    segment.timestamp,
+                    format_speaker_name(speaker, speaker_names),
+                    segment.text
+                )
             }
             None => {
                 format!("[{}] {}", segment.timestamp, segment.text)
             }
         };
-        lines.push(line);
+        lines.push(with_block_id(line, segment.audio_start_time.map(|start| ids.next_for_start(start))));
         lines.push(String::new()); // Blank line between segments
     }
 
@@ -454,6 +843,117 @@ fn insert_transcript_section(content: &str, transcript_section: &str) -> String
     result_lines.join("\n")
 }
 
+/// Matches the bold speaker label of a transcript line: `[ts] **Label:** text`
+static TRANSCRIPT_SPEAKER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\s*\[[^\]]*\]\s+)\*\*(.+?):\*\*").unwrap());
+
+/// Rewrite speaker labels inside the Transcript section of a meeting file
+///
+/// `replacements` maps the label currently in the file (e.g. "Speaker 1" or
+/// "[[Old Name]]") to the label to write. Each line is rewritten at most once,
+/// so swapping two names works. Content outside `## Transcript` is untouched.
+/// Returns the new content and the number of lines changed.
+pub fn rename_speakers_in_markdown(
+    content: &str,
+    replacements: &HashMap<String, String>,
+) -> (String, usize) {
+    let mut result_lines: Vec<String> = Vec::new();
+    let mut in_transcript_section = false;
+    let mut transcript_section_level = 0;
+    let mut changed = 0;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+
+        if is_heading(trimmed) {
+            let level = count_heading_level(trimmed);
+            if trimmed.eq_ignore_ascii_case("## Transcript") {
+                in_transcript_section = true;
+                transcript_section_level = level;
+            } else if in_transcript_section && level <= transcript_section_level {
+                in_transcript_section = false;
+            }
+        }
+
+        if in_transcript_section {
+            if let Some(caps) = TRANSCRIPT_SPEAKER_RE.captures(line) {
+                if let Some(new_label) = replacements.get(&caps[2]) {
+                    let whole = caps.get(0).unwrap();
+                    result_lines.push(format!(
+                        "{}**{}:**{}",
+                        &caps[1],
+                        new_label,
+                        &line[whole.end()..]
+                    ));
+                    changed += 1;
+                    continue;
+                }
+            }
+        }
+
+        result_lines.push(line.to_string());
+    }
+
+    let mut new_content = result_lines.join("\n");
+    if content.ends_with('\n') {
+        new_content.push('\n');
+    }
+    (new_content, changed)
+}
+
+/// Collect the distinct speaker labels used in the Transcript section
+pub fn extract_transcript_speakers(content: &str) -> Vec<String> {
+    let mut speakers: Vec<String> = Vec::new();
+    let mut in_transcript_section = false;
+    let mut transcript_section_level = 0;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+
+        if is_heading(trimmed) {
+            let level = count_heading_level(trimmed);
+            if trimmed.eq_ignore_ascii_case("## Transcript") {
+                in_transcript_section = true;
+                transcript_section_level = level;
+            } else if in_transcript_section && level <= transcript_section_level {
+                in_transcript_section = false;
+            }
+        }
+
+        if in_transcript_section {
+            if let Some(caps) = TRANSCRIPT_SPEAKER_RE.captures(line) {
+                let speaker = caps[2].to_string();
+                if !speakers.contains(&speaker) {
+                    speakers.push(speaker);
+                }
+            }
+        }
+    }
+
+    speakers
+}
+
+/// Rewrite speaker labels of a meeting file in place
+///
+/// Returns the number of transcript lines that changed; the file is only
+/// written when something changed.
+pub async fn update_speaker_names_in_file(
+    path: &Path,
+    replacements: &HashMap<String, String>,
+) -> Result<usize, WriteError> {
+    let original_content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| WriteError::ReadError(format!("Failed to read file: {}", e)))?;
+
+    let (updated_content, changed) = rename_speakers_in_markdown(&original_content, replacements);
+    if changed > 0 {
+        atomic_write(path, &updated_content).await?;
+        log::info!("Updated {} speaker labels in: {}", changed, path.display());
+    }
+
+    Ok(changed)
+}
+
 /// Count the heading level (number of # characters)
 fn count_heading_level(line: &str) -> usize {
     line.chars().take_while(|c| *c == '#').count()
@@ -482,23 +982,137 @@ pub async fn atomic_write(path: &Path, content: &str) -> Result<(), WriteError>
     Ok(())
 }
 
-/// Save transcript to a meeting file
-pub async fn save_transcript(request: &SaveTranscriptRequest) -> Result<(), WriteError> {
-    let path = Path::new(&request.file_path);
-
-    // Read original content
-    let original_content = tokio::fs::read_to_string(path)
+/// Write `content` only if the file still hashes to `expected_hash`
+///
+/// # Returns
+/// * `Ok(false)` - The file changed since it was read; nothing was written
+async fn write_if_unchanged(path: &Path, content: &str, expected_hash: &str) -> Result<bool, WriteError> {
+    let current = tokio::fs::read_to_string(path)
         .await
         .map_err(|e| WriteError::ReadError(format!("Failed to read file: {}", e)))?;
+    if content_hash(&current) != expected_hash {
+        return Ok(false);
+    }
+    if current != content {
+        atomic_write(path, content).await?;
+    }
+    Ok(true)
+}
 
+/// Read, transform and write a file, redoing the transform if the file
+/// changes in between (e.g. Obsidian saving or Sync pulling an edit)
+pub async fn update_file_guarded<F>(path: &Path, mut transform: F) -> Result<(), WriteError>
+where
+    F: FnMut(&str) -> String,
+{
+    for _ in 0..MAX_SAVE_ATTEMPTS {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| WriteError::ReadError(format!("Failed to read file: {}", e)))?;
+        let updated = transform(&content);
+        if write_if_unchanged(path, &updated, &content_hash(&content)).await? {
+            return Ok(());
+        }
+        log::warn!("{} changed while saving, retrying", path.display());
+    }
+    Err(WriteError::WriteError(format!(
+        "{} kept changing while saving",
+        path.display()
+    )))
+}
+
+/// Apply the transcript, status and action items of a request to note content
+fn apply_transcript_request(content: &str, request: &SaveTranscriptRequest) -> Result<String, WriteError> {
     // Merge transcript
-    let merged_content = merge_transcript(&original_content, &request.segments, request.update_status)?;
+    let mut merged_content = merge_transcript(
+        content,
+        &request.segments,
+        request.update_status,
+        &request.speaker_names,
+    )?;
+
+    // Merge action items as Obsidian Tasks checkboxes
+    if !request.action_items.is_empty() {
+        merged_content = merge_action_items(&merged_content, &request.action_items).0;
+    }
 
-    // Atomic write
-    atomic_write(path, &merged_content).await?;
+    let fields = note_metadata_fields(
+        &request.metadata,
+        request.segments.iter().filter_map(|s| s.speaker.as_deref()),
+        &request.speaker_names,
+    );
+    if !fields.is_empty() {
+        merged_content = set_frontmatter_fields(&merged_content, &fields);
+    }
 
-    log::info!("Saved transcript to: {}", request.file_path);
-    Ok(())
+    Ok(merged_content)
+}
+
+/// Save transcript to a meeting file
+///
+/// If `base_content` is given and the note changed since it was loaded, our
+/// changes are merged three-way with the user's. Sections edited on both
+/// sides keep the user's text and our full version goes to a conflict file.
+pub async fn save_transcript(request: &SaveTranscriptRequest) -> Result<SaveOutcome, WriteError> {
+    let path = Path::new(&request.file_path);
+
+    for _ in 0..MAX_SAVE_ATTEMPTS {
+        // Read current content
+        let current_content = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| WriteError::ReadError(format!("Failed to read file: {}", e)))?;
+        let current_hash = content_hash(&current_content);
+
+        let mut outcome = SaveOutcome::default();
+        let mut conflict_copy = None;
+        let merged_content = match request.base_content.as_deref() {
+            Some(base) if content_hash(base) != current_hash => {
+                log::warn!(
+                    "{} changed since it was loaded, merging concurrent edits",
+                    request.file_path
+                );
+                outcome.merged_concurrent_edits = true;
+                let ours = apply_transcript_request(base, request)?;
+                match three_way_merge(base, &ours, &current_content) {
+                    MergeResult::Clean(merged) => merged,
+                    MergeResult::Conflict { content, blocks } => {
+                        outcome.conflicting_sections = blocks;
+                        conflict_copy = Some(ours);
+                        content
+                    }
+                }
+            }
+            _ => apply_transcript_request(&current_content, request)?,
+        };
+
+        // Atomic write, unless the file changed while we were merging
+        if !write_if_unchanged(path, &merged_content, &current_hash).await? {
+            log::warn!("{} changed while saving, retrying", request.file_path);
+            continue;
+        }
+
+        if let Some(ours) = conflict_copy {
+            let copy_path = conflict_path(path);
+            tokio::fs::write(&copy_path, ours)
+                .await
+                .map_err(|e| WriteError::WriteError(format!("Failed to write conflict file: {}", e)))?;
+            log::warn!(
+                "Conflicting edits in {} ({}), saved our version to {}",
+                request.file_path,
+                outcome.conflicting_sections.join(", "),
+                copy_path.display()
+            );
+            outcome.conflict_path = Some(copy_path.to_string_lossy().to_string());
+        }
+
+        log::info!("Saved transcript to: {}", request.file_path);
+        return Ok(outcome);
+    }
+
+    Err(WriteError::WriteError(format!(
+        "{} kept changing while saving",
+        request.file_path
+    )))
 }
 
 #[cfg(test)]
@@ -532,15 +1146,17 @@ Background info...
                 timestamp: "00:00:12".to_string(),
                 speaker: Some("Speaker 1".to_string()),
                 text: "Hello everyone.".to_string(),
+                audio_start_time: None,
             },
             TranscriptSegment {
                 timestamp: "00:00:20".to_string(),
                 speaker: Some("Speaker 2".to_string()),
                 text: "Hi there!".to_string(),
+                audio_start_time: None,
             },
         ];
 
-        let result = format_transcript_section(&segments);
+        let result = format_transcript_section(&segments, &SpeakerNames::new());
         assert!(result.contains("## Transcript"));
         assert!(result.contains("[00:00:12] **Speaker 1:** Hello everyone."));
         assert!(result.contains("[00:00:20] **Speaker 2:** Hi there!"));
@@ -552,16 +1168,43 @@ Background info...
             timestamp: "00:01:00".to_string(),
             speaker: None,
             text: "Unattributed speech.".to_string(),
+            audio_start_time: None,
         }];
 
-        let result = format_transcript_section(&segments);
+        let result = format_transcript_section(&segments, &SpeakerNames::new());
         assert!(result.contains("[00:01:00] Unattributed speech."));
         assert!(!result.contains("**"));
     }
 
+    #[test]
+    fn test_transcript_lines_get_citation_block_ids() {
+        let segment = |timestamp: &str, start: Option<f64>| TranscriptSegment {
+            timestamp: timestamp.to_string(),
+            speaker: None,
+            text: "Budget.".to_string(),
+            audio_start_time: start,
+        };
+        let segments = vec![
+            segment("00:02:15", Some(135.4)),
+            segment("00:02:15", None),
+            segment("00:02:15", Some(135.9)),
+            segment("01:05:03", Some(3903.0)),
+        ];
+
+        let result = format_transcript_section(&segments, &SpeakerNames::new());
+        assert!(result.contains("[00:02:15] Budget. ^t-0215\n"));
+        assert!(result.contains("[00:02:15] Budget.\n"));
+        assert!(result.contains("[00:02:15] Budget. ^t-0215-2\n"));
+        assert!(!result.contains("t-0215-3"));
+        assert!(result.contains("[01:05:03] Budget. ^t-6503\n"));
+
+        let summary = insert_summary_section(&result, &citations::to_obsidian_links("- Approved [02:15](#t-0215)"));
+        assert!(summary.contains("- Approved [[#^t-0215|02:15]]"));
+    }
+
     #[test]
     fn test_format_transcript_empty() {
-        let result = format_transcript_section(&[]);
+        let result = format_transcript_section(&[], &SpeakerNames::new());
         assert!(result.contains("## Transcript"));
         assert!(result.contains("No transcript recorded"));
     }
@@ -601,10 +1244,11 @@ type: meeting
                 timestamp: "00:00:12".to_string(),
                 speaker: Some("Speaker 1".to_string()),
                 text: "Welcome everyone.".to_string(),
+                audio_start_time: None,
             },
         ];
 
-        let result = merge_transcript(SAMPLE_FILE, &segments, true).unwrap();
+        let result = merge_transcript(SAMPLE_FILE, &segments, true, &SpeakerNames::new()).unwrap();
 
         // Status should be updated
         assert!(result.contains("status: completed"));
@@ -644,9 +1288,10 @@ Some notes.
             timestamp: "00:01:00".to_string(),
             speaker: Some("Speaker 1".to_string()),
             text: "New transcript.".to_string(),
+            audio_start_time: None,
         }];
 
-        let result = merge_transcript(content_with_transcript, &segments, false).unwrap();
+        let result = merge_transcript(content_with_transcript, &segments, false, &SpeakerNames::new()).unwrap();
 
         // Old transcript should be replaced
         assert!(!result.contains("Old transcript content"));
@@ -684,21 +1329,28 @@ Content here.
         let data = MeetingMarkdownData {
             date: "2026-01-28".to_string(),
             time_start: "10:30:00".to_string(),
-            duration: "45:12".to_string(),
-            recording_filename: Some("meeting-2026-01-28_10-30-00.mp4".to_string()),
+            metadata: NoteMetadata {
+                duration_seconds: Some(2712.0),
+                recording: Some("meeting-2026-01-28_10-30-00.mp4".to_string()),
+                transcript_provider: Some("deepgram".to_string()),
+                ..Default::default()
+            },
             entries: vec![
                 MarkdownTranscriptEntry {
                     display_time: "[00:00:12]".to_string(),
                     text: "Welcome everyone.".to_string(),
                     speaker: Some("Speaker 1".to_string()),
+                    audio_start_time: None,
                 },
                 MarkdownTranscriptEntry {
                     display_time: "[00:00:34]".to_string(),
                     text: "Thanks for having us.".to_string(),
                     speaker: Some("Speaker 2".to_string()),
+                    audio_start_time: None,
                 },
             ],
             summary: None,
+            speaker_names: SpeakerNames::new(),
         };
 
         let result = generate_meeting_markdown(&data);
@@ -706,8 +1358,10 @@ Content here.
         // Check frontmatter
         assert!(result.contains("date: 2026-01-28"));
         assert!(result.contains("time_start: 10:30:00"));
-        assert!(result.contains("duration: 45:12"));
+        assert!(result.contains("duration: 45 minutes, 12 seconds"));
         assert!(result.contains("recording: meeting-2026-01-28_10-30-00.mp4"));
+        assert!(result.contains("transcript_provider: deepgram"));
+        assert!(result.contains("speakers:\n  - \"Speaker 1\"\n  - \"Speaker 2\"\n"));
         assert!(result.contains("status: completed"));
 
         // Check title
@@ -719,21 +1373,62 @@ Content here.
         assert!(result.contains("[00:00:34] **Speaker 2:** Thanks for having us."));
     }
 
+    #[test]
+    fn test_summary_citations_link_to_standalone_note_rows() {
+        // Transcript rows as the summarizer sees them: recording-relative times,
+        // with a row's further speaker turns on lines of their own
+        let cited = citations::annotate_transcript(
+            "[02:15] [Speaker 0]: Budget?\n[Speaker 1]: Approved.\n[02:15] Next item.",
+        );
+        let summary = citations::link_citations("- Budget approved [t-0215]\n- Moved on [t-0215-2]", &cited.segments);
+
+        // The same rows written with wall-clock display times, one entry per speaker turn
+        let entry = |text: &str, speaker: Option<&str>, start: Option<f64>| MarkdownTranscriptEntry {
+            display_time: "[10:32:15]".to_string(),
+            text: text.to_string(),
+            speaker: speaker.map(String::from),
+            audio_start_time: start,
+        };
+        let data = MeetingMarkdownData {
+            date: "2026-01-28".to_string(),
+            time_start: "10:30:00".to_string(),
+            metadata: NoteMetadata::default(),
+            entries: vec![
+                entry("Budget?", Some("Speaker 0"), Some(135.2)),
+                entry("Approved.", Some("Speaker 1"), None),
+                entry("Next item.", None, Some(135.7)),
+            ],
+            summary: None,
+            speaker_names: SpeakerNames::new(),
+        };
+        let note = insert_summary_section(&generate_meeting_markdown(&data), &citations::to_obsidian_links(&summary));
+
+        assert!(note.contains("- Budget approved [[#^t-0215|02:15]]"));
+        assert!(note.contains("- Moved on [[#^t-0215-2|02:15]]"));
+        assert!(note.contains("[10:32:15] **Speaker 0:** Budget? ^t-0215\n"));
+        assert!(note.contains("[10:32:15] **Speaker 1:** Approved.\n"));
+        assert!(note.contains("[10:32:15] Next item. ^t-0215-2\n"));
+        for id in cited.segments.keys() {
+            assert!(note.contains(&format!(" ^{}\n", id)), "no block for {}", id);
+        }
+    }
+
     #[test]
     fn test_generate_meeting_markdown_with_summary() {
         let data = MeetingMarkdownData {
             date: "2026-01-28".to_string(),
             time_start: "14:00:00".to_string(),
-            duration: "30:00".to_string(),
-            recording_filename: None,
+            metadata: NoteMetadata::default(),
             entries: vec![
                 MarkdownTranscriptEntry {
                     display_time: "[00:01:00]".to_string(),
                     text: "Let's discuss the roadmap.".to_string(),
                     speaker: None,
+                    audio_start_time: None,
                 },
             ],
             summary: Some("## Key Points\n- Discussed roadmap\n- Assigned tasks".to_string()),
+            speaker_names: SpeakerNames::new(),
         };
 
         let result = generate_meeting_markdown(&data);
@@ -753,10 +1448,10 @@ Content here.
         let data = MeetingMarkdownData {
             date: "2026-01-28".to_string(),
             time_start: "09:00:00".to_string(),
-            duration: "00:00".to_string(),
-            recording_filename: None,
+            metadata: NoteMetadata::default(),
             entries: vec![],
             summary: None,
+            speaker_names: SpeakerNames::new(),
         };
 
         let result = generate_meeting_markdown(&data);
@@ -765,6 +1460,41 @@ Content here.
         assert!(result.contains("No transcript recorded"));
     }
 
+    #[tokio::test]
+    async fn test_save_meeting_markdown_to_folder_keeps_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut data = MeetingMarkdownData {
+            date: "2026-01-28".to_string(),
+            time_start: "09:00:00".to_string(),
+            metadata: NoteMetadata::default(),
+            entries: vec![MarkdownTranscriptEntry {
+                display_time: "[00:00:05]".to_string(),
+                text: "First take.".to_string(),
+                speaker: None,
+                audio_start_time: None,
+            }],
+            summary: None,
+            speaker_names: SpeakerNames::new(),
+        };
+        let path = save_meeting_markdown_to_folder(dir.path(), "standup", &data).await.unwrap();
+
+        // Edited in Obsidian before the next save
+        let edited = std::fs::read_to_string(&path)
+            .unwrap()
+            .replace("## Transcript", "## Notes\n- Decided to ship\n\n## Transcript");
+        std::fs::write(&path, edited).unwrap();
+
+        data.metadata.duration_seconds = Some(300.0);
+        data.entries[0].text = "Second take.".to_string();
+        save_meeting_markdown_to_folder(dir.path(), "standup", &data).await.unwrap();
+
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("## Notes\n- Decided to ship"));
+        assert!(saved.contains("Second take."));
+        assert!(!saved.contains("First take."));
+        assert!(saved.contains("duration: 5 minutes"));
+    }
+
     #[test]
     fn test_insert_summary_section_new() {
         let content = r#"---
@@ -824,15 +1554,17 @@ Old summary content.
                 display_time: "[00:00:05]".to_string(),
                 text: "First line.".to_string(),
                 speaker: Some("Alice".to_string()),
+                audio_start_time: None,
             },
             MarkdownTranscriptEntry {
                 display_time: "[00:00:15]".to_string(),
                 text: "Second line.".to_string(),
                 speaker: Some("Bob".to_string()),
+                audio_start_time: None,
             },
         ];
 
-        let result = format_markdown_transcript(&entries);
+        let result = format_markdown_transcript(&entries, &SpeakerNames::new());
 
         assert!(result.contains("[00:00:05] **Alice:** First line."));
         assert!(result.contains("[00:00:15] **Bob:** Second line."));
@@ -845,13 +1577,258 @@ Old summary content.
                 display_time: "[00:01:00]".to_string(),
                 text: "Unattributed text.".to_string(),
                 speaker: None,
+                audio_start_time: None,
             },
         ];
 
-        let result = format_markdown_transcript(&entries);
+        let result = format_markdown_transcript(&entries, &SpeakerNames::new());
 
         assert!(result.contains("[00:01:00] Unattributed text."));
         assert!(!result.contains("**")); // No bold formatting without speaker
     }
-}
 
+    #[test]
+    fn test_format_transcript_section_with_speaker_names() {
+        let segments = vec![
+            TranscriptSegment {
+                timestamp: "00:00:05".to_string(),
+                speaker: Some("Speaker 1".to_string()),
+                text: "Hello.".to_string(),
+                audio_start_time: None,
+            },
+            TranscriptSegment {
+                timestamp: "00:00:09".to_string(),
+                speaker: Some("Speaker 2".to_string()),
+                text: "Hi there.".to_string(),
+                audio_start_time: None,
+            },
+        ];
+        let mut names = SpeakerNames::new();
+        names.insert("Speaker 1".to_string(), "Attendee A".to_string());
+
+        let result = format_transcript_section(&segments, &names);
+
+        assert!(result.contains("[00:00:05] **[[Attendee A]]:** Hello."));
+        assert!(result.contains("[00:00:09] **Speaker 2:** Hi there."));
+    }
+
+    #[test]
+    fn test_format_speaker_name_keeps_existing_wikilink() {
+        let mut names = SpeakerNames::new();
+        names.insert("Speaker 1".to_string(), "[[Attendee A]]".to_string());
+        names.insert("Speaker 2".to_string(), "  ".to_string());
+
+        assert_eq!(format_speaker_name("Speaker 1", &names), "[[Attendee A]]");
+        assert_eq!(format_speaker_name("Speaker 2", &names), "Speaker 2");
+        assert_eq!(format_speaker_name("Speaker 3", &names), "Speaker 3");
+    }
+
+    #[test]
+    fn test_rename_speakers_only_in_transcript_section() {
+        let content = "---\nstatus: completed\n---\n\n## Summary\n\n**Speaker 1:** proposed a plan.\n\n## Transcript\n\n[00:00:05] **Speaker 1:** Hello.\n\n[00:00:09] **[[Attendee A]]:** Hi.\n\n## Notes\n\n[00:00:10] **Speaker 1:** not a transcript line\n";
+        let mut replacements = HashMap::new();
+        replacements.insert("Speaker 1".to_string(), "[[Attendee A]]".to_string());
+        replacements.insert("[[Attendee A]]".to_string(), "[[Attendee B]]".to_string());
+
+        let (result, changed) = rename_speakers_in_markdown(content, &replacements);
+
+        assert_eq!(changed, 2);
+        assert!(result.contains("[00:00:05] **[[Attendee A]]:** Hello."));
+        assert!(result.contains("[00:00:09] **[[Attendee B]]:** Hi."));
+        assert!(result.contains("**Speaker 1:** proposed a plan."));
+        assert!(result.contains("[00:00:10] **Speaker 1:** not a transcript line"));
+        assert!(result.ends_with('\n'));
+    }
+
+    #[test]
+    fn test_extract_transcript_speakers() {
+        let content = "## Transcript\n\n[00:00:05] **Speaker 1:** Hello.\n\n[00:00:09] **[[Attendee A]]:** Hi.\n\n[00:00:12] **Speaker 1:** Again.\n\n[00:00:15] No speaker.\n";
+
+        let speakers = extract_transcript_speakers(content);
+
+        assert_eq!(speakers, vec!["Speaker 1".to_string(), "[[Attendee A]]".to_string()]);
+    }
+
+    #[test]
+    fn test_format_and_parse_action_item() {
+        let item = ActionItem {
+            text: "Send the deck".to_string(),
+            owner: Some("Attendee A".to_string()),
+            due: Some("2026-10-20".to_string()),
+            done: false,
+        };
+        let line = format_action_item(&item);
+        assert_eq!(line, "- [ ] Send the deck 📅 2026-10-20 [[Attendee A]]");
+        assert_eq!(parse_action_item(&line), Some(item));
+
+        let done = parse_action_item("- [x] Book the venue ⏫ ✅ 2026-10-18").unwrap();
+        assert!(done.done);
+        assert_eq!(done.text, "Book the venue");
+        assert!(done.owner.is_none() && done.due.is_none());
+
+        assert!(parse_action_item("- plain bullet").is_none());
+    }
+
+    #[test]
+    fn test_merge_action_items_dedupes_and_keeps_checked_state() {
+        let content = "# Sync\n\n## Action Items\n- [x] Send the deck 📅 2026-10-10 [[Attendee A]]\n- [ ] Book the venue\n\n## Transcript\n\n[00:00] Hi\n";
+        let items = vec![
+            ActionItem {
+                text: "Send the deck".to_string(),
+                owner: Some("Attendee B".to_string()),
+                due: Some("2026-10-20".to_string()),
+                done: false,
+            },
+            ActionItem {
+                text: "book the venue!".to_string(),
+                owner: Some("Attendee B".to_string()),
+                due: Some("2026-10-21".to_string()),
+                done: false,
+            },
+            ActionItem {
+                text: "Write notes".to_string(),
+                owner: Some("Attendee C".to_string()),
+                due: None,
+                done: false,
+            },
+            ActionItem {
+                text: "Write notes".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let (merged, added) = merge_action_items(content, &items);
+
+        assert_eq!(added, 1);
+        assert_eq!(
+            merged,
+            "# Sync\n\n## Action Items\n- [x] Send the deck 📅 2026-10-10 [[Attendee A]]\n- [ ] Book the venue 📅 2026-10-21 [[Attendee B]]\n- [ ] Write notes [[Attendee C]]\n\n## Transcript\n\n[00:00] Hi\n"
+        );
+
+        // Merging again is a no-op
+        let (again, added_again) = merge_action_items(&merged, &items);
+        assert_eq!(added_again, 0);
+        assert_eq!(again, merged);
+    }
+
+    #[test]
+    fn test_merge_action_items_creates_section_before_transcript() {
+        let content = "# Sync\n\n## Notes\n- hi\n\n## Transcript\n\n[00:00] Hi\n";
+        let items = vec![ActionItem {
+            text: "Ship it".to_string(),
+            due: Some("2026-10-20".to_string()),
+            ..Default::default()
+        }];
+
+        let (merged, added) = merge_action_items(content, &items);
+
+        assert_eq!(added, 1);
+        assert_eq!(
+            merged,
+            "# Sync\n\n## Notes\n- hi\n\n## Action Items\n\n- [ ] Ship it 📅 2026-10-20\n\n## Transcript\n\n[00:00] Hi\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_action_items_replaces_placeholder() {
+        let items = vec![ActionItem {
+            text: "Task".to_string(),
+            ..Default::default()
+        }];
+        let (merged, _) = merge_action_items("## Action Items\n- [ ]\n", &items);
+        assert_eq!(merged, "## Action Items\n- [ ] Task\n");
+    }
+
+    #[test]
+    fn test_merge_action_items_ignores_nested_heading() {
+        let items = vec![ActionItem {
+            text: "Send the deck".to_string(),
+            ..Default::default()
+        }];
+        let content = "# Kickoff\n\n## Summary\n\n### Action Items\n- Discussed owners\n\n## Transcript\n";
+        let (merged, added) = merge_action_items(content, &items);
+        assert_eq!(added, 1);
+        assert_eq!(
+            merged,
+            "# Kickoff\n\n## Summary\n\n### Action Items\n- Discussed owners\n\n## Action Items\n\n- [ ] Send the deck\n\n## Transcript\n"
+        );
+    }
+
+    fn transcript_request(path: &Path, base: &str) -> SaveTranscriptRequest {
+        SaveTranscriptRequest {
+            file_path: path.to_string_lossy().to_string(),
+            segments: vec![TranscriptSegment {
+                timestamp: "00:00:12".to_string(),
+                speaker: Some("Speaker 1".to_string()),
+                text: "Welcome everyone.".to_string(),
+                audio_start_time: None,
+            }],
+            update_status: true,
+            speaker_names: SpeakerNames::new(),
+            action_items: Vec::new(),
+            base_content: Some(base.to_string()),
+            metadata: NoteMetadata::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_transcript_merges_concurrent_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kickoff.md");
+        // Edited in Obsidian while recording
+        let edited = SAMPLE_FILE.replace("Background info...", "Background info...\nBudget is fixed.");
+        std::fs::write(&path, &edited).unwrap();
+
+        let outcome = save_transcript(&transcript_request(&path, SAMPLE_FILE)).await.unwrap();
+        let saved = std::fs::read_to_string(&path).unwrap();
+
+        assert!(outcome.merged_concurrent_edits);
+        assert!(outcome.conflict_path.is_none());
+        assert!(saved.contains("Budget is fixed."));
+        assert!(saved.contains("status: completed"));
+        assert!(saved.contains("[00:00:12] **Speaker 1:** Welcome everyone."));
+    }
+
+    #[tokio::test]
+    async fn test_save_transcript_writes_conflict_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kickoff.md");
+        let base = format!("{}\n## Transcript\n(pending)\n", SAMPLE_FILE);
+        let edited = base.replace("(pending)", "My own notes on what was said");
+        std::fs::write(&path, &edited).unwrap();
+
+        let outcome = save_transcript(&transcript_request(&path, &base)).await.unwrap();
+        let saved = std::fs::read_to_string(&path).unwrap();
+
+        assert!(saved.contains("My own notes on what was said"));
+        assert_eq!(outcome.conflicting_sections, vec!["## Transcript".to_string()]);
+        let conflict = std::fs::read_to_string(outcome.conflict_path.unwrap()).unwrap();
+        assert!(conflict.contains("[00:00:12] **Speaker 1:** Welcome everyone."));
+    }
+
+    #[test]
+    fn test_merge_writes_note_metadata() {
+        let mut request = transcript_request(Path::new("note.md"), SAMPLE_FILE);
+        request.metadata = NoteMetadata {
+            time_end: Some("2026-01-22T11:00:00".to_string()),
+            duration_seconds: Some(2700.0),
+            recording: Some("/recordings/kickoff.mp4".to_string()),
+            transcript_provider: Some("deepgram".to_string()),
+            summary_template: None,
+        };
+        request.speaker_names.insert("Speaker 1".to_string(), "Alice".to_string());
+
+        let content = apply_transcript_request(SAMPLE_FILE, &request).unwrap();
+        let prep = crate::obsidian::parse_meeting_file(Path::new("note.md"), &content).unwrap();
+
+        assert_eq!(prep.frontmatter.status.as_deref(), Some("completed"));
+        assert_eq!(prep.frontmatter.time_end.as_deref(), Some("2026-01-22T11:00:00"));
+        assert_eq!(prep.frontmatter.duration.as_deref(), Some("45 minutes"));
+        assert_eq!(prep.frontmatter.speakers, Some(vec!["[[Alice]]".to_string()]));
+        assert_eq!(prep.frontmatter.recording.as_deref(), Some("file:///recordings/kickoff.mp4"));
+        assert_eq!(prep.frontmatter.transcript_provider.as_deref(), Some("deepgram"));
+        // Existing keys keep their position
+        assert!(content.find("date: 2026-01-22").unwrap() < content.find("status: completed").unwrap());
+        assert!(content.find("status: completed").unwrap() < content.find("time_end:").unwrap());
+    }
+}
@@ -0,0 +1,482 @@
+use super::embeddings::{
+    local_model_dir, EmbeddingClient, DEFAULT_LOCAL_MODEL, DEFAULT_OLLAMA_MODEL, DEFAULT_OPENAI_MODEL,
+    LOCAL_PROVIDER,
+};
+use super::index::{format_offset, index_meeting, top_k};
+use crate::database::repositories::embedding::EmbeddingsRepository;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::setting::SettingsRepository;
+use crate::state::AppState;
+use crate::summary::llm_client::{generate_summary, LLMProvider};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+const DEFAULT_TOP_K: usize = 8;
+const MAX_TOP_K: usize = 50;
+
+/// Matches `[n]` citation markers in a generated answer
+static CITATION_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[(\d{1,3})\]").unwrap());
+
+const ASK_SYSTEM_PROMPT: &str = "You answer questions about the user's past meetings using only the numbered excerpts provided. \
+Cite every statement with the excerpt number in square brackets, e.g. [2]. \
+If the excerpts do not contain the answer, say so instead of guessing. Answer in markdown.";
+
+/// Embedding provider/model selection shared by the semantic commands
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingSelection {
+    /// "local", "ollama", "openai" or "custom-openai"
+    pub provider: Option<String>,
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticIndexRequest {
+    #[serde(default)]
+    pub embedding: EmbeddingSelection,
+    /// Index only these meetings (default: every meeting that is new or changed)
+    pub meeting_ids: Option<Vec<String>>,
+    /// Drop all vectors of the model first and re-embed everything
+    #[serde(default)]
+    pub rebuild: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticIndexResult {
+    pub model: String,
+    pub indexed_meetings: usize,
+    pub chunks: usize,
+    pub failed: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticIndexProgress {
+    pub meeting_id: String,
+    pub completed: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticSearchRequest {
+    pub query: String,
+    #[serde(default)]
+    pub embedding: EmbeddingSelection,
+    pub meeting_ids: Option<Vec<String>>,
+    pub limit: Option<usize>,
+}
+
+/// A retrieved chunk with the meeting and recording position it comes from
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticSearchHit {
+    pub meeting_id: String,
+    pub meeting_title: String,
+    pub meeting_created_at: String,
+    /// "transcript" or "summary"
+    pub source_type: String,
+    /// Transcript id of the first segment in the chunk, or the meeting id for summaries
+    pub source_id: String,
+    pub content: String,
+    pub timestamp: Option<String>,
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AskMeetingsRequest {
+    pub question: String,
+    #[serde(default)]
+    pub embedding: EmbeddingSelection,
+    pub meeting_ids: Option<Vec<String>>,
+    pub top_k: Option<usize>,
+    /// LLM used for the answer (defaults to the summary model settings)
+    pub llm_provider: Option<String>,
+    pub llm_model: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Citation {
+    /// Number used as `[n]` in the answer
+    pub index: usize,
+    /// Whether the answer actually references this excerpt
+    pub cited: bool,
+    #[serde(flatten)]
+    pub hit: SemanticSearchHit,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AskMeetingsResponse {
+    pub answer: String,
+    pub citations: Vec<Citation>,
+}
+
+/// Resolve provider and model, preferring an installed local model
+fn resolve_embedding<R: Runtime>(app: &AppHandle<R>, selection: &EmbeddingSelection) -> (String, String) {
+    let provider = selection
+        .provider
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(str::to_lowercase)
+        .unwrap_or_else(|| {
+            let local_installed = local_model_dir(app, DEFAULT_LOCAL_MODEL)
+                .map(|dir| dir.join("model.onnx").exists())
+                .unwrap_or(false);
+            if local_installed { LOCAL_PROVIDER } else { "ollama" }.to_string()
+        });
+
+    let model = selection
+        .model
+        .as_deref()
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            match provider.as_str() {
+                LOCAL_PROVIDER => DEFAULT_LOCAL_MODEL,
+                "openai" => DEFAULT_OPENAI_MODEL,
+                _ => DEFAULT_OLLAMA_MODEL,
+            }
+            .to_string()
+        });
+
+    (provider, model)
+}
+
+async fn embedding_client<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    selection: &EmbeddingSelection,
+) -> Result<EmbeddingClient, String> {
+    let (provider, model) = resolve_embedding(app, selection);
+    EmbeddingClient::from_settings(app, pool, &provider, &model).await
+}
+
+/// Embed the query and return the closest chunks with their meeting metadata
+async fn retrieve(
+    pool: &SqlitePool,
+    client: &EmbeddingClient,
+    query: &str,
+    meeting_ids: Option<&[String]>,
+    limit: usize,
+) -> Result<Vec<SemanticSearchHit>, String> {
+    let query_vector = client
+        .embed(&[query.to_string()])
+        .await?
+        .pop()
+        .ok_or_else(|| "Embedding provider returned no vector for the query".to_string())?;
+
+    let embeddings = EmbeddingsRepository::get_embeddings_for_model(pool, client.model_key(), meeting_ids)
+        .await
+        .map_err(|e| format!("Failed to load embeddings: {}", e))?;
+    if embeddings.is_empty() {
+        return Err(format!(
+            "No meetings are indexed for {}. Run indexing first.",
+            client.model_key()
+        ));
+    }
+
+    let meetings: HashMap<String, (String, String)> = MeetingsRepository::get_meetings(pool)
+        .await
+        .map_err(|e| format!("Failed to load meetings: {}", e))?
+        .into_iter()
+        .map(|m| (m.id, (m.title, m.created_at.0.to_rfc3339())))
+        .collect();
+
+    Ok(top_k(&query_vector, embeddings, limit)
+        .into_iter()
+        .map(|(score, embedding)| {
+            let (meeting_title, meeting_created_at) = meetings
+                .get(&embedding.meeting_id)
+                .cloned()
+                .unwrap_or_default();
+            SemanticSearchHit {
+                meeting_id: embedding.meeting_id,
+                meeting_title,
+                meeting_created_at,
+                source_type: embedding.source_type,
+                source_id: embedding.source_id,
+                content: embedding.content,
+                timestamp: embedding.timestamp,
+                start_time: embedding.start_time,
+                end_time: embedding.end_time,
+                score,
+            }
+        })
+        .collect())
+}
+
+/// Numbered excerpts for the answer prompt
+fn build_context(hits: &[SemanticSearchHit]) -> String {
+    hits.iter()
+        .enumerate()
+        .map(|(i, hit)| {
+            let date = hit.meeting_created_at.get(..10).unwrap_or("");
+            let location = match hit.start_time {
+                Some(start) => format!("at [{}]", format_offset(start)),
+                None => format!("({})", hit.source_type),
+            };
+            format!(
+                "[{}] Meeting \"{}\" ({}) {}:\n{}",
+                i + 1,
+                hit.meeting_title,
+                date,
+                location,
+                hit.content
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Excerpt numbers referenced as `[n]` in the answer
+fn cited_indices(answer: &str) -> HashSet<usize> {
+    CITATION_RE
+        .captures_iter(answer)
+        .filter_map(|caps| caps[1].parse().ok())
+        .collect()
+}
+
+/// Serializes background indexing so embedding runs don't pile up
+static AUTO_INDEX_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+/// Re-index one meeting in the background after its transcript or summary changed
+///
+/// Only runs when the local embedding model is installed: meeting text never
+/// leaves the machine and no API usage is incurred without an explicit
+/// `api_semantic_index` request.
+pub fn spawn_auto_index<R: Runtime>(app: &AppHandle<R>, pool: SqlitePool, meeting_id: String) {
+    let local_installed = local_model_dir(app, DEFAULT_LOCAL_MODEL)
+        .map(|dir| dir.join("model.onnx").exists())
+        .unwrap_or(false);
+    if !local_installed {
+        log::debug!("No local embedding model, skipping automatic indexing of {}", meeting_id);
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _guard = AUTO_INDEX_LOCK.lock().await;
+        let selection = EmbeddingSelection {
+            provider: Some(LOCAL_PROVIDER.to_string()),
+            model: None,
+        };
+        let client = match embedding_client(&app, &pool, &selection).await {
+            Ok(client) => client,
+            Err(e) => {
+                log::warn!("Automatic indexing of {} skipped: {}", meeting_id, e);
+                return;
+            }
+        };
+        match index_meeting(&pool, &client, &meeting_id).await {
+            Ok(chunks) => log::info!("Indexed meeting {} for semantic search ({} chunks)", meeting_id, chunks),
+            Err(e) => log::warn!("Automatic indexing of {} failed: {}", meeting_id, e),
+        }
+    });
+}
+
+/// Tauri command to embed meetings for semantic search
+///
+/// Emits `semantic-index-progress` after every meeting.
+#[tauri::command]
+pub async fn api_semantic_index<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    request: SemanticIndexRequest,
+) -> Result<SemanticIndexResult, String> {
+    let pool = state.db_manager.pool();
+    let client = embedding_client(&app, pool, &request.embedding).await?;
+    let model = client.model_key().to_string();
+    log::info!("Semantic indexing with {} (rebuild: {})", model, request.rebuild);
+
+    if request.rebuild {
+        EmbeddingsRepository::delete_embeddings_for_model(pool, &model)
+            .await
+            .map_err(|e| format!("Failed to clear embeddings: {}", e))?;
+    }
+
+    let meeting_ids = match request.meeting_ids {
+        Some(ids) if !ids.is_empty() => ids,
+        _ => EmbeddingsRepository::get_meetings_to_index(pool, &model)
+            .await
+            .map_err(|e| format!("Failed to list meetings to index: {}", e))?,
+    };
+
+    let total = meeting_ids.len();
+    let mut result = SemanticIndexResult {
+        model,
+        indexed_meetings: 0,
+        chunks: 0,
+        failed: Vec::new(),
+    };
+
+    for (i, meeting_id) in meeting_ids.into_iter().enumerate() {
+        match index_meeting(pool, &client, &meeting_id).await {
+            Ok(chunks) => {
+                result.indexed_meetings += 1;
+                result.chunks += chunks;
+            }
+            Err(e) => {
+                log::error!("Failed to index meeting {}: {}", meeting_id, e);
+                result.failed.push((meeting_id.clone(), e));
+            }
+        }
+
+        let _ = app.emit(
+            "semantic-index-progress",
+            SemanticIndexProgress {
+                meeting_id,
+                completed: i + 1,
+                total,
+            },
+        );
+    }
+
+    log::info!(
+        "Semantic indexing finished: {} meetings, {} chunks, {} failed",
+        result.indexed_meetings,
+        result.chunks,
+        result.failed.len()
+    );
+    Ok(result)
+}
+
+/// Tauri command for meaning-based search across indexed meetings
+#[tauri::command]
+pub async fn api_semantic_search<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    request: SemanticSearchRequest,
+) -> Result<Vec<SemanticSearchHit>, String> {
+    if request.query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let pool = state.db_manager.pool();
+    let client = embedding_client(&app, pool, &request.embedding).await?;
+    let limit = request.limit.unwrap_or(DEFAULT_TOP_K).clamp(1, MAX_TOP_K);
+
+    retrieve(pool, &client, request.query.trim(), request.meeting_ids.as_deref(), limit).await
+}
+
+/// Tauri command to answer a question from meeting content
+///
+/// Retrieves the closest chunks, asks the LLM to answer with `[n]` references
+/// and returns the answer with the cited meetings and recording times.
+#[tauri::command]
+pub async fn api_ask_meetings<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    request: AskMeetingsRequest,
+) -> Result<AskMeetingsResponse, String> {
+    let question = request.question.trim();
+    if question.is_empty() {
+        return Err("Question cannot be empty".to_string());
+    }
+
+    let pool = state.db_manager.pool();
+    let client = embedding_client(&app, pool, &request.embedding).await?;
+    let limit = request.top_k.unwrap_or(DEFAULT_TOP_K).clamp(1, MAX_TOP_K);
+    let hits = retrieve(pool, &client, question, request.meeting_ids.as_deref(), limit).await?;
+
+    let config = SettingsRepository::get_model_config(pool)
+        .await
+        .map_err(|e| format!("Failed to load model settings: {}", e))?;
+    let provider_name = request
+        .llm_provider
+        .clone()
+        .or_else(|| config.as_ref().map(|c| c.provider.clone()))
+        .ok_or_else(|| "No LLM provider configured".to_string())?;
+    let model_name = request
+        .llm_model
+        .clone()
+        .or_else(|| config.as_ref().map(|c| c.model.clone()))
+        .ok_or_else(|| "No LLM model configured".to_string())?;
+    let provider = LLMProvider::from_str(&provider_name)?;
+
+    let mut api_key = String::new();
+    let mut ollama_endpoint = None;
+    let mut custom_openai_endpoint = None;
+    let (mut max_tokens, mut temperature, mut top_p) = (None, None, None);
+    match provider {
+        LLMProvider::Ollama => {
+            ollama_endpoint = config.as_ref().and_then(|c| c.ollama_endpoint.clone());
+        }
+        LLMProvider::BuiltInAI => {}
+        LLMProvider::CustomOpenAI => {
+            let custom = SettingsRepository::get_custom_openai_config(pool)
+                .await
+                .map_err(|e| format!("Failed to retrieve custom OpenAI config: {}", e))?
+                .ok_or_else(|| "Custom OpenAI provider selected but no configuration found".to_string())?;
+            custom_openai_endpoint = Some(custom.endpoint);
+            api_key = custom.api_key.unwrap_or_default();
+            max_tokens = custom.max_tokens.map(|t| t as u32);
+            temperature = custom.temperature;
+            top_p = custom.top_p;
+        }
+        _ => {
+            api_key = SettingsRepository::get_api_key(pool, &provider_name)
+                .await
+                .map_err(|e| format!("Failed to retrieve API key for {}: {}", provider_name, e))?
+                .filter(|key| !key.is_empty())
+                .ok_or_else(|| format!("API key not found for {}", provider_name))?;
+        }
+    }
+
+    let user_prompt = format!(
+        "<excerpts>\n{}\n</excerpts>\n\nQuestion: {}",
+        build_context(&hits),
+        question
+    );
+    let app_data_dir = app.path().app_data_dir().ok();
+
+    log::info!(
+        "Answering meeting question with {} excerpts using {}/{}",
+        hits.len(),
+        provider_name,
+        model_name
+    );
+
+    let answer = generate_summary(
+        &Client::new(),
+        &provider,
+        &model_name,
+        &api_key,
+        ASK_SYSTEM_PROMPT,
+        &user_prompt,
+        ollama_endpoint.as_deref(),
+        custom_openai_endpoint.as_deref(),
+        max_tokens,
+        temperature,
+        top_p,
+        app_data_dir.as_ref(),
+        None,
+    )
+    .await?;
+
+    let cited = cited_indices(&answer);
+    let citations = hits
+        .into_iter()
+        .enumerate()
+        .map(|(i, hit)| Citation {
+            index: i + 1,
+            cited: cited.contains(&(i + 1)),
+            hit,
+        })
+        .collect();
+
+    Ok(AskMeetingsResponse { answer, citations })
+}
@@ -0,0 +1,501 @@
+//! Embedding providers for semantic search
+//!
+//! - `local`: BERT-family sentence embedding model (e.g. all-MiniLM-L6-v2) run through `ort`,
+//!   installed at `<app_data>/models/embeddings/<model>/` as `model.onnx` + `vocab.txt`
+//! - `ollama`: `POST {endpoint}/api/embed` (endpoint from the model settings)
+//! - `openai`: OpenAI `/v1/embeddings` with the stored OpenAI API key
+//! - `custom-openai`: `{endpoint}/embeddings` of the configured OpenAI-compatible server
+
+use crate::database::repositories::setting::SettingsRepository;
+use crate::summary::llm_client::LLMProvider;
+use ndarray::Array2;
+use ort::execution_providers::CPUExecutionProvider;
+use ort::inputs;
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::TensorRef;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Runtime};
+
+pub const LOCAL_PROVIDER: &str = "local";
+pub const DEFAULT_LOCAL_MODEL: &str = "all-MiniLM-L6-v2";
+pub const DEFAULT_OLLAMA_MODEL: &str = "nomic-embed-text";
+pub const DEFAULT_OPENAI_MODEL: &str = "text-embedding-3-small";
+
+const EMBEDDING_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+const EMBEDDING_BATCH_SIZE: usize = 32;
+const LOCAL_MAX_TOKENS: usize = 256;
+
+// ============================================================================
+// WordPiece tokenizer (BERT vocab.txt)
+// ============================================================================
+
+/// Minimal uncased WordPiece tokenizer for BERT-style embedding models
+pub struct WordPieceTokenizer {
+    vocab: HashMap<String, i64>,
+    unk_id: i64,
+    cls_id: i64,
+    sep_id: i64,
+}
+
+impl WordPieceTokenizer {
+    pub fn from_vocab_file(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read vocabulary {}: {}", path.display(), e))?;
+        Self::from_vocab(content.lines())
+    }
+
+    pub fn from_vocab<'a>(tokens: impl Iterator<Item = &'a str>) -> Result<Self, String> {
+        let vocab: HashMap<String, i64> = tokens
+            .enumerate()
+            .map(|(id, token)| (token.trim_end().to_string(), id as i64))
+            .collect();
+
+        let lookup = |token: &str| {
+            vocab
+                .get(token)
+                .copied()
+                .ok_or_else(|| format!("Vocabulary is missing {}", token))
+        };
+
+        Ok(Self {
+            unk_id: lookup("[UNK]")?,
+            cls_id: lookup("[CLS]")?,
+            sep_id: lookup("[SEP]")?,
+            vocab,
+        })
+    }
+
+    /// Tokenize into ids wrapped in [CLS] ... [SEP], truncated to `max_len`
+    pub fn encode(&self, text: &str, max_len: usize) -> Vec<i64> {
+        let mut ids = vec![self.cls_id];
+        let budget = max_len.saturating_sub(2);
+
+        'words: for word in basic_tokenize(text) {
+            for id in self.wordpiece(&word) {
+                if ids.len() - 1 >= budget {
+                    break 'words;
+                }
+                ids.push(id);
+            }
+        }
+
+        ids.push(self.sep_id);
+        ids
+    }
+
+    fn wordpiece(&self, word: &str) -> Vec<i64> {
+        let chars: Vec<char> = word.chars().collect();
+        if chars.len() > 100 {
+            return vec![self.unk_id];
+        }
+
+        let mut pieces = Vec::new();
+        let mut start = 0;
+        while start < chars.len() {
+            let mut end = chars.len();
+            let mut found = None;
+            while start < end {
+                let piece: String = chars[start..end].iter().collect();
+                let candidate = if start > 0 { format!("##{}", piece) } else { piece };
+                if let Some(id) = self.vocab.get(&candidate) {
+                    found = Some(*id);
+                    break;
+                }
+                end -= 1;
+            }
+            match found {
+                Some(id) => {
+                    pieces.push(id);
+                    start = end;
+                }
+                None => return vec![self.unk_id],
+            }
+        }
+        pieces
+    }
+}
+
+/// Lowercase, split on whitespace and isolate punctuation
+fn basic_tokenize(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_whitespace() || c.is_control() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        } else if c.is_ascii_punctuation() || (!c.is_alphanumeric() && !c.is_whitespace()) {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            words.push(c.to_string());
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+// ============================================================================
+// Local ONNX embedding model
+// ============================================================================
+
+pub struct LocalEmbeddingModel {
+    session: Session,
+    tokenizer: WordPieceTokenizer,
+    output_name: String,
+    has_token_type_ids: bool,
+}
+
+impl LocalEmbeddingModel {
+    pub fn new(model_dir: &Path) -> Result<Self, String> {
+        let model_path = model_dir.join("model.onnx");
+        if !model_path.exists() {
+            return Err(format!("Embedding model not found: {}", model_path.display()));
+        }
+        let tokenizer = WordPieceTokenizer::from_vocab_file(&model_dir.join("vocab.txt"))?;
+
+        let session = Session::builder()
+            .and_then(|b| b.with_optimization_level(GraphOptimizationLevel::Level3))
+            .and_then(|b| b.with_execution_providers(vec![CPUExecutionProvider::default().build()]))
+            .and_then(|b| b.commit_from_file(&model_path))
+            .map_err(|e| format!("Failed to load embedding model: {}", e))?;
+
+        let has_token_type_ids = session.inputs.iter().any(|input| input.name == "token_type_ids");
+        let output_name = session
+            .outputs
+            .first()
+            .map(|output| output.name.clone())
+            .ok_or_else(|| "Embedding model has no outputs".to_string())?;
+
+        log::info!(
+            "Loaded local embedding model from {} (output '{}')",
+            model_dir.display(),
+            output_name
+        );
+
+        Ok(Self {
+            session,
+            tokenizer,
+            output_name,
+            has_token_type_ids,
+        })
+    }
+
+    /// Mean-pooled, L2-normalized sentence embedding
+    pub fn embed(&mut self, text: &str) -> Result<Vec<f32>, String> {
+        let ids = self.tokenizer.encode(text, LOCAL_MAX_TOKENS);
+        let len = ids.len();
+        let input_ids = Array2::from_shape_vec((1, len), ids).map_err(|e| e.to_string())?;
+        let attention_mask = Array2::<i64>::ones((1, len));
+        let token_type_ids = Array2::<i64>::zeros((1, len));
+
+        let to_tensor = |array: &Array2<i64>| {
+            TensorRef::from_array_view(array.view()).map_err(|e| format!("Failed to build tensor: {}", e))
+        };
+
+        let outputs = if self.has_token_type_ids {
+            self.session.run(inputs![
+                "input_ids" => to_tensor(&input_ids)?,
+                "attention_mask" => to_tensor(&attention_mask)?,
+                "token_type_ids" => to_tensor(&token_type_ids)?,
+            ])
+        } else {
+            self.session.run(inputs![
+                "input_ids" => to_tensor(&input_ids)?,
+                "attention_mask" => to_tensor(&attention_mask)?,
+            ])
+        }
+        .map_err(|e| format!("Embedding inference failed: {}", e))?;
+
+        let output = outputs
+            .get(self.output_name.as_str())
+            .ok_or_else(|| format!("Embedding output '{}' missing", self.output_name))?
+            .try_extract_array::<f32>()
+            .map_err(|e| format!("Failed to read embedding output: {}", e))?;
+
+        let embedding: Vec<f32> = match output.ndim() {
+            // [1, tokens, hidden]: mean pool over tokens (all tokens are attended)
+            3 => {
+                let hidden = output.shape()[2];
+                let tokens = output.shape()[1].max(1);
+                let mut pooled = vec![0.0f32; hidden];
+                for (i, value) in output.iter().enumerate() {
+                    pooled[i % hidden] += value;
+                }
+                pooled.iter().map(|v| v / tokens as f32).collect()
+            }
+            // [1, hidden]: already pooled
+            _ => output.iter().copied().collect(),
+        };
+
+        Ok(normalize(embedding))
+    }
+}
+
+// Loaded local models by directory
+static LOCAL_MODELS: Mutex<Option<HashMap<PathBuf, Arc<Mutex<LocalEmbeddingModel>>>>> = Mutex::new(None);
+
+fn get_or_load_local_model(model_dir: &Path) -> Result<Arc<Mutex<LocalEmbeddingModel>>, String> {
+    let mut guard = LOCAL_MODELS.lock().unwrap();
+    let models = guard.get_or_insert_with(HashMap::new);
+    if let Some(model) = models.get(model_dir) {
+        return Ok(model.clone());
+    }
+    let model = Arc::new(Mutex::new(LocalEmbeddingModel::new(model_dir)?));
+    models.insert(model_dir.to_path_buf(), model.clone());
+    Ok(model)
+}
+
+/// Directory of a local embedding model inside the app data directory
+pub fn local_model_dir<R: Runtime>(app: &AppHandle<R>, model: &str) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("models").join("embeddings").join(model))
+}
+
+// ============================================================================
+// HTTP providers
+// ============================================================================
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct OllamaEmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIEmbeddingResponse {
+    data: Vec<OpenAIEmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIEmbeddingData {
+    embedding: Vec<f32>,
+    #[serde(default)]
+    index: usize,
+}
+
+enum EmbeddingBackend {
+    Local(Arc<Mutex<LocalEmbeddingModel>>),
+    Ollama { url: String },
+    OpenAICompatible { url: String, api_key: String },
+}
+
+/// Embeds text with the configured provider
+pub struct EmbeddingClient {
+    backend: EmbeddingBackend,
+    client: Client,
+    model: String,
+    model_key: String,
+}
+
+impl EmbeddingClient {
+    /// Resolve the provider's endpoint and credentials from the app settings
+    pub async fn from_settings<R: Runtime>(
+        app: &AppHandle<R>,
+        pool: &SqlitePool,
+        provider: &str,
+        model: &str,
+    ) -> Result<Self, String> {
+        let backend = if provider.eq_ignore_ascii_case(LOCAL_PROVIDER) {
+            let model_dir = local_model_dir(app, model)
+                .ok_or_else(|| "Failed to resolve app data directory".to_string())?;
+            let model = tokio::task::spawn_blocking(move || get_or_load_local_model(&model_dir))
+                .await
+                .map_err(|e| format!("Embedding model load task failed: {}", e))??;
+            EmbeddingBackend::Local(model)
+        } else {
+            match LLMProvider::from_str(provider)? {
+                LLMProvider::Ollama => {
+                    let endpoint = SettingsRepository::get_model_config(pool)
+                        .await
+                        .ok()
+                        .flatten()
+                        .and_then(|config| config.ollama_endpoint)
+                        .filter(|endpoint| !endpoint.is_empty())
+                        .unwrap_or_else(|| "http://localhost:11434".to_string());
+                    EmbeddingBackend::Ollama {
+                        url: format!("{}/api/embed", endpoint.trim_end_matches('/')),
+                    }
+                }
+                LLMProvider::OpenAI => {
+                    let api_key = SettingsRepository::get_api_key(pool, "openai")
+                        .await
+                        .map_err(|e| format!("Failed to retrieve API key for openai: {}", e))?
+                        .filter(|key| !key.is_empty())
+                        .ok_or_else(|| "API key not found for openai".to_string())?;
+                    EmbeddingBackend::OpenAICompatible {
+                        url: "https://api.openai.com/v1/embeddings".to_string(),
+                        api_key,
+                    }
+                }
+                LLMProvider::CustomOpenAI => {
+                    let config = SettingsRepository::get_custom_openai_config(pool)
+                        .await
+                        .map_err(|e| format!("Failed to retrieve custom OpenAI config: {}", e))?
+                        .ok_or_else(|| "Custom OpenAI provider selected but no configuration found".to_string())?;
+                    EmbeddingBackend::OpenAICompatible {
+                        url: format!("{}/embeddings", config.endpoint.trim_end_matches('/')),
+                        api_key: config.api_key.unwrap_or_default(),
+                    }
+                }
+                other => {
+                    return Err(format!("Provider {:?} does not offer an embeddings API", other));
+                }
+            }
+        };
+
+        Ok(Self {
+            backend,
+            client: Client::new(),
+            model: model.to_string(),
+            model_key: format!("{}:{}", provider.to_lowercase(), model),
+        })
+    }
+
+    /// Identifier stored with every vector ("<provider>:<model>")
+    pub fn model_key(&self) -> &str {
+        &self.model_key
+    }
+
+    /// Embed texts in batches; returns one L2-normalized vector per text
+    pub async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(EMBEDDING_BATCH_SIZE) {
+            let batch_vectors = match &self.backend {
+                EmbeddingBackend::Local(model) => {
+                    let model = model.clone();
+                    let batch = batch.to_vec();
+                    tokio::task::spawn_blocking(move || {
+                        let mut model = model.lock().unwrap();
+                        batch.iter().map(|text| model.embed(text)).collect::<Result<Vec<_>, _>>()
+                    })
+                    .await
+                    .map_err(|e| format!("Embedding task failed: {}", e))??
+                }
+                EmbeddingBackend::Ollama { url } => {
+                    let response: OllamaEmbedResponse = self.post(url, None, batch).await?;
+                    response.embeddings
+                }
+                EmbeddingBackend::OpenAICompatible { url, api_key } => {
+                    let mut response: OpenAIEmbeddingResponse =
+                        self.post(url, Some(api_key.as_str()), batch).await?;
+                    response.data.sort_by_key(|data| data.index);
+                    response.data.into_iter().map(|data| data.embedding).collect()
+                }
+            };
+
+            if batch_vectors.len() != batch.len() {
+                return Err(format!(
+                    "Embedding provider returned {} vectors for {} inputs",
+                    batch_vectors.len(),
+                    batch.len()
+                ));
+            }
+            vectors.extend(batch_vectors.into_iter().map(normalize));
+        }
+        Ok(vectors)
+    }
+
+    async fn post<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        api_key: Option<&str>,
+        input: &[String],
+    ) -> Result<T, String> {
+        let mut request = self
+            .client
+            .post(url)
+            .json(&EmbeddingRequest {
+                model: &self.model,
+                input,
+            })
+            .timeout(EMBEDDING_REQUEST_TIMEOUT);
+        if let Some(key) = api_key.filter(|key| !key.is_empty()) {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send embedding request: {}", e))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Embedding request failed ({}): {}", status, body));
+        }
+
+        response
+            .json::<T>()
+            .await
+            .map_err(|e| format!("Failed to parse embedding response: {}", e))
+    }
+}
+
+pub fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > f32::EPSILON {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokenizer() -> WordPieceTokenizer {
+        let vocab = ["[PAD]", "[UNK]", "[CLS]", "[SEP]", "drop", "the", "v", "##1", "api", "?", "un", "##related"];
+        WordPieceTokenizer::from_vocab(vocab.into_iter()).unwrap()
+    }
+
+    #[test]
+    fn test_basic_tokenize_splits_punctuation() {
+        assert_eq!(
+            basic_tokenize("Drop the V1 API?"),
+            vec!["drop", "the", "v1", "api", "?"]
+        );
+    }
+
+    #[test]
+    fn test_wordpiece_encode() {
+        let ids = tokenizer().encode("Drop the v1 API? unrelated xyz", 64);
+        assert_eq!(ids, vec![2, 4, 5, 6, 7, 8, 9, 10, 11, 1, 3]);
+    }
+
+    #[test]
+    fn test_encode_truncates() {
+        let ids = tokenizer().encode("drop the api drop the api", 4);
+        assert_eq!(ids, vec![2, 4, 5, 3]);
+    }
+
+    #[test]
+    fn test_missing_special_tokens() {
+        assert!(WordPieceTokenizer::from_vocab(["hello"].into_iter()).is_err());
+    }
+
+    #[test]
+    fn test_normalize() {
+        let v = normalize(vec![3.0, 4.0]);
+        assert!((v[0] - 0.6).abs() < 1e-6 && (v[1] - 0.8).abs() < 1e-6);
+        assert_eq!(normalize(vec![0.0, 0.0]), vec![0.0, 0.0]);
+    }
+}
@@ -0,0 +1,306 @@
+//! Chunking, indexing and retrieval for semantic search
+//!
+//! Transcript segments are grouped into windows of consecutive segments so every
+//! chunk keeps the recording time of its first segment (used for citations).
+//! Summaries are split on paragraphs, each prefixed with its nearest heading.
+
+use super::embeddings::EmbeddingClient;
+use crate::api::MeetingTranscript;
+use crate::database::models::MeetingEmbedding;
+use crate::database::repositories::embedding::{decode_vector, EmbeddingsRepository, NewEmbedding};
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::search::{SOURCE_SUMMARY, SOURCE_TRANSCRIPT};
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use sqlx::SqlitePool;
+
+/// Target chunk size in characters (~200 tokens for English text)
+pub const DEFAULT_CHUNK_CHARS: usize = 800;
+
+/// Text chunk of a meeting before embedding
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextChunk {
+    pub source_type: String,
+    pub source_id: String,
+    pub chunk_index: i64,
+    pub content: String,
+    pub timestamp: Option<String>,
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+}
+
+/// Group consecutive transcript segments into chunks of up to `max_chars`
+///
+/// The chunk's `source_id`, `timestamp` and `start_time` come from its first segment.
+pub fn chunk_transcripts(transcripts: &[MeetingTranscript], max_chars: usize) -> Vec<TextChunk> {
+    let mut ordered: Vec<&MeetingTranscript> = transcripts
+        .iter()
+        .filter(|t| !t.text.trim().is_empty())
+        .collect();
+    ordered.sort_by(|a, b| match (a.audio_start_time, b.audio_start_time) {
+        (Some(a_start), Some(b_start)) => a_start.partial_cmp(&b_start).unwrap_or(std::cmp::Ordering::Equal),
+        _ => a.timestamp.cmp(&b.timestamp),
+    });
+
+    let mut chunks: Vec<TextChunk> = Vec::new();
+    let mut current: Option<TextChunk> = None;
+
+    for segment in ordered {
+        let text = segment.text.trim();
+        if let Some(chunk) = current.as_mut() {
+            if chunk.content.len() + text.len() + 1 <= max_chars {
+                chunk.content.push('\n');
+                chunk.content.push_str(text);
+                chunk.end_time = segment.audio_end_time.or(chunk.end_time);
+                continue;
+            }
+            chunks.push(current.take().unwrap());
+        }
+        current = Some(TextChunk {
+            source_type: SOURCE_TRANSCRIPT.to_string(),
+            source_id: segment.id.clone(),
+            chunk_index: chunks.len() as i64,
+            content: text.to_string(),
+            timestamp: Some(segment.timestamp.clone()),
+            start_time: segment.audio_start_time,
+            end_time: segment.audio_end_time,
+        });
+    }
+
+    chunks.extend(current);
+    chunks
+}
+
+/// Split summary markdown into paragraph chunks, each prefixed with its section heading
+pub fn chunk_markdown(meeting_id: &str, markdown: &str, max_chars: usize) -> Vec<TextChunk> {
+    let mut chunks: Vec<TextChunk> = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut buffer = String::new();
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') {
+            flush_summary_chunk(meeting_id, &mut buffer, &heading, &mut chunks);
+            heading = Some(trimmed.trim_start_matches('#').trim().to_string()).filter(|h| !h.is_empty());
+        } else if trimmed.is_empty() && buffer.len() >= max_chars / 2 {
+            flush_summary_chunk(meeting_id, &mut buffer, &heading, &mut chunks);
+        } else {
+            if buffer.len() + trimmed.len() > max_chars {
+                flush_summary_chunk(meeting_id, &mut buffer, &heading, &mut chunks);
+            }
+            if !trimmed.is_empty() {
+                buffer.push_str(trimmed);
+                buffer.push('\n');
+            }
+        }
+    }
+    flush_summary_chunk(meeting_id, &mut buffer, &heading, &mut chunks);
+
+    chunks
+}
+
+fn flush_summary_chunk(
+    meeting_id: &str,
+    buffer: &mut String,
+    heading: &Option<String>,
+    chunks: &mut Vec<TextChunk>,
+) {
+    let body = buffer.trim();
+    if !body.is_empty() {
+        let content = match heading {
+            Some(h) => format!("{}\n{}", h, body),
+            None => body.to_string(),
+        };
+        chunks.push(TextChunk {
+            source_type: SOURCE_SUMMARY.to_string(),
+            source_id: meeting_id.to_string(),
+            chunk_index: chunks.len() as i64,
+            content,
+            timestamp: None,
+            start_time: None,
+            end_time: None,
+        });
+    }
+    buffer.clear();
+}
+
+/// Extract the markdown body of a stored summary result (`{"markdown": ...}`)
+pub fn summary_markdown(result: &str) -> Option<String> {
+    let markdown = match serde_json::from_str::<serde_json::Value>(result) {
+        Ok(value) => value
+            .get("markdown")
+            .and_then(|m| m.as_str())
+            .map(str::to_string)?,
+        Err(_) => result.to_string(),
+    };
+    Some(markdown).filter(|m| !m.trim().is_empty())
+}
+
+/// Embed and store all chunks of a meeting, replacing its previous vectors for this model
+pub async fn index_meeting(
+    pool: &SqlitePool,
+    client: &EmbeddingClient,
+    meeting_id: &str,
+) -> Result<usize, String> {
+    let details = MeetingsRepository::get_meeting(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting {}: {}", meeting_id, e))?
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+
+    let mut chunks = chunk_transcripts(&details.transcripts, DEFAULT_CHUNK_CHARS);
+
+    let summary = SummaryProcessesRepository::get_summary_data(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary for {}: {}", meeting_id, e))?;
+    if let Some(markdown) = summary
+        .and_then(|process| process.result)
+        .and_then(|result| summary_markdown(&result))
+    {
+        chunks.extend(chunk_markdown(meeting_id, &markdown, DEFAULT_CHUNK_CHARS));
+    }
+
+    let texts: Vec<String> = chunks.iter().map(|chunk| chunk.content.clone()).collect();
+    let vectors = client.embed(&texts).await?;
+
+    let embeddings: Vec<NewEmbedding> = chunks
+        .into_iter()
+        .zip(vectors)
+        .map(|(chunk, vector)| NewEmbedding {
+            source_type: chunk.source_type,
+            source_id: chunk.source_id,
+            chunk_index: chunk.chunk_index,
+            content: chunk.content,
+            timestamp: chunk.timestamp,
+            start_time: chunk.start_time,
+            end_time: chunk.end_time,
+            vector,
+        })
+        .collect();
+
+    EmbeddingsRepository::replace_meeting_embeddings(pool, meeting_id, client.model_key(), &embeddings)
+        .await
+        .map_err(|e| format!("Failed to store embeddings for {}: {}", meeting_id, e))
+}
+
+/// Dot product of two L2-normalized vectors (= cosine similarity)
+pub fn similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Brute-force top-k retrieval, highest similarity first
+pub fn top_k(query: &[f32], embeddings: Vec<MeetingEmbedding>, k: usize) -> Vec<(f32, MeetingEmbedding)> {
+    let mut scored: Vec<(f32, MeetingEmbedding)> = embeddings
+        .into_iter()
+        .map(|embedding| (similarity(query, &decode_vector(&embedding.vector)), embedding))
+        .collect();
+    scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    scored.truncate(k);
+    scored
+}
+
+/// Format seconds from the start of the recording as `MM:SS` (or `H:MM:SS`)
+pub fn format_offset(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    let (hours, minutes, secs) = (total / 3600, (total % 3600) / 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{:02}:{:02}", minutes, secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::repositories::embedding::encode_vector;
+
+    fn segment(id: &str, text: &str, start: f64) -> MeetingTranscript {
+        MeetingTranscript {
+            id: id.to_string(),
+            text: text.to_string(),
+            timestamp: format!("2026-01-01T10:00:{:02}Z", start as u64),
+            audio_start_time: Some(start),
+            audio_end_time: Some(start + 5.0),
+            duration: Some(5.0),
+        }
+    }
+
+    fn embedding(id: i64, vector: &[f32]) -> MeetingEmbedding {
+        MeetingEmbedding {
+            id,
+            meeting_id: "meeting-1".to_string(),
+            source_type: SOURCE_TRANSCRIPT.to_string(),
+            source_id: format!("t-{}", id),
+            chunk_index: id,
+            content: String::new(),
+            timestamp: None,
+            start_time: None,
+            end_time: None,
+            model: "local:test".to_string(),
+            dimensions: vector.len() as i64,
+            vector: encode_vector(vector),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_chunk_transcripts_groups_in_order() {
+        let transcripts = vec![
+            segment("b", "second segment", 5.0),
+            segment("a", "first segment", 0.0),
+            segment("c", "third segment is a bit longer", 10.0),
+        ];
+        let chunks = chunk_transcripts(&transcripts, 30);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].source_id, "a");
+        assert_eq!(chunks[0].content, "first segment\nsecond segment");
+        assert_eq!(chunks[0].start_time, Some(0.0));
+        assert_eq!(chunks[0].end_time, Some(10.0));
+        assert_eq!(chunks[1].source_id, "c");
+        assert_eq!(chunks[1].chunk_index, 1);
+    }
+
+    #[test]
+    fn test_chunk_markdown_keeps_headings() {
+        let markdown = "# Summary\n\nWe agreed to ship.\n\n## Action Items\n\n- Write docs\n- Tag release\n";
+        let chunks = chunk_markdown("meeting-1", markdown, 800);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].content, "Summary\nWe agreed to ship.");
+        assert_eq!(chunks[1].content, "Action Items\n- Write docs\n- Tag release");
+        assert!(chunks.iter().all(|c| c.source_type == SOURCE_SUMMARY));
+    }
+
+    #[test]
+    fn test_summary_markdown() {
+        assert_eq!(
+            summary_markdown(r##"{"markdown":"# Notes"}"##).as_deref(),
+            Some("# Notes")
+        );
+        assert_eq!(summary_markdown(r#"{"other":1}"#), None);
+        assert_eq!(summary_markdown("plain text").as_deref(), Some("plain text"));
+    }
+
+    #[test]
+    fn test_top_k_orders_by_similarity() {
+        let embeddings = vec![
+            embedding(1, &[1.0, 0.0]),
+            embedding(2, &[0.0, 1.0]),
+            embedding(3, &[0.6, 0.8]),
+        ];
+        let results = top_k(&[0.0, 1.0], embeddings, 2);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].1.id, 2);
+        assert_eq!(results[1].1.id, 3);
+    }
+
+    #[test]
+    fn test_format_offset() {
+        assert_eq!(format_offset(135.4), "02:15");
+        assert_eq!(format_offset(3725.0), "1:02:05");
+    }
+}
@@ -0,0 +1,10 @@
+/// Semantic search module - meaning-based search and Q&A over past meetings
+///
+/// This module contains:
+/// - Embedding providers (local ONNX model, Ollama, OpenAI, custom OpenAI-compatible)
+/// - Chunking of transcripts and summaries, with vectors stored in SQLite
+/// - Tauri commands for indexing, semantic search and "ask your meetings"
+
+pub mod commands;
+pub mod embeddings;
+pub mod index;
@@ -0,0 +1,237 @@
+//! Timestamp citations linking summary bullets to transcript segments
+//!
+//! Every transcript segment gets an id derived from its recording-relative
+//! start time: `t-0215` for 02:15, `t-6503` for 65:03. Segments starting in the
+//! same second get a numeric suffix (`t-0215-2`) in order of appearance. Ids are
+//! assigned per transcript row, not per speaker line, so the summarizer and the
+//! Obsidian writer assign identical ids to the same rows.
+//!
+//! The model sees the ids in place of the `[MM:SS]` timestamps and cites them
+//! as `[t-0215]`. Citations are stored as `[02:15](#t-0215)` links, which the app
+//! uses to seek playback and the Obsidian writer turns into block links
+//! (`[[#^t-0215|02:15]]`) to transcript lines ending in `^t-0215`.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Leading `[MM:SS]` or `[HH:MM:SS]` timestamp of a transcript line
+static LINE_TIMESTAMP_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\s*)\[(\d{1,3}:\d{2}(?::\d{2})?)\]").unwrap());
+
+/// Model citation: `[t-0215]` or `[t-0215, t-0342]`
+static CITATION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[ \t]*\[\s*(t-\d{4,}(?:-\d+)?(?:\s*[,;]\s*t-\d{4,}(?:-\d+)?)*)\s*\]").unwrap()
+});
+
+static CITATION_ID_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"t-\d{4,}(?:-\d+)?").unwrap());
+
+/// Stored citation link: `[02:15](#t-0215)`
+static CITATION_LINK_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[(\d{1,3}:\d{2}(?::\d{2})?)\]\(#(t-\d{4,}(?:-\d+)?)\)").unwrap());
+
+/// Parse a `MM:SS` or `HH:MM:SS` timestamp (brackets optional) into seconds
+pub fn parse_timestamp(timestamp: &str) -> Option<u32> {
+    let timestamp = timestamp.trim().trim_start_matches('[').trim_end_matches(']');
+    let parts: Vec<u32> = timestamp
+        .split(':')
+        .map(|part| part.parse().ok())
+        .collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [mins, secs] if *secs < 60 => Some(mins * 60 + secs),
+        [hours, mins, secs] if *mins < 60 && *secs < 60 => Some(hours * 3600 + mins * 60 + secs),
+        _ => None,
+    }
+}
+
+/// Display label of a citation: `02:15`, or `1:05:03` past the first hour
+pub fn format_label(seconds: u32) -> String {
+    let (hours, mins, secs) = (seconds / 3600, (seconds % 3600) / 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, mins, secs)
+    } else {
+        format!("{:02}:{:02}", mins, secs)
+    }
+}
+
+/// Assigns segment ids in transcript order, suffixing repeats within a second
+#[derive(Debug, Default)]
+pub struct SegmentIds {
+    seen: HashMap<u32, usize>,
+}
+
+impl SegmentIds {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of the next segment starting at `seconds`
+    pub fn next_id(&mut self, seconds: u32) -> String {
+        let count = self.seen.entry(seconds).or_insert(0);
+        *count += 1;
+        let base = format!("t-{:02}{:02}", seconds / 60, seconds % 60);
+        if *count == 1 {
+            base
+        } else {
+            format!("{}-{}", base, count)
+        }
+    }
+
+    /// Id of the next segment starting `start` seconds into the recording
+    pub fn next_for_start(&mut self, start: f64) -> String {
+        self.next_id(start.max(0.0).floor() as u32)
+    }
+
+    /// Id of the next segment with a `[MM:SS]`/`HH:MM:SS` timestamp, if it parses
+    pub fn next_for_timestamp(&mut self, timestamp: &str) -> Option<String> {
+        parse_timestamp(timestamp).map(|seconds| self.next_id(seconds))
+    }
+}
+
+/// Transcript prepared for a cited summary
+#[derive(Debug, Clone, Default)]
+pub struct CitedTranscript {
+    /// Transcript with each line's timestamp replaced by its segment id
+    pub text: String,
+    /// Start time in seconds of every segment id in `text`
+    pub segments: HashMap<String, u32>,
+}
+
+impl CitedTranscript {
+    /// Whether any line had a timestamp to cite
+    pub fn has_segments(&self) -> bool {
+        !self.segments.is_empty()
+    }
+}
+
+/// Replace the leading `[MM:SS]` of each transcript line with its segment id
+///
+/// Lines without a recording-relative timestamp are kept as they are and
+/// cannot be cited.
+pub fn annotate_transcript(text: &str) -> CitedTranscript {
+    let mut ids = SegmentIds::new();
+    let mut segments = HashMap::new();
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| {
+            let Some(caps) = LINE_TIMESTAMP_RE.captures(line) else {
+                return line.to_string();
+            };
+            let Some(seconds) = parse_timestamp(&caps[2]) else {
+                return line.to_string();
+            };
+            let id = ids.next_id(seconds);
+            let annotated = format!("{}[{}]{}", &caps[1], id, &line[caps[0].len()..]);
+            segments.insert(id, seconds);
+            annotated
+        })
+        .collect();
+
+    CitedTranscript {
+        text: lines.join("\n"),
+        segments,
+    }
+}
+
+/// Prompt instructions asking for per-bullet citations
+pub fn citation_instructions() -> &'static str {
+    "\n**CITATIONS:**\nEach transcript line starts with a segment id such as `[t-0215]`. \
+     End every bullet point and table row with the id(s) of the lines supporting it, e.g. `[t-0215]` \
+     or `[t-0215, t-0342]`. Only cite ids that appear in the source text; never invent or reformat them. \
+     Do not cite the meeting title or headings.\n"
+}
+
+/// Hint for chunk prompts, so chunk summaries keep the ids for the final report
+pub fn chunk_citation_hint() -> &'static str {
+    "\n\nEach line starts with a segment id such as [t-0215]. Keep the ids of the lines each point comes from, e.g. \"Budget approved [t-0215]\"."
+}
+
+/// Turn `[t-0215]` citations into `[02:15](#t-0215)` links
+///
+/// Ids missing from `segments` (hallucinated or mangled by the model) are
+/// dropped, along with citations left empty.
+pub fn link_citations(markdown: &str, segments: &HashMap<String, u32>) -> String {
+    let mut result = String::with_capacity(markdown.len());
+    let mut last = 0;
+    for caps in CITATION_RE.captures_iter(markdown) {
+        let whole = caps.get(0).unwrap();
+        // Already a markdown link label, e.g. `[t-0215](...)`
+        if markdown[whole.end()..].starts_with('(') {
+            continue;
+        }
+        result.push_str(&markdown[last..whole.start()]);
+        last = whole.end();
+
+        let links: Vec<String> = CITATION_ID_RE
+            .find_iter(&caps[1])
+            .filter_map(|id| {
+                let seconds = segments.get(id.as_str())?;
+                Some(format!("[{}](#{})", format_label(*seconds), id.as_str()))
+            })
+            .collect();
+        if !links.is_empty() {
+            result.push(' ');
+            result.push_str(&links.join(", "));
+        }
+    }
+    result.push_str(&markdown[last..]);
+    result
+}
+
+/// Turn `[02:15](#t-0215)` citation links into Obsidian block links
+pub fn to_obsidian_links(markdown: &str) -> String {
+    CITATION_LINK_RE
+        .replace_all(markdown, "[[#^$2|$1]]")
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_ids() {
+        assert_eq!(parse_timestamp("[02:15]"), Some(135));
+        assert_eq!(parse_timestamp("01:05:03"), Some(3903));
+        assert_eq!(parse_timestamp("65:03"), Some(3903));
+        assert_eq!(parse_timestamp("2:75"), None);
+        assert_eq!(parse_timestamp("2024-01-01T10:00:00"), None);
+
+        let mut ids = SegmentIds::new();
+        assert_eq!(ids.next_id(135), "t-0215");
+        assert_eq!(ids.next_id(135), "t-0215-2");
+        assert_eq!(ids.next_for_timestamp("[01:05:03]").as_deref(), Some("t-6503"));
+        assert_eq!(ids.next_for_start(135.9), "t-0215-3");
+        assert_eq!(format_label(135), "02:15");
+        assert_eq!(format_label(3903), "1:05:03");
+    }
+
+    #[test]
+    fn test_annotate_transcript() {
+        let cited = annotate_transcript("[00:05] Hello\n[02:15] Budget is approved\n[02:15] Great\n2024-01-01 Old line");
+        assert_eq!(
+            cited.text,
+            "[t-0005] Hello\n[t-0215] Budget is approved\n[t-0215-2] Great\n2024-01-01 Old line"
+        );
+        assert_eq!(cited.segments.len(), 3);
+        assert_eq!(cited.segments["t-0215-2"], 135);
+    }
+
+    #[test]
+    fn test_link_citations_drops_unknown_ids() {
+        let cited = annotate_transcript("[00:05] Hello\n[02:15] Budget is approved");
+        let markdown = "- Budget approved [t-0215]\n- Greeting [t-0005, t-9999]\n- Made up [t-4242]\n- [Link](https://x.y)";
+        assert_eq!(
+            link_citations(markdown, &cited.segments),
+            "- Budget approved [02:15](#t-0215)\n- Greeting [00:05](#t-0005)\n- Made up\n- [Link](https://x.y)"
+        );
+    }
+
+    #[test]
+    fn test_to_obsidian_links() {
+        assert_eq!(
+            to_obsidian_links("- Budget approved [02:15](#t-0215), [1:05:03](#t-6503-2)"),
+            "- Budget approved [[#^t-0215|02:15]], [[#^t-6503-2|1:05:03]]"
+        );
+    }
+}
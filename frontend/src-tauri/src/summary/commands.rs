@@ -1,8 +1,9 @@
 use crate::database::repositories::{
-    meeting::MeetingsRepository, summary::SummaryProcessesRepository,
+    meeting::MeetingsRepository, setting::SettingsRepository, summary::SummaryProcessesRepository,
     transcript_chunk::TranscriptChunksRepository,
 };
 use crate::state::AppState;
+use crate::summary::gemini::{GeminiClient, GeminiModel};
 use crate::summary::service::SummaryService;
 use log::{error as log_error, info as log_info, warn as log_warn};
 use serde::{Deserialize, Serialize};
@@ -18,6 +19,9 @@ pub struct SummaryResponse {
     pub end: Option<String>,
     pub data: Option<serde_json::Value>,
     pub error: Option<String>,
+    /// Stage and partial markdown of a running generation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -115,6 +119,15 @@ pub async fn api_get_summary<R: Runtime>(
                 }
             };
 
+            // Progress is only meaningful while the generation runs
+            let progress = match status.as_str() {
+                "pending" | "processing" => process
+                    .metadata
+                    .as_deref()
+                    .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok()),
+                _ => None,
+            };
+
             let response = SummaryResponse {
                 status: status.clone(),
                 meeting_name,
@@ -123,6 +136,7 @@ pub async fn api_get_summary<R: Runtime>(
                 end: process.end_time.map(|t| t.to_rfc3339()),
                 data,
                 error,
+                progress,
             };
 
             log_info!(
@@ -151,6 +165,7 @@ pub async fn api_get_summary<R: Runtime>(
                 end: None,
                 data: None,
                 error: None,
+                progress: None,
             })
         }
         Err(e) => {
@@ -162,7 +177,11 @@ pub async fn api_get_summary<R: Runtime>(
 
 /// Processes transcript and generates summary (Native SQLx implementation)
 ///
-/// Spawns a background task and returns immediately with process_id
+/// Spawns a background task and returns immediately with process_id.
+/// `note_path` points at the meeting's prep note; without it the markdown in
+/// the meeting's recording folder is used. With `auto_template` (set when the
+/// summary is generated automatically) the note's frontmatter may pick another
+/// template than `template_id`.
 #[tauri::command]
 pub async fn api_process_transcript<R: Runtime>(
     app: AppHandle<R>,
@@ -175,6 +194,8 @@ pub async fn api_process_transcript<R: Runtime>(
     _overlap: Option<i32>,
     custom_prompt: Option<String>,
     template_id: Option<String>,
+    note_path: Option<String>,
+    auto_template: Option<bool>,
     _auth_token: Option<String>,
 ) -> Result<ProcessTranscriptResponse, String> {
     use uuid::Uuid;
@@ -227,6 +248,8 @@ pub async fn api_process_transcript<R: Runtime>(
             model_name,
             final_prompt,
             final_template_id,
+            note_path,
+            auto_template.unwrap_or(false),
         )
         .await;
     });
@@ -275,3 +298,33 @@ pub async fn api_cancel_summary<R: Runtime>(
         }))
     }
 }
+
+/// Lists Gemini models that can generate summaries
+///
+/// Uses the given API key, or the saved Gemini key when none is passed
+/// (the settings dialog lists models before the key is saved).
+#[tauri::command]
+pub async fn api_get_gemini_models<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    api_key: Option<String>,
+) -> Result<Vec<GeminiModel>, String> {
+    log_info!("api_get_gemini_models called");
+
+    let api_key = match api_key.filter(|key| !key.trim().is_empty()) {
+        Some(key) => key,
+        None => SettingsRepository::get_api_key(state.db_manager.pool(), "gemini")
+            .await
+            .map_err(|e| format!("Failed to retrieve API key for gemini: {}", e))?
+            .filter(|key| !key.is_empty())
+            .ok_or_else(|| "API key not found for gemini".to_string())?,
+    };
+
+    GeminiClient::new(reqwest::Client::new(), api_key)
+        .list_models()
+        .await
+        .map_err(|e| {
+            log_error!("Failed to list Gemini models: {}", e);
+            e
+        })
+}
@@ -0,0 +1,471 @@
+//! Google Gemini client (Generative Language API)
+//!
+//! Uses `models/{model}:generateContent` with the system prompt passed as a
+//! `systemInstruction`, and `models` for listing the models that support
+//! content generation. Safety blocks are reported as errors that name the
+//! block reason and the categories that triggered it, instead of an empty summary.
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+/// Production API base URL
+pub const GEMINI_API_BASE_URL: &str = "https://generativelanguage.googleapis.com";
+
+const GEMINI_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Finish reasons that mean the answer was withheld rather than completed
+const BLOCKED_FINISH_REASONS: &[&str] = &[
+    "SAFETY",
+    "RECITATION",
+    "BLOCKLIST",
+    "PROHIBITED_CONTENT",
+    "SPII",
+    "IMAGE_SAFETY",
+];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateContentRequest {
+    system_instruction: GeminiContent,
+    contents: Vec<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generation_config: Option<GenerationConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct GeminiContent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    #[serde(default)]
+    parts: Vec<GeminiPart>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct GeminiPart {
+    #[serde(default)]
+    text: String,
+}
+
+/// Optional sampling parameters and output format
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// `application/json` to get a JSON answer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_mime_type: Option<String>,
+}
+
+impl GenerationConfig {
+    fn is_empty(&self) -> bool {
+        self.max_output_tokens.is_none()
+            && self.temperature.is_none()
+            && self.top_p.is_none()
+            && self.response_mime_type.is_none()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateContentResponse {
+    #[serde(default)]
+    candidates: Vec<Candidate>,
+    prompt_feedback: Option<PromptFeedback>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Candidate {
+    content: Option<GeminiContent>,
+    finish_reason: Option<String>,
+    #[serde(default)]
+    safety_ratings: Vec<SafetyRating>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PromptFeedback {
+    block_reason: Option<String>,
+    #[serde(default)]
+    safety_ratings: Vec<SafetyRating>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SafetyRating {
+    category: String,
+    #[serde(default)]
+    probability: String,
+    #[serde(default)]
+    blocked: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    error: ErrorBody,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListModelsResponse {
+    #[serde(default)]
+    models: Vec<ApiModel>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiModel {
+    name: String,
+    display_name: Option<String>,
+    input_token_limit: Option<u32>,
+    output_token_limit: Option<u32>,
+    #[serde(default)]
+    supported_generation_methods: Vec<String>,
+}
+
+/// A Gemini model that supports `generateContent`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GeminiModel {
+    /// Model id without the `models/` prefix (e.g. "gemini-2.5-flash")
+    pub id: String,
+    pub name: String,
+    pub input_token_limit: Option<u32>,
+    pub output_token_limit: Option<u32>,
+}
+
+/// Client for the Gemini API
+pub struct GeminiClient {
+    client: Client,
+    api_key: String,
+    base_url: String,
+}
+
+impl GeminiClient {
+    /// Create a client against the production Gemini API
+    pub fn new(client: Client, api_key: String) -> Self {
+        Self::with_base_url(client, api_key, GEMINI_API_BASE_URL.to_string())
+    }
+
+    /// Create a client against a custom base URL (a proxy or a mock server)
+    pub fn with_base_url(client: Client, api_key: String, base_url: String) -> Self {
+        Self {
+            client,
+            api_key,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Generate text for a system + user prompt
+    pub async fn generate_content(
+        &self,
+        model: &str,
+        system_prompt: &str,
+        user_prompt: &str,
+        config: GenerationConfig,
+        cancellation_token: Option<&CancellationToken>,
+    ) -> Result<String, String> {
+        let model = model.trim_start_matches("models/");
+        let url = format!("{}/v1beta/models/{}:generateContent", self.base_url, model);
+        let body = GenerateContentRequest {
+            system_instruction: GeminiContent {
+                role: None,
+                parts: vec![GeminiPart { text: system_prompt.to_string() }],
+            },
+            contents: vec![GeminiContent {
+                role: Some("user".to_string()),
+                parts: vec![GeminiPart { text: user_prompt.to_string() }],
+            }],
+            generation_config: (!config.is_empty()).then_some(config),
+        };
+
+        info!("🐞 LLM Request to Gemini: model={}", model);
+
+        let request_future = self
+            .client
+            .post(&url)
+            .header("x-goog-api-key", &self.api_key)
+            .json(&body)
+            .timeout(GEMINI_TIMEOUT)
+            .send();
+
+        let response = match cancellation_token {
+            Some(token) => tokio::select! {
+                result = request_future => result,
+                _ = token.cancelled() => {
+                    return Err("Summary generation was cancelled".to_string());
+                }
+            },
+            None => request_future.await,
+        }
+        .map_err(|e| {
+            if e.is_timeout() {
+                format!("Gemini request timed out after {} seconds", GEMINI_TIMEOUT.as_secs())
+            } else {
+                format!("Failed to send request to Gemini: {}", e)
+            }
+        })?;
+
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read Gemini response: {}", e))?;
+        if !status.is_success() {
+            return Err(api_error(status.as_u16(), &text));
+        }
+
+        let parsed: GenerateContentResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("Failed to parse Gemini response: {}", e))?;
+        info!("🐞 LLM Response received from Gemini");
+        extract_text(parsed)
+    }
+
+    /// List models that support `generateContent`, following pagination
+    pub async fn list_models(&self) -> Result<Vec<GeminiModel>, String> {
+        let mut models = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut request = self
+                .client
+                .get(format!("{}/v1beta/models", self.base_url))
+                .header("x-goog-api-key", &self.api_key)
+                .query(&[("pageSize", "100")]);
+            if let Some(token) = &page_token {
+                request = request.query(&[("pageToken", token.as_str())]);
+            }
+
+            let response = request
+                .timeout(Duration::from_secs(30))
+                .send()
+                .await
+                .map_err(|e| format!("Failed to list Gemini models: {}", e))?;
+            let status = response.status();
+            let text = response
+                .text()
+                .await
+                .map_err(|e| format!("Failed to read Gemini response: {}", e))?;
+            if !status.is_success() {
+                return Err(api_error(status.as_u16(), &text));
+            }
+
+            let page: ListModelsResponse = serde_json::from_str(&text)
+                .map_err(|e| format!("Failed to parse Gemini models: {}", e))?;
+            models.extend(
+                page.models
+                    .into_iter()
+                    .filter(|m| m.supported_generation_methods.iter().any(|g| g == "generateContent"))
+                    .map(|m| {
+                        let id = m.name.trim_start_matches("models/").to_string();
+                        GeminiModel {
+                            name: m.display_name.unwrap_or_else(|| id.clone()),
+                            id,
+                            input_token_limit: m.input_token_limit,
+                            output_token_limit: m.output_token_limit,
+                        }
+                    }),
+            );
+
+            match page.next_page_token.filter(|t| !t.is_empty()) {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        Ok(models)
+    }
+}
+
+/// Readable message for a non-success response
+fn api_error(status: u16, body: &str) -> String {
+    match serde_json::from_str::<ErrorResponse>(body) {
+        Ok(error) if status == 400 && error.error.message.contains("API key") => {
+            format!("Gemini rejected the API key: {}", error.error.message)
+        }
+        Ok(error) if status == 429 || error.error.status == "RESOURCE_EXHAUSTED" => {
+            format!("Gemini rate limit or quota exceeded: {}", error.error.message)
+        }
+        Ok(error) if status == 404 => {
+            format!("Gemini model not found: {}", error.error.message)
+        }
+        Ok(error) => format!(
+            "Gemini API request failed ({} {}): {}",
+            status, error.error.status, error.error.message
+        ),
+        Err(_) => format!("Gemini API request failed ({}): {}", status, body),
+    }
+}
+
+/// Categories that caused a block (flagged as blocked, or rated HIGH)
+fn blocked_categories(ratings: &[SafetyRating]) -> String {
+    let categories: Vec<&str> = ratings
+        .iter()
+        .filter(|r| r.blocked || r.probability == "HIGH")
+        .map(|r| r.category.trim_start_matches("HARM_CATEGORY_"))
+        .collect();
+    if categories.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", categories.join(", "))
+    }
+}
+
+/// Text of the first candidate, or an error explaining why there is none
+fn extract_text(response: GenerateContentResponse) -> Result<String, String> {
+    if let Some(feedback) = &response.prompt_feedback {
+        if let Some(reason) = &feedback.block_reason {
+            return Err(format!(
+                "Gemini blocked the transcript: {}{}",
+                reason,
+                blocked_categories(&feedback.safety_ratings)
+            ));
+        }
+    }
+
+    let candidate = response
+        .candidates
+        .into_iter()
+        .next()
+        .ok_or("No content in Gemini response")?;
+    let text: String = candidate
+        .content
+        .map(|content| content.parts.into_iter().map(|p| p.text).collect())
+        .unwrap_or_default();
+
+    if let Some(reason) = candidate.finish_reason.as_deref() {
+        if BLOCKED_FINISH_REASONS.contains(&reason) {
+            return Err(format!(
+                "Gemini stopped generating the summary: {}{}",
+                reason,
+                blocked_categories(&candidate.safety_ratings)
+            ));
+        }
+    }
+
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("No content in Gemini response".to_string());
+    }
+    Ok(text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::spawn_mock_server;
+
+    fn client(base_url: String) -> GeminiClient {
+        GeminiClient::with_base_url(Client::new(), "test-key".to_string(), base_url)
+    }
+
+    #[tokio::test]
+    async fn test_generate_content_sends_system_instruction() {
+        let (base_url, handle) = spawn_mock_server(
+            "200 OK",
+            r##"{"candidates":[{"content":{"role":"model","parts":[{"text":"# Summary\n"},{"text":"Done."}]},"finishReason":"STOP"}]}"##,
+        )
+        .await;
+
+        let text = client(base_url)
+            .generate_content(
+                "models/gemini-2.5-flash",
+                "You summarize meetings.",
+                "Transcript",
+                GenerationConfig { temperature: Some(0.2), ..Default::default() },
+                None,
+            )
+            .await
+            .unwrap();
+        let request = handle.await.unwrap();
+
+        assert_eq!(text, "# Summary\nDone.");
+        assert!(request.starts_with("POST /v1beta/models/gemini-2.5-flash:generateContent "));
+        assert!(request.to_lowercase().contains("x-goog-api-key: test-key"));
+        assert!(request.contains(r#""systemInstruction":{"parts":[{"text":"You summarize meetings."}]}"#));
+        assert!(request.contains(r#""generationConfig":{"temperature":0.2}"#));
+    }
+
+    #[tokio::test]
+    async fn test_generate_content_reports_safety_block() {
+        let (base_url, _handle) = spawn_mock_server(
+            "200 OK",
+            r#"{"promptFeedback":{"blockReason":"SAFETY","safetyRatings":[{"category":"HARM_CATEGORY_HARASSMENT","probability":"HIGH"},{"category":"HARM_CATEGORY_HATE_SPEECH","probability":"NEGLIGIBLE"}]}}"#,
+        )
+        .await;
+
+        let err = client(base_url)
+            .generate_content("gemini-2.5-flash", "s", "u", GenerationConfig::default(), None)
+            .await
+            .unwrap_err();
+        assert_eq!(err, "Gemini blocked the transcript: SAFETY (HARASSMENT)");
+    }
+
+    #[tokio::test]
+    async fn test_generate_content_maps_api_errors() {
+        let (base_url, _handle) = spawn_mock_server(
+            "400 Bad Request",
+            r#"{"error":{"code":400,"message":"API key not valid. Please pass a valid API key.","status":"INVALID_ARGUMENT"}}"#,
+        )
+        .await;
+
+        let err = client(base_url)
+            .generate_content("gemini-2.5-flash", "s", "u", GenerationConfig::default(), None)
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("Gemini rejected the API key"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_list_models_filters_generate_content() {
+        let (base_url, handle) = spawn_mock_server(
+            "200 OK",
+            r#"{"models":[
+                {"name":"models/gemini-2.5-flash","displayName":"Gemini 2.5 Flash","inputTokenLimit":1048576,"outputTokenLimit":65536,"supportedGenerationMethods":["generateContent","countTokens"]},
+                {"name":"models/text-embedding-004","displayName":"Text Embedding 004","supportedGenerationMethods":["embedContent"]}
+            ]}"#,
+        )
+        .await;
+
+        let models = client(base_url).list_models().await.unwrap();
+        let request = handle.await.unwrap();
+
+        assert!(request.starts_with("GET /v1beta/models?pageSize=100 "));
+        assert_eq!(
+            models,
+            vec![GeminiModel {
+                id: "gemini-2.5-flash".to_string(),
+                name: "Gemini 2.5 Flash".to_string(),
+                input_token_limit: Some(1048576),
+                output_token_limit: Some(65536),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_extract_text_finish_reason_block() {
+        let response: GenerateContentResponse = serde_json::from_str(
+            r#"{"candidates":[{"finishReason":"RECITATION","safetyRatings":[]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            extract_text(response).unwrap_err(),
+            "Gemini stopped generating the summary: RECITATION"
+        );
+    }
+}
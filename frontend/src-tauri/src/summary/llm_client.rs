@@ -1,11 +1,19 @@
+use crate::summary::gemini::{GeminiClient, GenerationConfig};
+use crate::summary::streaming::{DeltaCallback, LineBuffer, StreamEvent, StreamFormat};
+use crate::summary::templates::json_schema_to_gbnf;
+use futures_util::StreamExt;
 use reqwest::{header, Client};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::path::PathBuf;
 use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 use tracing::info;
 
 const REQUEST_TIMEOUT_DURATION: Duration = Duration::from_secs(300);
+/// Streams have no overall deadline (local models can take longer than
+/// `REQUEST_TIMEOUT_DURATION`), only a limit on the silence between chunks
+const STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(120);
 
 // Generic structure for OpenAI-compatible API chat messages
 #[derive(Debug, Serialize)]
@@ -25,6 +33,28 @@ pub struct ChatRequest {
     pub temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_p: Option<f32>,
+    // Always sent: Ollama's native API streams unless told otherwise
+    pub stream: bool,
+    /// OpenAI-compatible structured output (`json_schema` or `json_object`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<Value>,
+    /// Ollama native structured output (a JSON schema)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<Value>,
+    /// Ollama native sampling parameters (`/api/chat` ignores the top-level ones)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<OllamaOptions>,
+}
+
+/// Model options of an Ollama `/api/chat` request
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct OllamaOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_predict: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
 }
 
 // Generic structure for OpenAI-compatible API chat responses
@@ -43,6 +73,12 @@ pub struct MessageContent {
     pub content: String,
 }
 
+// Ollama native `/api/chat` response (non-streaming)
+#[derive(Deserialize, Debug)]
+pub struct OllamaChatResponse {
+    pub message: MessageContent,
+}
+
 // Claude-specific request structure
 #[derive(Debug, Serialize)]
 pub struct ClaudeRequest {
@@ -50,6 +86,8 @@ pub struct ClaudeRequest {
     pub max_tokens: u32,
     pub system: String,
     pub messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub stream: bool,
 }
 
 // Claude-specific response structure
@@ -73,6 +111,7 @@ pub enum LLMProvider {
     OpenRouter,
     BuiltInAI,
     CustomOpenAI,
+    Gemini,
 }
 
 impl LLMProvider {
@@ -86,6 +125,7 @@ impl LLMProvider {
             "openrouter" => Ok(Self::OpenRouter),
             "builtin-ai" | "local-llama" | "localllama" => Ok(Self::BuiltInAI),
             "custom-openai" => Ok(Self::CustomOpenAI),
+            "gemini" => Ok(Self::Gemini),
             _ => Err(format!("Unsupported LLM provider: {}", s)),
         }
     }
@@ -102,9 +142,9 @@ impl LLMProvider {
 /// * `user_prompt` - User query/content to process
 /// * `ollama_endpoint` - Optional custom Ollama endpoint (defaults to localhost:11434)
 /// * `custom_openai_endpoint` - Optional custom OpenAI-compatible endpoint
-/// * `max_tokens` - Optional max tokens (for CustomOpenAI provider)
-/// * `temperature` - Optional temperature (for CustomOpenAI provider)
-/// * `top_p` - Optional top_p (for CustomOpenAI provider)
+/// * `max_tokens` - Optional max tokens (for CustomOpenAI, Ollama and Gemini providers)
+/// * `temperature` - Optional temperature (for CustomOpenAI, Ollama and Gemini providers)
+/// * `top_p` - Optional top_p (for CustomOpenAI, Ollama and Gemini providers)
 /// * `app_data_dir` - Optional app data directory (for BuiltInAI provider)
 /// * `cancellation_token` - Optional token to cancel the request
 ///
@@ -125,6 +165,138 @@ pub async fn generate_summary(
     app_data_dir: Option<&PathBuf>,
     cancellation_token: Option<&CancellationToken>,
 ) -> Result<String, String> {
+    generate(
+        client,
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        user_prompt,
+        ollama_endpoint,
+        custom_openai_endpoint,
+        max_tokens,
+        temperature,
+        top_p,
+        app_data_dir,
+        cancellation_token,
+        None,
+        None,
+    )
+    .await
+}
+
+/// Generates a summary like `generate_summary`, passing text to `on_delta` as it streams in
+///
+/// OpenAI-compatible providers and Claude stream server-sent events, Ollama
+/// streams NDJSON from its native `/api/chat` endpoint and BuiltInAI streams
+/// token frames from the sidecar. Gemini is not streamed; its full answer is
+/// passed to `on_delta` once.
+///
+/// # Returns
+/// The complete generated text (the concatenated deltas, trimmed)
+pub async fn generate_summary_stream(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    ollama_endpoint: Option<&str>,
+    custom_openai_endpoint: Option<&str>,
+    max_tokens: Option<u32>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    app_data_dir: Option<&PathBuf>,
+    cancellation_token: Option<&CancellationToken>,
+    on_delta: DeltaCallback<'_>,
+) -> Result<String, String> {
+    generate(
+        client,
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        user_prompt,
+        ollama_endpoint,
+        custom_openai_endpoint,
+        max_tokens,
+        temperature,
+        top_p,
+        app_data_dir,
+        cancellation_token,
+        Some(on_delta),
+        None,
+    )
+    .await
+}
+
+/// Generates a JSON answer following `schema`, like `generate_summary`
+///
+/// Each provider is constrained as far as its API allows: OpenAI and custom
+/// OpenAI-compatible endpoints get the schema as `response_format`, Groq and
+/// OpenRouter get JSON mode, Ollama gets the schema as `format`, Gemini gets a
+/// JSON response type, Claude's answer is prefilled with `{`, and BuiltInAI
+/// decodes with a grammar compiled from the schema. The answer still has to
+/// be validated by the caller.
+///
+/// With `on_delta`, the raw JSON is passed on as it streams in (see
+/// `generate_summary_stream`); the built-in model passes its answer once.
+pub async fn generate_summary_json(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    schema: &Value,
+    ollama_endpoint: Option<&str>,
+    custom_openai_endpoint: Option<&str>,
+    max_tokens: Option<u32>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    app_data_dir: Option<&PathBuf>,
+    cancellation_token: Option<&CancellationToken>,
+    on_delta: Option<DeltaCallback<'_>>,
+) -> Result<String, String> {
+    generate(
+        client,
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        user_prompt,
+        ollama_endpoint,
+        custom_openai_endpoint,
+        max_tokens,
+        temperature,
+        top_p,
+        app_data_dir,
+        cancellation_token,
+        on_delta,
+        Some(schema),
+    )
+    .await
+}
+
+async fn generate(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    ollama_endpoint: Option<&str>,
+    custom_openai_endpoint: Option<&str>,
+    max_tokens: Option<u32>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    app_data_dir: Option<&PathBuf>,
+    cancellation_token: Option<&CancellationToken>,
+    on_delta: Option<DeltaCallback<'_>>,
+    json_schema: Option<&Value>,
+) -> Result<String, String> {
+    let stream = on_delta.is_some();
+
     // Check if cancelled before starting
     if let Some(token) = cancellation_token {
         if token.is_cancelled() {
@@ -137,17 +309,79 @@ pub async fn generate_summary(
         let app_data_dir = app_data_dir
             .ok_or_else(|| "app_data_dir is required for BuiltInAI provider".to_string())?;
 
-        return crate::summary::summary_engine::generate_with_builtin(
-            app_data_dir,
-            model_name,
-            system_prompt,
-            user_prompt,
-            cancellation_token,
-        )
-        .await
-        .map_err(|e| e.to_string());
+        if let Some(schema) = json_schema {
+            return crate::summary::summary_engine::generate_json_with_builtin(
+                app_data_dir,
+                model_name,
+                system_prompt,
+                user_prompt,
+                &json_schema_to_gbnf(schema),
+                cancellation_token,
+            )
+            .await
+            .map(|text| {
+                if let Some(on_delta) = on_delta {
+                    on_delta(&text);
+                }
+                text
+            })
+            .map_err(|e| e.to_string());
+        }
+
+        let result = match on_delta {
+            Some(on_delta) => {
+                crate::summary::summary_engine::stream_with_builtin(
+                    app_data_dir,
+                    model_name,
+                    system_prompt,
+                    user_prompt,
+                    cancellation_token,
+                    on_delta,
+                )
+                .await
+            }
+            None => {
+                crate::summary::summary_engine::generate_with_builtin(
+                    app_data_dir,
+                    model_name,
+                    system_prompt,
+                    user_prompt,
+                    cancellation_token,
+                )
+                .await
+            }
+        };
+        return result.map_err(|e| e.to_string());
+    }
+
+    // Gemini has its own request/response format
+    if provider == &LLMProvider::Gemini {
+        return GeminiClient::new(client.clone(), api_key.to_string())
+            .generate_content(
+                model_name,
+                system_prompt,
+                user_prompt,
+                GenerationConfig {
+                    max_output_tokens: max_tokens,
+                    temperature,
+                    top_p,
+                    response_mime_type: json_schema.map(|_| "application/json".to_string()),
+                },
+                cancellation_token,
+            )
+            .await
+            .map(|text| {
+                if let Some(on_delta) = on_delta {
+                    on_delta(&text);
+                }
+                text
+            });
     }
 
+    // Streams use Ollama's native API, which sends NDJSON; so does structured
+    // output, which the native API constrains with `format`
+    let ollama_native_api = provider == &LLMProvider::Ollama && (stream || json_schema.is_some());
+
     let (api_url, mut headers) = match provider {
         LLMProvider::OpenAI => (
             "https://api.openai.com/v1/chat/completions".to_string(),
@@ -165,10 +399,8 @@ pub async fn generate_summary(
             let host = ollama_endpoint
                 .map(|s| s.to_string())
                 .unwrap_or_else(|| "http://localhost:11434".to_string());
-            (
-                format!("{}/v1/chat/completions", host),
-                header::HeaderMap::new(),
-            )
+            let path = if ollama_native_api { "api/chat" } else { "v1/chat/completions" };
+            (format!("{}/{}", host, path), header::HeaderMap::new())
         }
         LLMProvider::CustomOpenAI => {
             let endpoint = custom_openai_endpoint
@@ -194,9 +426,9 @@ pub async fn generate_summary(
             );
             ("https://api.anthropic.com/v1/messages".to_string(), header_map)
         }
-        LLMProvider::BuiltInAI => {
-            // This case is handled earlier with early returns
-            unreachable!("BuiltInAI is handled before this match statement")
+        LLMProvider::BuiltInAI | LLMProvider::Gemini => {
+            // These cases are handled earlier with early returns
+            unreachable!("BuiltInAI and Gemini are handled before this match statement")
         }
     };
 
@@ -218,11 +450,28 @@ pub async fn generate_summary(
 
     // Build request body based on provider
     let request_body = if provider != &LLMProvider::Claude {
-        // For CustomOpenAI, apply optional parameters if provided
-        let (max_tokens_val, temperature_val, top_p_val) = if provider == &LLMProvider::CustomOpenAI {
-            (max_tokens, temperature, top_p)
+        // For CustomOpenAI and Ollama, apply optional parameters if provided
+        let (max_tokens_val, temperature_val, top_p_val) =
+            if provider == &LLMProvider::CustomOpenAI || provider == &LLMProvider::Ollama {
+                (max_tokens, temperature, top_p)
+            } else {
+                (None, None, None)
+            };
+        // Ollama's native API takes them as model options instead
+        let options = if ollama_native_api {
+            Some(OllamaOptions {
+                num_predict: max_tokens_val,
+                temperature: temperature_val,
+                top_p: top_p_val,
+            })
+            .filter(|options| options != &OllamaOptions::default())
         } else {
+            None
+        };
+        let (max_tokens_val, temperature_val, top_p_val) = if ollama_native_api {
             (None, None, None)
+        } else {
+            (max_tokens_val, temperature_val, top_p_val)
         };
 
         serde_json::json!(ChatRequest {
@@ -240,28 +489,39 @@ pub async fn generate_summary(
             max_tokens: max_tokens_val,
             temperature: temperature_val,
             top_p: top_p_val,
+            stream,
+            response_format: json_schema.and_then(|schema| openai_response_format(provider, schema)),
+            format: json_schema.filter(|_| provider == &LLMProvider::Ollama).cloned(),
+            options,
         })
     } else {
+        let mut messages = vec![ChatMessage {
+            role: "user".to_string(),
+            content: user_prompt.to_string(),
+        }];
+        if json_schema.is_some() {
+            messages.push(ChatMessage {
+                role: "assistant".to_string(),
+                content: CLAUDE_JSON_PREFILL.to_string(),
+            });
+        }
         serde_json::json!(ClaudeRequest {
             system: system_prompt.to_string(),
             model: model_name.to_string(),
             max_tokens: 2048,
-            messages: vec![ChatMessage {
-                role: "user".to_string(),
-                content: user_prompt.to_string(),
-            }]
+            messages,
+            stream,
         })
     };
 
     info!("🐞 LLM Request to {}: model={}", provider_name(provider), model_name);
 
     // Send request with timeout and cancellation support
-    let request_future = client
-        .post(api_url)
-        .headers(headers)
-        .json(&request_body)
-        .timeout(REQUEST_TIMEOUT_DURATION)
-        .send();
+    let mut request = client.post(api_url).headers(headers).json(&request_body);
+    if !stream {
+        request = request.timeout(REQUEST_TIMEOUT_DURATION);
+    }
+    let request_future = request.send();
 
     // Use tokio::select to race between cancellation and request completion
     let response = if let Some(token) = cancellation_token {
@@ -297,6 +557,24 @@ pub async fn generate_summary(
         return Err(format!("LLM API request failed: {}", error_body));
     }
 
+    if let Some(on_delta) = on_delta {
+        let format = match provider {
+            LLMProvider::Claude => StreamFormat::ClaudeSse,
+            LLMProvider::Ollama => StreamFormat::OllamaNdjson,
+            _ => StreamFormat::OpenAISse,
+        };
+        // The prefill is not repeated in the answer
+        let prefill = if provider == &LLMProvider::Claude && json_schema.is_some() {
+            on_delta(CLAUDE_JSON_PREFILL);
+            CLAUDE_JSON_PREFILL
+        } else {
+            ""
+        };
+        let text = read_stream(response, format, on_delta, cancellation_token).await?;
+        info!("🐞 LLM stream completed from {} ({} chars)", provider_name(provider), text.len());
+        return Ok(format!("{}{}", prefill, text));
+    }
+
     // Parse response based on provider
     if provider == &LLMProvider::Claude {
         let chat_response = response
@@ -312,7 +590,20 @@ pub async fn generate_summary(
             .ok_or("No content in LLM response")?
             .text
             .trim();
+        if json_schema.is_some() {
+            // The prefill is not repeated in the answer
+            return Ok(format!("{}{}", CLAUDE_JSON_PREFILL, content));
+        }
         Ok(content.to_string())
+    } else if provider == &LLMProvider::Ollama && json_schema.is_some() {
+        let chat_response = response
+            .json::<OllamaChatResponse>()
+            .await
+            .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
+
+        info!("🐞 LLM Response received from Ollama");
+
+        Ok(chat_response.message.content.trim().to_string())
     } else {
         let chat_response = response
             .json::<ChatResponse>()
@@ -332,6 +623,88 @@ pub async fn generate_summary(
     }
 }
 
+/// Reads a streamed response, passing each text delta to `on_delta`
+async fn read_stream(
+    response: reqwest::Response,
+    format: StreamFormat,
+    on_delta: DeltaCallback<'_>,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<String, String> {
+    let mut body = response.bytes_stream();
+    let mut lines = LineBuffer::new();
+    let mut text = String::new();
+
+    // Applies one line to `text`; Ok(true) once the provider signalled the end
+    let apply = |line: &str, text: &mut String| -> Result<bool, String> {
+        match format.parse_line(line) {
+            StreamEvent::Delta(delta) => {
+                on_delta(&delta);
+                text.push_str(&delta);
+                Ok(false)
+            }
+            StreamEvent::FinalDelta(delta) => {
+                on_delta(&delta);
+                text.push_str(&delta);
+                Ok(true)
+            }
+            StreamEvent::Done => Ok(true),
+            StreamEvent::Error(e) => Err(format!("LLM stream failed: {}", e)),
+            StreamEvent::Ignore => Ok(false),
+        }
+    };
+
+    'stream: loop {
+        let next_chunk = tokio::time::timeout(STREAM_IDLE_TIMEOUT, body.next());
+        let next_chunk = if let Some(token) = cancellation_token {
+            tokio::select! {
+                result = next_chunk => result,
+                _ = token.cancelled() => {
+                    return Err("Summary generation was cancelled".to_string());
+                }
+            }
+        } else {
+            next_chunk.await
+        };
+
+        let chunk = match next_chunk {
+            Ok(Some(chunk)) => chunk.map_err(|e| format!("Failed to read LLM stream: {}", e))?,
+            Ok(None) => break,
+            Err(_) => {
+                return Err(format!(
+                    "LLM stream stalled: no data for {} seconds",
+                    STREAM_IDLE_TIMEOUT.as_secs()
+                ))
+            }
+        };
+        for line in lines.push(&chunk) {
+            if apply(&line, &mut text)? {
+                break 'stream;
+            }
+        }
+    }
+    if let Some(line) = lines.finish() {
+        apply(&line, &mut text)?;
+    }
+
+    Ok(text.trim().to_string())
+}
+
+/// Claude has no JSON mode; starting its answer with `{` keeps it to JSON
+const CLAUDE_JSON_PREFILL: &str = "{";
+
+/// `response_format` requesting JSON from an OpenAI-compatible provider
+fn openai_response_format(provider: &LLMProvider, schema: &Value) -> Option<Value> {
+    match provider {
+        LLMProvider::OpenAI | LLMProvider::CustomOpenAI => Some(serde_json::json!({
+            "type": "json_schema",
+            "json_schema": { "name": "meeting_summary", "schema": schema, "strict": false }
+        })),
+        LLMProvider::Groq | LLMProvider::OpenRouter => Some(serde_json::json!({ "type": "json_object" })),
+        // Ollama uses `format`; the others have their own request formats
+        _ => None,
+    }
+}
+
 /// Helper function to get provider name for logging
 fn provider_name(provider: &LLMProvider) -> &str {
     match provider {
@@ -342,5 +715,101 @@ fn provider_name(provider: &LLMProvider) -> &str {
         LLMProvider::BuiltInAI => "Built-in AI",
         LLMProvider::OpenRouter => "OpenRouter",
         LLMProvider::CustomOpenAI => "Custom OpenAI",
+        LLMProvider::Gemini => "Gemini",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::spawn_mock_server;
+    use std::sync::Mutex;
+
+    async fn stream_from(body: &'static str, format: StreamFormat) -> (Result<String, String>, Vec<String>) {
+        let (base_url, _request) = spawn_mock_server("200 OK", body).await;
+        let response = Client::new().get(base_url).send().await.unwrap();
+        let deltas = Mutex::new(Vec::new());
+        let on_delta = |delta: &str| deltas.lock().unwrap().push(delta.to_string());
+        let result = read_stream(response, format, &on_delta, None).await;
+        (result, deltas.into_inner().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_read_stream_openai_sse() {
+        let body = concat!(
+            ": keep-alive\n\n",
+            "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"## Summary\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"\\nDone.\"}}]}\n\n",
+            "data: [DONE]\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"ignored\"}}]}\n\n",
+        );
+        let (result, deltas) = stream_from(body, StreamFormat::OpenAISse).await;
+
+        assert_eq!(result.unwrap(), "## Summary\nDone.");
+        assert_eq!(deltas, vec!["## Summary", "\nDone."]);
+    }
+
+    #[tokio::test]
+    async fn test_read_stream_ollama_keeps_content_of_final_line() {
+        let body = concat!(
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"Hello\"},\"done\":false}\n",
+            "{\"message\":{\"role\":\"assistant\",\"content\":\" world\"},\"done\":true}",
+        );
+        let (result, deltas) = stream_from(body, StreamFormat::OllamaNdjson).await;
+
+        assert_eq!(result.unwrap(), "Hello world");
+        assert_eq!(deltas, vec!["Hello", " world"]);
+    }
+
+    #[tokio::test]
+    async fn test_read_stream_reports_provider_error() {
+        let body = concat!(
+            "data: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi\"}}\n\n",
+            "data: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n",
+        );
+        let (result, deltas) = stream_from(body, StreamFormat::ClaudeSse).await;
+
+        assert_eq!(result.unwrap_err(), "LLM stream failed: Overloaded");
+        assert_eq!(deltas, vec!["Hi"]);
+    }
+
+    #[tokio::test]
+    async fn test_ollama_stream_sends_sampling_parameters_as_options() {
+        let (base_url, request) = spawn_mock_server(
+            "200 OK",
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"ok\"},\"done\":true}\n",
+        )
+        .await;
+        let on_delta = |_: &str| {};
+        let text = generate(
+            &Client::new(),
+            &LLMProvider::Ollama,
+            "llama3",
+            "",
+            "system",
+            "user",
+            Some(&base_url),
+            None,
+            Some(512),
+            Some(0.2),
+            Some(0.9),
+            None,
+            None,
+            Some(&on_delta),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(text, "ok");
+
+        let request = request.await.unwrap();
+        assert!(request.starts_with("POST /api/chat "));
+        let body: Value = serde_json::from_str(request.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        assert_eq!(body["options"]["num_predict"], 512);
+        assert_eq!(body["options"]["top_p"].as_f64().unwrap() as f32, 0.9);
+        assert!(body["options"]["temperature"].is_number());
+        assert!(body.get("max_tokens").is_none());
+        assert!(body.get("temperature").is_none());
     }
 }
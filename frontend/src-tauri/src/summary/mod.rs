@@ -1,7 +1,7 @@
 /// Summary module - handles all meeting summary generation functionality
 ///
 /// This module contains:
-/// - LLM client for communicating with various AI providers (OpenAI, Claude, Groq, Ollama, OpenRouter, CustomOpenAI)
+/// - LLM client for communicating with various AI providers (OpenAI, Claude, Gemini, Groq, Ollama, OpenRouter, CustomOpenAI)
 /// - Processor for chunking transcripts and generating summaries
 /// - Service layer for orchestrating summary generation
 /// - Templates for structured meeting summary generation
@@ -30,31 +30,47 @@ pub struct CustomOpenAIConfig {
     pub top_p: Option<f32>,
 }
 
+pub mod citations;
 pub mod commands;
+pub mod gemini;
 pub mod llm_client;
+pub mod prep;
 pub mod processor;
 pub mod service;
+pub mod streaming;
 pub mod summary_engine;
 pub mod template_commands;
+pub mod template_rules;
 pub mod templates;
 
 // Re-export Tauri commands (with their generated __cmd__ variants)
 pub use commands::{
-    __cmd__api_cancel_summary, __cmd__api_get_summary, __cmd__api_process_transcript,
-    __cmd__api_save_meeting_summary, api_cancel_summary, api_get_summary,
-    api_process_transcript, api_save_meeting_summary,
+    __cmd__api_cancel_summary, __cmd__api_get_gemini_models, __cmd__api_get_summary,
+    __cmd__api_process_transcript, __cmd__api_save_meeting_summary, api_cancel_summary,
+    api_get_gemini_models, api_get_summary, api_process_transcript, api_save_meeting_summary,
+};
+
+// Re-export template rule commands
+pub use template_rules::{
+    __cmd__api_get_template_rules, __cmd__api_save_template_rules, api_get_template_rules,
+    api_save_template_rules,
 };
 
 // Re-export template commands
 pub use template_commands::{
-    __cmd__api_get_template_details, __cmd__api_list_templates, __cmd__api_validate_template,
-    api_get_template_details, api_list_templates, api_validate_template,
+    __cmd__api_delete_template, __cmd__api_duplicate_template, __cmd__api_export_template,
+    __cmd__api_get_template, __cmd__api_get_template_details, __cmd__api_import_template,
+    __cmd__api_list_template_versions, __cmd__api_list_templates,
+    __cmd__api_restore_template_version, __cmd__api_save_template, __cmd__api_validate_template,
+    api_delete_template, api_duplicate_template, api_export_template, api_get_template,
+    api_get_template_details, api_import_template, api_list_template_versions, api_list_templates,
+    api_restore_template_version, api_save_template, api_validate_template,
 };
 
 // Re-export commonly used items
 pub use llm_client::LLMProvider;
 pub use processor::{
     chunk_text, clean_llm_markdown_output, extract_meeting_name_from_markdown,
-    generate_meeting_summary, rough_token_count,
+    generate_meeting_summary, rough_token_count, SummaryError, SummaryProgress,
 };
 pub use service::SummaryService;
@@ -0,0 +1,215 @@
+//! Meeting prep as structured input to summary generation
+//!
+//! When a meeting has a prep note, its agenda, goals, context and attendees are
+//! given to the model next to the transcript. The summary then gets an
+//! "Agenda Coverage" section stating, per agenda item and goal, whether it was
+//! decided, discussed, left open or not discussed, and statements are
+//! attributed to attendees by name.
+
+use crate::obsidian::speakers::normalize_person_name;
+use crate::obsidian::MeetingPrep;
+use crate::summary::templates::TemplateSection;
+
+/// Heading of the coverage section appended to the summary (below `##`, so it
+/// stays inside the note's `## Summary` section)
+pub const AGENDA_COVERAGE_HEADING: &str = "### Agenda Coverage";
+
+/// Prep note data used by summary generation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SummaryPrep {
+    pub title: String,
+    pub agenda: Vec<String>,
+    pub goals: Vec<String>,
+    pub context: String,
+    pub notes: Vec<String>,
+    /// Attendee names (wikilinks and aliases stripped)
+    pub attendees: Vec<String>,
+}
+
+impl SummaryPrep {
+    /// Prep data of a parsed note; `None` when it has nothing to summarize against
+    pub fn from_meeting_prep(prep: &MeetingPrep) -> Option<Self> {
+        let summary_prep = Self {
+            title: prep.title.trim().to_string(),
+            agenda: non_empty(&prep.agenda),
+            goals: non_empty(&prep.goals),
+            context: prep.context.trim().to_string(),
+            notes: non_empty(&prep.notes),
+            attendees: prep
+                .frontmatter
+                .attendees
+                .iter()
+                .flatten()
+                .map(|attendee| normalize_person_name(attendee))
+                .filter(|name| !name.is_empty())
+                .collect(),
+        };
+
+        let is_empty = summary_prep.agenda.is_empty()
+            && summary_prep.goals.is_empty()
+            && summary_prep.context.is_empty()
+            && summary_prep.attendees.is_empty();
+        (!is_empty).then_some(summary_prep)
+    }
+
+    /// Whether there are agenda items or goals to report coverage for
+    pub fn has_coverage_items(&self) -> bool {
+        !self.agenda.is_empty() || !self.goals.is_empty()
+    }
+
+    /// `<meeting_prep>` block for the final user prompt
+    pub fn to_prompt_block(&self) -> String {
+        let mut block = String::from("<meeting_prep>\n");
+        if !self.title.is_empty() {
+            block.push_str(&format!("Title: {}\n", self.title));
+        }
+        push_list(&mut block, "Attendees", &self.attendees);
+        push_list(&mut block, "Agenda", &self.agenda);
+        push_list(&mut block, "Goals", &self.goals);
+        if !self.context.is_empty() {
+            block.push_str(&format!("Context:\n{}\n", self.context));
+        }
+        push_list(&mut block, "Prep notes", &self.notes);
+        block.push_str("</meeting_prep>");
+        block
+    }
+
+    /// Extra system prompt instructions for a prep-aware summary
+    pub fn to_instructions(&self) -> String {
+        let mut instructions = self.to_structured_instructions();
+        if self.has_coverage_items() {
+            instructions.push_str(&format!(
+                "- After the template sections, add a `{}` section. List every agenda item and goal from the prep, in order, as \
+                 `- **<item>**: <status> - <one sentence on the outcome>`, where <status> is one of \
+                 Decided, Discussed, Open or Not discussed. End the section with one sentence stating \
+                 whether the meeting achieved what was prepared.\n",
+                AGENDA_COVERAGE_HEADING
+            ));
+        }
+        instructions
+    }
+
+    /// Extra system prompt instructions for a structured (JSON) summary, where
+    /// coverage is reported in `coverage_section` instead
+    pub fn to_structured_instructions(&self) -> String {
+        let mut instructions = String::from(
+            "\n**MEETING PREP:**\nThe user prepared for this meeting; the prep is in `<meeting_prep>`. \
+             Use it to interpret the discussion, but only report what the source text supports.\n",
+        );
+        if !self.attendees.is_empty() {
+            instructions.push_str(
+                "- Attribute statements, decisions and action items to attendees by name when the source text makes the speaker clear; never guess.\n",
+            );
+        }
+        instructions
+    }
+
+    /// Template section reporting agenda coverage in a structured summary
+    pub fn coverage_section(&self) -> Option<TemplateSection> {
+        self.has_coverage_items().then(|| TemplateSection {
+            title: AGENDA_COVERAGE_HEADING.trim_start_matches('#').trim().to_string(),
+            instruction: "List every agenda item and goal from `<meeting_prep>`, in order, as \
+                          `**<item>**: <status> - <one sentence on the outcome>`, where <status> is one of \
+                          Decided, Discussed, Open or Not discussed. Add a last item stating whether the \
+                          meeting achieved what was prepared"
+                .to_string(),
+            format: "list".to_string(),
+            columns: None,
+            item_format: None,
+            example_item_format: None,
+        })
+    }
+
+    /// Hint for chunk prompts, so chunk summaries keep what the coverage section needs
+    pub fn to_chunk_hint(&self) -> Option<String> {
+        if !self.has_coverage_items() {
+            return None;
+        }
+        let items: Vec<&str> = self
+            .agenda
+            .iter()
+            .chain(self.goals.iter())
+            .map(String::as_str)
+            .collect();
+        Some(format!(
+            "\n\nThe meeting was prepared with these agenda items and goals; note whatever is said, decided or left open about them:\n- {}",
+            items.join("\n- ")
+        ))
+    }
+}
+
+fn non_empty(items: &[String]) -> Vec<String> {
+    items
+        .iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn push_list(block: &mut String, label: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    block.push_str(&format!("{}:\n", label));
+    for item in items {
+        block.push_str(&format!("- {}\n", item));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn prep() -> MeetingPrep {
+        let mut prep = MeetingPrep::new(PathBuf::from("sync.md"), String::new());
+        prep.title = "Apollo Sync".to_string();
+        prep.agenda = vec!["Budget".to_string(), " ".to_string(), "Hiring".to_string()];
+        prep.goals = vec!["Agree on Q3 scope".to_string()];
+        prep.frontmatter.attendees = Some(vec!["[[Alice Smith|Alice]]".to_string(), "Bob".to_string()]);
+        prep
+    }
+
+    #[test]
+    fn test_from_meeting_prep_normalizes_items_and_attendees() {
+        let summary_prep = SummaryPrep::from_meeting_prep(&prep()).unwrap();
+        assert_eq!(summary_prep.agenda, vec!["Budget", "Hiring"]);
+        assert_eq!(summary_prep.attendees, vec!["Alice Smith", "Bob"]);
+        assert!(summary_prep.has_coverage_items());
+
+        let empty = MeetingPrep::new(PathBuf::from("empty.md"), String::new());
+        assert!(SummaryPrep::from_meeting_prep(&empty).is_none());
+    }
+
+    #[test]
+    fn test_prompt_block_and_instructions() {
+        let summary_prep = SummaryPrep::from_meeting_prep(&prep()).unwrap();
+        let block = summary_prep.to_prompt_block();
+        assert!(block.starts_with("<meeting_prep>\nTitle: Apollo Sync\nAttendees:\n- Alice Smith\n- Bob\n"));
+        assert!(block.contains("Agenda:\n- Budget\n- Hiring\nGoals:\n- Agree on Q3 scope\n"));
+        assert!(!block.contains("Context:"));
+
+        let instructions = summary_prep.to_instructions();
+        assert!(instructions.contains(AGENDA_COVERAGE_HEADING));
+        assert!(instructions.contains("by name"));
+
+        let hint = summary_prep.to_chunk_hint().unwrap();
+        assert!(hint.ends_with("- Budget\n- Hiring\n- Agree on Q3 scope"));
+
+        let structured = summary_prep.to_structured_instructions();
+        assert!(structured.contains("by name"));
+        assert!(!structured.contains(AGENDA_COVERAGE_HEADING));
+        assert_eq!(summary_prep.coverage_section().unwrap().title, "Agenda Coverage");
+    }
+
+    #[test]
+    fn test_attendees_only_prep_skips_coverage() {
+        let mut prep = MeetingPrep::new(PathBuf::from("call.md"), String::new());
+        prep.frontmatter.attendees = Some(vec!["Carol".to_string()]);
+        let summary_prep = SummaryPrep::from_meeting_prep(&prep).unwrap();
+        assert!(!summary_prep.has_coverage_items());
+        assert!(!summary_prep.to_instructions().contains(AGENDA_COVERAGE_HEADING));
+        assert!(summary_prep.to_chunk_hint().is_none());
+        assert!(summary_prep.coverage_section().is_none());
+    }
+}
@@ -2,13 +2,24 @@ use crate::database::repositories::{
     meeting::MeetingsRepository, setting::SettingsRepository, summary::SummaryProcessesRepository,
 };
 use crate::summary::llm_client::LLMProvider;
-use crate::summary::processor::{extract_meeting_name_from_markdown, generate_meeting_summary};
+use crate::summary::prep::SummaryPrep;
+use crate::summary::template_rules::{load_template_rules, select_template};
+use crate::summary::templates;
+use crate::summary::processor::{
+    action_items_from_structured, clean_llm_markdown_output, extract_action_items,
+    extract_meeting_name_from_markdown, generate_meeting_summary, SummaryError, SummaryProgress,
+};
+use crate::obsidian::find_markdown_file_in_folder;
+use crate::obsidian::parser::parse_meeting_file;
+use crate::obsidian::MeetingPrep;
 use crate::ollama::metadata::ModelMetadataCache;
+use serde::Serialize;
 use sqlx::SqlitePool;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 use once_cell::sync::Lazy;
@@ -22,6 +33,152 @@ static METADATA_CACHE: Lazy<ModelMetadataCache> = Lazy::new(|| {
 static CANCELLATION_REGISTRY: Lazy<Arc<Mutex<HashMap<String, CancellationToken>>>> =
     Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
+/// Event with the stage of a running summary generation
+pub const SUMMARY_PROGRESS_EVENT: &str = "summary-progress";
+/// Event with the partial markdown of the report being written
+pub const SUMMARY_STREAM_EVENT: &str = "summary-stream";
+/// Minimum time between `summary-stream` events
+const STREAM_EMIT_INTERVAL: Duration = Duration::from_millis(150);
+/// Minimum time between partial markdown writes to `summary_processes`
+const STREAM_PERSIST_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Serialize)]
+struct SummaryProgressPayload<'a> {
+    meeting_id: &'a str,
+    #[serde(flatten)]
+    progress: &'a SummaryProgress,
+}
+
+#[derive(Serialize)]
+struct SummaryStreamPayload<'a> {
+    meeting_id: &'a str,
+    markdown: &'a str,
+}
+
+/// Writes progress snapshots to `summary_processes` one at a time, in order
+///
+/// Spawning a task per update would let an older snapshot land after a newer one.
+struct ProgressWriter {
+    sender: tokio::sync::mpsc::UnboundedSender<serde_json::Value>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl ProgressWriter {
+    fn spawn(pool: SqlitePool, meeting_id: String) -> Self {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<serde_json::Value>();
+        let task = tokio::spawn(async move {
+            while let Some(value) = receiver.recv().await {
+                if let Err(e) = SummaryProcessesRepository::update_process_progress(&pool, &meeting_id, &value).await {
+                    warn!("Failed to store summary progress for {}: {}", meeting_id, e);
+                }
+            }
+        });
+        Self { sender, task }
+    }
+
+    fn send(&self, value: serde_json::Value) {
+        // Only fails once the writer is gone, when progress no longer matters
+        let _ = self.sender.send(value);
+    }
+
+    /// Wait until every queued snapshot has been written
+    async fn finish(self) {
+        drop(self.sender);
+        if let Err(e) = self.task.await {
+            warn!("Summary progress writer failed: {}", e);
+        }
+    }
+}
+
+#[derive(Default)]
+struct StreamState {
+    markdown: String,
+    last_emit: Option<Instant>,
+    last_persist: Option<Instant>,
+}
+
+/// Relays generation progress to the UI (events) and to `summary_processes.metadata`
+///
+/// The partial report is updated per token, so stream events and database
+/// writes are throttled.
+struct ProgressRelay<R: Runtime> {
+    app: AppHandle<R>,
+    writer: ProgressWriter,
+    meeting_id: String,
+    state: Mutex<StreamState>,
+}
+
+impl<R: Runtime> ProgressRelay<R> {
+    fn new(app: AppHandle<R>, pool: SqlitePool, meeting_id: String) -> Self {
+        Self {
+            app,
+            writer: ProgressWriter::spawn(pool, meeting_id.clone()),
+            meeting_id,
+            state: Mutex::new(StreamState::default()),
+        }
+    }
+
+    /// Flush pending progress writes before the final status is stored
+    async fn finish(self) {
+        self.writer.finish().await;
+    }
+
+    fn on_progress(&self, progress: SummaryProgress) {
+        let payload = SummaryProgressPayload {
+            meeting_id: &self.meeting_id,
+            progress: &progress,
+        };
+        if let Err(e) = self.app.emit(SUMMARY_PROGRESS_EVENT, &payload) {
+            warn!("Failed to emit {}: {}", SUMMARY_PROGRESS_EVENT, e);
+        }
+        if let Ok(mut state) = self.state.lock() {
+            state.last_persist = Some(Instant::now());
+        }
+        self.persist(&progress, None);
+    }
+
+    fn on_partial(&self, markdown: &str) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.markdown.clear();
+        state.markdown.push_str(markdown);
+
+        let due = |last: Option<Instant>, interval: Duration| last.map_or(true, |t| t.elapsed() >= interval);
+        let emit = due(state.last_emit, STREAM_EMIT_INTERVAL);
+        let persist = due(state.last_persist, STREAM_PERSIST_INTERVAL);
+        if !emit && !persist {
+            return;
+        }
+
+        let markdown = clean_llm_markdown_output(&state.markdown);
+        if emit {
+            state.last_emit = Some(Instant::now());
+            let payload = SummaryStreamPayload {
+                meeting_id: &self.meeting_id,
+                markdown: &markdown,
+            };
+            if let Err(e) = self.app.emit(SUMMARY_STREAM_EVENT, &payload) {
+                warn!("Failed to emit {}: {}", SUMMARY_STREAM_EVENT, e);
+            }
+        }
+        if persist {
+            state.last_persist = Some(Instant::now());
+            drop(state);
+            self.persist(&SummaryProgress::Writing, Some(markdown));
+        }
+    }
+
+    /// Store the stage (and partial report) so a reloaded UI can pick it up
+    fn persist(&self, progress: &SummaryProgress, markdown: Option<String>) {
+        let mut value = serde_json::to_value(progress).unwrap_or_default();
+        if let (Some(object), Some(markdown)) = (value.as_object_mut(), markdown) {
+            object.insert("markdown".to_string(), serde_json::Value::String(markdown));
+        }
+        self.writer.send(value);
+    }
+}
+
 /// Summary service - handles all summary generation logic
 pub struct SummaryService;
 
@@ -61,7 +218,8 @@ impl SummaryService {
     /// Processes transcript in the background and generates summary
     ///
     /// This function is designed to be spawned as an async task and does not block
-    /// the main thread. It updates the database with progress and results.
+    /// the main thread. It updates the database with progress and results, and
+    /// emits `summary-progress` / `summary-stream` events while generating.
     ///
     /// # Arguments
     /// * `_app` - Tauri app handle (for future use)
@@ -72,7 +230,10 @@ impl SummaryService {
     /// * `model_name` - Specific model (e.g., "gpt-4", "llama3.2:latest")
     /// * `custom_prompt` - Optional user-provided context
     /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
-    pub async fn process_transcript_background<R: tauri::Runtime>(
+    /// * `note_path` - Optional prep note whose agenda, goals and attendees guide the summary
+    /// * `auto_template` - Pick the template from the note's frontmatter (see `template_rules`),
+    ///   falling back to `template_id`
+    pub async fn process_transcript_background<R: Runtime>(
         _app: AppHandle<R>,
         pool: SqlitePool,
         meeting_id: String,
@@ -80,7 +241,9 @@ impl SummaryService {
         model_provider: String,
         model_name: String,
         custom_prompt: String,
-        template_id: String,
+        mut template_id: String,
+        note_path: Option<String>,
+        auto_template: bool,
     ) {
         let start_time = Instant::now();
         info!(
@@ -212,14 +375,47 @@ impl SummaryService {
                 }
             }
         } else {
-            // Cloud providers (OpenAI, Claude, Groq, CustomOpenAI) handle large contexts automatically
+            // Cloud providers (OpenAI, Claude, Gemini, Groq, CustomOpenAI) handle large contexts automatically
             100000  // Effectively unlimited for single-pass processing
         };
 
         // Get app data directory for BuiltInAI provider
         let app_data_dir = _app.path().app_data_dir().ok();
 
-        // Generate summary
+        let note = Self::load_meeting_note(&pool, &meeting_id, note_path.as_deref()).await;
+        let prep = note.as_ref().and_then(|note| {
+            let prep = SummaryPrep::from_meeting_prep(note)?;
+            info!(
+                "Using prep from {} ({} agenda items, {} goals, {} attendees)",
+                note.file_path.display(),
+                prep.agenda.len(),
+                prep.goals.len(),
+                prep.attendees.len()
+            );
+            Some(prep)
+        });
+
+        if auto_template {
+            if let Some(note) = &note {
+                let rules = load_template_rules(&_app);
+                let selection = select_template(&note.frontmatter, &rules, |id| templates::get_template(id).is_ok());
+                if let Some(selection) = selection {
+                    info!(
+                        "Auto-selected template '{}' for meeting_id {} from the note's {} ({})",
+                        selection.template_id,
+                        meeting_id,
+                        selection.matched_field,
+                        selection.keyword.as_deref().unwrap_or(&selection.template_id)
+                    );
+                    template_id = selection.template_id;
+                }
+            }
+        }
+
+        // Generate summary, streaming progress to the UI
+        let relay = ProgressRelay::new(_app.clone(), pool.clone(), meeting_id.clone());
+        let on_progress = |progress: SummaryProgress| relay.on_progress(progress);
+        let on_partial = |markdown: &str| relay.on_partial(markdown);
         let client = reqwest::Client::new();
         let result = generate_meeting_summary(
             &client,
@@ -229,6 +425,7 @@ impl SummaryService {
             &text,
             &custom_prompt,
             &template_id,
+            prep.as_ref(),
             token_threshold,
             ollama_endpoint.as_deref(),
             custom_openai_endpoint.as_deref(),
@@ -237,17 +434,18 @@ impl SummaryService {
             custom_openai_top_p,
             app_data_dir.as_ref(),
             Some(&cancellation_token),
+            Some(&on_progress),
+            Some(&on_partial),
         )
         .await;
+        relay.finish().await;
 
         let duration = start_time.elapsed().as_secs_f64();
 
-        // Clean up cancellation token regardless of outcome
-        Self::cleanup_cancellation_token(&meeting_id);
-
         match result {
-            Ok((mut final_markdown, num_chunks)) => {
+            Ok((mut final_markdown, structured, num_chunks)) => {
                 if num_chunks == 0 && final_markdown.is_empty() {
+                    Self::cleanup_cancellation_token(&meeting_id);
                     Self::update_process_failed(
                         &pool,
                         &meeting_id,
@@ -295,10 +493,65 @@ impl SummaryService {
                     }
                 }
 
-                // Create result JSON with markdown only (summary_json will be added on first edit)
-                let result_json = serde_json::json!({
+                // Structured action items for the Obsidian Tasks checkboxes, taken from
+                // the report's own section when it has one
+                let extracted = match structured.as_ref().and_then(action_items_from_structured) {
+                    Some(items) => Ok(items),
+                    None => {
+                        let meeting_date = match MeetingsRepository::get_meeting_metadata(&pool, &meeting_id).await {
+                            Ok(Some(meeting)) => meeting.created_at.0.format("%Y-%m-%d").to_string(),
+                            _ => chrono::Local::now().format("%Y-%m-%d").to_string(),
+                        };
+                        extract_action_items(
+                            &client,
+                            &provider,
+                            &model_name,
+                            &final_api_key,
+                            &final_markdown,
+                            &meeting_date,
+                            ollama_endpoint.as_deref(),
+                            custom_openai_endpoint.as_deref(),
+                            custom_openai_max_tokens,
+                            custom_openai_temperature,
+                            custom_openai_top_p,
+                            app_data_dir.as_ref(),
+                            Some(&cancellation_token),
+                        )
+                        .await
+                    }
+                };
+                let action_items = match extracted {
+                    Ok(items) => {
+                        info!("Extracted {} action items for meeting_id: {}", items.len(), meeting_id);
+                        items
+                    }
+                    Err(_) if cancellation_token.is_cancelled() => {
+                        Self::cleanup_cancellation_token(&meeting_id);
+                        info!("Summary generation was cancelled for meeting_id: {}", meeting_id);
+                        if let Err(db_err) = SummaryProcessesRepository::update_process_cancelled(&pool, &meeting_id).await {
+                            error!("Failed to update DB status to cancelled for {}: {}", meeting_id, db_err);
+                        }
+                        return;
+                    }
+                    Err(e) => {
+                        warn!("Failed to extract action items for {}: {}", meeting_id, e);
+                        Vec::new()
+                    }
+                };
+
+                // Clean up cancellation token now that no more LLM calls are made
+                Self::cleanup_cancellation_token(&meeting_id);
+
+                // Create result JSON with markdown and action items (summary_json will be added on first edit)
+                let mut result_json = serde_json::json!({
                     "markdown": final_markdown,
+                    "action_items": action_items,
+                    "template": template_id,
                 });
+                // Per-section content, validated against the template
+                if let Some(structured) = structured {
+                    result_json["structured"] = serde_json::json!(structured);
+                }
 
                 // Update database with completed status
                 if let Err(e) = SummaryProcessesRepository::update_process_completed(
@@ -319,19 +572,50 @@ impl SummaryService {
                         "Summary saved successfully for meeting_id: {}",
                         meeting_id
                     );
+                    crate::semantic::commands::spawn_auto_index(&_app, pool.clone(), meeting_id.clone());
                 }
             }
-            Err(e) => {
-                // Check if error is due to cancellation
-                if e.contains("cancelled") {
-                    info!("Summary generation was cancelled for meeting_id: {}", meeting_id);
-                    if let Err(db_err) = SummaryProcessesRepository::update_process_cancelled(&pool, &meeting_id).await {
-                        error!("Failed to update DB status to cancelled for {}: {}", meeting_id, db_err);
-                    }
-                } else {
-                    Self::update_process_failed(&pool, &meeting_id, &e).await;
+            Err(SummaryError::Cancelled) => {
+                Self::cleanup_cancellation_token(&meeting_id);
+                info!("Summary generation was cancelled for meeting_id: {}", meeting_id);
+                if let Err(db_err) = SummaryProcessesRepository::update_process_cancelled(&pool, &meeting_id).await {
+                    error!("Failed to update DB status to cancelled for {}: {}", meeting_id, db_err);
                 }
             }
+            Err(SummaryError::Failed(e)) => {
+                Self::cleanup_cancellation_token(&meeting_id);
+                Self::update_process_failed(&pool, &meeting_id, &e).await;
+            }
+        }
+    }
+
+    /// Loads the meeting's note from `note_path`, or the markdown in the
+    /// meeting's recording folder
+    ///
+    /// A missing or unparsable note only means the summary is not prep-aware
+    /// and the template is not picked from its frontmatter.
+    async fn load_meeting_note(pool: &SqlitePool, meeting_id: &str, note_path: Option<&str>) -> Option<MeetingPrep> {
+        let path = match note_path.filter(|p| !p.trim().is_empty()) {
+            Some(path) => PathBuf::from(path),
+            None => {
+                let meeting = MeetingsRepository::get_meeting_metadata(pool, meeting_id).await.ok()??;
+                find_markdown_file_in_folder(Path::new(&meeting.folder_path?))?
+            }
+        };
+
+        let content = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("Failed to read prep note {}: {}", path.display(), e);
+                return None;
+            }
+        };
+        match parse_meeting_file(&path, &content) {
+            Ok(note) => Some(note),
+            Err(e) => {
+                warn!("Failed to parse prep note {}: {}", path.display(), e);
+                None
+            }
         }
     }
 
@@ -356,3 +640,32 @@ impl SummaryService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{insert_meeting, test_pool};
+
+    #[tokio::test]
+    async fn test_progress_writer_stores_snapshots_in_order() {
+        let pool = test_pool().await;
+        insert_meeting(&pool, "m1", "Standup").await;
+        SummaryProcessesRepository::create_or_reset_process(&pool, "m1")
+            .await
+            .unwrap();
+
+        let writer = ProgressWriter::spawn(pool.clone(), "m1".to_string());
+        for i in 0..50 {
+            writer.send(serde_json::json!({ "stage": "writing", "markdown": format!("part {}", i) }));
+        }
+        writer.finish().await;
+
+        let process = SummaryProcessesRepository::get_summary_data(&pool, "m1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(process.status, "processing");
+        let metadata: serde_json::Value = serde_json::from_str(&process.metadata.unwrap()).unwrap();
+        assert_eq!(metadata["markdown"], "part 49");
+    }
+}
@@ -0,0 +1,245 @@
+//! Incremental parsing of streamed LLM responses
+//!
+//! Providers stream completions in two wire formats:
+//! - Server-sent events (OpenAI-compatible APIs and Claude): `data: {...}` lines
+//! - Newline-delimited JSON (Ollama's native `/api/chat`): one object per line
+//!
+//! Network chunks can split a line (or a UTF-8 character) anywhere, so both
+//! formats go through `LineBuffer`, which only yields complete lines.
+
+use serde_json::Value;
+
+/// Callback receiving each piece of generated text as it arrives
+pub type DeltaCallback<'a> = &'a (dyn Fn(&str) + Send + Sync);
+
+/// Meaning of one line of a streamed response
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    /// Text to append to the completion
+    Delta(String),
+    /// Last text of the completion, sent together with the end signal
+    FinalDelta(String),
+    /// The provider signalled the end of the completion
+    Done,
+    /// The provider reported an error mid-stream
+    Error(String),
+    /// Keep-alives, comments and metadata events
+    Ignore,
+}
+
+/// Wire format of a streaming provider
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StreamFormat {
+    /// OpenAI-compatible `chat.completion.chunk` events
+    OpenAISse,
+    /// Anthropic Messages API events
+    ClaudeSse,
+    /// Ollama `/api/chat` objects
+    OllamaNdjson,
+}
+
+impl StreamFormat {
+    /// Parse one complete line in this format
+    pub fn parse_line(self, line: &str) -> StreamEvent {
+        match self {
+            StreamFormat::OpenAISse => parse_openai_sse_line(line),
+            StreamFormat::ClaudeSse => parse_claude_sse_line(line),
+            StreamFormat::OllamaNdjson => parse_ollama_ndjson_line(line),
+        }
+    }
+}
+
+/// Buffers raw bytes and yields complete lines
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+    pending: Vec<u8>,
+}
+
+impl LineBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a network chunk and return the lines it completed (without `\r\n`)
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(bytes);
+        let mut lines = Vec::new();
+        while let Some(pos) = self.pending.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line[..line.len() - 1]);
+            lines.push(line.trim_end_matches('\r').to_string());
+        }
+        lines
+    }
+
+    /// Remaining text once the stream ended without a trailing newline
+    pub fn finish(&mut self) -> Option<String> {
+        let rest = std::mem::take(&mut self.pending);
+        let rest = String::from_utf8_lossy(&rest).trim().to_string();
+        (!rest.is_empty()).then_some(rest)
+    }
+}
+
+/// JSON payload of an SSE `data:` line (other SSE fields are ignored)
+fn sse_data(line: &str) -> Option<&str> {
+    line.strip_prefix("data:").map(str::trim)
+}
+
+fn parse_json(payload: &str) -> Result<Value, StreamEvent> {
+    serde_json::from_str(payload)
+        .map_err(|e| StreamEvent::Error(format!("Malformed stream event: {} ({})", payload, e)))
+}
+
+/// Error message of an `{"error": ...}` object (string or `{message}`)
+fn error_message(value: &Value) -> Option<String> {
+    let error = value.get("error")?;
+    Some(
+        error
+            .get("message")
+            .and_then(Value::as_str)
+            .or_else(|| error.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string()),
+    )
+}
+
+/// Parse an OpenAI-compatible SSE line (`choices[0].delta.content`)
+pub fn parse_openai_sse_line(line: &str) -> StreamEvent {
+    let Some(payload) = sse_data(line) else {
+        return StreamEvent::Ignore;
+    };
+    if payload == "[DONE]" {
+        return StreamEvent::Done;
+    }
+    let value = match parse_json(payload) {
+        Ok(value) => value,
+        Err(event) => return event,
+    };
+    if let Some(message) = error_message(&value) {
+        return StreamEvent::Error(message);
+    }
+    match value
+        .pointer("/choices/0/delta/content")
+        .and_then(Value::as_str)
+    {
+        Some(text) if !text.is_empty() => StreamEvent::Delta(text.to_string()),
+        _ => StreamEvent::Ignore,
+    }
+}
+
+/// Parse a Claude SSE line (`content_block_delta` text deltas)
+pub fn parse_claude_sse_line(line: &str) -> StreamEvent {
+    let Some(payload) = sse_data(line) else {
+        return StreamEvent::Ignore;
+    };
+    let value = match parse_json(payload) {
+        Ok(value) => value,
+        Err(event) => return event,
+    };
+    match value.get("type").and_then(Value::as_str) {
+        Some("content_block_delta") => match value.pointer("/delta/text").and_then(Value::as_str) {
+            Some(text) if !text.is_empty() => StreamEvent::Delta(text.to_string()),
+            _ => StreamEvent::Ignore,
+        },
+        Some("message_stop") => StreamEvent::Done,
+        Some("error") => StreamEvent::Error(error_message(&value).unwrap_or_else(|| payload.to_string())),
+        _ => StreamEvent::Ignore,
+    }
+}
+
+/// Parse an Ollama `/api/chat` NDJSON line (`message.content`, `done`)
+pub fn parse_ollama_ndjson_line(line: &str) -> StreamEvent {
+    let line = line.trim();
+    if line.is_empty() {
+        return StreamEvent::Ignore;
+    }
+    let value = match parse_json(line) {
+        Ok(value) => value,
+        Err(event) => return event,
+    };
+    if let Some(message) = error_message(&value) {
+        return StreamEvent::Error(message);
+    }
+    let done = value.get("done").and_then(Value::as_bool) == Some(true);
+    match value.pointer("/message/content").and_then(Value::as_str) {
+        Some(text) if !text.is_empty() && done => StreamEvent::FinalDelta(text.to_string()),
+        Some(text) if !text.is_empty() => StreamEvent::Delta(text.to_string()),
+        _ if done => StreamEvent::Done,
+        _ => StreamEvent::Ignore,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_buffer_handles_split_lines_and_utf8() {
+        let mut buffer = LineBuffer::new();
+        let bytes = "data: {\"a\":\"caf\u{e9}\"}\r\ndata: [DONE]".as_bytes();
+        // Split inside the two-byte 'é'
+        let split = bytes.iter().position(|b| *b == 0xC3).unwrap() + 1;
+
+        assert!(buffer.push(&bytes[..split]).is_empty());
+        assert_eq!(buffer.push(&bytes[split..]), vec!["data: {\"a\":\"caf\u{e9}\"}".to_string()]);
+        assert_eq!(buffer.finish(), Some("data: [DONE]".to_string()));
+        assert_eq!(buffer.finish(), None);
+    }
+
+    #[test]
+    fn test_parse_openai_sse() {
+        assert_eq!(
+            parse_openai_sse_line(r###"data: {"choices":[{"index":0,"delta":{"content":"## Sum"}}]}"###),
+            StreamEvent::Delta("## Sum".to_string())
+        );
+        assert_eq!(
+            parse_openai_sse_line(r#"data: {"choices":[{"index":0,"delta":{"role":"assistant"}}]}"#),
+            StreamEvent::Ignore
+        );
+        assert_eq!(parse_openai_sse_line(": keep-alive"), StreamEvent::Ignore);
+        assert_eq!(parse_openai_sse_line("data: [DONE]"), StreamEvent::Done);
+        assert_eq!(
+            parse_openai_sse_line(r#"data: {"error":{"message":"Rate limit reached"}}"#),
+            StreamEvent::Error("Rate limit reached".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_claude_sse() {
+        assert_eq!(parse_claude_sse_line("event: content_block_delta"), StreamEvent::Ignore);
+        assert_eq!(
+            parse_claude_sse_line(
+                r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hello"}}"#
+            ),
+            StreamEvent::Delta("Hello".to_string())
+        );
+        assert_eq!(
+            parse_claude_sse_line(r#"data: {"type":"message_stop"}"#),
+            StreamEvent::Done
+        );
+        assert_eq!(
+            parse_claude_sse_line(r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#),
+            StreamEvent::Error("Overloaded".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_ollama_ndjson() {
+        assert_eq!(
+            parse_ollama_ndjson_line(r#"{"model":"llama3","message":{"role":"assistant","content":"Hi"},"done":false}"#),
+            StreamEvent::Delta("Hi".to_string())
+        );
+        assert_eq!(
+            parse_ollama_ndjson_line(r#"{"model":"llama3","message":{"role":"assistant","content":""},"done":true}"#),
+            StreamEvent::Done
+        );
+        assert_eq!(
+            parse_ollama_ndjson_line(r#"{"model":"llama3","message":{"role":"assistant","content":"bye"},"done":true}"#),
+            StreamEvent::FinalDelta("bye".to_string())
+        );
+        assert_eq!(
+            parse_ollama_ndjson_line(r#"{"error":"model 'llama9' not found"}"#),
+            StreamEvent::Error("model 'llama9' not found".to_string())
+        );
+    }
+}
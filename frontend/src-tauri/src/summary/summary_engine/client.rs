@@ -15,6 +15,7 @@ use tokio_util::sync::CancellationToken;
 
 use super::models;
 use super::sidecar::SidecarManager;
+use crate::summary::streaming::DeltaCallback;
 
 // ============================================================================
 // Request/Response Types
@@ -33,12 +34,17 @@ enum Request {
         top_k: Option<i32>,
         top_p: Option<f32>,
         stop_tokens: Option<Vec<String>>,
+        stream: bool,
+        // GBNF grammar constraining the output
+        #[serde(skip_serializing_if = "Option::is_none")]
+        grammar: Option<String>,
     },
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum Response {
+    Token { text: String },
     Response { text: String, error: Option<String> },
     Error { message: String },
 }
@@ -132,6 +138,49 @@ pub async fn generate_with_builtin(
     system_prompt: &str,
     user_prompt: &str,
     cancellation_token: Option<&CancellationToken>,
+) -> Result<String> {
+    generate(app_data_dir, model_name, system_prompt, user_prompt, cancellation_token, None, None).await
+}
+
+/// Generate text using built-in AI, passing text to `on_delta` as it is produced
+///
+/// The sidecar sends a `token` frame per decoded piece of text before the
+/// final response. The returned text is the final response, which is
+/// authoritative (stop tokens are trimmed from it).
+pub async fn stream_with_builtin(
+    app_data_dir: &PathBuf,
+    model_name: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    cancellation_token: Option<&CancellationToken>,
+    on_delta: DeltaCallback<'_>,
+) -> Result<String> {
+    generate(app_data_dir, model_name, system_prompt, user_prompt, cancellation_token, Some(on_delta), None).await
+}
+
+/// Generate text using built-in AI, constrained by a GBNF `grammar`
+///
+/// Used for structured (JSON) output: the sidecar only samples tokens the
+/// grammar allows, so the result is syntactically valid when not cut off.
+pub async fn generate_json_with_builtin(
+    app_data_dir: &PathBuf,
+    model_name: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    grammar: &str,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<String> {
+    generate(app_data_dir, model_name, system_prompt, user_prompt, cancellation_token, None, Some(grammar)).await
+}
+
+async fn generate(
+    app_data_dir: &PathBuf,
+    model_name: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    cancellation_token: Option<&CancellationToken>,
+    on_delta: Option<DeltaCallback<'_>>,
+    grammar: Option<&str>,
 ) -> Result<String> {
     // Check cancellation at start
     if let Some(token) = cancellation_token {
@@ -184,6 +233,8 @@ pub async fn generate_with_builtin(
         top_k: Some(model_def.sampling.top_k),
         top_p: Some(model_def.sampling.top_p),
         stop_tokens: Some(model_def.sampling.stop_tokens.clone()),
+        stream: on_delta.is_some(),
+        grammar: grammar.map(str::to_string),
     };
 
     let request_json = serde_json::to_string(&request)?;
@@ -193,10 +244,23 @@ pub async fn generate_with_builtin(
 
     log::info!("Sending generation request to sidecar");
 
-    // Race between send_request and cancellation token
+    let send = async {
+        match on_delta {
+            Some(on_delta) => {
+                let mut on_frame = |frame: &str| match serde_json::from_str::<Response>(frame) {
+                    Ok(Response::Token { text }) => on_delta(&text),
+                    _ => log::warn!("Ignoring malformed token frame: {}", frame),
+                };
+                manager.send_request_streaming(request_json, timeout, &mut on_frame).await
+            }
+            None => manager.send_request(request_json, timeout).await,
+        }
+    };
+
+    // Race between the request and cancellation token
     let response_json = if let Some(token) = cancellation_token {
         tokio::select! {
-            result = manager.send_request(request_json, timeout) => {
+            result = send => {
                 result?
             }
             _ = token.cancelled() => {
@@ -209,7 +273,7 @@ pub async fn generate_with_builtin(
             }
         }
     } else {
-        manager.send_request(request_json, timeout).await?
+        send.await?
     };
 
     // Check cancellation before parsing response
@@ -233,6 +297,7 @@ pub async fn generate_with_builtin(
             }
         }
         Response::Error { message } => Err(anyhow!("Sidecar error: {}", message)),
+        Response::Token { .. } => Err(anyhow!("Unexpected token frame: {}", response_json)),
     }
 }
 
@@ -8,7 +8,7 @@ pub mod models;
 pub mod sidecar;
 
 // Re-export commonly used types
-pub use client::{generate_with_builtin, is_sidecar_healthy, shutdown_sidecar_gracefully, force_shutdown_sidecar};
+pub use client::{generate_json_with_builtin, generate_with_builtin, stream_with_builtin, is_sidecar_healthy, shutdown_sidecar_gracefully, force_shutdown_sidecar};
 pub use commands::{
     __cmd__builtin_ai_cancel_download, __cmd__builtin_ai_delete_model,
     __cmd__builtin_ai_download_model, __cmd__builtin_ai_get_available_summary_model,
@@ -389,6 +389,63 @@ impl SidecarManager {
         }
     }
 
+    /// Send a streaming request, passing each `token` frame to `on_frame`
+    ///
+    /// Returns the first non-token frame (the final response). The timeout
+    /// covers the whole generation, as with `send_request`.
+    pub async fn send_request_streaming(
+        &self,
+        request_json: String,
+        timeout: Duration,
+        on_frame: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<String> {
+        let _guard = RequestGuard::new(self.active_request_count.clone());
+
+        {
+            let mut stdin_lock = self.stdin_writer.lock().await;
+            let stdin = stdin_lock
+                .as_mut()
+                .ok_or_else(|| anyhow!("Sidecar not running"))?;
+
+            stdin
+                .write_all(request_json.as_bytes())
+                .await
+                .context("Failed to write request to stdin")?;
+            stdin
+                .write_all(b"\n")
+                .await
+                .context("Failed to write newline")?;
+            stdin.flush().await.context("Failed to flush stdin")?;
+        }
+
+        let read_frames = async {
+            loop {
+                let line = self.read_response().await?;
+                // llama-helper serializes the `type` tag first
+                if line.starts_with(r#"{"type":"token""#) {
+                    on_frame(&line);
+                    continue;
+                }
+                return Ok::<String, anyhow::Error>(line);
+            }
+        };
+
+        match tokio::time::timeout(timeout, read_frames).await {
+            Ok(Ok(response)) => {
+                self.update_activity().await;
+                Ok(response)
+            }
+            Ok(Err(e)) => Err(e),
+            Err(_) => {
+                log::error!("Streaming request timeout after {:?}, shutting down sidecar", timeout);
+                if let Err(shutdown_err) = self.shutdown().await {
+                    log::error!("Failed to shutdown sidecar after timeout: {}", shutdown_err);
+                }
+                Err(anyhow!("Request timed out after {:?}", timeout))
+            }
+        }
+    }
+
     /// Read a single line response from stdout
     async fn read_response(&self) -> Result<String> {
         let mut stdout_lock = self.stdout_reader.lock().await;
@@ -1,5 +1,6 @@
-use crate::summary::templates;
+use crate::summary::templates::{self, Template, TemplateSource, TemplateVersion};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use tauri::Runtime;
 use tracing::{info, warn};
 
@@ -14,6 +15,12 @@ pub struct TemplateInfo {
 
     /// Brief description of the template's purpose
     pub description: String,
+
+    /// Where the template comes from (custom templates override shipped ones)
+    pub source: Option<TemplateSource>,
+
+    /// Whether a bundled or built-in template has the same id
+    pub has_default: bool,
 }
 
 /// Detailed template structure for preview/debugging
@@ -32,6 +39,33 @@ pub struct TemplateDetails {
     pub sections: Vec<String>,
 }
 
+/// Full template for the template editor
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateDocument {
+    /// Template identifier
+    pub id: String,
+
+    /// Where the template comes from
+    pub source: Option<TemplateSource>,
+
+    /// Whether a bundled or built-in template has the same id
+    pub has_default: bool,
+
+    /// Template content, including its version
+    pub template: Template,
+}
+
+impl TemplateDocument {
+    fn new(id: String, template: Template) -> Self {
+        Self {
+            source: templates::template_source(&id),
+            has_default: templates::is_shipped_template(&id),
+            id,
+            template,
+        }
+    }
+}
+
 /// Lists all available templates
 ///
 /// Returns templates from both built-in (embedded) and custom (user data directory) sources.
@@ -50,6 +84,8 @@ pub async fn api_list_templates<R: Runtime>(
     let template_infos: Vec<TemplateInfo> = templates
         .into_iter()
         .map(|(id, name, description)| TemplateInfo {
+            source: templates::template_source(&id),
+            has_default: templates::is_shipped_template(&id),
             id,
             name,
             description,
@@ -123,6 +159,149 @@ pub async fn api_validate_template<R: Runtime>(
     }
 }
 
+/// Gets a full template for editing
+///
+/// # Arguments
+/// * `template_id` - Template identifier
+///
+/// # Returns
+/// The effective template (custom, bundled or built-in) with its source
+#[tauri::command]
+pub async fn api_get_template<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    template_id: String,
+) -> Result<TemplateDocument, String> {
+    info!("api_get_template called for template_id: {}", template_id);
+
+    let template = templates::get_template(&template_id)?;
+    Ok(TemplateDocument::new(template_id, template))
+}
+
+/// Creates or updates a custom template
+///
+/// The template is validated before it is written to the custom templates
+/// directory. Saving a template with the id of a shipped one overrides it.
+///
+/// # Arguments
+/// * `template_id` - Template identifier (lowercase letters, digits, `_` and `-`)
+/// * `template` - Template content; its version is assigned on save
+///
+/// # Returns
+/// The saved template with its new version
+#[tauri::command]
+pub async fn api_save_template<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    template_id: String,
+    template: Template,
+) -> Result<TemplateDocument, String> {
+    info!("api_save_template called for template_id: {}", template_id);
+
+    let template = templates::save_custom_template(&template_id, template).map_err(|e| {
+        warn!("Failed to save template '{}': {}", template_id, e);
+        e
+    })?;
+    Ok(TemplateDocument::new(template_id, template))
+}
+
+/// Deletes a custom template
+///
+/// Shipped templates cannot be deleted; deleting a custom override restores
+/// the shipped template with the same id.
+#[tauri::command]
+pub async fn api_delete_template<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    template_id: String,
+) -> Result<(), String> {
+    info!("api_delete_template called for template_id: {}", template_id);
+
+    templates::delete_custom_template(&template_id)
+}
+
+/// Copies a template (e.g. a built-in one) to a new custom template
+///
+/// # Arguments
+/// * `source_id` - Template to copy
+/// * `new_id` - Identifier of the copy
+/// * `name` - Display name of the copy (defaults to "<name> (copy)")
+#[tauri::command]
+pub async fn api_duplicate_template<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    source_id: String,
+    new_id: String,
+    name: Option<String>,
+) -> Result<TemplateDocument, String> {
+    info!("api_duplicate_template called: {} -> {}", source_id, new_id);
+
+    let template = templates::duplicate_template(&source_id, &new_id, name)?;
+    Ok(TemplateDocument::new(new_id, template))
+}
+
+/// Lists the archived versions of a custom template, newest first
+#[tauri::command]
+pub async fn api_list_template_versions<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    template_id: String,
+) -> Result<Vec<TemplateVersion>, String> {
+    info!("api_list_template_versions called for template_id: {}", template_id);
+
+    templates::list_template_versions(&template_id)
+}
+
+/// Restores an archived version of a custom template
+///
+/// The restored content is saved as a new version, so the restore itself can
+/// be undone.
+#[tauri::command]
+pub async fn api_restore_template_version<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    template_id: String,
+    version: u32,
+) -> Result<TemplateDocument, String> {
+    info!(
+        "api_restore_template_version called for template_id: {}, version: {}",
+        template_id, version
+    );
+
+    let template = templates::restore_template_version(&template_id, version)?;
+    Ok(TemplateDocument::new(template_id, template))
+}
+
+/// Imports a template JSON file as a custom template
+///
+/// # Arguments
+/// * `path` - Template JSON file
+/// * `template_id` - Identifier to import as (defaults to the file name)
+/// * `overwrite` - Replace an existing template with the same id
+#[tauri::command]
+pub async fn api_import_template<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    path: String,
+    template_id: Option<String>,
+    overwrite: Option<bool>,
+) -> Result<TemplateDocument, String> {
+    info!("api_import_template called for path: {}", path);
+
+    let (id, template) =
+        templates::import_template(&PathBuf::from(&path), template_id, overwrite.unwrap_or(false))
+            .map_err(|e| {
+                warn!("Failed to import template from {}: {}", path, e);
+                e
+            })?;
+    Ok(TemplateDocument::new(id, template))
+}
+
+/// Exports a template to a JSON file that can be shared and imported
+#[tauri::command]
+pub async fn api_export_template<R: Runtime>(
+    _app: tauri::AppHandle<R>,
+    template_id: String,
+    path: String,
+) -> Result<(), String> {
+    info!("api_export_template called for template_id: {}", template_id);
+
+    templates::export_template(&template_id, &PathBuf::from(path))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,226 @@
+//! Automatic template selection from a meeting note's frontmatter
+//!
+//! When a summary is generated automatically, the template is picked from the
+//! meeting note, in order:
+//! 1. an explicit `summary_template:` field
+//! 2. the first rule whose keyword matches the note's `type:`
+//! 3. the first rule whose keyword matches one of its `tags`
+//!
+//! Otherwise the template the caller asked for is used. Rules are stored with
+//! the app settings and editable in the UI; templates that don't exist are
+//! skipped.
+
+use crate::obsidian::MeetingFrontmatter;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+use tracing::warn;
+
+const TEMPLATE_RULES_STORE: &str = "summary_templates.json";
+const TEMPLATE_RULES_KEY: &str = "template_rules";
+
+/// Maps a meeting type or tag to a summary template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateRule {
+    /// Meeting type or tag, matched case-insensitively against whole words
+    /// (`standup` matches `daily-standup` and `#meeting/standup`)
+    pub keyword: String,
+    /// Template used for matching meetings
+    pub template_id: String,
+}
+
+impl TemplateRule {
+    fn new(keyword: &str, template_id: &str) -> Self {
+        Self {
+            keyword: keyword.to_string(),
+            template_id: template_id.to_string(),
+        }
+    }
+}
+
+/// Rules used until the user edits them
+pub fn default_template_rules() -> Vec<TemplateRule> {
+    vec![
+        TemplateRule::new("standup", "daily_standup"),
+        TemplateRule::new("retro", "retrospective"),
+        TemplateRule::new("retrospective", "retrospective"),
+        TemplateRule::new("client", "sales_marketing_client_call"),
+        TemplateRule::new("sales", "sales_marketing_client_call"),
+        TemplateRule::new("project", "project_sync"),
+    ]
+}
+
+/// Template picked for a meeting note and why
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TemplateSelection {
+    pub template_id: String,
+    /// `summary_template`, `type` or `tags`
+    pub matched_field: &'static str,
+    /// Rule keyword that matched (None for `summary_template`)
+    pub keyword: Option<String>,
+}
+
+/// Lowercase words of a type or tag: `#Meeting/Daily-Standup` -> meeting, daily, standup
+fn words(value: &str) -> Vec<String> {
+    value
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Whether `keyword` matches `value` as a whole or as one of its words
+fn keyword_matches(keyword: &str, value: &str) -> bool {
+    let keyword = keyword.trim().trim_start_matches('#').to_lowercase();
+    if keyword.is_empty() {
+        return false;
+    }
+    let value = value.trim().trim_start_matches('#').to_lowercase();
+    value == keyword || words(&value).contains(&keyword)
+}
+
+/// Pick a template for a note, see the module docs for the order
+///
+/// # Arguments
+/// * `frontmatter` - The meeting note's frontmatter
+/// * `rules` - Keyword rules, in priority order
+/// * `is_available` - Whether a template id exists
+pub fn select_template(
+    frontmatter: &MeetingFrontmatter,
+    rules: &[TemplateRule],
+    is_available: impl Fn(&str) -> bool,
+) -> Option<TemplateSelection> {
+    if let Some(id) = frontmatter.summary_template.as_deref().map(str::trim).filter(|id| !id.is_empty()) {
+        if is_available(id) {
+            return Some(TemplateSelection {
+                template_id: id.to_string(),
+                matched_field: "summary_template",
+                keyword: None,
+            });
+        }
+        warn!("Note asks for unknown summary template '{}', ignoring it", id);
+    }
+
+    let tags = frontmatter.tags.as_ref().map(|tags| tags.as_vec()).unwrap_or_default();
+    let candidates = frontmatter
+        .meeting_type
+        .iter()
+        .map(|meeting_type| ("type", meeting_type))
+        .chain(tags.iter().map(|tag| ("tags", tag)));
+
+    for (field, value) in candidates {
+        let rule = rules
+            .iter()
+            .find(|rule| keyword_matches(&rule.keyword, value) && is_available(rule.template_id.trim()));
+        if let Some(rule) = rule {
+            return Some(TemplateSelection {
+                template_id: rule.template_id.trim().to_string(),
+                matched_field: field,
+                keyword: Some(rule.keyword.clone()),
+            });
+        }
+    }
+    None
+}
+
+/// Configured rules, or the defaults if none were saved
+pub fn load_template_rules<R: Runtime>(app: &AppHandle<R>) -> Vec<TemplateRule> {
+    app.store(TEMPLATE_RULES_STORE)
+        .ok()
+        .and_then(|store| store.get(TEMPLATE_RULES_KEY))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_else(default_template_rules)
+}
+
+/// Tauri command to get the template selection rules
+#[tauri::command]
+pub async fn api_get_template_rules<R: Runtime>(app: AppHandle<R>) -> Result<Vec<TemplateRule>, String> {
+    Ok(load_template_rules(&app))
+}
+
+/// Tauri command to save the template selection rules
+///
+/// Rules with an empty keyword or template are dropped.
+#[tauri::command]
+pub async fn api_save_template_rules<R: Runtime>(
+    app: AppHandle<R>,
+    rules: Vec<TemplateRule>,
+) -> Result<(), String> {
+    let rules: Vec<TemplateRule> = rules
+        .into_iter()
+        .map(|rule| TemplateRule::new(rule.keyword.trim(), rule.template_id.trim()))
+        .filter(|rule| !rule.keyword.is_empty() && !rule.template_id.is_empty())
+        .collect();
+    let store = app
+        .store(TEMPLATE_RULES_STORE)
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+    store.set(
+        TEMPLATE_RULES_KEY,
+        serde_json::to_value(&rules).map_err(|e| format!("Failed to serialize rules: {}", e))?,
+    );
+    store
+        .save()
+        .map_err(|e| format!("Failed to save store to disk: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::obsidian::types::StringOrVec;
+
+    fn frontmatter(meeting_type: Option<&str>, tags: &[&str], summary_template: Option<&str>) -> MeetingFrontmatter {
+        MeetingFrontmatter {
+            meeting_type: meeting_type.map(str::to_string),
+            tags: Some(StringOrVec::Multiple(tags.iter().map(|t| t.to_string()).collect())),
+            summary_template: summary_template.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn available(id: &str) -> bool {
+        ["daily_standup", "retrospective", "sales_marketing_client_call", "standard_meeting"].contains(&id)
+    }
+
+    #[test]
+    fn test_keyword_matches_words() {
+        assert!(keyword_matches("standup", "Daily-Standup"));
+        assert!(keyword_matches("standup", "#meeting/standup"));
+        assert!(keyword_matches("client call", "Client Call"));
+        assert!(!keyword_matches("retro", "retrospective"));
+        assert!(!keyword_matches("", "meeting"));
+    }
+
+    #[test]
+    fn test_frontmatter_override_wins() {
+        let rules = default_template_rules();
+        let fm = frontmatter(Some("standup"), &["client"], Some("standard_meeting"));
+        let selection = select_template(&fm, &rules, available).unwrap();
+        assert_eq!(selection.template_id, "standard_meeting");
+        assert_eq!(selection.matched_field, "summary_template");
+
+        // Unknown overrides fall through to the rules
+        let fm = frontmatter(Some("standup"), &[], Some("deleted_template"));
+        assert_eq!(select_template(&fm, &rules, available).unwrap().template_id, "daily_standup");
+    }
+
+    #[test]
+    fn test_type_before_tags() {
+        let rules = default_template_rules();
+        let fm = frontmatter(Some("retro"), &["meeting", "client"], None);
+        let selection = select_template(&fm, &rules, available).unwrap();
+        assert_eq!((selection.template_id.as_str(), selection.matched_field), ("retrospective", "type"));
+
+        let fm = frontmatter(Some("meeting"), &["meeting", "acme-client"], None);
+        let selection = select_template(&fm, &rules, available).unwrap();
+        assert_eq!(selection.template_id, "sales_marketing_client_call");
+        assert_eq!(selection.keyword.as_deref(), Some("client"));
+    }
+
+    #[test]
+    fn test_no_match_or_missing_template() {
+        let rules = default_template_rules();
+        assert!(select_template(&frontmatter(Some("meeting"), &["meeting"], None), &rules, available).is_none());
+        // project_sync is not available here
+        assert!(select_template(&frontmatter(Some("project"), &[], None), &rules, available).is_none());
+    }
+}
@@ -26,6 +26,16 @@
 //!
 //! // List available templates
 //! let available = templates::list_templates();
+//!
+//! // Compile to a JSON schema and validate a model's JSON answer against it
+//! let schema = template.to_json_schema();
+//! let summary = templates::parse_structured_summary("daily_standup", &template, &answer)?;
+//! let markdown = summary.to_markdown();
+//!
+//! // Copy a built-in template, then save an edited version
+//! let mut custom = templates::duplicate_template("daily_standup", "team_standup", None)?;
+//! custom.sections.remove(0);
+//! let custom = templates::save_custom_template("team_standup", custom)?;
 //! ```
 //!
 //! # Custom Templates
@@ -36,9 +46,13 @@
 //! - Linux: `~/.config/Meetily/templates/`
 //!
 //! Custom templates must follow the JSON schema defined in `types::Template`.
+//! They can also be created, duplicated from a shipped template, edited with
+//! version history, imported and exported from the app (see `store`).
 
 mod defaults;
 mod loader;
+mod schema;
+mod store;
 mod types;
 
 // Re-export public API
@@ -46,7 +60,16 @@ pub use loader::{
     get_template, list_template_ids, list_templates, set_bundled_templates_dir,
     validate_and_parse_template,
 };
-pub use types::{Template, TemplateSection};
+pub use schema::{
+    json_schema_to_gbnf, parse_structured_summary, section_key, ChecklistItem, KeyValue,
+    SectionContent, StructuredSection, StructuredSummary,
+};
+pub use store::{
+    delete_custom_template, duplicate_template, export_template, import_template,
+    is_shipped_template, list_template_versions, restore_template_version, save_custom_template,
+    template_source, validate_template_id, TemplateSource, TemplateVersion,
+};
+pub use types::{Template, TemplateSection, SECTION_FORMATS};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,931 @@
+//! Structured summary output
+//!
+//! A template compiles into a JSON schema (`{"title": ..., "sections": {...}}`
+//! with one property per section) that providers are asked to follow, and into
+//! an equivalent GBNF grammar for constrained decoding with the built-in model.
+//! The model's answer is validated and repaired against the template, stored as
+//! a `StructuredSummary`, and rendered to markdown deterministically.
+
+use super::types::{Template, TemplateSection};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// Written for sections the model left empty
+pub const EMPTY_SECTION_TEXT: &str = "None noted in this section.";
+
+/// JSON key of a section: its title in snake_case (`Action Items` -> `action_items`)
+pub fn section_key(title: &str) -> String {
+    let mut key = String::new();
+    for c in title.chars() {
+        if c.is_alphanumeric() {
+            key.extend(c.to_lowercase());
+        } else if !key.is_empty() && !key.ends_with('_') {
+            key.push('_');
+        }
+    }
+    key.trim_end_matches('_').to_string()
+}
+
+/// Whether a section's content is a list of items rather than text
+fn is_list_format(format: &str) -> bool {
+    format == "list"
+}
+
+/// Table header of a table-formatted list section
+fn table_header(section: &TemplateSection) -> Option<&str> {
+    section
+        .item_format
+        .as_deref()
+        .or(section.example_item_format.as_deref())
+        .filter(|format| format.trim_start().starts_with('|'))
+}
+
+/// Column names of a table section
+fn table_columns(section: &TemplateSection) -> &[String] {
+    section.columns.as_deref().unwrap_or_default()
+}
+
+/// Schema of an array of objects with the given string/boolean fields, all required
+fn object_array_schema(fields: &[(String, &str)], description: &str) -> Value {
+    let properties: Map<String, Value> = fields
+        .iter()
+        .map(|(name, kind)| (name.clone(), json!({ "type": kind })))
+        .collect();
+    let required: Vec<&String> = fields.iter().map(|(name, _)| name).collect();
+    json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false
+        },
+        "description": description
+    })
+}
+
+impl Template {
+    /// JSON schema of a summary following this template
+    pub fn to_json_schema(&self) -> Value {
+        let mut properties = Map::new();
+        for section in &self.sections {
+            let schema = match section.format.as_str() {
+                "list" => json!({ "type": "array", "items": { "type": "string" }, "description": section.instruction }),
+                "table" => {
+                    let fields: Vec<(String, &str)> =
+                        table_columns(section).iter().map(|column| (section_key(column), "string")).collect();
+                    object_array_schema(&fields, &section.instruction)
+                }
+                "checklist" => object_array_schema(
+                    &[("text".to_string(), "string"), ("done".to_string(), "boolean")],
+                    &section.instruction,
+                ),
+                "key_value" => object_array_schema(
+                    &[("key".to_string(), "string"), ("value".to_string(), "string")],
+                    &section.instruction,
+                ),
+                _ => json!({ "type": "string", "description": section.instruction }),
+            };
+            properties.insert(section_key(&section.title), schema);
+        }
+        let required: Vec<String> = self.sections.iter().map(|s| section_key(&s.title)).collect();
+
+        json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": "string", "description": "Concise, descriptive meeting title" },
+                "sections": {
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                    "additionalProperties": false
+                }
+            },
+            "required": ["title", "sections"],
+            "additionalProperties": false
+        })
+    }
+
+    /// Prompt instructions describing the JSON answer
+    pub fn to_json_instructions(&self) -> String {
+        let mut instructions = String::from(
+            "Answer with a single JSON object with a `title` string and a `sections` object with these keys:\n",
+        );
+        for section in &self.sections {
+            let kind = match section.format.as_str() {
+                "list" if table_header(section).is_some() => {
+                    "array of strings, one markdown table row (`| ... |`) per item, no header row".to_string()
+                }
+                "list" => "array of strings, one item each, without bullet markers".to_string(),
+                "table" => format!(
+                    "array of row objects with the keys {}",
+                    table_columns(section)
+                        .iter()
+                        .map(|column| format!("`{}`", section_key(column)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                "checklist" => "array of `{\"text\": string, \"done\": boolean}` items".to_string(),
+                "key_value" => "array of `{\"key\": string, \"value\": string}` items".to_string(),
+                _ => "string".to_string(),
+            };
+            instructions.push_str(&format!("- `{}` ({}): '{}' section\n", section_key(&section.title), kind, section.title));
+        }
+        instructions.push_str("Use \"\" or [] for sections without relevant information.\n");
+        instructions
+    }
+}
+
+/// Item of a checklist section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+}
+
+/// Entry of a key-value section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyValue {
+    pub key: String,
+    pub value: String,
+}
+
+/// Content of one summary section
+///
+/// Stored untagged; which variant a stored value is depends on the section's
+/// format (an empty `[]` fits every list-like variant), so it is only
+/// deserialized as part of a `StructuredSection`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum SectionContent {
+    Text(String),
+    Items(Vec<String>),
+    /// Table rows, one cell per column
+    Rows(Vec<Vec<String>>),
+    Checklist(Vec<ChecklistItem>),
+    Pairs(Vec<KeyValue>),
+}
+
+impl SectionContent {
+    /// Stored content of a section with the given format
+    fn from_stored(format: &str, value: Value) -> Result<Self, serde_json::Error> {
+        Ok(match format {
+            "list" => SectionContent::Items(serde_json::from_value(value)?),
+            "table" => SectionContent::Rows(serde_json::from_value(value)?),
+            "checklist" => SectionContent::Checklist(serde_json::from_value(value)?),
+            "key_value" => SectionContent::Pairs(serde_json::from_value(value)?),
+            _ => SectionContent::Text(serde_json::from_value(value)?),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            SectionContent::Text(text) => text.trim().is_empty(),
+            SectionContent::Items(items) => items.is_empty(),
+            SectionContent::Rows(rows) => rows.is_empty(),
+            SectionContent::Checklist(items) => items.is_empty(),
+            SectionContent::Pairs(pairs) => pairs.is_empty(),
+        }
+    }
+
+    /// Apply `f` to every piece of text (paragraph, item, cell, value)
+    pub fn map_text(&mut self, mut f: impl FnMut(&str) -> String) {
+        match self {
+            SectionContent::Text(text) => *text = f(text),
+            SectionContent::Items(items) => items.iter_mut().for_each(|item| *item = f(item)),
+            SectionContent::Rows(rows) => rows.iter_mut().flatten().for_each(|cell| *cell = f(cell)),
+            SectionContent::Checklist(items) => items.iter_mut().for_each(|item| item.text = f(&item.text)),
+            SectionContent::Pairs(pairs) => pairs.iter_mut().for_each(|pair| pair.value = f(&pair.value)),
+        }
+    }
+}
+
+/// One section of a structured summary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "StoredSection")]
+pub struct StructuredSection {
+    /// JSON key (see `section_key`)
+    pub key: String,
+    pub title: String,
+    pub format: String,
+    /// Table header for table-formatted list sections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_format: Option<String>,
+    /// Column names of table sections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Vec<String>>,
+    pub content: SectionContent,
+}
+
+/// `StructuredSection` as stored, before its content is read according to its format
+#[derive(Deserialize)]
+struct StoredSection {
+    key: String,
+    title: String,
+    format: String,
+    #[serde(default)]
+    item_format: Option<String>,
+    #[serde(default)]
+    columns: Option<Vec<String>>,
+    content: Value,
+}
+
+impl TryFrom<StoredSection> for StructuredSection {
+    type Error = String;
+
+    fn try_from(stored: StoredSection) -> Result<Self, Self::Error> {
+        let content = SectionContent::from_stored(&stored.format, stored.content)
+            .map_err(|e| format!("Invalid content of section '{}' ({}): {}", stored.key, stored.format, e))?;
+        Ok(StructuredSection {
+            key: stored.key,
+            title: stored.title,
+            format: stored.format,
+            item_format: stored.item_format,
+            columns: stored.columns,
+            content,
+        })
+    }
+}
+
+/// Summary following a template, as stored in `summary_processes.result.structured`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredSummary {
+    /// Template the summary was generated with
+    pub template: String,
+    pub title: String,
+    pub sections: Vec<StructuredSection>,
+}
+
+impl StructuredSummary {
+    /// Section by key or title
+    pub fn section(&self, key_or_title: &str) -> Option<&StructuredSection> {
+        let key = section_key(key_or_title);
+        self.sections.iter().find(|section| section.key == key)
+    }
+
+    /// Render as markdown: `# title`, then `**Section**` headings in template order
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        if !self.title.trim().is_empty() {
+            markdown.push_str(&format!("# {}\n\n", self.title.trim()));
+        }
+        for section in &self.sections {
+            markdown.push_str(&format!("**{}**\n\n", section.title));
+            match &section.content {
+                content if content.is_empty() => markdown.push_str(EMPTY_SECTION_TEXT),
+                SectionContent::Text(text) => markdown.push_str(text.trim()),
+                SectionContent::Items(items) => match &section.item_format {
+                    Some(header) => {
+                        markdown.push_str(header.trim());
+                        for item in items {
+                            markdown.push('\n');
+                            markdown.push_str(&table_row(item));
+                        }
+                    }
+                    None => {
+                        let lines: Vec<String> = items.iter().map(|item| format!("- {}", item)).collect();
+                        markdown.push_str(&lines.join("\n"));
+                    }
+                },
+                SectionContent::Rows(rows) => {
+                    let columns = section.columns.clone().unwrap_or_default();
+                    let mut lines = vec![
+                        table_line(&columns),
+                        table_line(&vec!["---".to_string(); columns.len()]),
+                    ];
+                    lines.extend(rows.iter().map(|row| table_line(row)));
+                    markdown.push_str(&lines.join("\n"));
+                }
+                SectionContent::Checklist(items) => {
+                    let lines: Vec<String> = items
+                        .iter()
+                        .map(|item| format!("- [{}] {}", if item.done { "x" } else { " " }, item.text))
+                        .collect();
+                    markdown.push_str(&lines.join("\n"));
+                }
+                SectionContent::Pairs(pairs) => {
+                    let lines: Vec<String> = pairs
+                        .iter()
+                        .map(|pair| format!("- **{}**: {}", pair.key, pair.value))
+                        .collect();
+                    markdown.push_str(&lines.join("\n"));
+                }
+            }
+            markdown.push_str("\n\n");
+        }
+        markdown.trim_end().to_string()
+    }
+}
+
+/// Markdown table line; pipes and line breaks inside cells are escaped
+fn table_line(cells: &[String]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .map(|cell| cell.trim().replace('|', "\\|").replace('\n', " "))
+        .collect();
+    format!("| {} |", cells.join(" | "))
+}
+
+fn table_row(item: &str) -> String {
+    let item = item.trim();
+    match (item.starts_with('|'), item.ends_with('|')) {
+        (true, true) => item.to_string(),
+        (true, false) => format!("{} |", item),
+        (false, true) => format!("| {}", item),
+        (false, false) => format!("| {} |", item),
+    }
+}
+
+/// Validate a model answer against `template`, repairing what can be repaired
+///
+/// Accepts JSON wrapped in prose or code fences, truncated output, trailing
+/// commas, sections at the top level instead of under `sections`, keys that
+/// match a section title instead of its key, and lists given as text (or text
+/// given as lists). Missing sections are left empty; unknown keys are dropped.
+pub fn parse_structured_summary(
+    template_id: &str,
+    template: &Template,
+    raw: &str,
+) -> Result<StructuredSummary, String> {
+    let json = extract_json_object(raw).ok_or_else(|| "Response contains no JSON object".to_string())?;
+    let value: Value = serde_json::from_str(json)
+        .or_else(|_| serde_json::from_str(&repair_json(json)))
+        .map_err(|e| format!("Response is not valid JSON: {}", e))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| "Response is not a JSON object".to_string())?;
+
+    let title = object.get("title").map(value_to_text).unwrap_or_default();
+    let fields = object.get("sections").and_then(Value::as_object).unwrap_or(object);
+
+    let mut found = 0;
+    let sections: Vec<StructuredSection> = template
+        .sections
+        .iter()
+        .map(|section| {
+            let key = section_key(&section.title);
+            let value = fields
+                .get(&key)
+                .or_else(|| fields.iter().find(|(k, _)| section_key(k) == key).map(|(_, v)| v));
+            if value.is_some() {
+                found += 1;
+            }
+            let header = table_header(section).filter(|_| is_list_format(&section.format));
+            let value = value.unwrap_or(&Value::Null);
+            let content = match section.format.as_str() {
+                "list" => SectionContent::Items(value_to_items(value, header)),
+                "table" => SectionContent::Rows(value_to_rows(value, table_columns(section))),
+                "checklist" => SectionContent::Checklist(value_to_checklist(value)),
+                "key_value" => SectionContent::Pairs(value_to_pairs(value)),
+                _ => SectionContent::Text(value_to_text(value)),
+            };
+            StructuredSection {
+                key,
+                title: section.title.clone(),
+                format: section.format.clone(),
+                item_format: header.map(str::to_string),
+                columns: section.columns.clone().filter(|_| section.format == "table"),
+                content,
+            }
+        })
+        .collect();
+
+    if found == 0 {
+        return Err(format!(
+            "Response has none of the template sections ({})",
+            template.sections.iter().map(|s| section_key(&s.title)).collect::<Vec<_>>().join(", ")
+        ));
+    }
+
+    Ok(StructuredSummary {
+        template: template_id.to_string(),
+        title,
+        sections,
+    })
+}
+
+/// The outermost `{...}` of a response (to the end when it was cut off)
+fn extract_json_object(raw: &str) -> Option<&str> {
+    // Reasoning models may think aloud before answering
+    let raw = raw.rsplit("</think>").next().unwrap_or(raw);
+    let start = raw.find('{')?;
+    let end = raw.rfind('}').filter(|end| *end > start).map_or(raw.len(), |end| end + 1);
+    Some(&raw[start..end])
+}
+
+/// Best-effort fix of common JSON mistakes in model output: raw newlines in
+/// strings, trailing commas, and unterminated strings/objects/arrays
+fn repair_json(json: &str) -> String {
+    let mut repaired = String::with_capacity(json.len() + 8);
+    let mut closers = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in json.chars() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                '\n' => {
+                    repaired.push_str("\\n");
+                    continue;
+                }
+                '\r' | '\t' => {
+                    repaired.push(' ');
+                    continue;
+                }
+                _ => {}
+            }
+            repaired.push(c);
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' => closers.push('}'),
+            '[' => closers.push(']'),
+            '}' | ']' => {
+                trim_trailing_comma(&mut repaired);
+                closers.pop();
+            }
+            _ => {}
+        }
+        repaired.push(c);
+    }
+
+    if escaped {
+        repaired.pop();
+    }
+    if in_string {
+        repaired.push('"');
+    }
+    // A cut-off `"key":` or `"key"` cannot be completed; drop the dangling pair
+    if let Some(before_colon) = repaired.trim_end().strip_suffix(':') {
+        if let Some(key_start) = before_colon.trim_end().strip_suffix('"').and_then(|s| s.rfind('"')) {
+            repaired.truncate(key_start);
+        }
+    }
+    while let Some(closer) = closers.pop() {
+        trim_trailing_comma(&mut repaired);
+        repaired.push(closer);
+    }
+    repaired
+}
+
+fn trim_trailing_comma(json: &mut String) {
+    let end = json.trim_end().len();
+    if json[..end].ends_with(',') {
+        json.truncate(end - 1);
+    }
+}
+
+/// Text of a JSON value; arrays become one line per element
+fn value_to_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.trim().to_string(),
+        Value::Array(items) => items
+            .iter()
+            .map(value_to_text)
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, value)| format!("{}: {}", key, value_to_text(value)))
+            .collect::<Vec<_>>()
+            .join("; "),
+        other => other.to_string(),
+    }
+}
+
+/// Items of a list section; text is split into lines and bullet markers removed
+fn value_to_items(value: &Value, table_header: Option<&str>) -> Vec<String> {
+    let table = table_header.is_some();
+    let header_rows: Vec<String> = table_header
+        .map(|header| header.lines().map(normalize_row).collect())
+        .unwrap_or_default();
+    let items: Vec<String> = match value {
+        Value::Array(items) => items
+            .iter()
+            .map(|item| match item {
+                Value::Object(fields) if table => {
+                    let cells: Vec<String> = fields.values().map(value_to_text).collect();
+                    format!("| {} |", cells.join(" | "))
+                }
+                Value::Array(cells) if table => {
+                    let cells: Vec<String> = cells.iter().map(value_to_text).collect();
+                    format!("| {} |", cells.join(" | "))
+                }
+                other => value_to_text(other),
+            })
+            .collect(),
+        Value::Null => Vec::new(),
+        other => value_to_text(other).lines().map(str::to_string).collect(),
+    };
+
+    items
+        .iter()
+        .map(|item| strip_bullet(item.trim()))
+        // Models sometimes repeat the table header rows
+        .filter(|item| !item.is_empty() && !header_rows.contains(&normalize_row(item)))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Elements of an array, or the lines of text given instead of one
+fn value_elements(value: &Value) -> Vec<Value> {
+    match value {
+        Value::Array(items) => items.clone(),
+        Value::Null => Vec::new(),
+        other => value_to_text(other)
+            .lines()
+            .map(|line| Value::String(line.to_string()))
+            .collect(),
+    }
+}
+
+/// Rows of a table section with one cell per column
+///
+/// Rows may be objects keyed by column (key or title), cell arrays, or
+/// markdown table rows; repeated header and separator rows are dropped.
+fn value_to_rows(value: &Value, columns: &[String]) -> Vec<Vec<String>> {
+    let keys: Vec<String> = columns.iter().map(|column| section_key(column)).collect();
+    value_elements(value)
+        .iter()
+        .filter_map(|row| {
+            let mut cells: Vec<String> = match row {
+                Value::Object(fields) => keys
+                    .iter()
+                    .map(|key| {
+                        fields
+                            .iter()
+                            .find(|(k, _)| section_key(k) == *key)
+                            .map(|(_, v)| value_to_text(v))
+                            .unwrap_or_default()
+                    })
+                    .collect(),
+                Value::Array(cells) => cells.iter().map(value_to_text).collect(),
+                other => {
+                    let text = value_to_text(other);
+                    let text = strip_bullet(text.trim());
+                    if text.starts_with('|') {
+                        text.trim_matches('|').split('|').map(|cell| cell.trim().to_string()).collect()
+                    } else {
+                        vec![text.to_string()]
+                    }
+                }
+            };
+            let is_separator = cells.iter().all(|cell| !cell.is_empty() && cell.chars().all(|c| matches!(c, '-' | ':' | ' ')));
+            let is_header = cells.iter().map(|cell| section_key(cell)).eq(keys.iter().cloned());
+            if cells.iter().all(|cell| cell.is_empty()) || is_separator || is_header {
+                return None;
+            }
+            cells.resize(columns.len(), String::new());
+            Some(cells)
+        })
+        .collect()
+}
+
+/// Items of a checklist section; accepts `{text, done}` objects and `- [x] text` lines
+fn value_to_checklist(value: &Value) -> Vec<ChecklistItem> {
+    value_elements(value)
+        .iter()
+        .filter_map(|item| {
+            let (text, done) = match item {
+                Value::Object(fields) => {
+                    let text = ["text", "item", "task"]
+                        .iter()
+                        .find_map(|key| fields.get(*key))
+                        .map(value_to_text)
+                        .unwrap_or_else(|| value_to_text(item));
+                    let done = ["done", "checked", "completed"]
+                        .iter()
+                        .find_map(|key| fields.get(*key))
+                        .is_some_and(|done| done.as_bool().unwrap_or_else(|| value_to_text(done) == "true"));
+                    (text, done)
+                }
+                other => {
+                    let text = value_to_text(other);
+                    let text = strip_bullet(text.trim());
+                    if let Some(rest) = text.strip_prefix("[x]").or_else(|| text.strip_prefix("[X]")) {
+                        (rest.trim().to_string(), true)
+                    } else {
+                        (text.strip_prefix("[ ]").unwrap_or(text).trim().to_string(), false)
+                    }
+                }
+            };
+            (!text.is_empty()).then_some(ChecklistItem { text, done })
+        })
+        .collect()
+}
+
+/// Entries of a key-value section; accepts `{key, value}` objects, a plain
+/// object of entries and `Key: value` lines
+fn value_to_pairs(value: &Value) -> Vec<KeyValue> {
+    if let Value::Object(fields) = value {
+        return fields
+            .iter()
+            .map(|(key, value)| KeyValue {
+                key: key.clone(),
+                value: value_to_text(value),
+            })
+            .collect();
+    }
+    value_elements(value)
+        .iter()
+        .filter_map(|entry| {
+            let (key, value) = match entry {
+                Value::Object(fields) if fields.contains_key("key") => (
+                    fields.get("key").map(value_to_text).unwrap_or_default(),
+                    fields.get("value").map(value_to_text).unwrap_or_default(),
+                ),
+                Value::Object(fields) => {
+                    let (key, value) = fields.iter().next()?;
+                    (key.clone(), value_to_text(value))
+                }
+                other => {
+                    let text = value_to_text(other);
+                    let text = strip_bullet(text.trim());
+                    match text.split_once(':') {
+                        Some((key, value)) => (key.replace("**", "").trim().to_string(), value.replace("**", "").trim().to_string()),
+                        None => (text.to_string(), String::new()),
+                    }
+                }
+            };
+            (!key.is_empty()).then_some(KeyValue { key, value })
+        })
+        .collect()
+}
+
+fn strip_bullet(item: &str) -> &str {
+    let stripped = item
+        .strip_prefix("- ")
+        .or_else(|| item.strip_prefix("* "))
+        .or_else(|| item.strip_prefix("• "));
+    if let Some(stripped) = stripped {
+        return stripped.trim_start();
+    }
+    // Numbered items: "1. text" / "1) text"
+    let digits = item.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        if let Some(rest) = item[digits..].strip_prefix(". ").or_else(|| item[digits..].strip_prefix(") ")) {
+            return rest.trim_start();
+        }
+    }
+    item
+}
+
+/// Table row without spacing or emphasis, for comparing rows
+fn normalize_row(row: &str) -> String {
+    row.chars().filter(|c| !c.is_whitespace() && *c != '*').collect()
+}
+
+/// GBNF grammar accepting exactly the JSON described by `schema`
+///
+/// Supports the subset `Template::to_json_schema` produces: objects with fixed
+/// properties (all emitted, in schema order), arrays, strings and booleans.
+pub fn json_schema_to_gbnf(schema: &Value) -> String {
+    let mut rules = Vec::new();
+    let root = gbnf_rule(schema, "root", &mut rules);
+    if root != "root" {
+        rules.insert(0, format!("root ::= {}", root));
+    }
+    rules.push(r#"string ::= "\"" ( [^"\\\x7F\x00-\x1F] | "\\" ( ["\\/bfnrt] | "u" [0-9a-fA-F] [0-9a-fA-F] [0-9a-fA-F] [0-9a-fA-F] ) )* "\"""#.to_string());
+    rules.push("ws ::= [ \\t\\n]*".to_string());
+    rules.join("\n")
+}
+
+/// Name (or inline expression) of the rule matching `schema`, adding rules it needs
+fn gbnf_rule(schema: &Value, name: &str, rules: &mut Vec<String>) -> String {
+    match schema.get("type").and_then(Value::as_str) {
+        Some("object") => {
+            let empty = Map::new();
+            let properties = schema.get("properties").and_then(Value::as_object).unwrap_or(&empty);
+            let mut members = Vec::new();
+            for (key, property) in properties {
+                let rule_name = format!("{}-{}", name, key.replace('_', "-"));
+                let value = gbnf_rule(property, &rule_name, rules);
+                members.push(format!(r#""\"{}\"" ws ":" ws {}"#, key, value));
+            }
+            let body = if members.is_empty() {
+                r#""{" ws "}""#.to_string()
+            } else {
+                format!(r#""{{" ws {} ws "}}""#, members.join(r#" ws "," ws "#))
+            };
+            rules.push(format!("{} ::= {}", name, body));
+            name.to_string()
+        }
+        Some("array") => {
+            let item = schema
+                .get("items")
+                .map(|items| gbnf_rule(items, &format!("{}-item", name), rules))
+                .unwrap_or_else(|| "string".to_string());
+            rules.push(format!(r#"{} ::= "[" ws ( {} ( ws "," ws {} )* )? ws "]""#, name, item, item));
+            name.to_string()
+        }
+        Some("boolean") => r#"( "true" | "false" )"#.to_string(),
+        _ => "string".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> Template {
+        let section = |title: &str, format: &str, item_format: Option<&str>| TemplateSection {
+            title: title.to_string(),
+            instruction: format!("Fill {}", title),
+            format: format.to_string(),
+            columns: None,
+            item_format: item_format.map(str::to_string),
+            example_item_format: None,
+        };
+        Template {
+            name: "Test".to_string(),
+            description: "Test template".to_string(),
+            sections: vec![
+                section("Summary", "paragraph", None),
+                section("Key Decisions", "list", None),
+                section("Action Items", "list", Some("| Owner | Task |\n| --- | --- |")),
+            ],
+            version: 0,
+        }
+    }
+
+    fn rich_template() -> Template {
+        let section = |title: &str, format: &str, columns: Option<&[&str]>| TemplateSection {
+            title: title.to_string(),
+            instruction: format!("Fill {}", title),
+            format: format.to_string(),
+            columns: columns.map(|columns| columns.iter().map(|c| c.to_string()).collect()),
+            item_format: None,
+            example_item_format: None,
+        };
+        Template {
+            name: "Rich".to_string(),
+            description: "Rich formats".to_string(),
+            sections: vec![
+                section("Risks", "table", Some(&["Risk", "Due Date"])),
+                section("Next Steps", "checklist", None),
+                section("Facts", "key_value", None),
+            ],
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn test_json_schema() {
+        assert_eq!(section_key("Action Items"), "action_items");
+        assert_eq!(section_key("Risks & Blockers!"), "risks_blockers");
+
+        let schema = template().to_json_schema();
+        let sections = &schema["properties"]["sections"];
+        assert_eq!(sections["properties"]["summary"]["type"], "string");
+        assert_eq!(sections["properties"]["key_decisions"]["type"], "array");
+        assert_eq!(sections["required"], json!(["summary", "key_decisions", "action_items"]));
+    }
+
+    #[test]
+    fn test_parse_valid_summary_and_render() {
+        let raw = r#"{"title":"Budget Review","sections":{"summary":"We reviewed the budget.","key_decisions":["Approve Q3 budget [t-0215]"],"action_items":["| Alice | Send report |"]}}"#;
+        let summary = parse_structured_summary("standard_meeting", &template(), raw).unwrap();
+
+        assert_eq!(summary.title, "Budget Review");
+        assert_eq!(
+            summary.section("Key Decisions").unwrap().content,
+            SectionContent::Items(vec!["Approve Q3 budget [t-0215]".to_string()])
+        );
+        assert_eq!(
+            summary.to_markdown(),
+            "# Budget Review\n\n**Summary**\n\nWe reviewed the budget.\n\n**Key Decisions**\n\n- Approve Q3 budget [t-0215]\n\n**Action Items**\n\n| Owner | Task |\n| --- | --- |\n| Alice | Send report |"
+        );
+    }
+
+    #[test]
+    fn test_parse_repairs_malformed_output() {
+        // Code fence, flat sections keyed by title, text list, object rows, trailing comma, truncation
+        let raw = "Here you go:\n```json\n{\"title\": \"Sync\", \"Summary\": [\"Line one\", \"Line two\"], \"Key Decisions\": \"- Ship it\\n- Hire\", \"action_items\": [{\"owner\": \"Bob\", \"task\": \"Deploy\"}, \"| Owner | Task |\",], \"notes\": \"dropped\", \"extra\": \"cut off";
+        let summary = parse_structured_summary("t", &template(), raw).unwrap();
+
+        assert_eq!(summary.section("summary").unwrap().content, SectionContent::Text("Line one\nLine two".to_string()));
+        assert_eq!(
+            summary.section("key_decisions").unwrap().content,
+            SectionContent::Items(vec!["Ship it".to_string(), "Hire".to_string()])
+        );
+        assert_eq!(
+            summary.section("action_items").unwrap().content,
+            SectionContent::Items(vec!["| Bob | Deploy |".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_parse_drops_dangling_key() {
+        let raw = r#"{"title": "Sync", "sections": {"summary": "Short.", "key_decisions":"#;
+        let summary = parse_structured_summary("t", &template(), raw).unwrap();
+        assert_eq!(summary.section("summary").unwrap().content, SectionContent::Text("Short.".to_string()));
+        assert!(summary.section("key_decisions").unwrap().content.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_unrelated_output() {
+        assert!(parse_structured_summary("t", &template(), "No JSON here").is_err());
+        assert!(parse_structured_summary("t", &template(), r#"{"answer": 42}"#).is_err());
+    }
+
+    #[test]
+    fn test_empty_sections_render_placeholder() {
+        let summary = parse_structured_summary("t", &template(), r#"{"title":"","sections":{"summary":""}}"#).unwrap();
+        assert!(summary.to_markdown().starts_with(&format!("**Summary**\n\n{}", EMPTY_SECTION_TEXT)));
+    }
+
+    #[test]
+    fn test_gbnf_grammar() {
+        let grammar = json_schema_to_gbnf(&template().to_json_schema());
+        assert!(grammar.contains("root-sections-action-items ::= \"[\" ws ( string ( ws \",\" ws string )* )? ws \"]\""));
+        assert!(grammar.contains(r#""\"sections\"" ws ":" ws root-sections"#));
+        assert!(grammar.contains(r#""\"title\"" ws ":" ws string"#));
+        assert!(grammar.contains("\nroot ::= \"{\" ws "));
+        assert!(grammar.contains("string ::= "));
+    }
+
+    #[test]
+    fn test_rich_formats_schema() {
+        let schema = rich_template().to_json_schema();
+        let sections = &schema["properties"]["sections"]["properties"];
+        assert_eq!(sections["risks"]["items"]["required"], json!(["risk", "due_date"]));
+        assert_eq!(sections["next_steps"]["items"]["properties"]["done"]["type"], "boolean");
+        assert_eq!(sections["facts"]["items"]["required"], json!(["key", "value"]));
+
+        let grammar = json_schema_to_gbnf(&schema);
+        assert!(grammar.contains(r#""\"done\"" ws ":" ws ( "true" | "false" )"#));
+        assert!(rich_template().to_json_instructions().contains("keys `risk`, `due_date`"));
+    }
+
+    #[test]
+    fn test_parse_and_render_rich_formats() {
+        let raw = r#"{"title":"Plan","sections":{
+            "risks":[{"risk":"Vendor delay","due_date":"Friday"},["Budget | overrun"],"| Risk | Due Date |","| --- | --- |","| Hiring | Q3 |"],
+            "next_steps":[{"text":"Book room","done":true},"- [ ] Send invite",{"item":"Review","done":"false"}],
+            "facts":[{"key":"Budget","value":"10k"},"**Owner**: Alice",{"Deadline":"May"}]}}"#;
+        let summary = parse_structured_summary("t", &rich_template(), raw).unwrap();
+
+        assert_eq!(
+            summary.section("risks").unwrap().content,
+            SectionContent::Rows(vec![
+                vec!["Vendor delay".to_string(), "Friday".to_string()],
+                vec!["Budget | overrun".to_string(), String::new()],
+                vec!["Hiring".to_string(), "Q3".to_string()],
+            ])
+        );
+        assert_eq!(
+            summary.to_markdown(),
+            "# Plan\n\n**Risks**\n\n| Risk | Due Date |\n| --- | --- |\n| Vendor delay | Friday |\n| Budget \\| overrun |  |\n| Hiring | Q3 |\n\n\
+             **Next Steps**\n\n- [x] Book room\n- [ ] Send invite\n- [ ] Review\n\n\
+             **Facts**\n\n- **Budget**: 10k\n- **Owner**: Alice\n- **Deadline**: May"
+        );
+
+        // Stored summaries deserialize back into the same content
+        let stored: StructuredSummary = serde_json::from_value(serde_json::to_value(&summary).unwrap()).unwrap();
+        assert_eq!(stored, summary);
+    }
+
+    #[test]
+    fn test_stored_empty_sections_keep_their_format() {
+        let summary = parse_structured_summary("t", &rich_template(), r#"{"risks":[],"next_steps":[],"facts":[]}"#).unwrap();
+        let stored: StructuredSummary = serde_json::from_value(serde_json::to_value(&summary).unwrap()).unwrap();
+
+        assert_eq!(stored.section("risks").unwrap().content, SectionContent::Rows(Vec::new()));
+        assert_eq!(stored.section("next_steps").unwrap().content, SectionContent::Checklist(Vec::new()));
+        assert_eq!(stored.section("facts").unwrap().content, SectionContent::Pairs(Vec::new()));
+        assert_eq!(stored, summary);
+
+        // Content that does not fit the format is rejected rather than misread
+        let mut value = serde_json::to_value(&summary).unwrap();
+        value["sections"][0]["content"] = serde_json::json!("not rows");
+        assert!(serde_json::from_value::<StructuredSummary>(value).is_err());
+    }
+
+    #[test]
+    fn test_rich_formats_from_text() {
+        let raw = r#"{"risks":"| Vendor delay | Friday |","next_steps":"- [x] Book room\n- Send invite","facts":{"Budget":"10k"}}"#;
+        let summary = parse_structured_summary("t", &rich_template(), raw).unwrap();
+        assert_eq!(
+            summary.section("risks").unwrap().content,
+            SectionContent::Rows(vec![vec!["Vendor delay".to_string(), "Friday".to_string()]])
+        );
+        assert_eq!(
+            summary.section("next_steps").unwrap().content,
+            SectionContent::Checklist(vec![
+                ChecklistItem { text: "Book room".to_string(), done: true },
+                ChecklistItem { text: "Send invite".to_string(), done: false },
+            ])
+        );
+        assert_eq!(
+            summary.section("facts").unwrap().content,
+            SectionContent::Pairs(vec![KeyValue { key: "Budget".to_string(), value: "10k".to_string() }])
+        );
+    }
+}
@@ -0,0 +1,350 @@
+//! Editing custom templates from the app
+//!
+//! Custom templates are stored as `{id}.json` in the custom templates directory
+//! and override shipped (bundled or built-in) templates with the same id. Every
+//! save bumps the template's `version` and archives the file it replaces as
+//! `.versions/{id}/v{version}.json`, so edits can be rolled back.
+
+use super::defaults;
+use super::loader::{self, get_custom_templates_dir, load_bundled_template};
+use super::schema::section_key;
+use super::types::Template;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+const VERSIONS_DIR: &str = ".versions";
+
+/// Where the effective template with an id comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateSource {
+    Custom,
+    Bundled,
+    BuiltIn,
+}
+
+/// Archived revision of a custom template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateVersion {
+    pub version: u32,
+    pub name: String,
+    /// When the revision was replaced (RFC 3339)
+    pub saved_at: Option<String>,
+}
+
+/// Check that an id is usable as a file name: lowercase letters, digits, `_` and `-`
+pub fn validate_template_id(id: &str) -> Result<(), String> {
+    if id.is_empty() || id.len() > 64 {
+        return Err("Template id must be between 1 and 64 characters".to_string());
+    }
+    if !id
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "Template id '{}' may only contain lowercase letters, digits, '_' and '-'",
+            id
+        ));
+    }
+    Ok(())
+}
+
+fn custom_dir() -> Result<PathBuf, String> {
+    get_custom_templates_dir().ok_or_else(|| "Could not resolve the custom templates directory".to_string())
+}
+
+/// Whether a bundled or built-in template has this id
+pub fn is_shipped_template(id: &str) -> bool {
+    defaults::get_builtin_template(id).is_some() || load_bundled_template(id).is_some()
+}
+
+/// Source of the template `get_template(id)` returns, if any
+pub fn template_source(id: &str) -> Option<TemplateSource> {
+    let is_custom = custom_dir().is_ok_and(|dir| dir.join(format!("{}.json", id)).is_file());
+    if is_custom {
+        Some(TemplateSource::Custom)
+    } else if load_bundled_template(id).is_some() {
+        Some(TemplateSource::Bundled)
+    } else if defaults::get_builtin_template(id).is_some() {
+        Some(TemplateSource::BuiltIn)
+    } else {
+        None
+    }
+}
+
+/// Validate and save a custom template, archiving the revision it replaces
+///
+/// # Returns
+/// The saved template with its new version
+pub fn save_custom_template(id: &str, template: Template) -> Result<Template, String> {
+    save_in(&custom_dir()?, id, template)
+}
+
+/// Delete a custom template
+///
+/// A shipped template with the same id becomes effective again. The deleted
+/// revision is archived like any other, so it can still be restored.
+pub fn delete_custom_template(id: &str) -> Result<(), String> {
+    delete_in(&custom_dir()?, id)
+}
+
+/// Copy any template (e.g. a built-in one) to a new custom template
+pub fn duplicate_template(source_id: &str, new_id: &str, name: Option<String>) -> Result<Template, String> {
+    validate_template_id(new_id)?;
+    if template_source(new_id).is_some() {
+        return Err(format!("A template with id '{}' already exists", new_id));
+    }
+    let mut template = loader::get_template(source_id)?;
+    template.name = name
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| format!("{} (copy)", template.name));
+    template.version = 0;
+    save_custom_template(new_id, template)
+}
+
+/// Archived revisions of a custom template, newest first
+pub fn list_template_versions(id: &str) -> Result<Vec<TemplateVersion>, String> {
+    list_versions_in(&custom_dir()?, id)
+}
+
+/// Save an archived revision as the newest version of a custom template
+pub fn restore_template_version(id: &str, version: u32) -> Result<Template, String> {
+    restore_in(&custom_dir()?, id, version)
+}
+
+/// Write the effective template with an id to a JSON file
+pub fn export_template(id: &str, path: &Path) -> Result<(), String> {
+    let mut template = loader::get_template(id)?;
+    // Revisions are local to this installation
+    template.version = 0;
+    let json = serde_json::to_string_pretty(&template)
+        .map_err(|e| format!("Failed to serialize template: {}", e))?;
+    std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    info!("Exported template '{}' to {:?}", id, path);
+    Ok(())
+}
+
+/// Import a template JSON file as a custom template
+///
+/// The id defaults to the file name. Existing templates with that id are only
+/// replaced when `overwrite` is set.
+///
+/// # Returns
+/// The id and the saved template
+pub fn import_template(path: &Path, id: Option<String>, overwrite: bool) -> Result<(String, Template), String> {
+    let (suggested_id, template) = read_import(path)?;
+    let id = id.filter(|id| !id.is_empty()).unwrap_or(suggested_id);
+    validate_template_id(&id)?;
+    if !overwrite && template_source(&id).is_some() {
+        return Err(format!("A template with id '{}' already exists", id));
+    }
+    let template = save_custom_template(&id, template)?;
+    info!("Imported template '{}' from {:?}", id, path);
+    Ok((id, template))
+}
+
+/// Parse and validate a template file; the suggested id is its file name in snake_case
+fn read_import(path: &Path) -> Result<(String, Template), String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut template = loader::validate_and_parse_template(&content)?;
+    template.version = 0;
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+    let mut id = section_key(stem);
+    if validate_template_id(&id).is_err() {
+        id = section_key(&template.name);
+    }
+    Ok((id, template))
+}
+
+fn template_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.json", id))
+}
+
+fn versions_dir(dir: &Path, id: &str) -> PathBuf {
+    dir.join(VERSIONS_DIR).join(id)
+}
+
+fn save_in(dir: &Path, id: &str, mut template: Template) -> Result<Template, String> {
+    validate_template_id(id)?;
+    template.validate()?;
+
+    let path = template_path(dir, id);
+    let previous = match archive_current(dir, id)? {
+        Some(version) => version,
+        None => latest_version(dir, id)?,
+    };
+    template.version = previous + 1;
+
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create templates directory: {}", e))?;
+    let json = serde_json::to_string_pretty(&template)
+        .map_err(|e| format!("Failed to serialize template: {}", e))?;
+    // Write through a temporary file so a crash never leaves a truncated template
+    let tmp_path = dir.join(format!("{}.json.tmp", id));
+    std::fs::write(&tmp_path, json).map_err(|e| format!("Failed to write template '{}': {}", id, e))?;
+    std::fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to save template '{}': {}", id, e))?;
+
+    info!("Saved custom template '{}' version {}", id, template.version);
+    Ok(template)
+}
+
+/// Newest archived version of a template, 0 without history
+fn latest_version(dir: &Path, id: &str) -> Result<u32, String> {
+    Ok(list_versions_in(dir, id)?.first().map_or(0, |v| v.version))
+}
+
+/// Copy the current custom template to its history
+///
+/// # Returns
+/// The version it was archived as, None if there is no custom template
+fn archive_current(dir: &Path, id: &str) -> Result<Option<u32>, String> {
+    let path = template_path(dir, id);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let current = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read template '{}': {}", id, e))?;
+    // Hand-edited files may lack a version or repeat an archived one
+    let version = serde_json::from_str::<Template>(&current)
+        .map_or(0, |template| template.version)
+        .max(latest_version(dir, id)? + 1);
+
+    let archive_dir = versions_dir(dir, id);
+    std::fs::create_dir_all(&archive_dir)
+        .map_err(|e| format!("Failed to create template history directory: {}", e))?;
+    std::fs::write(archive_dir.join(format!("v{}.json", version)), current)
+        .map_err(|e| format!("Failed to archive template '{}': {}", id, e))?;
+    Ok(Some(version))
+}
+
+fn delete_in(dir: &Path, id: &str) -> Result<(), String> {
+    validate_template_id(id)?;
+    let path = template_path(dir, id);
+    if archive_current(dir, id)?.is_none() {
+        return Err(format!("Template '{}' is not a custom template", id));
+    }
+    std::fs::remove_file(&path).map_err(|e| format!("Failed to delete template '{}': {}", id, e))?;
+    info!("Deleted custom template '{}'", id);
+    Ok(())
+}
+
+fn list_versions_in(dir: &Path, id: &str) -> Result<Vec<TemplateVersion>, String> {
+    validate_template_id(id)?;
+    let Ok(entries) = std::fs::read_dir(versions_dir(dir, id)) else {
+        return Ok(Vec::new());
+    };
+
+    let mut versions: Vec<TemplateVersion> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let version = file_name.to_str()?.strip_prefix('v')?.strip_suffix(".json")?.parse().ok()?;
+            let name = std::fs::read_to_string(entry.path())
+                .ok()
+                .and_then(|content| serde_json::from_str::<Template>(&content).ok())
+                .map(|template| template.name)
+                .unwrap_or_default();
+            let saved_at = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .map(|modified| DateTime::<Utc>::from(modified).to_rfc3339());
+            Some(TemplateVersion { version, name, saved_at })
+        })
+        .collect();
+    versions.sort_by_key(|v| std::cmp::Reverse(v.version));
+    Ok(versions)
+}
+
+fn restore_in(dir: &Path, id: &str, version: u32) -> Result<Template, String> {
+    validate_template_id(id)?;
+    let archived = versions_dir(dir, id).join(format!("v{}.json", version));
+    let content = std::fs::read_to_string(&archived)
+        .map_err(|_| format!("Version {} of template '{}' not found", version, id))?;
+    let template = loader::validate_and_parse_template(&content)?;
+    info!("Restoring template '{}' to version {}", id, version);
+    save_in(dir, id, template)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(name: &str) -> Template {
+        loader::validate_and_parse_template(&format!(
+            r#"{{"name": "{}", "description": "Test", "sections": [{{"title": "Summary", "instruction": "Summarize", "format": "paragraph"}}]}}"#,
+            name
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_template_id() {
+        assert!(validate_template_id("team_sync-2").is_ok());
+        assert!(validate_template_id("").is_err());
+        assert!(validate_template_id("../evil").is_err());
+        assert!(validate_template_id("Team Sync").is_err());
+    }
+
+    #[test]
+    fn test_save_archives_previous_versions() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(save_in(dir.path(), "sync", template("First")).unwrap().version, 1);
+        assert_eq!(save_in(dir.path(), "sync", template("Second")).unwrap().version, 2);
+        assert_eq!(save_in(dir.path(), "sync", template("Third")).unwrap().version, 3);
+
+        let versions = list_versions_in(dir.path(), "sync").unwrap();
+        let names: Vec<(u32, &str)> = versions.iter().map(|v| (v.version, v.name.as_str())).collect();
+        assert_eq!(names, vec![(2, "Second"), (1, "First")]);
+        assert!(!dir.path().join("sync.json.tmp").exists());
+
+        let restored = restore_in(dir.path(), "sync", 1).unwrap();
+        assert_eq!((restored.name.as_str(), restored.version), ("First", 4));
+        assert_eq!(list_versions_in(dir.path(), "sync").unwrap()[0].version, 3);
+    }
+
+    #[test]
+    fn test_save_rejects_invalid_template() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut invalid = template("Broken");
+        invalid.sections[0].format = "table".to_string();
+
+        assert!(save_in(dir.path(), "broken", invalid).is_err());
+        assert!(!template_path(dir.path(), "broken").exists());
+    }
+
+    #[test]
+    fn test_delete_keeps_history() {
+        let dir = tempfile::tempdir().unwrap();
+        save_in(dir.path(), "sync", template("First")).unwrap();
+        save_in(dir.path(), "sync", template("Second")).unwrap();
+
+        delete_in(dir.path(), "sync").unwrap();
+        assert!(delete_in(dir.path(), "sync").is_err());
+        assert!(!template_path(dir.path(), "sync").exists());
+        assert_eq!(list_versions_in(dir.path(), "sync").unwrap().len(), 2);
+
+        // Numbering continues after the archived revisions
+        assert_eq!(save_in(dir.path(), "sync", template("Again")).unwrap().version, 3);
+        assert_eq!(restore_in(dir.path(), "sync", 2).unwrap().name, "Second");
+    }
+
+    #[test]
+    fn test_read_import_suggests_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Client Kickoff.json");
+        let mut exported = template("Kickoff");
+        exported.version = 7;
+        std::fs::write(&path, serde_json::to_string(&exported).unwrap()).unwrap();
+
+        let (id, imported) = read_import(&path).unwrap();
+        assert_eq!(id, "client_kickoff");
+        assert_eq!(imported.version, 0);
+
+        std::fs::write(&path, r#"{"name": "Bad", "description": "", "sections": []}"#).unwrap();
+        assert!(read_import(&path).is_err());
+    }
+}
@@ -1,5 +1,9 @@
+use super::schema::section_key;
 use serde::{Deserialize, Serialize};
 
+/// Section formats a template may use
+pub const SECTION_FORMATS: &[&str] = &["paragraph", "list", "string", "table", "checklist", "key_value"];
+
 /// Represents a single section in a meeting template
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemplateSection {
@@ -9,9 +13,13 @@ pub struct TemplateSection {
     /// Instruction for the LLM on what to extract/include
     pub instruction: String,
 
-    /// Format type: "paragraph", "list", or "string"
+    /// Format type: "paragraph", "list", "string", "table", "checklist" or "key_value"
     pub format: String,
 
+    /// Column headers of a "table" section
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Vec<String>>,
+
     /// Optional markdown formatting hint for list items (e.g., table structure)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub item_format: Option<String>,
@@ -32,6 +40,14 @@ pub struct Template {
 
     /// List of sections in the template
     pub sections: Vec<TemplateSection>,
+
+    /// Revision of a custom template, bumped on every save (0 for shipped templates)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub version: u32,
+}
+
+fn is_zero(version: &u32) -> bool {
+    *version == 0
 }
 
 impl Template {
@@ -58,12 +74,36 @@ impl Template {
                 return Err(format!("Section '{}' has empty instruction", section.title));
             }
 
-            match section.format.as_str() {
-                "paragraph" | "list" | "string" => {},
-                other => return Err(format!(
-                    "Section '{}' has invalid format '{}'. Must be 'paragraph', 'list', or 'string'",
-                    section.title, other
-                )),
+            if !SECTION_FORMATS.contains(&section.format.as_str()) {
+                return Err(format!(
+                    "Section '{}' has invalid format '{}'. Must be one of: {}",
+                    section.title,
+                    section.format,
+                    SECTION_FORMATS.join(", ")
+                ));
+            }
+
+            if section.format == "table" {
+                let columns = section.columns.as_deref().unwrap_or_default();
+                if columns.is_empty() || columns.iter().any(|c| c.trim().is_empty()) {
+                    return Err(format!("Table section '{}' needs non-empty column names", section.title));
+                }
+                // Rows are keyed by `section_key(column)`, so keys must be unique
+                for (i, column) in columns.iter().enumerate() {
+                    let key = section_key(column);
+                    if key.is_empty() {
+                        return Err(format!(
+                            "Column '{}' of table section '{}' needs a letter or digit",
+                            column, section.title
+                        ));
+                    }
+                    if let Some(other) = columns[..i].iter().find(|other| section_key(other) == key) {
+                        return Err(format!(
+                            "Columns '{}' and '{}' of table section '{}' are too similar; rename one of them",
+                            other, column, section.title
+                        ));
+                    }
+                }
             }
         }
 
@@ -103,6 +143,20 @@ impl Template {
                     format
                 ));
             }
+
+            match section.format.as_str() {
+                "table" => instructions.push_str(&format!(
+                    "  - Format this section as a markdown table with the columns: {}.\n",
+                    section.columns.as_deref().unwrap_or_default().join(" | ")
+                )),
+                "checklist" => instructions.push_str(
+                    "  - Format this section as a checklist: `- [ ] item`, or `- [x] item` for items already done.\n",
+                ),
+                "key_value" => instructions.push_str(
+                    "  - Format this section as `- **Key**: value` lines.\n",
+                ),
+                _ => {}
+            }
         }
 
         instructions
@@ -123,10 +177,12 @@ mod tests {
                     title: "Summary".to_string(),
                     instruction: "Provide a summary".to_string(),
                     format: "paragraph".to_string(),
+                    columns: None,
                     item_format: None,
                     example_item_format: None,
                 },
             ],
+            version: 0,
         };
 
         assert!(template.validate().is_ok());
@@ -138,6 +194,7 @@ mod tests {
             name: "".to_string(),
             description: "A test template".to_string(),
             sections: vec![],
+            version: 0,
         };
 
         assert!(template.validate().is_err());
@@ -153,12 +210,45 @@ mod tests {
                     title: "Test".to_string(),
                     instruction: "Test".to_string(),
                     format: "invalid".to_string(),
+                    columns: None,
                     item_format: None,
                     example_item_format: None,
                 },
             ],
+            version: 0,
         };
 
         assert!(template.validate().is_err());
     }
+
+    #[test]
+    fn test_validate_table_needs_columns() {
+        let mut template = Template {
+            name: "Test".to_string(),
+            description: "Test".to_string(),
+            sections: vec![TemplateSection {
+                title: "Risks".to_string(),
+                instruction: "List risks".to_string(),
+                format: "table".to_string(),
+                columns: None,
+                item_format: None,
+                example_item_format: None,
+            }],
+            version: 0,
+        };
+        assert!(template.validate().is_err());
+
+        template.sections[0].columns = Some(vec!["Risk".to_string(), "Owner".to_string()]);
+        assert!(template.validate().is_ok());
+        assert!(template.to_section_instructions().contains("columns: Risk | Owner"));
+
+        template.sections[0].columns = Some(vec!["Risk".to_string(), "Owner".to_string(), "owner".to_string()]);
+        assert!(template.validate().unwrap_err().contains("'Owner' and 'owner'"));
+
+        template.sections[0].columns = Some(vec!["Due date".to_string(), "Due-Date".to_string()]);
+        assert!(template.validate().is_err());
+
+        template.sections[0].columns = Some(vec!["Risk".to_string(), "?".to_string()]);
+        assert!(template.validate().is_err());
+    }
 }
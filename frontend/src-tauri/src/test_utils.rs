@@ -0,0 +1,76 @@
+//! Shared helpers for HTTP client tests: a local server that answers with
+//! canned responses and hands back the raw requests it received.
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// Serves a single canned HTTP response.
+///
+/// Returns the server's base URL and a handle resolving to the raw request
+/// (head and body).
+pub(crate) async fn spawn_mock_server(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+    let (base_url, handle) = spawn_mock_server_sequence(vec![(status, body)]).await;
+    let handle = tokio::spawn(async move { handle.await.unwrap().remove(0) });
+    (base_url, handle)
+}
+
+/// Serves one canned `(status, body)` response per connection, in order.
+///
+/// Returns the server's base URL and a handle resolving to the raw requests,
+/// once every response has been sent.
+pub(crate) async fn spawn_mock_server_sequence(
+    responses: Vec<(&'static str, &'static str)>,
+) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            requests.push(read_request(&mut socket).await);
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        }
+        requests
+    });
+
+    (base_url, handle)
+}
+
+/// Reads the request headers, then the body according to Content-Length
+async fn read_request(socket: &mut TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+
+    loop {
+        let n = socket.read(&mut buf).await.unwrap();
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request).to_string();
+        if let Some(header_end) = text.find("\r\n\r\n") {
+            let content_length = text[..header_end]
+                .lines()
+                .find_map(|l| {
+                    l.to_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|v| v.trim().parse::<usize>().unwrap_or(0))
+                })
+                .unwrap_or(0);
+            if request.len() >= header_end + 4 + content_length {
+                break;
+            }
+        }
+        if n == 0 {
+            break;
+        }
+    }
+
+    String::from_utf8_lossy(&request).to_string()
+}
@@ -25,6 +25,8 @@ export interface MeetingPrep {
   action_items: string[];
   goals: string[];
   context: string;
+  /** Sections for user-configured fields, keyed by field name */
+  custom_fields?: Record<string, string[]>;
   raw_content: string;
 }
 
@@ -144,6 +146,7 @@ export function PrepPanel({ meetingPrep, isLoading = false }: PrepPanelProps) {
   const hasActionItems = meetingPrep.action_items.length > 0;
   const hasGoals = meetingPrep.goals.length > 0;
   const hasContext = meetingPrep.context.trim().length > 0;
+  const customFields = Object.entries(meetingPrep.custom_fields ?? {}).filter(([, items]) => items.length > 0);
   const hasAttendees = meetingPrep.frontmatter.attendees && meetingPrep.frontmatter.attendees.length > 0;
 
   return (
@@ -345,10 +348,34 @@ export function PrepPanel({ meetingPrep, isLoading = false }: PrepPanelProps) {
               </AccordionContent>
             </AccordionItem>
           )}
+
+          {/* Custom sections from section label overrides */}
+          {customFields.map(([field, items]) => (
+            <AccordionItem key={field} value={`custom-${field}`} className="border rounded-lg px-4">
+              <AccordionTrigger className="py-3">
+                <div className="flex items-center gap-2">
+                  <FileText className="w-4 h-4 text-gray-500" />
+                  <span className="capitalize">{field.replace(/_/g, ' ')}</span>
+                  <Badge variant="outline" className="ml-2">
+                    {items.length}
+                  </Badge>
+                </div>
+              </AccordionTrigger>
+              <AccordionContent>
+                <ul className="space-y-1 pb-2">
+                  {items.map((item, index) => (
+                    <li key={index} className="text-sm text-gray-700">
+                      {item}
+                    </li>
+                  ))}
+                </ul>
+              </AccordionContent>
+            </AccordionItem>
+          ))}
         </Accordion>
 
         {/* Show message if no content sections */}
-        {!hasAgenda && !hasNotes && !hasActionItems && !hasGoals && !hasContext && (
+        {!hasAgenda && !hasNotes && !hasActionItems && !hasGoals && !hasContext && customFields.length === 0 && (
           <div className="text-center text-gray-500 py-8">
             <p>No preparation content found in this file.</p>
             <p className="text-sm mt-2">
@@ -0,0 +1,93 @@
+'use client';
+
+import { useState, useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { CalendarDays } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import {
+  DropdownMenu,
+  DropdownMenuContent,
+  DropdownMenuItem,
+  DropdownMenuLabel,
+  DropdownMenuTrigger,
+} from '@/components/ui/dropdown-menu';
+
+/** A meeting note from the indexed Obsidian vault */
+export interface VaultMeeting {
+  file_path: string;
+  title: string;
+  date?: string | null;
+  time_start?: string | null;
+  status?: string | null;
+  attendees: string[];
+}
+
+interface TodaysMeetingsProps {
+  onSelect: (meeting: VaultMeeting) => void;
+  disabled?: boolean;
+}
+
+/** Start time as HH:MM, from `HH:MM` or an ISO date-time */
+function formatStartTime(timeStart?: string | null): string | null {
+  const match = timeStart?.match(/(\d{1,2}:\d{2})/);
+  return match ? match[1] : null;
+}
+
+/**
+ * Today's meetings from the Obsidian vault index. Hidden while no vault is
+ * configured; picking a meeting hands its prep note to `onSelect`.
+ */
+export function TodaysMeetings({ onSelect, disabled }: TodaysMeetingsProps) {
+  const [meetings, setMeetings] = useState<VaultMeeting[] | null>(null);
+
+  const loadMeetings = useCallback(async () => {
+    try {
+      setMeetings(await invoke<VaultMeeting[]>('get_todays_meetings'));
+    } catch {
+      // No vault configured
+      setMeetings(null);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadMeetings();
+    const unlisten = listen('obsidian-vault-updated', () => loadMeetings());
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [loadMeetings]);
+
+  if (meetings === null) {
+    return null;
+  }
+
+  return (
+    <DropdownMenu onOpenChange={open => open && loadMeetings()}>
+      <DropdownMenuTrigger asChild>
+        <Button variant="outline" size="sm" disabled={disabled}>
+          <CalendarDays className="w-4 h-4 mr-2" />
+          Today&apos;s Meetings ({meetings.length})
+        </Button>
+      </DropdownMenuTrigger>
+      <DropdownMenuContent align="start" className="w-72">
+        <DropdownMenuLabel>Start recording with a prep note</DropdownMenuLabel>
+        {meetings.length === 0 && (
+          <div className="px-2 py-1.5 text-sm text-gray-500">No meetings scheduled today</div>
+        )}
+        {meetings.map(meeting => (
+          <DropdownMenuItem
+            key={meeting.file_path}
+            onClick={() => onSelect(meeting)}
+            title={meeting.file_path}
+          >
+            <span className="w-12 shrink-0 text-xs text-gray-500">
+              {formatStartTime(meeting.time_start) ?? '—'}
+            </span>
+            <span className="truncate">{meeting.title}</span>
+          </DropdownMenuItem>
+        ))}
+      </DropdownMenuContent>
+    </DropdownMenu>
+  );
+}
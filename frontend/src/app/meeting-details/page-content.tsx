@@ -13,6 +13,7 @@ import { useSummaryGeneration } from '@/hooks/meeting-details/useSummaryGenerati
 import { useTemplates } from '@/hooks/meeting-details/useTemplates';
 import { useCopyOperations } from '@/hooks/meeting-details/useCopyOperations';
 import { useMeetingOperations } from '@/hooks/meeting-details/useMeetingOperations';
+import { useCitationPlayback } from '@/hooks/meeting-details/useCitationPlayback';
 import { useConfig } from '@/contexts/ConfigContext';
 
 export default function PageContent({
@@ -113,6 +114,8 @@ export default function PageContent({
     meeting,
   });
 
+  const citationPlayback = useCitationPlayback({ meeting });
+
   // Track page view
   useEffect(() => {
     Analytics.trackPageView('meeting_details');
@@ -125,7 +128,8 @@ export default function PageContent({
     const autoGenerate = async () => {
       if (shouldAutoGenerate && meetingData.transcripts.length > 0 && !cancelled) {
         console.log(`🤖 Auto-generating summary with ${modelConfig.provider}/${modelConfig.model}...`);
-        await summaryGeneration.handleGenerateSummary('');
+        // Auto-generated summaries use the template the meeting note asks for
+        await summaryGeneration.handleGenerateSummary('', true);
 
         // Notify parent that auto-generation is complete (only if not cancelled)
         if (onAutoGenerateComplete && !cancelled) {
@@ -152,6 +156,7 @@ export default function PageContent({
       <div className="flex flex-1 overflow-hidden">
         <TranscriptPanel
           transcripts={meetingData.transcripts}
+          meetingId={meeting.id}
           customPrompt={customPrompt}
           onPromptChange={setCustomPrompt}
           onCopyTranscript={copyOperations.handleCopyTranscript}
@@ -196,6 +201,8 @@ export default function PageContent({
           summaryError={summaryGeneration.summaryError}
           onRegenerateSummary={summaryGeneration.handleRegenerateSummary}
           getSummaryStatusMessage={summaryGeneration.getSummaryStatusMessage}
+          streamingMarkdown={summaryGeneration.streamingMarkdown}
+          onCitationClick={citationPlayback.handleCitationClick}
           availableTemplates={templates.availableTemplates}
           selectedTemplate={templates.selectedTemplate}
           onTemplateSelect={templates.handleTemplateSelection}
@@ -12,6 +12,7 @@ import Analytics from '@/lib/analytics';
 import { SettingsModals } from './_components/SettingsModal';
 import { TranscriptPanel } from './_components/TranscriptPanel';
 import { PrepPanel, MeetingPrep } from './_components/PrepPanel';
+import { TodaysMeetings, VaultMeeting } from './_components/TodaysMeetings';
 import { useModalState } from '@/hooks/useModalState';
 import { useRecordingStateSync } from '@/hooks/useRecordingStateSync';
 import { useRecordingStart } from '@/hooks/useRecordingStart';
@@ -77,6 +78,29 @@ export default function Home() {
 
   const router = useRouter();
 
+  // Load a meeting file as the prep note of the next recording
+  const loadMeetingFile = async (path: string): Promise<MeetingPrep | null> => {
+    setIsLoadingFile(true);
+    try {
+      const prep = await invoke<MeetingPrep>('open_meeting_file', { path });
+      setMeetingPrep(prep);
+      sessionStorage.setItem('active_prep_note_path', prep.file_path);
+      sessionStorage.setItem('active_prep_note_base', prep.raw_content);
+      toast.success('Meeting file loaded', {
+        description: prep.title || 'Untitled Meeting',
+      });
+      return prep;
+    } catch (error) {
+      console.error('Failed to parse meeting file:', error);
+      toast.error('Failed to load meeting file', {
+        description: error instanceof Error ? error.message : 'Unknown error',
+      });
+      return null;
+    } finally {
+      setIsLoadingFile(false);
+    }
+  };
+
   // Handle opening meeting file
   const handleOpenFile = async () => {
     try {
@@ -87,21 +111,7 @@ export default function Home() {
       });
 
       if (selected && typeof selected === 'string') {
-        setIsLoadingFile(true);
-        try {
-          const prep = await invoke<MeetingPrep>('open_meeting_file', { path: selected });
-          setMeetingPrep(prep);
-          toast.success('Meeting file loaded', {
-            description: prep.title || 'Untitled Meeting',
-          });
-        } catch (error) {
-          console.error('Failed to parse meeting file:', error);
-          toast.error('Failed to load meeting file', {
-            description: error instanceof Error ? error.message : 'Unknown error',
-          });
-        } finally {
-          setIsLoadingFile(false);
-        }
+        await loadMeetingFile(selected);
       }
     } catch (error) {
       console.error('Failed to open file dialog:', error);
@@ -109,9 +119,19 @@ export default function Home() {
     }
   };
 
+  // Start recording against the prep note of one of today's meetings
+  const handleTodaysMeetingSelect = async (meeting: VaultMeeting) => {
+    const prep = await loadMeetingFile(meeting.file_path);
+    if (prep && !recordingState.isRecording) {
+      await handleRecordingStart();
+    }
+  };
+
   // Handle closing meeting file
   const handleCloseFile = () => {
     setMeetingPrep(null);
+    sessionStorage.removeItem('active_prep_note_path');
+    sessionStorage.removeItem('active_prep_note_base');
   };
 
   useEffect(() => {
@@ -286,6 +306,11 @@ export default function Home() {
             {isLoadingFile ? 'Loading...' : 'Open Meeting File'}
           </Button>
 
+          <TodaysMeetings
+            onSelect={handleTodaysMeetingSelect}
+            disabled={isLoadingFile || recordingState.isRecording}
+          />
+
           {meetingPrep && (
             <div className="flex items-center gap-2 text-sm text-gray-600">
               <span className="max-w-[300px] truncate" title={meetingPrep.file_path}>
@@ -9,6 +9,9 @@ import { TranscriptSettings, TranscriptModelProps } from '@/components/Transcrip
 import { RecordingSettings } from '@/components/RecordingSettings';
 import { PreferenceSettings } from '@/components/PreferenceSettings';
 import { SummaryModelSettings } from '@/components/SummaryModelSettings';
+import { TemplateSettings } from '@/components/TemplateSettings';
+import { TemplateRuleSettings } from '@/components/TemplateRuleSettings';
+import { ObsidianVaultSettings } from '@/components/ObsidianVaultSettings';
 import { useConfig } from '@/contexts/ConfigContext';
 import { Tabs, TabsList, TabsTrigger, TabsContent } from '@/components/ui/tabs';
 
@@ -109,7 +112,10 @@ export default function SettingsPage() {
             </TabsList>
 
             <TabsContent value="general">
-              <PreferenceSettings />
+              <div className="flex flex-col gap-4">
+                <PreferenceSettings />
+                <ObsidianVaultSettings />
+              </div>
             </TabsContent>
             <TabsContent value="recording">
               <RecordingSettings />
@@ -121,7 +127,11 @@ export default function SettingsPage() {
               />
             </TabsContent>
             <TabsContent value="summaryModels">
-              <SummaryModelSettings />
+              <div className="flex flex-col gap-4">
+                <SummaryModelSettings />
+                <TemplateSettings />
+                <TemplateRuleSettings />
+              </div>
             </TabsContent>
           </Tabs>
         </div>
@@ -0,0 +1,83 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { toast } from 'sonner';
+import { Switch } from './ui/switch';
+
+const FAILOVER_KEY = 'deepgram_local_failover';
+const RERUN_GAPS_KEY = 'deepgram_rerun_gaps';
+
+/**
+ * What happens when Deepgram keeps failing during a meeting: continue on a
+ * downloaded Parakeet or Whisper model, and optionally send the audio of the
+ * outage through Deepgram again once it recovers.
+ */
+export function DeepgramFailoverSettings() {
+  const [failover, setFailover] = useState(false);
+  const [rerunGaps, setRerunGaps] = useState(false);
+
+  useEffect(() => {
+    const loadPreferences = async () => {
+      try {
+        const { Store } = await import('@tauri-apps/plugin-store');
+        const store = await Store.load('preferences.json');
+        setFailover(await store.get<boolean>(FAILOVER_KEY) ?? false);
+        setRerunGaps(await store.get<boolean>(RERUN_GAPS_KEY) ?? false);
+      } catch (error) {
+        console.error('Failed to load Deepgram failover preferences:', error);
+      }
+    };
+    loadPreferences();
+  }, []);
+
+  const savePreference = async (key: string, enabled: boolean) => {
+    try {
+      const { Store } = await import('@tauri-apps/plugin-store');
+      const store = await Store.load('preferences.json');
+      await store.set(key, enabled);
+      await store.save();
+      toast.success('Preference saved', { description: 'Applies from the next recording.' });
+    } catch (error) {
+      console.error('Failed to save Deepgram failover preference:', error);
+      toast.error('Failed to save preference');
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <div className="flex items-center justify-between p-3 border rounded-lg">
+        <div className="flex-1 pr-4">
+          <div className="text-sm font-medium">Fall back to a local model</div>
+          <div className="text-xs text-gray-500">
+            Keep transcribing with a downloaded Parakeet or Whisper model when Deepgram is unreachable or out of credit
+          </div>
+        </div>
+        <Switch
+          checked={failover}
+          onCheckedChange={(enabled) => {
+            setFailover(enabled);
+            savePreference(FAILOVER_KEY, enabled);
+          }}
+        />
+      </div>
+
+      {failover && (
+        <div className="flex items-center justify-between p-3 border rounded-lg">
+          <div className="flex-1 pr-4">
+            <div className="text-sm font-medium">Re-transcribe the outage with Deepgram</div>
+            <div className="text-xs text-gray-500">
+              Once Deepgram recovers, replace the local model&apos;s segments with Deepgram results (up to 15 minutes of audio)
+            </div>
+          </div>
+          <Switch
+            checked={rerunGaps}
+            onCheckedChange={(enabled) => {
+              setRerunGaps(enabled);
+              savePreference(RERUN_GAPS_KEY, enabled);
+            }}
+          />
+        </div>
+      )}
+    </div>
+  );
+}
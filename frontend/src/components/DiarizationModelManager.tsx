@@ -0,0 +1,127 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { toast } from 'sonner';
+import { CheckCircle2, Download, Loader2, X } from 'lucide-react';
+import { Button } from './ui/button';
+import { Label } from './ui/label';
+import { Progress } from './ui/progress';
+
+interface DiarizationModelStatus {
+  installed: boolean;
+  downloading: boolean;
+  path: string | null;
+  size_mb: number;
+}
+
+interface DownloadProgress {
+  progress: number;
+  downloaded_mb: number;
+  total_mb: number;
+  speed_mbps: number;
+  status: 'downloading' | 'completed';
+}
+
+/**
+ * Optional speaker embedding model for every provider except Deepgram, which
+ * diarizes server-side. When installed, live and re-transcribed meetings get
+ * `[Speaker N]` labels.
+ */
+export function DiarizationModelManager() {
+  const [status, setStatus] = useState<DiarizationModelStatus | null>(null);
+  const [progress, setProgress] = useState<DownloadProgress | null>(null);
+
+  const refreshStatus = async () => {
+    try {
+      setStatus(await invoke<DiarizationModelStatus>('diarization_get_model_status'));
+    } catch (error) {
+      console.error('Failed to load speaker model status:', error);
+    }
+  };
+
+  useEffect(() => {
+    refreshStatus();
+
+    const unlistenProgress = listen<DownloadProgress>('diarization-model-download-progress', (event) => {
+      setProgress(event.payload);
+    });
+    const unlistenComplete = listen('diarization-model-download-complete', () => {
+      setProgress(null);
+      refreshStatus();
+      toast.success('Speaker model installed');
+    });
+
+    return () => {
+      unlistenProgress.then((unlisten) => unlisten());
+      unlistenComplete.then((unlisten) => unlisten());
+    };
+  }, []);
+
+  const handleDownload = async () => {
+    setProgress({ progress: 0, downloaded_mb: 0, total_mb: 0, speed_mbps: 0, status: 'downloading' });
+    try {
+      await invoke('diarization_download_model');
+    } catch (error) {
+      setProgress(null);
+      if (!String(error).includes('cancelled')) {
+        toast.error('Speaker model download failed', { description: String(error) });
+      }
+    } finally {
+      refreshStatus();
+    }
+  };
+
+  const handleCancel = async () => {
+    try {
+      await invoke('diarization_cancel_download');
+    } catch (error) {
+      console.error('Failed to cancel speaker model download:', error);
+    }
+  };
+
+  const isDownloading = progress !== null || status?.downloading;
+
+  return (
+    <div className="space-y-2 rounded-lg border border-gray-200 p-3">
+      <div className="flex items-center justify-between gap-2">
+        <div>
+          <Label className="text-sm font-medium text-gray-700">Speaker labels</Label>
+          <p className="text-xs text-gray-500">
+            Separates speakers on-device as <code>[Speaker N]</code> (~27 MB model).
+          </p>
+        </div>
+
+        {status?.installed && !isDownloading ? (
+          <span className="flex items-center gap-1 text-xs text-green-700">
+            <CheckCircle2 className="h-4 w-4" />
+            Installed
+          </span>
+        ) : isDownloading ? (
+          <Button variant="outline" size="sm" onClick={handleCancel}>
+            <X className="h-4 w-4" />
+            Cancel
+          </Button>
+        ) : (
+          <Button variant="outline" size="sm" onClick={handleDownload}>
+            <Download className="h-4 w-4" />
+            Download
+          </Button>
+        )}
+      </div>
+
+      {isDownloading && (
+        <div className="space-y-1">
+          <Progress value={progress?.progress ?? 0} />
+          <p className="flex items-center gap-1 text-xs text-gray-500">
+            <Loader2 className="h-3 w-3 animate-spin" />
+            {progress && progress.total_mb > 0
+              ? `${progress.downloaded_mb.toFixed(1)} / ${progress.total_mb.toFixed(1)} MB (${progress.speed_mbps.toFixed(1)} MB/s)`
+              : 'Starting download...'}
+          </p>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,130 @@
+"use client";
+
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { toast } from 'sonner';
+import { Loader2 } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { MeetingSpeakers, SaveMeetingSpeakersResult } from '@/types';
+
+interface SpeakerNamesDialogProps {
+  meetingId: string;
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+}
+
+export function SpeakerNamesDialog({ meetingId, open, onOpenChange }: SpeakerNamesDialogProps) {
+  const [speakers, setSpeakers] = useState<MeetingSpeakers | null>(null);
+  const [names, setNames] = useState<Record<string, string>>({});
+  const [isLoading, setIsLoading] = useState(false);
+  const [isSaving, setIsSaving] = useState(false);
+
+  useEffect(() => {
+    if (!open) return;
+
+    setIsLoading(true);
+    invoke<MeetingSpeakers>('get_meeting_speakers', { meetingId, notePath: null })
+      .then((result) => {
+        setSpeakers(result);
+        setNames(
+          Object.fromEntries(result.speakers.map((s) => [s.label, s.display_name ?? '']))
+        );
+      })
+      .catch((error) => {
+        console.error('Failed to load speakers:', error);
+        toast.error('Failed to load speakers', { description: String(error) });
+      })
+      .finally(() => setIsLoading(false));
+  }, [open, meetingId]);
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    try {
+      const result = await invoke<SaveMeetingSpeakersResult>('save_meeting_speakers', {
+        request: { meeting_id: meetingId, speakers: names, note_paths: [] },
+      });
+      if (result.failed_files.length > 0) {
+        toast.warning('Speaker names saved, but some notes could not be updated', {
+          description: result.failed_files.map(([file, error]) => `${file}: ${error}`).join('\n'),
+        });
+      } else {
+        toast.success('Speaker names saved');
+      }
+      onOpenChange(false);
+    } catch (error) {
+      console.error('Failed to save speakers:', error);
+      toast.error('Failed to save speaker names', { description: String(error) });
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="sm:max-w-[480px]">
+        <DialogHeader>
+          <DialogTitle>Speakers</DialogTitle>
+          <DialogDescription>
+            Name the speakers of this meeting. Named speakers become links in the meeting note.
+          </DialogDescription>
+        </DialogHeader>
+
+        {isLoading ? (
+          <div className="flex justify-center py-6">
+            <Loader2 className="h-5 w-5 animate-spin text-gray-500" />
+          </div>
+        ) : speakers && speakers.speakers.length > 0 ? (
+          <div className="space-y-3 py-2">
+            {speakers.speakers.map(({ label }) => (
+              <div key={label} className="grid grid-cols-3 items-center gap-3">
+                <Label htmlFor={`speaker-${label}`} className="text-sm text-gray-600">
+                  {label}
+                </Label>
+                <Input
+                  id={`speaker-${label}`}
+                  className="col-span-2"
+                  list="speaker-name-candidates"
+                  placeholder="Name"
+                  value={names[label] ?? ''}
+                  onChange={(e) => setNames((prev) => ({ ...prev, [label]: e.target.value }))}
+                />
+              </div>
+            ))}
+            <datalist id="speaker-name-candidates">
+              {speakers.candidates.map((candidate) => (
+                <option key={candidate} value={candidate} />
+              ))}
+            </datalist>
+          </div>
+        ) : (
+          <p className="py-4 text-sm text-gray-500">
+            No speakers were detected. Re-transcribe with diarization to separate speakers.
+          </p>
+        )}
+
+        <DialogFooter>
+          <Button variant="outline" onClick={() => onOpenChange(false)} disabled={isSaving}>
+            Cancel
+          </Button>
+          <Button
+            onClick={handleSave}
+            disabled={isSaving || isLoading || !speakers || speakers.speakers.length === 0}
+          >
+            {isSaving && <Loader2 className="h-4 w-4 animate-spin" />}
+            Save
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
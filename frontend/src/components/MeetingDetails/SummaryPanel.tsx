@@ -8,6 +8,7 @@ import { ModelConfig } from '@/components/ModelSettingsModal';
 import { SummaryGeneratorButtonGroup } from './SummaryGeneratorButtonGroup';
 import { SummaryUpdaterButtonGroup } from './SummaryUpdaterButtonGroup';
 import Analytics from '@/lib/analytics';
+import { parseCitationHref } from '@/hooks/meeting-details/useCitationPlayback';
 import { RefObject } from 'react';
 
 interface SummaryPanelProps {
@@ -43,6 +44,8 @@ interface SummaryPanelProps {
   summaryError: string | null;
   onRegenerateSummary: () => Promise<void>;
   getSummaryStatusMessage: (status: 'idle' | 'processing' | 'summarizing' | 'regenerating' | 'completed' | 'error') => string;
+  streamingMarkdown?: string;
+  onCitationClick?: (seconds: number) => void;
   availableTemplates: Array<{ id: string, name: string, description: string }>;
   selectedTemplate: string;
   onTemplateSelect: (templateId: string, templateName: string) => void;
@@ -79,6 +82,8 @@ export function SummaryPanel({
   summaryError,
   onRegenerateSummary,
   getSummaryStatusMessage,
+  streamingMarkdown = '',
+  onCitationClick,
   availableTemplates,
   selectedTemplate,
   onTemplateSelect,
@@ -160,13 +165,21 @@ export function SummaryPanel({
               onOpenModelSettings={onOpenModelSettings}
             />
           </div>
-          {/* Loading spinner */}
-          <div className="flex items-center justify-center flex-1">
-            <div className="text-center">
-              <div className="inline-block animate-spin rounded-full h-12 w-12 border-t-2 border-b-2 border-blue-500 mb-4"></div>
-              <p className="text-gray-600">Generating AI Summary...</p>
+          {streamingMarkdown ? (
+            /* Partial summary as it streams in */
+            <div className="flex-1 overflow-y-auto min-h-0 px-6 pb-6">
+              <p className="text-sm text-gray-500 mb-2">{getSummaryStatusMessage(summaryStatus)}</p>
+              <div className="text-sm text-gray-800 whitespace-pre-wrap">{streamingMarkdown}</div>
             </div>
-          </div>
+          ) : (
+            /* Loading spinner */
+            <div className="flex items-center justify-center flex-1">
+              <div className="text-center">
+                <div className="inline-block animate-spin rounded-full h-12 w-12 border-t-2 border-b-2 border-blue-500 mb-4"></div>
+                <p className="text-gray-600">{getSummaryStatusMessage(summaryStatus) || 'Generating AI Summary...'}</p>
+              </div>
+            </div>
+          )}
         </div>
       ) : !aiSummary ? (
         <div className="flex flex-col h-full">
@@ -242,7 +255,18 @@ export function SummaryPanel({
               ) : null}
             </div>
           )}
-          <div className="p-6 w-full">
+          <div
+            className="p-6 w-full"
+            onClickCapture={(e) => {
+              // Timestamp citations (`#t-0215`) seek the recording instead of navigating
+              const link = (e.target as HTMLElement).closest('a');
+              const seconds = parseCitationHref(link?.getAttribute('href') ?? null);
+              if (seconds === null || !onCitationClick) return;
+              e.preventDefault();
+              e.stopPropagation();
+              onCitationClick(seconds);
+            }}
+          >
             <BlockNoteSummaryView
               ref={summaryRef}
               summaryData={aiSummary}
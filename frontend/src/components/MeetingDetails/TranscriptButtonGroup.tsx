@@ -2,7 +2,7 @@
 
 import { Button } from '@/components/ui/button';
 import { ButtonGroup } from '@/components/ui/button-group';
-import { Copy, FolderOpen } from 'lucide-react';
+import { Copy, FolderOpen, Users } from 'lucide-react';
 import Analytics from '@/lib/analytics';
 
 
@@ -10,13 +10,15 @@ interface TranscriptButtonGroupProps {
   transcriptCount: number;
   onCopyTranscript: () => void;
   onOpenMeetingFolder: () => Promise<void>;
+  onEditSpeakers?: () => void;
 }
 
 
 export function TranscriptButtonGroup({
   transcriptCount,
   onCopyTranscript,
-  onOpenMeetingFolder
+  onOpenMeetingFolder,
+  onEditSpeakers
 }: TranscriptButtonGroupProps) {
   return (
     <div className="flex items-center justify-center w-full gap-2">
@@ -48,6 +50,22 @@ export function TranscriptButtonGroup({
           <FolderOpen className="xl:mr-2" size={18} />
           <span className="hidden lg:inline">Recording</span>
         </Button>
+
+        {onEditSpeakers && (
+          <Button
+            size="sm"
+            variant="outline"
+            onClick={() => {
+              Analytics.trackButtonClick('edit_speakers', 'meeting_details');
+              onEditSpeakers();
+            }}
+            disabled={transcriptCount === 0}
+            title="Name Speakers"
+          >
+            <Users />
+            <span className="hidden lg:inline">Speakers</span>
+          </Button>
+        )}
       </ButtonGroup>
     </div>
   );
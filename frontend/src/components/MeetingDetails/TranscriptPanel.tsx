@@ -4,10 +4,13 @@ import { Transcript, TranscriptSegmentData } from '@/types';
 import { TranscriptView } from '@/components/TranscriptView';
 import { VirtualizedTranscriptView } from '@/components/VirtualizedTranscriptView';
 import { TranscriptButtonGroup } from './TranscriptButtonGroup';
-import { useMemo } from 'react';
+import { SpeakerNamesDialog } from './SpeakerNamesDialog';
+import { useMemo, useState } from 'react';
 
 interface TranscriptPanelProps {
   transcripts: Transcript[];
+  // Enables speaker naming when set
+  meetingId?: string;
   customPrompt: string;
   onPromptChange: (value: string) => void;
   onCopyTranscript: () => void;
@@ -27,6 +30,7 @@ interface TranscriptPanelProps {
 
 export function TranscriptPanel({
   transcripts,
+  meetingId,
   customPrompt,
   onPromptChange,
   onCopyTranscript,
@@ -41,6 +45,8 @@ export function TranscriptPanel({
   loadedCount,
   onLoadMore,
 }: TranscriptPanelProps) {
+  const [isSpeakerDialogOpen, setIsSpeakerDialogOpen] = useState(false);
+
   // Convert transcripts to segments if pagination is not used but we want virtualization
   const convertedSegments = useMemo(() => {
     if (usePagination && segments) {
@@ -64,9 +70,18 @@ export function TranscriptPanel({
           transcriptCount={usePagination ? (totalCount ?? convertedSegments.length) : (transcripts?.length || 0)}
           onCopyTranscript={onCopyTranscript}
           onOpenMeetingFolder={onOpenMeetingFolder}
+          onEditSpeakers={meetingId ? () => setIsSpeakerDialogOpen(true) : undefined}
         />
       </div>
 
+      {meetingId && (
+        <SpeakerNamesDialog
+          meetingId={meetingId}
+          open={isSpeakerDialogOpen}
+          onOpenChange={setIsSpeakerDialogOpen}
+        />
+      )}
+
       {/* Transcript content - use virtualized view for better performance */}
       <div className="flex-1 overflow-hidden pb-4">
         <VirtualizedTranscriptView
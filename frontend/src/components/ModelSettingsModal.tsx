@@ -22,7 +22,7 @@ import { cn, isOllamaNotInstalledError } from '@/lib/utils';
 import { toast } from 'sonner';
 
 export interface ModelConfig {
-  provider: 'ollama' | 'groq' | 'claude' | 'openai' | 'openrouter' | 'builtin-ai' | 'custom-openai';
+  provider: 'ollama' | 'groq' | 'claude' | 'gemini' | 'openai' | 'openrouter' | 'builtin-ai' | 'custom-openai';
   model: string;
   whisperModel: string;
   apiKey?: string | null;
@@ -51,6 +51,13 @@ interface OpenRouterModel {
   completion_price?: string;
 }
 
+interface GeminiModel {
+  id: string;
+  name: string;
+  input_token_limit?: number;
+  output_token_limit?: number;
+}
+
 interface ModelSettingsModalProps {
   modelConfig: ModelConfig;
   setModelConfig: (config: ModelConfig | ((prev: ModelConfig) => ModelConfig)) => void;
@@ -79,6 +86,8 @@ export function ModelSettingsModal({
   const [openRouterModels, setOpenRouterModels] = useState<OpenRouterModel[]>([]);
   const [openRouterError, setOpenRouterError] = useState<string>('');
   const [isLoadingOpenRouter, setIsLoadingOpenRouter] = useState<boolean>(false);
+  const [geminiModels, setGeminiModels] = useState<GeminiModel[]>([]);
+  const [isLoadingGemini, setIsLoadingGemini] = useState<boolean>(false);
   const [ollamaEndpoint, setOllamaEndpoint] = useState<string>(modelConfig.ollamaEndpoint || '');
   const [isLoadingOllama, setIsLoadingOllama] = useState<boolean>(false);
   const [lastFetchedEndpoint, setLastFetchedEndpoint] = useState<string>(modelConfig.ollamaEndpoint || '');
@@ -168,6 +177,9 @@ export function ModelSettingsModal({
   const modelOptions: Record<string, string[]> = {
     ollama: models.map((model) => model.name),
     claude: ['claude-sonnet-4-5-20250929', 'claude-haiku-4-5-20251001', 'claude-opus-4-5-20251101'],
+    gemini: geminiModels.length > 0
+      ? geminiModels.map((m) => m.id)
+      : ['gemini-2.5-flash', 'gemini-2.5-pro', 'gemini-2.5-flash-lite'],
     groq: ['llama-3.3-70b-versatile'],
     openai: [
       'gpt-5',
@@ -201,6 +213,7 @@ export function ModelSettingsModal({
 
   const requiresApiKey =
     modelConfig.provider === 'claude' ||
+    modelConfig.provider === 'gemini' ||
     modelConfig.provider === 'groq' ||
     modelConfig.provider === 'openai' ||
     modelConfig.provider === 'openrouter';
@@ -440,6 +453,19 @@ export function ModelSettingsModal({
     };
   }, [modelConfig.provider]); // Only depend on provider, NOT endpoint
 
+  // Gemini lists models per API key; falls back to a static list without one
+  const loadGeminiModels = async (key?: string) => {
+    try {
+      setIsLoadingGemini(true);
+      const data = (await invoke('api_get_gemini_models', { apiKey: key || null })) as GeminiModel[];
+      setGeminiModels(data);
+    } catch (err) {
+      console.warn('Failed to load Gemini models, using defaults:', err);
+    } finally {
+      setIsLoadingGemini(false);
+    }
+  };
+
   const loadOpenRouterModels = async () => {
     if (openRouterModels.length > 0) return; // Already loaded
 
@@ -689,6 +715,10 @@ export function ModelSettingsModal({
                   loadOpenRouterModels();
                 }
 
+                if (provider === 'gemini') {
+                  loadGeminiModels();
+                }
+
                 // Load Built-in AI models when selected
                 if (provider === 'builtin-ai') {
                   loadBuiltinAiModels();
@@ -718,6 +748,7 @@ export function ModelSettingsModal({
                 <SelectItem value="builtin-ai">Built-in AI (Offline, No API needed)</SelectItem>
                 <SelectItem value="claude">Claude</SelectItem>
                 <SelectItem value="custom-openai">Custom Server (OpenAI)</SelectItem>
+                <SelectItem value="gemini">Gemini</SelectItem>
                 <SelectItem value="groq">Groq</SelectItem>
                 <SelectItem value="ollama">Ollama</SelectItem>
                 <SelectItem value="openai">OpenAI</SelectItem>
@@ -736,7 +767,8 @@ export function ModelSettingsModal({
                   <SelectValue placeholder="Select model" />
                 </SelectTrigger>
                 <SelectContent className="max-h-48 overflow-y-auto">
-                  {modelConfig.provider === 'openrouter' && isLoadingOpenRouter ? (
+                  {(modelConfig.provider === 'openrouter' && isLoadingOpenRouter) ||
+                  (modelConfig.provider === 'gemini' && isLoadingGemini) ? (
                     <SelectItem value="loading" disabled>
                       Loading models...
                     </SelectItem>
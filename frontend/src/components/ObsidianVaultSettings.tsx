@@ -0,0 +1,91 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { open } from '@tauri-apps/plugin-dialog';
+import { toast } from 'sonner';
+import { FolderOpen, RefreshCw, X } from 'lucide-react';
+import { Button } from './ui/button';
+
+/**
+ * The Obsidian vault whose meeting notes are indexed. It feeds the list of
+ * today's meetings, vault-relative recording links and the vault's section
+ * labels.
+ */
+export function ObsidianVaultSettings() {
+  const [vaultRoot, setVaultRoot] = useState<string | null>(null);
+  const [isIndexing, setIsIndexing] = useState(false);
+
+  useEffect(() => {
+    invoke<string | null>('get_obsidian_vault')
+      .then(setVaultRoot)
+      .catch(error => console.error('Failed to load Obsidian vault:', error));
+  }, []);
+
+  const updateVault = async (path: string | null) => {
+    setIsIndexing(true);
+    try {
+      const count = await invoke<number>('set_obsidian_vault', { path });
+      setVaultRoot(path);
+      if (path) {
+        toast.success('Obsidian vault indexed', { description: `${count} meeting notes found` });
+      } else {
+        toast.success('Obsidian vault cleared');
+      }
+    } catch (error) {
+      console.error('Failed to set Obsidian vault:', error);
+      toast.error('Failed to set Obsidian vault', { description: String(error) });
+    } finally {
+      setIsIndexing(false);
+    }
+  };
+
+  const handleChoose = async () => {
+    const selected = await open({ directory: true, multiple: false });
+    if (selected && typeof selected === 'string') {
+      await updateVault(selected);
+    }
+  };
+
+  const handleRescan = async () => {
+    setIsIndexing(true);
+    try {
+      const count = await invoke<number>('rescan_obsidian_vault');
+      toast.success('Obsidian vault rescanned', { description: `${count} meeting notes found` });
+    } catch (error) {
+      toast.error('Failed to rescan Obsidian vault', { description: String(error) });
+    } finally {
+      setIsIndexing(false);
+    }
+  };
+
+  return (
+    <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+      <h3 className="text-lg font-semibold text-gray-900 mb-2">Obsidian Vault</h3>
+      <p className="text-sm text-gray-600 mb-4">
+        Meeting notes (tagged <code>meeting</code>) in this vault are listed under today&apos;s meetings, so recording can
+        start right from their prep note.
+      </p>
+
+      <div className="flex items-center gap-2">
+        <div className="flex-1 px-3 py-2 text-sm border rounded-md bg-gray-50 truncate" title={vaultRoot ?? undefined}>
+          {vaultRoot ?? <span className="text-gray-400">No vault selected</span>}
+        </div>
+        <Button variant="outline" size="sm" onClick={handleChoose} disabled={isIndexing}>
+          <FolderOpen className="w-4 h-4 mr-2" />
+          {vaultRoot ? 'Change' : 'Choose Folder'}
+        </Button>
+        {vaultRoot && (
+          <>
+            <Button variant="outline" size="sm" onClick={handleRescan} disabled={isIndexing} title="Rescan vault">
+              <RefreshCw className={`w-4 h-4 ${isIndexing ? 'animate-spin' : ''}`} />
+            </Button>
+            <Button variant="ghost" size="sm" onClick={() => updateVault(null)} disabled={isIndexing} title="Clear vault">
+              <X className="w-4 h-4" />
+            </Button>
+          </>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,160 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { toast } from 'sonner';
+import { Eye, EyeOff } from 'lucide-react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Label } from './ui/label';
+
+interface RemoteWhisperConfig {
+  endpoint: string;
+  apiKey: string | null;
+  authHeader: string | null;
+  timeoutSecs: number | null;
+  maxConcurrentRequests: number | null;
+}
+
+interface RemoteWhisperSettingsProps {
+  language: string;
+}
+
+const parseOptionalNumber = (value: string): number | null => {
+  const parsed = parseInt(value, 10);
+  return Number.isNaN(parsed) ? null : parsed;
+};
+
+/**
+ * Connection settings for a whisper.cpp server on the LAN
+ * (backend/whisper-custom/server). Audio is sent to its /inference endpoint.
+ */
+export function RemoteWhisperSettings({ language }: RemoteWhisperSettingsProps) {
+  const [endpoint, setEndpoint] = useState('');
+  const [apiKey, setApiKey] = useState('');
+  const [authHeader, setAuthHeader] = useState('');
+  const [timeoutSecs, setTimeoutSecs] = useState('');
+  const [maxConcurrent, setMaxConcurrent] = useState('');
+  const [showApiKey, setShowApiKey] = useState(false);
+  const [isTesting, setIsTesting] = useState(false);
+  const [isSaving, setIsSaving] = useState(false);
+
+  useEffect(() => {
+    const load = async () => {
+      try {
+        const config = await invoke<RemoteWhisperConfig | null>('api_get_remote_whisper_config');
+        if (config) {
+          setEndpoint(config.endpoint);
+          setApiKey(config.apiKey ?? '');
+          setAuthHeader(config.authHeader ?? '');
+          setTimeoutSecs(config.timeoutSecs?.toString() ?? '');
+          setMaxConcurrent(config.maxConcurrentRequests?.toString() ?? '');
+        }
+      } catch (error) {
+        console.error('Failed to load remote Whisper config:', error);
+      }
+    };
+    load();
+  }, []);
+
+  const connectionArgs = () => ({
+    endpoint,
+    apiKey: apiKey || null,
+    authHeader: authHeader || null,
+    timeoutSecs: parseOptionalNumber(timeoutSecs),
+  });
+
+  const handleTest = async () => {
+    setIsTesting(true);
+    try {
+      const result = await invoke<{ message: string }>('api_test_remote_whisper_connection', connectionArgs());
+      toast.success('Remote Whisper server reachable', { description: result.message });
+    } catch (error) {
+      toast.error('Connection failed', { description: String(error) });
+    } finally {
+      setIsTesting(false);
+    }
+  };
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    try {
+      await invoke('api_save_remote_whisper_config', {
+        ...connectionArgs(),
+        maxConcurrentRequests: parseOptionalNumber(maxConcurrent),
+      });
+      await invoke('api_save_transcript_config', {
+        provider: 'remoteWhisper',
+        model: 'whisper.cpp',
+        apiKey: null,
+        language,
+      });
+      toast.success('Remote Whisper server saved');
+    } catch (error) {
+      toast.error('Failed to save remote Whisper server', { description: String(error) });
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <div>
+        <Label className="block text-sm font-medium text-gray-700 mb-1">Server URL</Label>
+        <Input
+          className="mx-1"
+          value={endpoint}
+          placeholder="http://gpu-box.local:8178"
+          onChange={e => setEndpoint(e.target.value)}
+        />
+        <p className="text-xs text-gray-500 mt-1 mx-1">
+          A whisper.cpp server. <code>/inference</code> is added when the URL has no path.
+        </p>
+      </div>
+
+      <div className="flex gap-2">
+        <div className="flex-1">
+          <Label className="block text-sm font-medium text-gray-700 mb-1">Token (optional)</Label>
+          <div className="relative mx-1">
+            <Input
+              type={showApiKey ? 'text' : 'password'}
+              className="pr-10"
+              value={apiKey}
+              placeholder="For a proxy in front of the server"
+              onChange={e => setApiKey(e.target.value)}
+            />
+            <div className="absolute inset-y-0 right-0 pr-1 flex items-center">
+              <Button type="button" variant="ghost" size="icon" onClick={() => setShowApiKey(!showApiKey)}>
+                {showApiKey ? <EyeOff className="h-4 w-4" /> : <Eye className="h-4 w-4" />}
+              </Button>
+            </div>
+          </div>
+        </div>
+        <div className="w-48">
+          <Label className="block text-sm font-medium text-gray-700 mb-1">Auth header</Label>
+          <Input value={authHeader} placeholder="Authorization" onChange={e => setAuthHeader(e.target.value)} />
+        </div>
+      </div>
+
+      <div className="flex gap-2 mx-1">
+        <div className="w-40">
+          <Label className="block text-sm font-medium text-gray-700 mb-1">Timeout (s)</Label>
+          <Input type="number" min={1} value={timeoutSecs} placeholder="60" onChange={e => setTimeoutSecs(e.target.value)} />
+        </div>
+        <div className="w-40">
+          <Label className="block text-sm font-medium text-gray-700 mb-1">Parallel requests</Label>
+          <Input type="number" min={1} value={maxConcurrent} placeholder="2" onChange={e => setMaxConcurrent(e.target.value)} />
+        </div>
+      </div>
+
+      <div className="flex justify-end gap-2">
+        <Button variant="outline" size="sm" disabled={!endpoint.trim() || isTesting} onClick={handleTest}>
+          {isTesting ? 'Testing...' : 'Test connection'}
+        </Button>
+        <Button size="sm" disabled={!endpoint.trim() || isSaving} onClick={handleSave}>
+          Save
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,118 @@
+'use client';
+
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { toast } from 'sonner';
+import { Plus, Trash2 } from 'lucide-react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from './ui/select';
+import type { TemplateInfo } from './TemplateSettings';
+
+interface TemplateRule {
+  keyword: string;
+  template_id: string;
+}
+
+/**
+ * Rules picking the template of automatically generated summaries from the
+ * meeting note's `type:` or `tags`. A `summary_template:` field in the note
+ * always wins.
+ */
+export function TemplateRuleSettings() {
+  const [rules, setRules] = useState<TemplateRule[]>([]);
+  const [templates, setTemplates] = useState<TemplateInfo[]>([]);
+  const [isDirty, setIsDirty] = useState(false);
+
+  useEffect(() => {
+    const load = async () => {
+      try {
+        const [savedRules, availableTemplates] = await Promise.all([
+          invoke<TemplateRule[]>('api_get_template_rules'),
+          invoke<TemplateInfo[]>('api_list_templates'),
+        ]);
+        setRules(savedRules);
+        setTemplates(availableTemplates);
+      } catch (error) {
+        console.error('Failed to load template rules:', error);
+      }
+    };
+    load();
+  }, []);
+
+  const updateRule = (index: number, changes: Partial<TemplateRule>) => {
+    setRules(rules.map((rule, i) => (i === index ? { ...rule, ...changes } : rule)));
+    setIsDirty(true);
+  };
+
+  const handleSave = async () => {
+    try {
+      await invoke('api_save_template_rules', { rules });
+      setRules(rules.filter(rule => rule.keyword.trim() && rule.template_id));
+      setIsDirty(false);
+      toast.success('Template rules saved');
+    } catch (error) {
+      toast.error('Failed to save template rules', { description: String(error) });
+    }
+  };
+
+  return (
+    <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+      <h3 className="text-lg font-semibold text-gray-900 mb-2">Automatic Template Selection</h3>
+      <p className="text-sm text-gray-600 mb-4">
+        Auto-generated summaries use the template matching the meeting note&apos;s <code>type</code> or <code>tags</code>,
+        checked top to bottom. A <code>summary_template</code> field in the note always wins.
+      </p>
+
+      <div className="flex flex-col gap-2">
+        {rules.map((rule, index) => (
+          <div key={index} className="flex items-center gap-2">
+            <Input
+              className="w-48"
+              value={rule.keyword}
+              placeholder="Type or tag, e.g. standup"
+              onChange={e => updateRule(index, { keyword: e.target.value })}
+            />
+            <span className="text-sm text-gray-500">→</span>
+            <Select value={rule.template_id} onValueChange={templateId => updateRule(index, { template_id: templateId })}>
+              <SelectTrigger className="w-64">
+                <SelectValue placeholder="Template" />
+              </SelectTrigger>
+              <SelectContent>
+                {templates.map(template => (
+                  <SelectItem key={template.id} value={template.id}>{template.name}</SelectItem>
+                ))}
+                {rule.template_id && !templates.some(template => template.id === rule.template_id) && (
+                  <SelectItem value={rule.template_id}>{rule.template_id} (missing)</SelectItem>
+                )}
+              </SelectContent>
+            </Select>
+            <Button
+              variant="ghost"
+              size="icon"
+              onClick={() => {
+                setRules(rules.filter((_, i) => i !== index));
+                setIsDirty(true);
+              }}
+            >
+              <Trash2 />
+            </Button>
+          </div>
+        ))}
+      </div>
+
+      <div className="flex justify-between mt-4">
+        <Button
+          variant="outline"
+          size="sm"
+          onClick={() => setRules([...rules, { keyword: '', template_id: '' }])}
+        >
+          <Plus /> Add rule
+        </Button>
+        <Button size="sm" disabled={!isDirty} onClick={handleSave}>
+          Save
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,500 @@
+'use client';
+
+import { useState, useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { open, save } from '@tauri-apps/plugin-dialog';
+import { toast } from 'sonner';
+import { ArrowDown, ArrowUp, Copy, Download, History, Plus, Trash2, Upload } from 'lucide-react';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Textarea } from './ui/textarea';
+import { Badge } from './ui/badge';
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from './ui/select';
+import { Dialog, DialogContent, DialogDescription, DialogFooter, DialogHeader, DialogTitle } from './ui/dialog';
+
+export type TemplateSource = 'custom' | 'bundled' | 'built_in';
+
+export interface TemplateSection {
+  title: string;
+  instruction: string;
+  format: string;
+  columns?: string[];
+  item_format?: string;
+  example_item_format?: string;
+}
+
+export interface Template {
+  name: string;
+  description: string;
+  sections: TemplateSection[];
+  version?: number;
+}
+
+export interface TemplateInfo {
+  id: string;
+  name: string;
+  description: string;
+  source: TemplateSource | null;
+  has_default: boolean;
+}
+
+interface TemplateDocument {
+  id: string;
+  source: TemplateSource | null;
+  has_default: boolean;
+  template: Template;
+}
+
+interface TemplateVersion {
+  version: number;
+  name: string;
+  saved_at: string | null;
+}
+
+const SECTION_FORMATS = [
+  { value: 'paragraph', label: 'Paragraph' },
+  { value: 'list', label: 'Bullet list' },
+  { value: 'string', label: 'Short text' },
+  { value: 'table', label: 'Table' },
+  { value: 'checklist', label: 'Checklist' },
+  { value: 'key_value', label: 'Key-value' },
+];
+
+const SOURCE_LABELS: Record<TemplateSource, string> = {
+  custom: 'Custom',
+  bundled: 'Bundled',
+  built_in: 'Built-in',
+};
+
+/** Template id derived from a display name: `Client Kickoff` -> `client_kickoff` */
+function toTemplateId(name: string): string {
+  return name.toLowerCase().replace(/[^a-z0-9]+/g, '_').replace(/^_+|_+$/g, '').slice(0, 64);
+}
+
+/** Keep only characters allowed in template ids while typing */
+const sanitizeTemplateId = (id: string) => id.toLowerCase().replace(/[^a-z0-9_-]/g, '_').slice(0, 64);
+
+const emptySection = (): TemplateSection => ({ title: '', instruction: '', format: 'list' });
+
+/** Pending "new template" or "duplicate" dialog */
+type CreateRequest = { sourceId: string | null; name: string; id: string };
+
+export function TemplateSettings() {
+  const [templates, setTemplates] = useState<TemplateInfo[]>([]);
+  const [selected, setSelected] = useState<TemplateDocument | null>(null);
+  const [draft, setDraft] = useState<Template | null>(null);
+  const [versions, setVersions] = useState<TemplateVersion[]>([]);
+  const [showVersions, setShowVersions] = useState(false);
+  const [createRequest, setCreateRequest] = useState<CreateRequest | null>(null);
+  const [isSaving, setIsSaving] = useState(false);
+
+  const isCustom = selected?.source === 'custom';
+  const isDirty = !!selected && !!draft && JSON.stringify(selected.template) !== JSON.stringify(draft);
+
+  const fetchTemplates = useCallback(async () => {
+    try {
+      setTemplates(await invoke<TemplateInfo[]>('api_list_templates'));
+    } catch (error) {
+      console.error('Failed to fetch templates:', error);
+      toast.error('Failed to load templates');
+    }
+  }, []);
+
+  const selectTemplate = useCallback(async (templateId: string) => {
+    try {
+      const document = await invoke<TemplateDocument>('api_get_template', { templateId });
+      setSelected(document);
+      setDraft(document.template);
+      setShowVersions(false);
+      setVersions(document.source === 'custom'
+        ? await invoke<TemplateVersion[]>('api_list_template_versions', { templateId })
+        : []);
+    } catch (error) {
+      console.error('Failed to load template:', error);
+      toast.error('Failed to load template', { description: String(error) });
+    }
+  }, []);
+
+  // Show a saved, imported or restored template and refresh the list
+  const showDocument = useCallback(async (document: TemplateDocument) => {
+    await fetchTemplates();
+    await selectTemplate(document.id);
+  }, [fetchTemplates, selectTemplate]);
+
+  useEffect(() => {
+    fetchTemplates();
+  }, [fetchTemplates]);
+
+  const updateSection = (index: number, changes: Partial<TemplateSection>) => {
+    if (!draft) return;
+    const sections = draft.sections.map((section, i) => (i === index ? { ...section, ...changes } : section));
+    setDraft({ ...draft, sections });
+  };
+
+  const moveSection = (index: number, offset: number) => {
+    if (!draft) return;
+    const target = index + offset;
+    if (target < 0 || target >= draft.sections.length) return;
+    const sections = [...draft.sections];
+    [sections[index], sections[target]] = [sections[target], sections[index]];
+    setDraft({ ...draft, sections });
+  };
+
+  const removeSection = (index: number) => {
+    if (!draft) return;
+    setDraft({ ...draft, sections: draft.sections.filter((_, i) => i !== index) });
+  };
+
+  const handleSave = async () => {
+    if (!selected || !draft) return;
+    setIsSaving(true);
+    try {
+      const sections = draft.sections.map(section => (
+        section.columns
+          ? { ...section, columns: section.columns.map(column => column.trim()).filter(Boolean) }
+          : section
+      ));
+      const document = await invoke<TemplateDocument>('api_save_template', {
+        templateId: selected.id,
+        template: { ...draft, sections },
+      });
+      toast.success(`Saved "${document.template.name}"`, { description: `Version ${document.template.version}` });
+      await showDocument(document);
+    } catch (error) {
+      toast.error('Template is not valid', { description: String(error) });
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleCreate = async () => {
+    if (!createRequest) return;
+    const { sourceId, name, id } = createRequest;
+    try {
+      const document = sourceId
+        ? await invoke<TemplateDocument>('api_duplicate_template', { sourceId, newId: id, name })
+        : await invoke<TemplateDocument>('api_save_template', {
+            templateId: id,
+            template: {
+              name,
+              description: name,
+              sections: [{ title: 'Summary', instruction: 'Summarize the meeting', format: 'paragraph' }],
+            },
+          });
+      setCreateRequest(null);
+      toast.success(`Created "${document.template.name}"`);
+      await showDocument(document);
+    } catch (error) {
+      toast.error('Failed to create template', { description: String(error) });
+    }
+  };
+
+  const handleDelete = async () => {
+    if (!selected) return;
+    const action = selected.has_default ? 'Reset' : 'Delete';
+    if (!window.confirm(`${action} "${selected.template.name}"? Earlier versions stay restorable.`)) return;
+    try {
+      await invoke('api_delete_template', { templateId: selected.id });
+      toast.success(selected.has_default ? 'Template reset to default' : 'Template deleted');
+      await fetchTemplates();
+      if (selected.has_default) {
+        await selectTemplate(selected.id);
+      } else {
+        setSelected(null);
+        setDraft(null);
+      }
+    } catch (error) {
+      toast.error('Failed to delete template', { description: String(error) });
+    }
+  };
+
+  const handleRestore = async (version: number) => {
+    if (!selected) return;
+    try {
+      const document = await invoke<TemplateDocument>('api_restore_template_version', {
+        templateId: selected.id,
+        version,
+      });
+      toast.success(`Restored version ${version}`);
+      await showDocument(document);
+    } catch (error) {
+      toast.error('Failed to restore version', { description: String(error) });
+    }
+  };
+
+  const handleImport = async () => {
+    try {
+      const path = await open({
+        filters: [{ name: 'Template', extensions: ['json'] }],
+        multiple: false,
+        directory: false,
+      });
+      if (!path || typeof path !== 'string') return;
+
+      let document: TemplateDocument;
+      try {
+        document = await invoke<TemplateDocument>('api_import_template', { path });
+      } catch (error) {
+        if (!String(error).includes('already exists') || !window.confirm(`${error}. Replace it?`)) throw error;
+        document = await invoke<TemplateDocument>('api_import_template', { path, overwrite: true });
+      }
+      toast.success(`Imported "${document.template.name}"`);
+      await showDocument(document);
+    } catch (error) {
+      toast.error('Failed to import template', { description: String(error) });
+    }
+  };
+
+  const handleExport = async () => {
+    if (!selected) return;
+    try {
+      const path = await save({
+        defaultPath: `${selected.id}.json`,
+        filters: [{ name: 'Template', extensions: ['json'] }],
+      });
+      if (!path) return;
+      await invoke('api_export_template', { templateId: selected.id, path });
+      toast.success('Template exported');
+    } catch (error) {
+      toast.error('Failed to export template', { description: String(error) });
+    }
+  };
+
+  return (
+    <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+      <div className="flex items-start justify-between mb-4">
+        <div>
+          <h3 className="text-lg font-semibold text-gray-900 mb-2">Summary Templates</h3>
+          <p className="text-sm text-gray-600">
+            Choose which sections summaries contain. Duplicate a built-in template to customize it.
+          </p>
+        </div>
+        <div className="flex gap-2">
+          <Button variant="outline" size="sm" onClick={handleImport}>
+            <Upload /> Import
+          </Button>
+          <Button size="sm" onClick={() => setCreateRequest({ sourceId: null, name: '', id: '' })}>
+            <Plus /> New
+          </Button>
+        </div>
+      </div>
+
+      <div className="grid grid-cols-[220px_1fr] gap-6">
+        <ul className="flex flex-col gap-1">
+          {templates.map(template => (
+            <li key={template.id}>
+              <button
+                onClick={() => selectTemplate(template.id)}
+                className={`w-full text-left rounded-md px-3 py-2 text-sm hover:bg-gray-100 ${
+                  selected?.id === template.id ? 'bg-gray-100 font-medium' : ''
+                }`}
+              >
+                <div className="truncate">{template.name}</div>
+                {template.source && (
+                  <Badge variant={template.source === 'custom' ? 'default' : 'secondary'} className="mt-1">
+                    {template.source === 'custom' && template.has_default ? 'Customized' : SOURCE_LABELS[template.source]}
+                  </Badge>
+                )}
+              </button>
+            </li>
+          ))}
+        </ul>
+
+        {selected && draft ? (
+          <div className="flex flex-col gap-4">
+            <div className="flex items-center justify-between">
+              <div className="text-xs text-gray-500">
+                <code>{selected.id}</code>
+                {isCustom && draft.version ? ` · version ${draft.version}` : ''}
+              </div>
+              <div className="flex gap-2">
+                <Button
+                  variant="outline"
+                  size="sm"
+                  onClick={() => setCreateRequest({
+                    sourceId: selected.id,
+                    name: `${draft.name} (copy)`,
+                    id: toTemplateId(`${selected.id} copy`),
+                  })}
+                >
+                  <Copy /> Duplicate
+                </Button>
+                <Button variant="outline" size="sm" onClick={handleExport}>
+                  <Download /> Export
+                </Button>
+                {isCustom && (
+                  <>
+                    <Button
+                      variant="outline"
+                      size="sm"
+                      disabled={versions.length === 0}
+                      onClick={() => setShowVersions(!showVersions)}
+                    >
+                      <History /> History
+                    </Button>
+                    <Button variant="outline" size="sm" onClick={handleDelete}>
+                      <Trash2 /> {selected.has_default ? 'Reset' : 'Delete'}
+                    </Button>
+                  </>
+                )}
+              </div>
+            </div>
+
+            {!isCustom && (
+              <p className="text-sm text-gray-600 rounded-md bg-gray-50 p-3">
+                Shipped templates are read-only. Duplicate this template to edit a copy.
+              </p>
+            )}
+
+            {showVersions && (
+              <ul className="rounded-md border border-gray-200 divide-y text-sm">
+                {versions.map(version => (
+                  <li key={version.version} className="flex items-center justify-between px-3 py-2">
+                    <span>
+                      Version {version.version} · {version.name}
+                      {version.saved_at && (
+                        <span className="text-gray-500"> · replaced {new Date(version.saved_at).toLocaleString()}</span>
+                      )}
+                    </span>
+                    <Button variant="ghost" size="sm" onClick={() => handleRestore(version.version)}>
+                      Restore
+                    </Button>
+                  </li>
+                ))}
+              </ul>
+            )}
+
+            <fieldset disabled={!isCustom} className="flex flex-col gap-4">
+              <Input
+                value={draft.name}
+                placeholder="Template name"
+                onChange={e => setDraft({ ...draft, name: e.target.value })}
+              />
+              <Textarea
+                value={draft.description}
+                placeholder="What is this template for?"
+                rows={2}
+                onChange={e => setDraft({ ...draft, description: e.target.value })}
+              />
+
+              {draft.sections.map((section, index) => (
+                <div key={index} className="rounded-md border border-gray-200 p-4 flex flex-col gap-3">
+                  <div className="flex gap-2">
+                    <Input
+                      value={section.title}
+                      placeholder="Section title"
+                      onChange={e => updateSection(index, { title: e.target.value })}
+                    />
+                    <Select
+                      value={section.format}
+                      disabled={!isCustom}
+                      onValueChange={format => updateSection(index, {
+                        format,
+                        columns: format === 'table' ? section.columns ?? ['Item', 'Owner'] : undefined,
+                      })}
+                    >
+                      <SelectTrigger className="w-40">
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        {SECTION_FORMATS.map(format => (
+                          <SelectItem key={format.value} value={format.value}>{format.label}</SelectItem>
+                        ))}
+                      </SelectContent>
+                    </Select>
+                    <Button variant="ghost" size="icon" onClick={() => moveSection(index, -1)} disabled={index === 0}>
+                      <ArrowUp />
+                    </Button>
+                    <Button
+                      variant="ghost"
+                      size="icon"
+                      onClick={() => moveSection(index, 1)}
+                      disabled={index === draft.sections.length - 1}
+                    >
+                      <ArrowDown />
+                    </Button>
+                    <Button variant="ghost" size="icon" onClick={() => removeSection(index)}>
+                      <Trash2 />
+                    </Button>
+                  </div>
+                  <Textarea
+                    value={section.instruction}
+                    placeholder="What should the summary put in this section?"
+                    rows={2}
+                    onChange={e => updateSection(index, { instruction: e.target.value })}
+                  />
+                  {section.format === 'table' && (
+                    <Input
+                      value={(section.columns ?? []).join(', ')}
+                      placeholder="Columns, comma separated (e.g. Task, Owner, Due)"
+                      onChange={e => updateSection(index, {
+                        columns: e.target.value.split(',').map(column => column.trimStart()),
+                      })}
+                    />
+                  )}
+                </div>
+              ))}
+            </fieldset>
+
+            {isCustom && (
+              <div className="flex justify-between">
+                <Button
+                  variant="outline"
+                  size="sm"
+                  onClick={() => setDraft({ ...draft, sections: [...draft.sections, emptySection()] })}
+                >
+                  <Plus /> Add section
+                </Button>
+                <div className="flex gap-2">
+                  <Button variant="ghost" size="sm" disabled={!isDirty} onClick={() => setDraft(selected.template)}>
+                    Discard
+                  </Button>
+                  <Button size="sm" disabled={!isDirty || isSaving} onClick={handleSave}>
+                    {isSaving ? 'Saving...' : 'Save'}
+                  </Button>
+                </div>
+              </div>
+            )}
+          </div>
+        ) : (
+          <p className="text-sm text-gray-500">Select a template to view or edit it.</p>
+        )}
+      </div>
+
+      <Dialog open={!!createRequest} onOpenChange={isOpen => !isOpen && setCreateRequest(null)}>
+        <DialogContent>
+          <DialogHeader>
+            <DialogTitle>{createRequest?.sourceId ? 'Duplicate template' : 'New template'}</DialogTitle>
+            <DialogDescription>
+              The id names the template file and cannot be changed later.
+            </DialogDescription>
+          </DialogHeader>
+          {createRequest && (
+            <div className="flex flex-col gap-3">
+              <Input
+                value={createRequest.name}
+                placeholder="Template name"
+                onChange={e => setCreateRequest({
+                  ...createRequest,
+                  name: e.target.value,
+                  id: toTemplateId(e.target.value),
+                })}
+              />
+              <Input
+                value={createRequest.id}
+                placeholder="template_id"
+                onChange={e => setCreateRequest({ ...createRequest, id: sanitizeTemplateId(e.target.value) })}
+              />
+            </div>
+          )}
+          <DialogFooter>
+            <Button variant="outline" onClick={() => setCreateRequest(null)}>Cancel</Button>
+            <Button disabled={!createRequest?.name.trim() || !createRequest?.id} onClick={handleCreate}>
+              Create
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+    </div>
+  );
+}
@@ -7,10 +7,13 @@ import { Label } from './ui/label';
 import { Eye, EyeOff, Lock, Unlock } from 'lucide-react';
 import { ModelManager } from './WhisperModelManager';
 import { ParakeetModelManager } from './ParakeetModelManager';
+import { RemoteWhisperSettings } from './RemoteWhisperSettings';
+import { DeepgramFailoverSettings } from './DeepgramFailoverSettings';
+import { DiarizationModelManager } from './DiarizationModelManager';
 
 
 export interface TranscriptModelProps {
-    provider: 'localWhisper' | 'parakeet' | 'deepgram' | 'elevenLabs' | 'groq' | 'openai';
+    provider: 'localWhisper' | 'parakeet' | 'deepgram' | 'elevenLabs' | 'groq' | 'openai' | 'remoteWhisper';
     model: string;
     apiKey?: string | null;
     language?: string | null;
@@ -82,7 +85,7 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
     const [language, setLanguage] = useState<string>(transcriptModelConfig.language || 'en');
 
     useEffect(() => {
-        if (transcriptModelConfig.provider === 'localWhisper' || transcriptModelConfig.provider === 'parakeet') {
+        if (transcriptModelConfig.provider === 'localWhisper' || transcriptModelConfig.provider === 'parakeet' || transcriptModelConfig.provider === 'remoteWhisper') {
             setApiKey(null);
         }
     }, [transcriptModelConfig.provider]);
@@ -126,6 +129,7 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
     const modelOptions = {
         localWhisper: [selectedWhisperModel],
         parakeet: [selectedParakeetModel],
+        remoteWhisper: ['whisper.cpp'],
         deepgram: [
             // Nova-2 models - support streaming diarization (recommended)
             'nova-2-meeting',      // Optimized for meetings (recommended)
@@ -140,9 +144,13 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
             'nova-3',              // Highest accuracy (no diarization)
             'nova-3-medical',      // Medical (no diarization)
         ],
-        elevenLabs: ['eleven_multilingual_v2'],
-        groq: ['llama-3.3-70b-versatile'],
-        openai: ['gpt-4o'],
+        elevenLabs: ['scribe_v1'],
+        groq: ['whisper-large-v3-turbo', 'whisper-large-v3'],
+        openai: [
+            'gpt-4o-transcribe',         // Highest accuracy
+            'gpt-4o-mini-transcribe',    // Faster, cheaper
+            'whisper-1',
+        ],
     };
     const requiresApiKey = transcriptModelConfig.provider === 'deepgram' || transcriptModelConfig.provider === 'elevenLabs' || transcriptModelConfig.provider === 'openai' || transcriptModelConfig.provider === 'groq';
 
@@ -199,7 +207,7 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
                                     const provider = value as TranscriptModelProps['provider'];
                                     const newModel = provider === 'localWhisper' ? selectedWhisperModel : modelOptions[provider][0];
                                     setTranscriptModelConfig({ ...transcriptModelConfig, provider, model: newModel });
-                                    if (provider !== 'localWhisper') {
+                                    if (provider !== 'localWhisper' && provider !== 'remoteWhisper') {
                                         fetchApiKey(provider);
                                     }
                                 }}
@@ -210,14 +218,15 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
                                 <SelectContent>
                                     <SelectItem value="parakeet">⚡ Parakeet (Recommended - Real-time / Accurate)</SelectItem>
                                     <SelectItem value="localWhisper">🏠 Local Whisper (High Accuracy)</SelectItem>
+                                    <SelectItem value="remoteWhisper">🖧 Whisper Server (LAN - whisper.cpp)</SelectItem>
                                     <SelectItem value="deepgram">☁️ Deepgram (Cloud - Speaker Diarization)</SelectItem>
-                                    {/* <SelectItem value="elevenLabs">☁️ ElevenLabs</SelectItem>
-                                    <SelectItem value="groq">☁️ Groq</SelectItem>
-                                    <SelectItem value="openai">☁️ OpenAI</SelectItem> */}
+                                    <SelectItem value="openai">☁️ OpenAI (Cloud - Whisper / GPT-4o)</SelectItem>
+                                    <SelectItem value="groq">☁️ Groq (Cloud - Fast Whisper)</SelectItem>
+                                    <SelectItem value="elevenLabs">☁️ ElevenLabs Scribe (Cloud)</SelectItem>
                                 </SelectContent>
                             </Select>
 
-                            {transcriptModelConfig.provider !== 'localWhisper' && transcriptModelConfig.provider !== 'parakeet' && (
+                            {transcriptModelConfig.provider !== 'localWhisper' && transcriptModelConfig.provider !== 'parakeet' && transcriptModelConfig.provider !== 'remoteWhisper' && (
                                 <Select
                                     value={transcriptModelConfig.model}
                                     onValueChange={(value) => {
@@ -259,6 +268,18 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
                         </div>
                     )}
 
+                    {transcriptModelConfig.provider === 'remoteWhisper' && (
+                        <div className="mt-6">
+                            <RemoteWhisperSettings language={language} />
+                        </div>
+                    )}
+
+                    {transcriptModelConfig.provider !== 'deepgram' && (
+                        <div className="mt-4">
+                            <DiarizationModelManager />
+                        </div>
+                    )}
+
                     {/* Language selector for Deepgram */}
                     {transcriptModelConfig.provider === 'deepgram' && (
                         <div>
@@ -291,6 +312,12 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
                         </div>
                     )}
 
+                    {transcriptModelConfig.provider === 'deepgram' && (
+                        <div className="mt-6">
+                            <DeepgramFailoverSettings />
+                        </div>
+                    )}
+
                     {requiresApiKey && (
                         <div>
                             <Label className="block text-sm font-medium text-gray-700 mb-1">
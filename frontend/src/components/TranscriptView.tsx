@@ -302,6 +302,9 @@ export const TranscriptView: React.FC<TranscriptViewProps> = ({ transcripts, isR
                       )}
                     </span>
                   )}
+                  {transcript.provider && (
+                    <span className="text-xs text-gray-400 ml-1">via {transcript.provider}</span>
+                  )}
                 </TooltipContent>
               </Tooltip>
               <div className="flex-1">
@@ -296,6 +296,25 @@ export function TranscriptProvider({ children }: { children: ReactNode }) {
             buffer_size_before: transcriptBuffer.size
           });
 
+          // Revised text for an earlier segment (Deepgram re-ran a failover gap)
+          if (update.revision) {
+            const revise = (t: Transcript): Transcript => ({
+              ...t,
+              text: update.text,
+              confidence: update.confidence,
+              is_partial: false,
+              provider: update.provider,
+              words: update.words,
+            });
+            const buffered = transcriptBuffer.get(update.sequence_id);
+            if (buffered) {
+              transcriptBuffer.set(update.sequence_id, revise(buffered));
+            }
+            setTranscripts(prev => prev.map(t => t.sequence_id === update.sequence_id ? revise(t) : t));
+            console.log(`🔁 MAIN LISTENER: Revised transcript ${update.sequence_id} with ${update.provider}`);
+            return;
+          }
+
           // Check for duplicate sequence_id before processing
           if (transcriptBuffer.has(update.sequence_id)) {
             console.log('🚫 MAIN LISTENER: Duplicate sequence_id, skipping buffer:', update.sequence_id);
@@ -315,6 +334,8 @@ export function TranscriptProvider({ children }: { children: ReactNode }) {
             audio_start_time: update.audio_start_time,
             audio_end_time: update.audio_end_time,
             duration: update.duration,
+            provider: update.provider,
+            words: update.words,
           };
 
           // Add to buffer
@@ -383,6 +404,7 @@ export function TranscriptProvider({ children }: { children: ReactNode }) {
             audio_start_time: segment.audio_start_time,
             audio_end_time: segment.audio_end_time,
             duration: segment.duration,
+            provider: segment.provider,
           }));
 
           setTranscripts(formattedTranscripts);
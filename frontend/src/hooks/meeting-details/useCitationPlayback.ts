@@ -0,0 +1,64 @@
+import { useCallback, useEffect, useRef, useState } from 'react';
+import { toast } from 'sonner';
+import { useAudioPlayer } from '@/hooks/useAudioPlayer';
+
+/**
+ * Summary citations link to transcript segments as `#t-0215` (02:15), with a
+ * `-2` suffix for repeated segments within the same second.
+ * @returns The cited position in seconds, or null for other links
+ */
+export function parseCitationHref(href: string | null): number | null {
+  const match = href?.match(/#t-(\d+)(\d{2})(?:-\d+)?$/);
+  if (!match) return null;
+  return parseInt(match[1], 10) * 60 + parseInt(match[2], 10);
+}
+
+interface UseCitationPlaybackProps {
+  meeting: any;
+}
+
+/**
+ * Plays the meeting recording from a cited position.
+ * The recording is only loaded on the first citation click.
+ */
+export function useCitationPlayback({ meeting }: UseCitationPlaybackProps) {
+  const [audioPath, setAudioPath] = useState<string | null>(null);
+  const pendingSeekRef = useRef<number | null>(null);
+  const { duration, error, play, seek } = useAudioPlayer(audioPath);
+
+  const playFrom = useCallback(async (seconds: number) => {
+    await seek(seconds);
+    await play();
+  }, [seek, play]);
+
+  // Start pending playback once the recording is decoded
+  useEffect(() => {
+    if (duration > 0 && pendingSeekRef.current !== null) {
+      const seconds = pendingSeekRef.current;
+      pendingSeekRef.current = null;
+      playFrom(seconds);
+    }
+  }, [duration, playFrom]);
+
+  useEffect(() => {
+    if (error && pendingSeekRef.current !== null) {
+      pendingSeekRef.current = null;
+      toast.error('Could not play the recording', { description: error });
+    }
+  }, [error]);
+
+  const handleCitationClick = useCallback((seconds: number) => {
+    if (!meeting.folder_path) {
+      toast.error('No recording found for this meeting');
+      return;
+    }
+    if (!audioPath || duration === 0) {
+      pendingSeekRef.current = seconds;
+      setAudioPath(`${meeting.folder_path.replace(/[/\\]$/, '')}/audio.mp4`);
+      return;
+    }
+    playFrom(seconds);
+  }, [meeting.folder_path, audioPath, duration, playFrom]);
+
+  return { handleCitationClick };
+}
@@ -102,12 +102,14 @@ export function useMeetingData({ meeting, summaryData, onMeetingUpdated }: UseMe
     try {
       let formattedSummary: any;
       let markdownContent: string | undefined;
+      let actionItems: unknown[] | undefined;
 
       // Check if it's the new BlockNote format
       if ('markdown' in summary || 'summary_json' in summary) {
         console.log('📄 Saving new format (markdown/blocknote)');
         formattedSummary = summary;
         markdownContent = (summary as { markdown?: string }).markdown;
+        actionItems = (summary as { action_items?: unknown[] }).action_items;
       } else {
         console.log('📄 Saving legacy format');
         formattedSummary = {
@@ -143,6 +145,7 @@ export function useMeetingData({ meeting, summaryData, onMeetingUpdated }: UseMe
           await invokeTauri('update_meeting_summary', {
             path: folderPath,
             summary: markdownContent,
+            actionItems,
           });
 
           console.log('✅ Save meeting summary to markdown file success');
@@ -1,8 +1,9 @@
-import { useState, useCallback } from 'react';
+import { useState, useCallback, useEffect } from 'react';
 import { Transcript, Summary } from '@/types';
 import { ModelConfig } from '@/components/ModelSettingsModal';
 import { CurrentMeeting, useSidebar } from '@/components/Sidebar/SidebarProvider';
 import { invoke as invokeTauri } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { toast } from 'sonner';
 import Analytics from '@/lib/analytics';
 import { isOllamaNotInstalledError } from '@/lib/utils';
@@ -10,6 +11,14 @@ import { BuiltInModelInfo } from '@/lib/builtin-ai';
 
 type SummaryStatus = 'idle' | 'processing' | 'summarizing' | 'regenerating' | 'completed' | 'error';
 
+// Payload of the backend's `summary-progress` event
+export interface SummaryProgress {
+  meeting_id: string;
+  stage: 'chunk' | 'combining' | 'writing';
+  chunk?: number;
+  total_chunks?: number;
+}
+
 interface UseSummaryGenerationProps {
   meeting: any;
   transcripts: Transcript[];
@@ -36,11 +45,61 @@ export function useSummaryGeneration({
   const [summaryStatus, setSummaryStatus] = useState<SummaryStatus>('idle');
   const [summaryError, setSummaryError] = useState<string | null>(null);
   const [originalTranscript, setOriginalTranscript] = useState<string>('');
+  const [summaryProgress, setSummaryProgress] = useState<SummaryProgress | null>(null);
+  const [streamingMarkdown, setStreamingMarkdown] = useState<string>('');
 
   const { startSummaryPolling, stopSummaryPolling } = useSidebar();
 
+  // Follow progress and partial markdown of this meeting's running generation
+  useEffect(() => {
+    const unlisteners: Array<() => void> = [];
+    let cancelled = false;
+
+    const setupListeners = async () => {
+      const progressUnlisten = await listen<SummaryProgress>('summary-progress', (event) => {
+        if (event.payload.meeting_id === meeting.id) {
+          setSummaryProgress(event.payload);
+        }
+      });
+      const streamUnlisten = await listen<{ meeting_id: string; markdown: string }>('summary-stream', (event) => {
+        if (event.payload.meeting_id === meeting.id) {
+          setStreamingMarkdown(event.payload.markdown);
+        }
+      });
+      unlisteners.push(progressUnlisten, streamUnlisten);
+      if (cancelled) {
+        unlisteners.forEach((unlisten) => unlisten());
+      }
+    };
+
+    setupListeners();
+
+    return () => {
+      cancelled = true;
+      unlisteners.forEach((unlisten) => unlisten());
+    };
+  }, [meeting.id]);
+
+  // Progress only describes a running generation
+  useEffect(() => {
+    if (summaryStatus !== 'processing' && summaryStatus !== 'summarizing' && summaryStatus !== 'regenerating') {
+      setSummaryProgress(null);
+      setStreamingMarkdown('');
+    }
+  }, [summaryStatus]);
+
   // Helper to get status message
   const getSummaryStatusMessage = useCallback((status: SummaryStatus) => {
+    if (summaryProgress && (status === 'processing' || status === 'regenerating')) {
+      switch (summaryProgress.stage) {
+        case 'chunk':
+          return `Summarizing part ${summaryProgress.chunk} of ${summaryProgress.total_chunks}...`;
+        case 'combining':
+          return `Combining ${summaryProgress.total_chunks} partial summaries...`;
+        case 'writing':
+          return 'Writing summary...';
+      }
+    }
     switch (status) {
       case 'processing':
         return 'Processing transcript...';
@@ -55,20 +114,25 @@ export function useSummaryGeneration({
       default:
         return '';
     }
-  }, []);
+  }, [summaryProgress]);
 
   // Unified summary processing logic
   const processSummary = useCallback(async ({
     transcriptText,
     customPrompt = '',
     isRegeneration = false,
+    autoTemplate = false,
   }: {
     transcriptText: string;
     customPrompt?: string;
     isRegeneration?: boolean;
+    // Let the meeting note's frontmatter pick the template
+    autoTemplate?: boolean;
   }) => {
     setSummaryStatus(isRegeneration ? 'regenerating' : 'processing');
     setSummaryError(null);
+    setSummaryProgress(null);
+    setStreamingMarkdown('');
 
     try {
       if (!transcriptText.trim()) {
@@ -113,6 +177,9 @@ export function useSummaryGeneration({
         overlap: 1000,
         customPrompt: customPrompt,
         templateId: selectedTemplate,
+        // Prep note open while recording; the backend falls back to the meeting folder's note
+        notePath: localStorage.getItem(`meeting_prep_note:${meeting.id}`),
+        autoTemplate,
       }) as any;
 
       const process_id = result.process_id;
@@ -233,9 +300,28 @@ export function useSummaryGeneration({
             setAiSummary({ markdown: pollingResult.data.markdown } as any);
             setSummaryStatus('completed');
 
+            // Add the extracted action items to the prep note as Obsidian Tasks checkboxes
+            const prepNotePath = localStorage.getItem(`meeting_prep_note:${meeting.id}`);
+            const actionItems = pollingResult.data.action_items;
+            if (prepNotePath && Array.isArray(actionItems) && actionItems.length > 0) {
+              try {
+                const added = await invokeTauri('merge_meeting_action_items', {
+                  path: prepNotePath,
+                  actionItems,
+                }) as number;
+                console.log(`Merged ${added} new action items into the prep note`);
+              } catch (mergeError) {
+                // The summary is saved either way; the note just misses the tasks
+                console.warn('Failed to merge action items into the prep note:', mergeError);
+              }
+            }
+
             // Show success toast
+            const usedTemplate = pollingResult.data.template;
             toast.success('Summary generated successfully!', {
-              description: 'Your meeting summary is ready',
+              description: usedTemplate && usedTemplate !== selectedTemplate
+                ? `Used the "${usedTemplate}" template picked from the meeting note`
+                : 'Your meeting summary is ready',
               duration: 4000,
             });
 
@@ -390,7 +476,7 @@ export function useSummaryGeneration({
   }, []);
 
   // Public API: Generate summary from transcripts
-  const handleGenerateSummary = useCallback(async (customPrompt: string = '') => {
+  const handleGenerateSummary = useCallback(async (customPrompt: string = '', autoTemplate: boolean = false) => {
     // Check if model config is still loading
     if (isModelConfigLoading) {
       console.log('⏳ Model configuration is still loading, please wait...');
@@ -561,7 +647,7 @@ export function useSummaryGeneration({
       .map(t => `${formatTime(t.audio_start_time, t.timestamp)} ${t.text}`)
       .join('\n');
 
-    await processSummary({ transcriptText: fullTranscript, customPrompt });
+    await processSummary({ transcriptText: fullTranscript, customPrompt, autoTemplate });
   }, [meeting.id, fetchAllTranscripts, processSummary, modelConfig, isModelConfigLoading, selectedTemplate]);
 
   // Public API: Regenerate summary from original transcript
@@ -609,6 +695,8 @@ export function useSummaryGeneration({
   return {
     summaryStatus,
     summaryError,
+    summaryProgress,
+    streamingMarkdown,
     handleGenerateSummary,
     handleRegenerateSummary,
     handleStopGeneration,
@@ -40,7 +40,7 @@ interface UseModalStateReturn {
  *
  * Features:
  * - Unified modal state management
- * - Event listeners for chunk drops, transcription errors, model downloads
+ * - Event listeners for chunk drops, transcription errors, Deepgram reconnects, model downloads
  * - Auto-close on model download completion
  */
 export function useModalState(transcriptModelConfig?: TranscriptModelProps): UseModalStateReturn {
@@ -163,6 +163,136 @@ export function useModalState(transcriptModelConfig?: TranscriptModelProps): Use
     };
   }, [showModal]);
 
+  // Show Deepgram reconnects as a single updating toast
+  useEffect(() => {
+    let unlistenFn: (() => void) | undefined;
+
+    const setupConnectionStateListener = async () => {
+      try {
+        unlistenFn = await listen<{ state: string, attempt: number | null, message: string | null }>('deepgram-connection-state', (event) => {
+          console.log('Deepgram connection state:', event.payload);
+          const { state, attempt, message } = event.payload;
+          const id = 'deepgram-connection';
+
+          if (state === 'reconnecting') {
+            toast.loading('Connection to Deepgram lost', {
+              id,
+              description: `Reconnecting (attempt ${attempt ?? 1})... Audio is buffered and will be transcribed.`,
+            });
+          } else if (state === 'connected') {
+            toast.success('Reconnected to Deepgram', {
+              id,
+              description: 'Transcription resumed.',
+              duration: 3000,
+            });
+          } else if (state === 'error') {
+            toast.error('Could not reconnect to Deepgram', {
+              id,
+              description: message ?? undefined,
+              duration: 8000,
+            });
+          }
+        });
+      } catch (error) {
+        console.error('Failed to setup Deepgram connection state listener:', error);
+      }
+    };
+
+    setupConnectionStateListener();
+
+    return () => {
+      if (unlistenFn) {
+        unlistenFn();
+      }
+    };
+  }, []);
+
+  // Tell the user when transcription moves to a local model and back
+  useEffect(() => {
+    let unlistenFn: (() => void) | undefined;
+
+    const setupFailoverListener = async () => {
+      try {
+        unlistenFn = await listen<{ state: string, primary: string, fallback?: string, reason?: string, rerun_segments?: number }>('transcription-failover', (event) => {
+          console.log('Transcription failover:', event.payload);
+          const { state, primary, fallback, reason, rerun_segments } = event.payload;
+          const id = 'transcription-failover';
+
+          if (state === 'activated') {
+            toast.warning(`${primary} unavailable, transcribing with ${fallback}`, {
+              id,
+              description: reason,
+              duration: 8000,
+            });
+          } else if (state === 'recovered') {
+            toast.success(`${primary} is back`, {
+              id,
+              description: rerun_segments
+                ? `Re-transcribing ${rerun_segments} segment${rerun_segments === 1 ? '' : 's'} from the outage.`
+                : 'Transcription resumed.',
+              duration: 5000,
+            });
+          }
+        });
+      } catch (error) {
+        console.error('Failed to setup transcription failover listener:', error);
+      }
+    };
+
+    setupFailoverListener();
+
+    return () => {
+      if (unlistenFn) {
+        unlistenFn();
+      }
+    };
+  }, []);
+
+  // Tell the user about recordings queued for Deepgram while offline
+  useEffect(() => {
+    const unlistenFns: (() => void)[] = [];
+
+    const setupOfflineQueueListeners = async () => {
+      try {
+        unlistenFns.push(await listen<{ message: string }>('offline-capture-started', (event) => {
+          toast.warning('Deepgram unreachable', {
+            id: 'offline-capture',
+            description: event.payload.message,
+            duration: 10000,
+          });
+        }));
+
+        unlistenFns.push(await listen<{ meeting_id: string, meeting_name?: string, segment_count: number, summarized: boolean }>('pending-transcription-complete', (event) => {
+          const { meeting_name, segment_count, summarized } = event.payload;
+          toast.success(`Transcribed "${meeting_name || 'Meeting'}"`, {
+            description: `${segment_count} segment${segment_count === 1 ? '' : 's'}${summarized ? ' and a summary' : ''} added to the meeting note.`,
+            duration: 8000,
+          });
+        }));
+
+        unlistenFns.push(await listen<{ meeting_name?: string, folder_path: string, error: string, gave_up: boolean }>('pending-transcription-failed', (event) => {
+          const { meeting_name, error, gave_up } = event.payload;
+          if (gave_up) {
+            toast.error(`Couldn't transcribe "${meeting_name || 'Meeting'}"`, {
+              description: `${error}. The recording is still saved in the meeting folder.`,
+              duration: 10000,
+            });
+          } else {
+            console.warn('Queued transcription failed, will retry:', error);
+          }
+        }));
+      } catch (error) {
+        console.error('Failed to setup offline queue listeners:', error);
+      }
+    };
+
+    setupOfflineQueueListeners();
+
+    return () => {
+      unlistenFns.forEach((unlisten) => unlisten());
+    };
+  }, []);
+
   // Listen for model download completion to auto-close modal
   useEffect(() => {
     const setupDownloadListeners = async () => {
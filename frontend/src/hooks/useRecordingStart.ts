@@ -49,6 +49,22 @@ export function useRecordingStart(
     return `Meeting ${day}_${month}_${year}_${hours}_${minutes}_${seconds}`;
   }, []);
 
+  // Snapshot the open prep note; the transcript saved into it on stop is merged
+  // with edits made while recording instead of overwriting them
+  const snapshotPrepNote = useCallback(async () => {
+    sessionStorage.removeItem('active_prep_note_base');
+    const notePath = sessionStorage.getItem('active_prep_note_path');
+    if (!notePath) {
+      return;
+    }
+    try {
+      const prep = await invoke<{ raw_content: string }>('open_meeting_file', { path: notePath });
+      sessionStorage.setItem('active_prep_note_base', prep.raw_content);
+    } catch (error) {
+      console.warn('Failed to snapshot the prep note:', error);
+    }
+  }, []);
+
   // Check if Parakeet transcription model is ready
   const checkParakeetReady = useCallback(async (): Promise<boolean> => {
     try {
@@ -122,6 +138,7 @@ export function useRecordingStart(
         randomTitle
       );
       console.log('Backend recording started successfully');
+      await snapshotPrepNote();
 
       // Update state after successful backend start
       // Note: RECORDING status will be set by RecordingStateContext event listener
@@ -141,7 +158,7 @@ export function useRecordingStart(
       // Re-throw so RecordingControls can handle device-specific errors
       throw error;
     }
-  }, [generateMeetingTitle, setMeetingTitle, setIsRecording, clearTranscripts, setIsMeetingActive, checkParakeetReady, checkIfModelDownloading, selectedDevices, showModal, setStatus]);
+  }, [generateMeetingTitle, setMeetingTitle, setIsRecording, clearTranscripts, setIsMeetingActive, checkParakeetReady, checkIfModelDownloading, snapshotPrepNote, selectedDevices, showModal, setStatus]);
 
   // Check for autoStartRecording flag and start recording automatically
   useEffect(() => {
@@ -191,6 +208,7 @@ export function useRecordingStart(
               generatedMeetingTitle
             );
             console.log('Auto-start backend recording result:', result);
+            await snapshotPrepNote();
 
             // Update UI state after successful backend start
             // Note: RECORDING status will be set by RecordingStateContext event listener
@@ -226,6 +244,7 @@ export function useRecordingStart(
     setIsMeetingActive,
     checkParakeetReady,
     checkIfModelDownloading,
+    snapshotPrepNote,
     showModal,
     setStatus,
   ]);
@@ -278,6 +297,7 @@ export function useRecordingStart(
           generatedMeetingTitle
         );
         console.log('Backend recording result:', result);
+        await snapshotPrepNote();
 
         // Update UI state after successful backend start
         // Note: RECORDING status will be set by RecordingStateContext event listener
@@ -315,6 +335,7 @@ export function useRecordingStart(
     setIsMeetingActive,
     checkParakeetReady,
     checkIfModelDownloading,
+    snapshotPrepNote,
     showModal,
     setStatus,
   ]);
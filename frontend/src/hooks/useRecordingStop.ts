@@ -1,5 +1,6 @@
 import { useState, useEffect, useCallback, useRef } from 'react';
 import { useRouter } from 'next/navigation';
+import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { toast } from 'sonner';
 import { useTranscripts } from '@/contexts/TranscriptContext';
@@ -8,6 +9,7 @@ import { useRecordingState, RecordingStatus } from '@/contexts/RecordingStateCon
 import { storageService } from '@/services/storageService';
 import { transcriptService } from '@/services/transcriptService';
 import Analytics from '@/lib/analytics';
+import type { NoteTranscriptSegment, SaveOutcome, Transcript } from '@/types';
 
 type SummaryStatus = 'idle' | 'processing' | 'summarizing' | 'regenerating' | 'completed' | 'error';
 
@@ -20,6 +22,69 @@ interface UseRecordingStopReturn {
   setIsStopping: (value: boolean) => void;
 }
 
+/** Recording-relative seconds as HH:MM:SS */
+function formatOffset(seconds: number): string {
+  const total = Math.max(0, Math.floor(seconds));
+  const pad = (n: number) => String(n).padStart(2, '0');
+  return `${pad(Math.floor(total / 3600))}:${pad(Math.floor((total % 3600) / 60))}:${pad(total % 60)}`;
+}
+
+/**
+ * Note transcript lines; a leading "[Speaker N]:" label becomes the line's speaker.
+ * Only a row's first line carries its start time, so citation block ids are per row.
+ */
+function toNoteSegments(transcripts: Transcript[]): NoteTranscriptSegment[] {
+  return transcripts.flatMap(transcript => {
+    const timestamp = transcript.audio_start_time !== undefined
+      ? formatOffset(transcript.audio_start_time)
+      : transcript.timestamp;
+    return transcript.text
+      .split('\n')
+      .map(line => line.trim())
+      .filter(line => line.length > 0)
+      .map((line, index) => {
+        const audio_start_time = index === 0 ? transcript.audio_start_time ?? null : null;
+        const match = line.match(/^\[(Speaker \d+)\]:\s*(.*)$/);
+        return match
+          ? { timestamp, speaker: match[1], text: match[2], audio_start_time }
+          : { timestamp, speaker: null, text: line, audio_start_time };
+      });
+  });
+}
+
+/**
+ * Write the transcript into the prep note that was open while recording.
+ * `baseContent` is the note as it was when recording started, so edits made
+ * in Obsidian meanwhile are merged three-way instead of overwritten.
+ */
+async function savePrepNoteTranscript(notePath: string, baseContent: string, transcripts: Transcript[]) {
+  try {
+    const lastEnd = transcripts.reduce((end, t) => Math.max(end, t.audio_end_time ?? 0), 0);
+    const outcome = await invoke<SaveOutcome>('save_meeting_transcript', {
+      request: {
+        file_path: notePath,
+        segments: toNoteSegments(transcripts),
+        update_status: true,
+        base_content: baseContent,
+        metadata: lastEnd > 0 ? { duration_seconds: lastEnd } : {},
+      },
+    });
+    if (outcome.conflict_path) {
+      toast.warning('Meeting note was edited during the recording', {
+        description: `Kept your edits in ${outcome.conflicting_sections.join(', ')}; the transcript version was saved to ${outcome.conflict_path}`,
+        duration: 8000,
+      });
+    } else if (outcome.merged_concurrent_edits) {
+      console.log('Merged edits made to the prep note while recording');
+    }
+  } catch (error) {
+    console.error('Failed to write transcript to the prep note:', error);
+    toast.error('Failed to write transcript to the meeting note', {
+      description: error instanceof Error ? error.message : String(error),
+    });
+  }
+}
+
 /**
  * Custom hook for managing recording stop lifecycle.
  * Handles the complex stop sequence: transcription wait → buffer flush → SQLite save → navigation.
@@ -82,10 +147,11 @@ export function useRecordingStop(
           message: string;
           folder_path?: string;
           meeting_name?: string;
+          pending_transcription?: boolean;
         }>('recording-stopped', async (event) => {
           // Create promise that resolves when sessionStorage is set (prevents race condition)
           recordingStoppedDataRef.current = (async () => {
-            const { folder_path, meeting_name } = event.payload;
+            const { folder_path, meeting_name, pending_transcription } = event.payload;
 
             // Store folder_path and meeting_name for later use in handleRecordingStop
             if (folder_path) {
@@ -94,6 +160,10 @@ export function useRecordingStop(
             if (meeting_name) {
               sessionStorage.setItem('last_recording_meeting_name', meeting_name);
             }
+            // Recorded offline: Deepgram transcribes it once the connection is back
+            if (pending_transcription) {
+              sessionStorage.setItem('last_recording_pending_transcription', 'true');
+            }
           })();
 
         });
@@ -239,6 +309,7 @@ export function useRecordingStop(
         // Get folder_path and meeting_name from recording-stopped event
         const folderPath = sessionStorage.getItem('last_recording_folder_path');
         const savedMeetingName = sessionStorage.getItem('last_recording_meeting_name');
+        const pendingTranscription = sessionStorage.getItem('last_recording_pending_transcription') === 'true';
 
         console.log('💾 Saving COMPLETE transcripts to database...', {
           transcript_count: freshTranscripts.length,
@@ -248,6 +319,22 @@ export function useRecordingStop(
           last_transcript: freshTranscripts.length > 0 ? freshTranscripts[freshTranscripts.length - 1].text.substring(0, 30) + '...' : 'none',
         });
 
+        // An offline recording is transcribed later; its transcript goes to the prep note then
+        const prepNotePath = sessionStorage.getItem('active_prep_note_path');
+        const prepNoteBase = sessionStorage.getItem('active_prep_note_base');
+        if (pendingTranscription && folderPath && prepNotePath) {
+          try {
+            // Before the meeting is saved, since the queue picks the recording up from then on
+            await invoke('set_pending_transcription_note', {
+              folderPath,
+              notePath: prepNotePath,
+              baseContent: prepNoteBase,
+            });
+          } catch (error) {
+            console.error('Failed to attach the prep note to the queued recording:', error);
+          }
+        }
+
         try {
           const responseData = await storageService.saveMeeting(
             savedMeetingName || meetingTitle || 'New Meeting',  // PREFER savedMeetingName (backend source)
@@ -268,9 +355,21 @@ export function useRecordingStop(
           // Mark meeting as saved in IndexedDB (for recovery system)
           await markMeetingAsSaved();
 
+          // Remember the prep note that was open while recording, for prep-aware summaries
+          if (prepNotePath) {
+            localStorage.setItem(`meeting_prep_note:${meetingId}`, prepNotePath);
+          }
+
+          // Write the transcript into that note, merging edits made while recording
+          if (prepNotePath && prepNoteBase !== null && !pendingTranscription) {
+            await savePrepNoteTranscript(prepNotePath, prepNoteBase, freshTranscripts);
+          }
+          sessionStorage.removeItem('active_prep_note_base');
+
           // Clean up session storage
           sessionStorage.removeItem('last_recording_folder_path');
           sessionStorage.removeItem('last_recording_meeting_name');
+          sessionStorage.removeItem('last_recording_pending_transcription');
           // Clean up IndexedDB meeting ID (redundant with markMeetingAsSaved cleanup, but ensures cleanup)
           sessionStorage.removeItem('indexeddb_current_meeting_id');
 
@@ -296,7 +395,9 @@ export function useRecordingStop(
 
           // Show success toast with navigation option
           toast.success('Recording saved successfully!', {
-            description: `${freshTranscripts.length} transcript segments saved.`,
+            description: pendingTranscription
+              ? 'Recorded offline. The transcript and summary will be added once Deepgram is reachable.'
+              : `${freshTranscripts.length} transcript segments saved.`,
             action: {
               label: 'View Meeting',
               onClick: () => {
@@ -16,6 +16,18 @@ export interface Transcript {
   audio_start_time?: number; // Seconds from recording start (e.g., 125.3)
   audio_end_time?: number;   // Seconds from recording start (e.g., 128.6)
   duration?: number;          // Segment duration in seconds (e.g., 3.3)
+  provider?: string;          // Engine that produced the text (e.g., "Parakeet" during a Deepgram outage)
+  words?: WordTiming[];       // Word timings (Deepgram), saved to transcript_words with the meeting
+}
+
+// Word-level timing in recording-relative seconds
+export interface WordTiming {
+  word: string;
+  punctuated_word?: string;
+  start: number;
+  end: number;
+  confidence?: number;
+  speaker?: number;
 }
 
 export interface TranscriptUpdate {
@@ -30,6 +42,9 @@ export interface TranscriptUpdate {
   audio_start_time: number; // Seconds from recording start
   audio_end_time: number;   // Seconds from recording start
   duration: number;          // Segment duration in seconds
+  provider?: string;         // Engine that produced the text
+  revision?: boolean;        // Replaces the earlier update with the same sequence_id
+  words?: WordTiming[];      // Word timings, recording-relative (Deepgram only)
 }
 
 export interface Block {
@@ -108,3 +123,35 @@ export interface TranscriptSegmentData {
   text: string;
   confidence?: number;
 }
+
+// Speaker naming (diarized "Speaker N" labels -> people)
+export interface SpeakerEntry {
+  label: string;
+  display_name?: string | null;
+}
+
+export interface MeetingSpeakers {
+  meeting_id: string;
+  speakers: SpeakerEntry[];
+  candidates: string[];
+}
+
+export interface SaveMeetingSpeakersResult {
+  saved: number;
+  updated_files: string[];
+  failed_files: [string, string][];
+}
+
+// Writing a transcript into an existing meeting note (obsidian SaveTranscriptRequest)
+export interface NoteTranscriptSegment {
+  timestamp: string; // "HH:MM:SS"
+  speaker: string | null;
+  text: string;
+  audio_start_time?: number | null; // Row start in seconds; set on the first line of each transcript row
+}
+
+export interface SaveOutcome {
+  merged_concurrent_edits: boolean;
+  conflict_path: string | null;
+  conflicting_sections: string[];
+}
@@ -32,6 +32,12 @@ enum Request {
         top_k: Option<i32>,
         top_p: Option<f32>,
         stop_tokens: Option<Vec<String>>,
+        // Emit `token` frames before the final response
+        #[serde(default)]
+        stream: bool,
+        // GBNF grammar (root rule `root`) constraining the output
+        #[serde(default)]
+        grammar: Option<String>,
     },
     Ping,
     Shutdown,
@@ -40,6 +46,7 @@ enum Request {
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum Response {
+    Token { text: String },
     Response { text: String, error: Option<String> },
     Pong,
     Goodbye,
@@ -276,6 +283,8 @@ impl ModelState {
         top_k: i32,
         top_p: f32,
         stop_tokens: Vec<String>,
+        grammar: Option<&str>,
+        on_token: &mut dyn FnMut(&str),
     ) -> Result<String> {
         let start_time = Instant::now();
         let model = self.model.as_ref().context("Model not loaded")?;
@@ -326,9 +335,42 @@ impl ModelState {
         let mut n_cur = n_prompt_tokens;
         let mut decoder = encoding_rs::UTF_8.new_decoder();
         let mut output = String::new();
+        // Bytes of `output` already passed to `on_token`
+        let mut emitted = 0;
 
         eprintln!("🔄 Starting generation (max_tokens: {})", max_tokens);
 
+        use llama_cpp_2::sampling::LlamaSampler;
+
+        // The sampler lives for the whole generation: a grammar tracks the
+        // tokens accepted so far
+        let mut samplers = Vec::new();
+        if let Some(grammar) = grammar {
+            eprintln!("📐 Constraining output with grammar");
+            samplers.push(
+                LlamaSampler::grammar(model, grammar, "root")
+                    .map_err(|e| anyhow::anyhow!("Invalid grammar: {:?}", e))?,
+            );
+        }
+        if temperature <= 0.0 {
+            // Greedy sampling for temp <= 0
+            samplers.push(LlamaSampler::greedy());
+        } else {
+            // Random sampling with temperature/top_k/top_p
+            let seed = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u32;
+
+            samplers.extend([
+                LlamaSampler::top_k(top_k),
+                LlamaSampler::top_p(top_p, 1),
+                LlamaSampler::temp(temperature),
+                LlamaSampler::dist(seed),
+            ]);
+        }
+        let mut sampler = pin!(LlamaSampler::chain_simple(samplers));
+
         loop {
             // Check if we've generated enough tokens
             if (n_cur - n_prompt_tokens) >= max_tokens {
@@ -336,27 +378,6 @@ impl ModelState {
                 break;
             }
 
-            use llama_cpp_2::sampling::LlamaSampler;
-
-            let sampler = if temperature <= 0.0 {
-                // Greedy sampling for temp <= 0
-                LlamaSampler::chain_simple([LlamaSampler::greedy()])
-            } else {
-                // Random sampling with temperature/top_k/top_p
-                let seed = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_millis() as u32;
-
-                LlamaSampler::chain_simple([
-                    LlamaSampler::top_k(top_k),
-                    LlamaSampler::top_p(top_p, 1),
-                    LlamaSampler::temp(temperature),
-                    LlamaSampler::dist(seed),
-                ])
-            };
-
-            let mut sampler = pin!(sampler);
             let token = sampler.as_mut().sample(&ctx, batch.n_tokens() - 1);
             sampler.as_mut().accept(token);
 
@@ -395,6 +416,12 @@ impl ModelState {
                 break;
             }
 
+            let streamable = streamable_len(&output, &stop_tokens);
+            if streamable > emitted {
+                on_token(&output[emitted..streamable]);
+                emitted = streamable;
+            }
+
             batch.clear();
             batch
                 .add(token, n_cur, &[0], true)
@@ -403,6 +430,11 @@ impl ModelState {
             ctx.decode(&mut batch).context("failed to eval")?;
         }
 
+        // Flush text held back while it looked like the start of a stop token
+        if output.len() > emitted && output.is_char_boundary(emitted) {
+            on_token(&output[emitted..]);
+        }
+
         // Generation statistics
         let total_time = start_time.elapsed();
         let gen_time = total_time.saturating_sub(prompt_time);
@@ -428,6 +460,20 @@ impl ModelState {
     }
 }
 
+/// Length of `output` that can be streamed without leaking the start of a stop token
+fn streamable_len(output: &str, stop_tokens: &[String]) -> usize {
+    let mut len = output.len();
+    for stop_token in stop_tokens {
+        let held_back = (1..stop_token.len())
+            .rev()
+            .filter(|&k| stop_token.is_char_boundary(k))
+            .find(|&k| output.ends_with(&stop_token[..k]))
+            .unwrap_or(0);
+        len = len.min(output.len() - held_back);
+    }
+    len
+}
+
 // ============================================================================
 // Main Loop with Keep-Alive Protocol
 // ============================================================================
@@ -490,6 +536,8 @@ fn main() -> Result<()> {
                         top_k,
                         top_p,
                         stop_tokens,
+                        stream,
+                        grammar,
                     }) => {
                         let max_tokens = max_tokens.unwrap_or(512);
                         let context_size = context_size.unwrap_or(2048);
@@ -513,6 +561,13 @@ fn main() -> Result<()> {
                         }
 
                         // Generate response with sampling parameters
+                        let mut on_token = |text: &str| {
+                            if stream {
+                                if let Err(e) = send_response(&Response::Token { text: text.to_string() }) {
+                                    eprintln!("❌ Failed to send token frame: {}", e);
+                                }
+                            }
+                        };
                         match state.generate(
                             prompt,
                             max_tokens,
@@ -520,6 +575,8 @@ fn main() -> Result<()> {
                             top_k,
                             top_p,
                             stop_tokens,
+                            grammar.as_deref(),
+                            &mut on_token,
                         ) {
                             Ok(text) => {
                                 send_response(&Response::Response { text, error: None })?;